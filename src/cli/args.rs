@@ -40,6 +40,22 @@ pub struct Cli {
     /// List available plugins
     #[arg(short, long)]
     pub list_plugins: bool,
+
+    /// Swap partition or swap file image used to read swapped-out pages, as FILE or
+    /// TYPE=FILE (the kernel swap type); repeat for several swap areas
+    #[arg(long, value_name = "[TYPE=]FILE", value_parser = parse_swap_image)]
+    pub swap: Vec<SwapImageArg>,
+
+    /// CPU architecture of the dump (auto detects AArch64 from the symbols)
+    #[arg(long, value_enum, default_value = "auto")]
+    pub arch: ArchArg,
+}
+
+/// A swap area image given on the command line
+#[derive(Clone)]
+pub struct SwapImageArg {
+    pub swap_type: Option<u8>, // Matched against the dump's swap areas when omitted
+    pub path: std::path::PathBuf,
 }
 
 #[derive(Subcommand)]
@@ -74,6 +90,144 @@ pub enum PluginCommand {
         #[arg(long)]
         pid: Option<i32>,
     },
+
+    /// Reconstruct tmpfs/ramfs filesystems and memfd files
+    Tmpfs {
+        /// Export recovered file contents into this directory
+        #[arg(long, value_name = "DIR")]
+        dump_dir: Option<std::path::PathBuf>,
+    },
+
+    /// Recover bash command history from process memory
+    Bash {
+        /// Filter by PID
+        #[arg(long)]
+        pid: Option<i32>,
+
+        /// Process name to treat as a bash-compatible shell (repeatable)
+        #[arg(long, default_value = "bash")]
+        shell: Vec<String>,
+    },
+
+    /// List network interfaces, addresses and promiscuous mode
+    Ifconfig,
+
+    /// Extract the ARP and IPv6 neighbour caches
+    Arp,
+
+    /// Extract the IPv4 and IPv6 routing tables
+    Routes,
+
+    /// List Unix domain sockets with owning and peer processes
+    Unix,
+
+    /// List packet, raw and netlink sockets with attached BPF filters
+    Rawsockets,
+
+    /// List loaded eBPF programs with attach points and JIT images
+    Bpf {
+        /// Write program bytecode and JIT images into this directory
+        #[arg(long, value_name = "DIR")]
+        dump_dir: Option<std::path::PathBuf>,
+    },
+
+    /// List eBPF maps with key/value sizes and capacity
+    Bpfmaps,
+
+    /// Detect inline hooks by checking kernel function text integrity
+    Inlinehooks {
+        /// vmlinux image to compare kernel text against (prologue heuristics without one)
+        #[arg(long, value_name = "FILE")]
+        vmlinux: Option<std::path::PathBuf>,
+    },
+
+    /// List ftrace ops, kprobes and tracepoint probes with resolved callbacks
+    Tracehooks,
+
+    /// List netfilter hooks with priorities and resolved callbacks
+    Netfilter,
+
+    /// List keyboard, module, netdev, reboot and panic notifier callbacks
+    Notifiers,
+
+    /// Check tty line discipline receive_buf and driver write handlers
+    Ttycheck,
+
+    /// Check syscall tables, IDT, /proc and network ops structures for hijacked pointers
+    Opscheck {
+        /// Also list pointers that resolve into core kernel text
+        #[arg(long)]
+        all_entries: bool,
+    },
+
+    /// List per-process namespaces, namespace-local PIDs, hostnames and cgroups
+    Namespaces,
+
+    /// Group processes into containers by namespace set, hostname and cgroup
+    Containers,
+
+    /// Compare executable file mappings with the dynamic loader's link_map
+    Ldrmodules {
+        /// Only check this process
+        #[arg(long)]
+        pid: Option<i32>,
+    },
+
+    /// Find ELF images mapped into processes and optionally reconstruct them
+    Elfs {
+        /// Only check this process
+        #[arg(long)]
+        pid: Option<i32>,
+
+        /// Directory to write reconstructed ELF images into
+        #[arg(long, value_name = "DIR")]
+        dump_dir: Option<std::path::PathBuf>,
+    },
+
+    /// Scan memory with YARA-style rules
+    Yarascan {
+        /// Rule file (a subset of YARA syntax)
+        #[arg(long, value_name = "FILE")]
+        rules: std::path::PathBuf,
+
+        /// Address space to scan
+        #[arg(long, value_enum, default_value = "process")]
+        scope: ScanScopeArg,
+
+        /// Only scan this process (process scope)
+        #[arg(long)]
+        pid: Option<i32>,
+    },
+
+    /// Attribute strings in the dump to physical, kernel and process addresses
+    Strings {
+        /// Output of `strings -td` (or `offset:string` lines) run on the dump;
+        /// strings are extracted from the dump when omitted
+        #[arg(long, value_name = "FILE")]
+        strings_file: Option<std::path::PathBuf>,
+
+        /// Minimum length of extracted strings
+        #[arg(long, default_value_t = 8)]
+        min_length: usize,
+    },
+
+    /// List process memory mappings with resident, swapped and unavailable page counts
+    Maps {
+        /// Only list this process
+        #[arg(long)]
+        pid: Option<i32>,
+    },
+
+    /// Decode the struct page (flags, mapping, counts) of a physical address
+    Pageinfo {
+        /// Physical address (decimal or 0x-prefixed hex)
+        #[arg(long, value_parser = parse_address)]
+        address: u64,
+
+        /// Number of consecutive pages to decode
+        #[arg(long, default_value_t = 1)]
+        count: usize,
+    },
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
@@ -83,3 +237,52 @@ pub enum OutputFormatArg {
     Json,
     Jsonl,
 }
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+pub enum ArchArg {
+    Auto,
+    #[value(name = "x86_64")]
+    X86_64,
+    Aarch64,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+pub enum ScanScopeArg {
+    Physical,
+    Kernel,
+    Process,
+}
+
+/// Parse an address given in decimal or 0x-prefixed hex
+fn parse_address(value: &str) -> Result<u64, String> {
+    match value
+        .strip_prefix("0x")
+        .or_else(|| value.strip_prefix("0X"))
+    {
+        Some(hex) => u64::from_str_radix(hex, 16),
+        None => value.parse(),
+    }
+    .map_err(|e| format!("invalid address '{}': {}", value, e))
+}
+
+/// Parse a swap image argument: FILE or TYPE=FILE
+fn parse_swap_image(value: &str) -> Result<SwapImageArg, String> {
+    match value.split_once('=') {
+        Some((swap_type, path))
+            if !swap_type.is_empty() && swap_type.bytes().all(|b| b.is_ascii_digit()) =>
+        {
+            Ok(SwapImageArg {
+                swap_type: Some(
+                    swap_type
+                        .parse()
+                        .map_err(|e| format!("invalid swap type '{}': {}", swap_type, e))?,
+                ),
+                path: path.into(),
+            })
+        }
+        _ => Ok(SwapImageArg {
+            swap_type: None,
+            path: value.into(),
+        }),
+    }
+}
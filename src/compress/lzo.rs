@@ -0,0 +1,200 @@
+//! LZO1X decompression, including the lzo-rle zero-run extension (lib/lzo/lzo1x_decompress_safe.c)
+
+// M2_MAX_OFFSET: distance bias of the short match that follows a literal run
+const M2_MAX_OFFSET: usize = 0x0800;
+// Shortest zero run encoded by lzo-rle
+const MIN_ZERO_RUN_LENGTH: usize = 4;
+// Bound on the 0x00 bytes of a length extension (MAX_255_COUNT)
+const MAX_255_COUNT: usize = usize::MAX / 255 - 2;
+
+/// Decompress an LZO1X or lzo-rle stream into at most `max_output` bytes
+///
+/// Returns None on a malformed or truncated stream, or when the output would
+/// exceed `max_output`.
+pub fn decompress(input: &[u8], max_output: usize) -> Option<Vec<u8>> {
+    let mut out: Vec<u8> = Vec::with_capacity(max_output);
+    let mut ip = 0usize;
+    let mut state = 0usize;
+
+    if input.len() < 3 {
+        return None;
+    }
+    // lzo-rle streams start with 17 and a bitstream version
+    let mut bitstream_version = 0u8;
+    if input.len() >= 5 && input[0] == 17 {
+        bitstream_version = input[1];
+        ip = 2;
+    }
+
+    let byte = |ip: usize| input.get(ip).copied().map(usize::from);
+    let le16 = |ip: usize| Some(byte(ip)? | byte(ip + 1)? << 8);
+    let copy_literals = |out: &mut Vec<u8>, ip: &mut usize, count: usize| -> Option<()> {
+        if out.len() + count > max_output {
+            return None;
+        }
+        out.extend_from_slice(input.get(*ip..*ip + count)?);
+        *ip += count;
+        Some(())
+    };
+    // Length extension: each 0x00 adds 255, then the final byte
+    let extend = |ip: &mut usize, base: usize| -> Option<usize> {
+        let start = *ip;
+        while byte(*ip)? == 0 {
+            *ip += 1;
+        }
+        let zeros = *ip - start;
+        if zeros > MAX_255_COUNT {
+            return None;
+        }
+        let value = zeros * 255 + base + byte(*ip)?;
+        *ip += 1;
+        Some(value)
+    };
+
+    // An initial literal run is encoded as 17 + length
+    let first = byte(ip)?;
+    let mut next;
+    if first > 17 {
+        ip += 1;
+        let t = first - 17;
+        if t < 4 {
+            next = t;
+            copy_literals(&mut out, &mut ip, next)?;
+            state = next;
+        } else {
+            copy_literals(&mut out, &mut ip, t)?;
+            state = 4;
+        }
+    }
+
+    loop {
+        let mut t = byte(ip)?;
+        ip += 1;
+        let distance;
+
+        if t < 16 {
+            if state == 0 {
+                // Literal run
+                if t == 0 {
+                    t = extend(&mut ip, 15)?;
+                }
+                copy_literals(&mut out, &mut ip, t + 3)?;
+                state = 4;
+                continue;
+            } else if state != 4 {
+                // Two-byte match right after a short literal run
+                next = t & 3;
+                distance = 1 + (t >> 2) + (byte(ip)? << 2);
+                ip += 1;
+                t = 2;
+            } else {
+                // Three-byte match after a long literal run
+                next = t & 3;
+                distance = 1 + M2_MAX_OFFSET + (t >> 2) + (byte(ip)? << 2);
+                ip += 1;
+                t = 3;
+            }
+        } else if t >= 64 {
+            next = t & 3;
+            distance = 1 + ((t >> 2) & 7) + (byte(ip)? << 3);
+            ip += 1;
+            t = (t >> 5) - 1 + 2;
+        } else if t >= 32 {
+            t = (t & 31) + 2;
+            if t == 2 {
+                t = 2 + extend(&mut ip, 31)?;
+            }
+            let word = le16(ip)?;
+            ip += 2;
+            distance = 1 + (word >> 2);
+            next = word & 3;
+        } else {
+            let word = le16(ip)?;
+            if word & 0xfffc == 0xfffc && t & 0xf8 == 0x18 && bitstream_version > 0 {
+                // lzo-rle run of zeros
+                let run = ((t & 7) | byte(ip + 2)? << 3) + MIN_ZERO_RUN_LENGTH;
+                if out.len() + run > max_output {
+                    return None;
+                }
+                out.resize(out.len() + run, 0);
+                next = word & 3;
+                ip += 3;
+                copy_literals(&mut out, &mut ip, next)?;
+                state = next;
+                continue;
+            }
+            let far = (t & 8) << 11;
+            t = (t & 7) + 2;
+            let mut word = word;
+            if t == 2 {
+                t = 2 + extend(&mut ip, 7)?;
+                word = le16(ip)?;
+            }
+            ip += 2;
+            next = word & 3;
+            let near = far + (word >> 2);
+            if near == 0 {
+                // End of stream marker
+                return (t == 3 && ip == input.len()).then_some(out);
+            }
+            distance = near + 0x4000;
+        }
+
+        // Copy the match byte by byte; it may overlap its own output
+        if distance > out.len() || out.len() + t > max_output {
+            return None;
+        }
+        let start = out.len() - distance;
+        for i in 0..t {
+            let b = out[start + i];
+            out.push(b);
+        }
+
+        copy_literals(&mut out, &mut ip, next)?;
+        state = next;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decompress_literals_and_match() {
+        // 4 literals "abcd", a 4-byte match at distance 4, end marker
+        let stream = [
+            17 + 4,
+            b'a',
+            b'b',
+            b'c',
+            b'd',
+            (4 - 1) << 5 | ((4 - 1) & 7) << 2,
+            0,
+            0x11,
+            0,
+            0,
+        ];
+        assert_eq!(decompress(&stream, 16), Some(b"abcdabcd".to_vec()));
+        assert_eq!(decompress(&stream, 4), None);
+        assert_eq!(decompress(&stream[..6], 16), None);
+    }
+
+    #[test]
+    fn test_decompress_extended_matches() {
+        // M3 match with a length extension: 2 + 31 + 1 = 34 bytes at distance 4
+        let stream = [21, b'a', b'b', b'c', b'd', 0x20, 1, 12, 0, 0x11, 0, 0];
+        let expected: Vec<u8> = b"abcd".iter().copied().cycle().take(38).collect();
+        assert_eq!(decompress(&stream, 64), Some(expected));
+
+        // An M3 match of 2 + 31 + 64 * 255 + 44 bytes brings the output past
+        // 16K, then an M4 match of 2 + 7 + 3 bytes at distance 0x4000 + 4
+        let mut stream = vec![21, b'a', b'b', b'c', b'd', 0x20];
+        stream.extend([0; 64]);
+        stream.extend([44, 12, 0]);
+        stream.extend([0x10, 3, 16, 0]);
+        stream.extend([0x11, 0, 0]);
+        let m3 = 2 + 31 + 64 * 255 + 44;
+        let expected: Vec<u8> = b"abcd".iter().copied().cycle().take(4 + m3 + 12).collect();
+        assert_eq!(decompress(&stream, 32768), Some(expected));
+    }
+}
@@ -0,0 +1,60 @@
+//! Page decompression for compressed swap (zram, zswap)
+//!
+//! Supports the kernel's lzo / lzo-rle, lz4 / lz4hc and zstd compressors. When
+//! the algorithm is unknown each decoder is tried and only output of exactly one
+//! page is accepted.
+mod lzo;
+
+// zstd frame magic number
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
+/// Decompress one compressed page of `page_size` bytes
+///
+/// `algorithm` is the kernel crypto name ("lzo-rle", "lz4", "zstd", ...).
+pub fn decompress_page(algorithm: Option<&str>, data: &[u8], page_size: usize) -> Option<Vec<u8>> {
+    let page = match algorithm {
+        Some("lzo") | Some("lzo-rle") => lzo::decompress(data, page_size),
+        Some("lz4") | Some("lz4hc") => decompress_lz4(data, page_size),
+        Some("zstd") => decompress_zstd(data, page_size),
+        _ if data.starts_with(&ZSTD_MAGIC) => decompress_zstd(data, page_size),
+        _ => lzo::decompress(data, page_size)
+            .filter(|p| p.len() == page_size)
+            .or_else(|| decompress_lz4(data, page_size)),
+    }?;
+    (page.len() == page_size).then_some(page)
+}
+
+fn decompress_lz4(data: &[u8], page_size: usize) -> Option<Vec<u8>> {
+    let mut page = vec![0u8; page_size];
+    let written = lz4_flex::block::decompress_into(data, &mut page).ok()?;
+    page.truncate(written);
+    Some(page)
+}
+
+fn decompress_zstd(data: &[u8], page_size: usize) -> Option<Vec<u8>> {
+    let mut page = vec![0u8; page_size];
+    let written = ruzstd::FrameDecoder::new()
+        .decode_all(data, &mut page)
+        .ok()?;
+    page.truncate(written);
+    Some(page)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decompress_page_checks_length() {
+        let page: Vec<u8> = (0..4096u32).map(|i| (i % 7) as u8).collect();
+        let compressed = lz4_flex::block::compress(&page);
+        assert_eq!(
+            decompress_page(Some("lz4"), &compressed, 4096),
+            Some(page.clone())
+        );
+        // Autodetection falls through to lz4 when the data is not an lzo stream
+        assert_eq!(decompress_page(None, &compressed, 4096), Some(page));
+        // Output that is not exactly one page is rejected
+        assert_eq!(decompress_page(Some("lz4"), &compressed, 8192), None);
+    }
+}
@@ -57,7 +57,6 @@ pub struct DwarfSymbols {
     #[serde(default)]
     user_types: HashMap<String, DwarfStruct>,
     #[serde(default)]
-    #[allow(dead_code)]
     base_types: Option<HashMap<String, Value>>,
 }
 
@@ -128,6 +127,20 @@ pub fn get_struct_offsets(&self, struct_name: &str) -> Option<HashMap<String, us
             })
     }
 
+    /// Get the size and byte order ("little" / "big") of the "pointer" base type
+    pub fn get_pointer_type(&self) -> Option<(u64, &str)> {
+        let pointer = self.base_types.as_ref()?.get("pointer")?;
+        Some((
+            pointer.get("size")?.as_u64()?,
+            pointer.get("endian")?.as_str()?,
+        ))
+    }
+
+    /// Get the size of a structure in bytes
+    pub fn get_struct_size(&self, struct_name: &str) -> Option<usize> {
+        self.user_types.get(struct_name).map(|s| s.size)
+    }
+
     /// Get all structures
     #[allow(dead_code)]
     pub fn get_structs(&self) -> &HashMap<String, DwarfStruct> {
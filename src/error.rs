@@ -6,7 +6,6 @@ pub enum AnalysisError {
     IoError(std::io::Error),
     #[allow(dead_code)]
     MemoryMapError(String),
-    #[allow(dead_code)]
     ParseError(String),
     SymbolError(String),
     SymbolNotFound(String),
@@ -1,7 +1,13 @@
 //! CSV output formatter for the Linux Memory Parser tool
 use crate::error::AnalysisError;
 use crate::formats::traits::OutputFormatter;
-use crate::kernel::{ConnectionInfo, ModuleInfo, ProcessInfo};
+use crate::kernel::{
+    format_unix_time, join_display, BashHistoryInfo, BpfMapInfo, BpfProgInfo, ConnectionInfo,
+    ContainerInfo, ElfInfo, InlineHookInfo, InterfaceInfo, LibraryInfo, MapsInfo, ModuleInfo,
+    NeighbourInfo, NetfilterHookInfo, NotifierInfo, OpsPointerInfo, PageInfo, ProcessInfo,
+    ProcessNamespaceInfo, RawSocketInfo, RouteInfo, ScanMatchInfo, StringInfo, TmpfsFileInfo,
+    TraceHookInfo, TtyHookInfo, UnixSocketInfo,
+};
 use csv::Writer;
 
 /// CSV formatter that outputs data in comma-separated values format
@@ -54,6 +60,9 @@ fn format_connections(&self, connections: &[ConnectionInfo]) -> Result<String, A
             "remote_port",
             "state",
             "pid",
+            "fd",
+            "comm",
+            "inode",
         ])?;
 
         // Write data rows
@@ -66,6 +75,9 @@ fn format_connections(&self, connections: &[ConnectionInfo]) -> Result<String, A
                 conn.remote_port.to_string(),
                 conn.state.clone(),
                 conn.pid.to_string(),
+                conn.fd.map_or(String::new(), |fd| fd.to_string()),
+                conn.comm.clone(),
+                conn.inode.to_string(),
             ])?;
         }
 
@@ -94,4 +106,783 @@ fn format_modules(&self, modules: &[ModuleInfo]) -> Result<String, AnalysisError
         let data = wtr.into_inner()?;
         Ok(String::from_utf8(data)?)
     }
+
+    fn format_tmpfs_files(&self, files: &[TmpfsFileInfo]) -> Result<String, AnalysisError> {
+        let mut wtr = Writer::from_writer(vec![]);
+
+        // Write header
+        wtr.write_record(&[
+            "fs_type",
+            "mount",
+            "path",
+            "origin",
+            "inode",
+            "file_type",
+            "mode",
+            "uid",
+            "gid",
+            "size",
+            "cached_pages",
+            "atime",
+            "mtime",
+            "ctime",
+            "exported",
+        ])?;
+
+        // Write data rows
+        for file in files {
+            wtr.write_record(&[
+                file.fs_type.clone(),
+                file.mount.clone(),
+                file.path.clone(),
+                file.origin.clone(),
+                file.inode.to_string(),
+                file.file_type.clone(),
+                file.mode.clone(),
+                file.uid.to_string(),
+                file.gid.to_string(),
+                file.size.to_string(),
+                file.cached_pages.to_string(),
+                format_unix_time(file.atime),
+                format_unix_time(file.mtime),
+                format_unix_time(file.ctime),
+                file.exported.clone().unwrap_or_default(),
+            ])?;
+        }
+
+        wtr.flush()?;
+        let data = wtr.into_inner()?;
+        Ok(String::from_utf8(data)?)
+    }
+
+    fn format_bash_history(&self, entries: &[BashHistoryInfo]) -> Result<String, AnalysisError> {
+        let mut wtr = Writer::from_writer(vec![]);
+
+        // Write header
+        wtr.write_record(&["pid", "uid", "comm", "timestamp", "command"])?;
+
+        // Write data rows
+        for entry in entries {
+            wtr.write_record(&[
+                entry.pid.to_string(),
+                entry.uid.to_string(),
+                entry.comm.clone(),
+                format_unix_time(entry.timestamp),
+                entry.command.clone(),
+            ])?;
+        }
+
+        wtr.flush()?;
+        let data = wtr.into_inner()?;
+        Ok(String::from_utf8(data)?)
+    }
+
+    fn format_interfaces(&self, interfaces: &[InterfaceInfo]) -> Result<String, AnalysisError> {
+        let mut wtr = Writer::from_writer(vec![]);
+
+        // Write header
+        wtr.write_record(&[
+            "net_namespace",
+            "name",
+            "mac",
+            "mtu",
+            "flags",
+            "promiscuous",
+            "ipv4",
+            "ipv6",
+        ])?;
+
+        // Write data rows
+        for iface in interfaces {
+            wtr.write_record(&[
+                iface.net_namespace.to_string(),
+                iface.name.clone(),
+                iface.mac.clone(),
+                iface.mtu.to_string(),
+                iface.flags.clone(),
+                iface.promiscuous.to_string(),
+                iface.ipv4.join(" "),
+                iface.ipv6.join(" "),
+            ])?;
+        }
+
+        wtr.flush()?;
+        let data = wtr.into_inner()?;
+        Ok(String::from_utf8(data)?)
+    }
+
+    fn format_neighbours(&self, neighbours: &[NeighbourInfo]) -> Result<String, AnalysisError> {
+        let mut wtr = Writer::from_writer(vec![]);
+
+        // Write header
+        wtr.write_record(&[
+            "net_namespace",
+            "family",
+            "address",
+            "mac",
+            "interface",
+            "state",
+        ])?;
+
+        // Write data rows
+        for neigh in neighbours {
+            wtr.write_record(&[
+                neigh.net_namespace.to_string(),
+                neigh.family.clone(),
+                neigh.address.clone(),
+                neigh.mac.clone(),
+                neigh.interface.clone(),
+                neigh.state.clone(),
+            ])?;
+        }
+
+        wtr.flush()?;
+        let data = wtr.into_inner()?;
+        Ok(String::from_utf8(data)?)
+    }
+
+    fn format_routes(&self, routes: &[RouteInfo]) -> Result<String, AnalysisError> {
+        let mut wtr = Writer::from_writer(vec![]);
+
+        // Write header
+        wtr.write_record(&[
+            "net_namespace",
+            "family",
+            "table",
+            "destination",
+            "gateway",
+            "interface",
+            "metric",
+            "route_type",
+        ])?;
+
+        // Write data rows
+        for route in routes {
+            wtr.write_record(&[
+                route.net_namespace.to_string(),
+                route.family.clone(),
+                route.table.clone(),
+                route.destination.clone(),
+                route.gateway.clone(),
+                route.interface.clone(),
+                route.metric.to_string(),
+                route.route_type.clone(),
+            ])?;
+        }
+
+        wtr.flush()?;
+        let data = wtr.into_inner()?;
+        Ok(String::from_utf8(data)?)
+    }
+
+    fn format_unix_sockets(&self, sockets: &[UnixSocketInfo]) -> Result<String, AnalysisError> {
+        let mut wtr = Writer::from_writer(vec![]);
+
+        // Write header
+        wtr.write_record(&[
+            "net_namespace",
+            "inode",
+            "socket_type",
+            "state",
+            "path",
+            "owners",
+            "peer_inode",
+            "peer_pids",
+        ])?;
+
+        // Write data rows
+        for sock in sockets {
+            wtr.write_record(&[
+                sock.net_namespace.to_string(),
+                sock.inode.to_string(),
+                sock.socket_type.clone(),
+                sock.state.clone(),
+                sock.path.clone(),
+                join_display(&sock.owners),
+                sock.peer_inode.to_string(),
+                join_display(&sock.peer_pids),
+            ])?;
+        }
+
+        wtr.flush()?;
+        let data = wtr.into_inner()?;
+        Ok(String::from_utf8(data)?)
+    }
+
+    fn format_raw_sockets(&self, sockets: &[RawSocketInfo]) -> Result<String, AnalysisError> {
+        let mut wtr = Writer::from_writer(vec![]);
+
+        // Write header
+        wtr.write_record(&[
+            "net_namespace",
+            "family",
+            "protocol",
+            "socket_type",
+            "interface",
+            "portid",
+            "inode",
+            "owners",
+            "filter_insns",
+            "filter",
+        ])?;
+
+        // Write data rows
+        for sock in sockets {
+            wtr.write_record(&[
+                sock.net_namespace.to_string(),
+                sock.family.clone(),
+                sock.protocol.clone(),
+                sock.socket_type.clone(),
+                sock.interface.clone(),
+                sock.portid.map_or(String::new(), |p| p.to_string()),
+                sock.inode.to_string(),
+                join_display(&sock.owners),
+                sock.filter_insns.to_string(),
+                sock.filter.clone(),
+            ])?;
+        }
+
+        wtr.flush()?;
+        let data = wtr.into_inner()?;
+        Ok(String::from_utf8(data)?)
+    }
+
+    fn format_bpf_programs(&self, programs: &[BpfProgInfo]) -> Result<String, AnalysisError> {
+        let mut wtr = Writer::from_writer(vec![]);
+
+        // Write header
+        wtr.write_record(&[
+            "id",
+            "prog_type",
+            "name",
+            "tag",
+            "load_time",
+            "uid",
+            "attach",
+            "insns",
+            "jit_addr",
+            "jit_size",
+            "map_ids",
+            "exported",
+        ])?;
+
+        // Write data rows
+        for prog in programs {
+            wtr.write_record(&[
+                prog.id.to_string(),
+                prog.prog_type.clone(),
+                prog.name.clone(),
+                prog.tag.clone(),
+                prog.load_time.to_string(),
+                prog.uid.map(|u| u.to_string()).unwrap_or_default(),
+                prog.attach.join("; "),
+                prog.insns.to_string(),
+                format!("{:#x}", prog.jit_addr),
+                prog.jit_size.to_string(),
+                join_display(&prog.map_ids),
+                prog.exported.join("; "),
+            ])?;
+        }
+
+        wtr.flush()?;
+        let data = wtr.into_inner()?;
+        Ok(String::from_utf8(data)?)
+    }
+
+    fn format_bpf_maps(&self, maps: &[BpfMapInfo]) -> Result<String, AnalysisError> {
+        let mut wtr = Writer::from_writer(vec![]);
+
+        // Write header
+        wtr.write_record(&[
+            "id",
+            "map_type",
+            "name",
+            "key_size",
+            "value_size",
+            "max_entries",
+            "flags",
+        ])?;
+
+        // Write data rows
+        for map in maps {
+            wtr.write_record(&[
+                map.id.to_string(),
+                map.map_type.clone(),
+                map.name.clone(),
+                map.key_size.to_string(),
+                map.value_size.to_string(),
+                map.max_entries.to_string(),
+                format!("{:#x}", map.flags),
+            ])?;
+        }
+
+        wtr.flush()?;
+        let data = wtr.into_inner()?;
+        Ok(String::from_utf8(data)?)
+    }
+
+    fn format_inline_hooks(&self, hooks: &[InlineHookInfo]) -> Result<String, AnalysisError> {
+        let mut wtr = Writer::from_writer(vec![]);
+
+        // Write header
+        wtr.write_record(&[
+            "function",
+            "address",
+            "hook_offset",
+            "kind",
+            "target",
+            "original",
+            "current",
+        ])?;
+
+        // Write data rows
+        for hook in hooks {
+            wtr.write_record(&[
+                hook.function.clone(),
+                format!("{:#x}", hook.address),
+                hook.hook_offset.to_string(),
+                hook.kind.clone(),
+                hook.target.clone(),
+                hook.original.clone(),
+                hook.current.clone(),
+            ])?;
+        }
+
+        wtr.flush()?;
+        let data = wtr.into_inner()?;
+        Ok(String::from_utf8(data)?)
+    }
+
+    fn format_trace_hooks(&self, hooks: &[TraceHookInfo]) -> Result<String, AnalysisError> {
+        let mut wtr = Writer::from_writer(vec![]);
+
+        // Write header
+        wtr.write_record(&[
+            "hook_type",
+            "target",
+            "handler",
+            "callback",
+            "symbol",
+            "owner",
+            "details",
+        ])?;
+
+        // Write data rows
+        for hook in hooks {
+            wtr.write_record(&[
+                hook.hook_type.clone(),
+                hook.target.clone(),
+                hook.handler.clone(),
+                format!("{:#x}", hook.callback),
+                hook.symbol.clone(),
+                hook.owner.clone(),
+                hook.details.clone(),
+            ])?;
+        }
+
+        wtr.flush()?;
+        let data = wtr.into_inner()?;
+        Ok(String::from_utf8(data)?)
+    }
+
+    fn format_netfilter_hooks(&self, hooks: &[NetfilterHookInfo]) -> Result<String, AnalysisError> {
+        let mut wtr = Writer::from_writer(vec![]);
+
+        // Write header
+        wtr.write_record(&[
+            "net_namespace",
+            "family",
+            "hook",
+            "priority",
+            "callback",
+            "symbol",
+            "owner",
+        ])?;
+
+        // Write data rows
+        for hook in hooks {
+            wtr.write_record(&[
+                hook.net_namespace.to_string(),
+                hook.family.clone(),
+                hook.hook.clone(),
+                hook.priority.to_string(),
+                format!("{:#x}", hook.callback),
+                hook.symbol.clone(),
+                hook.owner.clone(),
+            ])?;
+        }
+
+        wtr.flush()?;
+        let data = wtr.into_inner()?;
+        Ok(String::from_utf8(data)?)
+    }
+
+    fn format_notifiers(&self, notifiers: &[NotifierInfo]) -> Result<String, AnalysisError> {
+        let mut wtr = Writer::from_writer(vec![]);
+
+        // Write header
+        wtr.write_record(&["chain", "priority", "callback", "symbol", "owner"])?;
+
+        // Write data rows
+        for notifier in notifiers {
+            wtr.write_record(&[
+                notifier.chain.clone(),
+                notifier.priority.to_string(),
+                format!("{:#x}", notifier.callback),
+                notifier.symbol.clone(),
+                notifier.owner.clone(),
+            ])?;
+        }
+
+        wtr.flush()?;
+        let data = wtr.into_inner()?;
+        Ok(String::from_utf8(data)?)
+    }
+
+    fn format_tty_hooks(&self, hooks: &[TtyHookInfo]) -> Result<String, AnalysisError> {
+        let mut wtr = Writer::from_writer(vec![]);
+
+        // Write header
+        wtr.write_record(&[
+            "tty", "driver", "ldisc", "handler", "callback", "symbol", "owner",
+        ])?;
+
+        // Write data rows
+        for hook in hooks {
+            wtr.write_record(&[
+                hook.tty.clone(),
+                hook.driver.clone(),
+                hook.ldisc.clone(),
+                hook.handler.clone(),
+                format!("{:#x}", hook.callback),
+                hook.symbol.clone(),
+                hook.owner.clone(),
+            ])?;
+        }
+
+        wtr.flush()?;
+        let data = wtr.into_inner()?;
+        Ok(String::from_utf8(data)?)
+    }
+
+    fn format_ops_pointers(&self, pointers: &[OpsPointerInfo]) -> Result<String, AnalysisError> {
+        let mut wtr = Writer::from_writer(vec![]);
+
+        // Write header
+        wtr.write_record(&["table", "member", "pointer", "symbol", "owner"])?;
+
+        // Write data rows
+        for entry in pointers {
+            wtr.write_record(&[
+                entry.table.clone(),
+                entry.member.clone(),
+                format!("{:#x}", entry.pointer),
+                entry.symbol.clone(),
+                entry.owner.clone(),
+            ])?;
+        }
+
+        wtr.flush()?;
+        let data = wtr.into_inner()?;
+        Ok(String::from_utf8(data)?)
+    }
+
+    fn format_process_namespaces(
+        &self,
+        processes: &[ProcessNamespaceInfo],
+    ) -> Result<String, AnalysisError> {
+        let mut wtr = Writer::from_writer(vec![]);
+
+        // Write header
+        wtr.write_record(&[
+            "pid",
+            "ns_pid",
+            "comm",
+            "user_ns",
+            "mnt_ns",
+            "uts_ns",
+            "ipc_ns",
+            "pid_ns",
+            "net_ns",
+            "cgroup_ns",
+            "time_ns",
+            "hostname",
+            "cgroup",
+        ])?;
+
+        // Write data rows
+        for process in processes {
+            wtr.write_record(&[
+                process.pid.to_string(),
+                process.ns_pid.to_string(),
+                process.comm.clone(),
+                process.user_ns.to_string(),
+                process.mnt_ns.to_string(),
+                process.uts_ns.to_string(),
+                process.ipc_ns.to_string(),
+                process.pid_ns.to_string(),
+                process.net_ns.to_string(),
+                process.cgroup_ns.to_string(),
+                process.time_ns.to_string(),
+                process.hostname.clone(),
+                process.cgroup.clone(),
+            ])?;
+        }
+
+        wtr.flush()?;
+        let data = wtr.into_inner()?;
+        Ok(String::from_utf8(data)?)
+    }
+
+    fn format_containers(&self, containers: &[ContainerInfo]) -> Result<String, AnalysisError> {
+        let mut wtr = Writer::from_writer(vec![]);
+
+        // Write header
+        wtr.write_record(&[
+            "container",
+            "runtime",
+            "pod",
+            "hostname",
+            "cgroup",
+            "pid_ns",
+            "mnt_ns",
+            "net_ns",
+            "user_ns",
+            "processes",
+            "pids",
+        ])?;
+
+        // Write data rows
+        for container in containers {
+            wtr.write_record(&[
+                container.container.clone(),
+                container.runtime.clone(),
+                container.pod.clone(),
+                container.hostname.clone(),
+                container.cgroup.clone(),
+                container.pid_ns.to_string(),
+                container.mnt_ns.to_string(),
+                container.net_ns.to_string(),
+                container.user_ns.to_string(),
+                container.processes.to_string(),
+                join_display(&container.pids),
+            ])?;
+        }
+
+        wtr.flush()?;
+        let data = wtr.into_inner()?;
+        Ok(String::from_utf8(data)?)
+    }
+
+    fn format_libraries(&self, libraries: &[LibraryInfo]) -> Result<String, AnalysisError> {
+        let mut wtr = Writer::from_writer(vec![]);
+
+        // Write header
+        wtr.write_record(&[
+            "pid",
+            "comm",
+            "base",
+            "mapped",
+            "linked",
+            "deleted",
+            "memfd",
+            "preloaded",
+            "path",
+        ])?;
+
+        // Write data rows
+        for lib in libraries {
+            wtr.write_record(&[
+                lib.pid.to_string(),
+                lib.comm.clone(),
+                format!("{:#x}", lib.base),
+                lib.mapped.to_string(),
+                lib.linked.to_string(),
+                lib.deleted.to_string(),
+                lib.memfd.to_string(),
+                lib.preloaded.to_string(),
+                lib.path.clone(),
+            ])?;
+        }
+
+        wtr.flush()?;
+        let data = wtr.into_inner()?;
+        Ok(String::from_utf8(data)?)
+    }
+
+    fn format_elfs(&self, elfs: &[ElfInfo]) -> Result<String, AnalysisError> {
+        let mut wtr = Writer::from_writer(vec![]);
+
+        // Write header
+        wtr.write_record(&[
+            "pid", "comm", "start", "end", "elf_type", "build_id", "deleted", "path", "exported",
+        ])?;
+
+        // Write data rows
+        for elf in elfs {
+            wtr.write_record(&[
+                elf.pid.to_string(),
+                elf.comm.clone(),
+                format!("{:#x}", elf.start),
+                format!("{:#x}", elf.end),
+                elf.elf_type.clone(),
+                elf.build_id.clone(),
+                elf.deleted.to_string(),
+                elf.path.clone(),
+                elf.exported.clone().unwrap_or_default(),
+            ])?;
+        }
+
+        wtr.flush()?;
+        let data = wtr.into_inner()?;
+        Ok(String::from_utf8(data)?)
+    }
+
+    fn format_scan_matches(&self, matches: &[ScanMatchInfo]) -> Result<String, AnalysisError> {
+        let mut wtr = Writer::from_writer(vec![]);
+
+        // Write header
+        wtr.write_record(&[
+            "rule", "string", "space", "address", "pid", "comm", "context", "data",
+        ])?;
+
+        // Write data rows
+        for m in matches {
+            wtr.write_record(&[
+                m.rule.clone(),
+                m.string.clone(),
+                m.space.clone(),
+                format!("{:#x}", m.address),
+                m.pid.map(|p| p.to_string()).unwrap_or_default(),
+                m.comm.clone(),
+                m.context.clone(),
+                m.data.clone(),
+            ])?;
+        }
+
+        wtr.flush()?;
+        let data = wtr.into_inner()?;
+        Ok(String::from_utf8(data)?)
+    }
+
+    fn format_strings(&self, strings: &[StringInfo]) -> Result<String, AnalysisError> {
+        let mut wtr = Writer::from_writer(vec![]);
+
+        // Write header
+        wtr.write_record(&["offset", "physical", "kernel", "owners", "string"])?;
+
+        // Write data rows
+        for s in strings {
+            wtr.write_record(&[
+                s.offset.to_string(),
+                s.physical.map(|p| format!("{:#x}", p)).unwrap_or_default(),
+                s.kernel
+                    .iter()
+                    .map(|a| format!("{:#x}", a))
+                    .collect::<Vec<_>>()
+                    .join("; "),
+                s.owners
+                    .iter()
+                    .map(|o| {
+                        format!(
+                            "{}/{}@{:#x} {:#x}-{:#x} {}",
+                            o.pid, o.comm, o.address, o.vma_start, o.vma_end, o.path
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join("; "),
+                s.string.clone(),
+            ])?;
+        }
+
+        wtr.flush()?;
+        let data = wtr.into_inner()?;
+        Ok(String::from_utf8(data)?)
+    }
+
+    fn format_page_info(&self, pages: &[PageInfo]) -> Result<String, AnalysisError> {
+        let mut wtr = Writer::from_writer(vec![]);
+
+        // Write header
+        wtr.write_record(&[
+            "physical",
+            "pfn",
+            "page",
+            "flags",
+            "flag_names",
+            "head",
+            "mapping",
+            "mapping_type",
+            "inode",
+            "path",
+            "index",
+            "refcount",
+            "mapcount",
+            "mapped_by",
+        ])?;
+
+        // Write data rows
+        for p in pages {
+            wtr.write_record(&[
+                format!("{:#x}", p.physical),
+                p.pfn.to_string(),
+                format!("{:#x}", p.page),
+                format!("{:#x}", p.flags),
+                p.flag_names.join("|"),
+                p.head.map(|h| h.to_string()).unwrap_or_default(),
+                format!("{:#x}", p.mapping),
+                p.mapping_type.clone(),
+                p.inode.map(|i| i.to_string()).unwrap_or_default(),
+                p.path.clone(),
+                p.index.to_string(),
+                p.refcount.to_string(),
+                p.mapcount.to_string(),
+                p.mapped_by.join("; "),
+            ])?;
+        }
+
+        wtr.flush()?;
+        let data = wtr.into_inner()?;
+        Ok(String::from_utf8(data)?)
+    }
+
+    fn format_maps(&self, maps: &[MapsInfo]) -> Result<String, AnalysisError> {
+        let mut wtr = Writer::from_writer(vec![]);
+
+        // Write header
+        wtr.write_record(&[
+            "pid",
+            "comm",
+            "start",
+            "end",
+            "perms",
+            "pgoff",
+            "resident",
+            "swapped",
+            "swap_readable",
+            "unavailable",
+            "path",
+        ])?;
+
+        // Write data rows
+        for m in maps {
+            wtr.write_record(&[
+                m.pid.to_string(),
+                m.comm.clone(),
+                format!("{:#x}", m.start),
+                format!("{:#x}", m.end),
+                m.perms.clone(),
+                m.pgoff.to_string(),
+                m.resident.to_string(),
+                m.swapped.to_string(),
+                m.swap_readable.to_string(),
+                m.unavailable.to_string(),
+                m.path.clone(),
+            ])?;
+        }
+
+        wtr.flush()?;
+        let data = wtr.into_inner()?;
+        Ok(String::from_utf8(data)?)
+    }
 }
@@ -1,7 +1,13 @@
 //! JSON output formatter for the Linux Memory Parser tool
 use crate::error::AnalysisError;
 use crate::formats::traits::OutputFormatter;
-use crate::kernel::{ConnectionInfo, ModuleInfo, ProcessInfo};
+use crate::kernel::{
+    BashHistoryInfo, BpfMapInfo, BpfProgInfo, ConnectionInfo, ContainerInfo, ElfInfo,
+    InlineHookInfo, InterfaceInfo, LibraryInfo, MapsInfo, ModuleInfo, NeighbourInfo,
+    NetfilterHookInfo, NotifierInfo, OpsPointerInfo, PageInfo, ProcessInfo, ProcessNamespaceInfo,
+    RawSocketInfo, RouteInfo, ScanMatchInfo, StringInfo, TmpfsFileInfo, TraceHookInfo, TtyHookInfo,
+    UnixSocketInfo,
+};
 use serde_json;
 
 #[derive(serde::Serialize)]
@@ -51,4 +57,283 @@ fn format_modules(&self, modules: &[ModuleInfo]) -> Result<String, AnalysisError
         let json = serde_json::to_string_pretty(&wrapper)?;
         Ok(json)
     }
+
+    fn format_tmpfs_files(&self, files: &[TmpfsFileInfo]) -> Result<String, AnalysisError> {
+        let wrapper = OutputWrapper {
+            plugin: "tmpfs".to_string(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            count: files.len(),
+            results: files.to_vec(),
+        };
+
+        let json = serde_json::to_string_pretty(&wrapper)?;
+        Ok(json)
+    }
+
+    fn format_bash_history(&self, entries: &[BashHistoryInfo]) -> Result<String, AnalysisError> {
+        let wrapper = OutputWrapper {
+            plugin: "bash".to_string(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            count: entries.len(),
+            results: entries.to_vec(),
+        };
+
+        let json = serde_json::to_string_pretty(&wrapper)?;
+        Ok(json)
+    }
+
+    fn format_interfaces(&self, interfaces: &[InterfaceInfo]) -> Result<String, AnalysisError> {
+        let wrapper = OutputWrapper {
+            plugin: "ifconfig".to_string(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            count: interfaces.len(),
+            results: interfaces.to_vec(),
+        };
+
+        let json = serde_json::to_string_pretty(&wrapper)?;
+        Ok(json)
+    }
+
+    fn format_neighbours(&self, neighbours: &[NeighbourInfo]) -> Result<String, AnalysisError> {
+        let wrapper = OutputWrapper {
+            plugin: "arp".to_string(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            count: neighbours.len(),
+            results: neighbours.to_vec(),
+        };
+
+        let json = serde_json::to_string_pretty(&wrapper)?;
+        Ok(json)
+    }
+
+    fn format_routes(&self, routes: &[RouteInfo]) -> Result<String, AnalysisError> {
+        let wrapper = OutputWrapper {
+            plugin: "routes".to_string(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            count: routes.len(),
+            results: routes.to_vec(),
+        };
+
+        let json = serde_json::to_string_pretty(&wrapper)?;
+        Ok(json)
+    }
+
+    fn format_unix_sockets(&self, sockets: &[UnixSocketInfo]) -> Result<String, AnalysisError> {
+        let wrapper = OutputWrapper {
+            plugin: "unix".to_string(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            count: sockets.len(),
+            results: sockets.to_vec(),
+        };
+
+        let json = serde_json::to_string_pretty(&wrapper)?;
+        Ok(json)
+    }
+
+    fn format_raw_sockets(&self, sockets: &[RawSocketInfo]) -> Result<String, AnalysisError> {
+        let wrapper = OutputWrapper {
+            plugin: "rawsockets".to_string(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            count: sockets.len(),
+            results: sockets.to_vec(),
+        };
+
+        let json = serde_json::to_string_pretty(&wrapper)?;
+        Ok(json)
+    }
+
+    fn format_bpf_programs(&self, programs: &[BpfProgInfo]) -> Result<String, AnalysisError> {
+        let wrapper = OutputWrapper {
+            plugin: "bpf".to_string(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            count: programs.len(),
+            results: programs.to_vec(),
+        };
+
+        let json = serde_json::to_string_pretty(&wrapper)?;
+        Ok(json)
+    }
+
+    fn format_bpf_maps(&self, maps: &[BpfMapInfo]) -> Result<String, AnalysisError> {
+        let wrapper = OutputWrapper {
+            plugin: "bpfmaps".to_string(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            count: maps.len(),
+            results: maps.to_vec(),
+        };
+
+        let json = serde_json::to_string_pretty(&wrapper)?;
+        Ok(json)
+    }
+
+    fn format_inline_hooks(&self, hooks: &[InlineHookInfo]) -> Result<String, AnalysisError> {
+        let wrapper = OutputWrapper {
+            plugin: "inlinehooks".to_string(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            count: hooks.len(),
+            results: hooks.to_vec(),
+        };
+
+        let json = serde_json::to_string_pretty(&wrapper)?;
+        Ok(json)
+    }
+
+    fn format_trace_hooks(&self, hooks: &[TraceHookInfo]) -> Result<String, AnalysisError> {
+        let wrapper = OutputWrapper {
+            plugin: "tracehooks".to_string(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            count: hooks.len(),
+            results: hooks.to_vec(),
+        };
+
+        let json = serde_json::to_string_pretty(&wrapper)?;
+        Ok(json)
+    }
+
+    fn format_netfilter_hooks(&self, hooks: &[NetfilterHookInfo]) -> Result<String, AnalysisError> {
+        let wrapper = OutputWrapper {
+            plugin: "netfilter".to_string(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            count: hooks.len(),
+            results: hooks.to_vec(),
+        };
+
+        let json = serde_json::to_string_pretty(&wrapper)?;
+        Ok(json)
+    }
+
+    fn format_notifiers(&self, notifiers: &[NotifierInfo]) -> Result<String, AnalysisError> {
+        let wrapper = OutputWrapper {
+            plugin: "notifiers".to_string(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            count: notifiers.len(),
+            results: notifiers.to_vec(),
+        };
+
+        let json = serde_json::to_string_pretty(&wrapper)?;
+        Ok(json)
+    }
+
+    fn format_tty_hooks(&self, hooks: &[TtyHookInfo]) -> Result<String, AnalysisError> {
+        let wrapper = OutputWrapper {
+            plugin: "ttycheck".to_string(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            count: hooks.len(),
+            results: hooks.to_vec(),
+        };
+
+        let json = serde_json::to_string_pretty(&wrapper)?;
+        Ok(json)
+    }
+
+    fn format_ops_pointers(&self, pointers: &[OpsPointerInfo]) -> Result<String, AnalysisError> {
+        let wrapper = OutputWrapper {
+            plugin: "opscheck".to_string(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            count: pointers.len(),
+            results: pointers.to_vec(),
+        };
+
+        let json = serde_json::to_string_pretty(&wrapper)?;
+        Ok(json)
+    }
+
+    fn format_process_namespaces(
+        &self,
+        processes: &[ProcessNamespaceInfo],
+    ) -> Result<String, AnalysisError> {
+        let wrapper = OutputWrapper {
+            plugin: "namespaces".to_string(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            count: processes.len(),
+            results: processes.to_vec(),
+        };
+
+        let json = serde_json::to_string_pretty(&wrapper)?;
+        Ok(json)
+    }
+
+    fn format_containers(&self, containers: &[ContainerInfo]) -> Result<String, AnalysisError> {
+        let wrapper = OutputWrapper {
+            plugin: "containers".to_string(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            count: containers.len(),
+            results: containers.to_vec(),
+        };
+
+        let json = serde_json::to_string_pretty(&wrapper)?;
+        Ok(json)
+    }
+
+    fn format_libraries(&self, libraries: &[LibraryInfo]) -> Result<String, AnalysisError> {
+        let wrapper = OutputWrapper {
+            plugin: "ldrmodules".to_string(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            count: libraries.len(),
+            results: libraries.to_vec(),
+        };
+
+        let json = serde_json::to_string_pretty(&wrapper)?;
+        Ok(json)
+    }
+
+    fn format_elfs(&self, elfs: &[ElfInfo]) -> Result<String, AnalysisError> {
+        let wrapper = OutputWrapper {
+            plugin: "elfs".to_string(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            count: elfs.len(),
+            results: elfs.to_vec(),
+        };
+
+        let json = serde_json::to_string_pretty(&wrapper)?;
+        Ok(json)
+    }
+
+    fn format_scan_matches(&self, matches: &[ScanMatchInfo]) -> Result<String, AnalysisError> {
+        let wrapper = OutputWrapper {
+            plugin: "yarascan".to_string(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            count: matches.len(),
+            results: matches.to_vec(),
+        };
+
+        let json = serde_json::to_string_pretty(&wrapper)?;
+        Ok(json)
+    }
+
+    fn format_strings(&self, strings: &[StringInfo]) -> Result<String, AnalysisError> {
+        let wrapper = OutputWrapper {
+            plugin: "strings".to_string(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            count: strings.len(),
+            results: strings.to_vec(),
+        };
+
+        let json = serde_json::to_string_pretty(&wrapper)?;
+        Ok(json)
+    }
+
+    fn format_page_info(&self, pages: &[PageInfo]) -> Result<String, AnalysisError> {
+        let wrapper = OutputWrapper {
+            plugin: "pageinfo".to_string(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            count: pages.len(),
+            results: pages.to_vec(),
+        };
+
+        let json = serde_json::to_string_pretty(&wrapper)?;
+        Ok(json)
+    }
+
+    fn format_maps(&self, maps: &[MapsInfo]) -> Result<String, AnalysisError> {
+        let wrapper = OutputWrapper {
+            plugin: "maps".to_string(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            count: maps.len(),
+            results: maps.to_vec(),
+        };
+
+        let json = serde_json::to_string_pretty(&wrapper)?;
+        Ok(json)
+    }
 }
@@ -1,7 +1,13 @@
 //! JSONL (JSON Lines) output formatter for the Linux Memory Parser tool
 use crate::error::AnalysisError;
 use crate::formats::traits::OutputFormatter;
-use crate::kernel::{ConnectionInfo, ModuleInfo, ProcessInfo};
+use crate::kernel::{
+    BashHistoryInfo, BpfMapInfo, BpfProgInfo, ConnectionInfo, ContainerInfo, ElfInfo,
+    InlineHookInfo, InterfaceInfo, LibraryInfo, MapsInfo, ModuleInfo, NeighbourInfo,
+    NetfilterHookInfo, NotifierInfo, OpsPointerInfo, PageInfo, ProcessInfo, ProcessNamespaceInfo,
+    RawSocketInfo, RouteInfo, ScanMatchInfo, StringInfo, TmpfsFileInfo, TraceHookInfo, TtyHookInfo,
+    UnixSocketInfo,
+};
 use serde_json;
 
 /// JSONL formatter that outputs data as JSON objects, one per line
@@ -43,4 +49,283 @@ fn format_modules(&self, modules: &[ModuleInfo]) -> Result<String, AnalysisError
 
         Ok(output)
     }
+
+    fn format_tmpfs_files(&self, files: &[TmpfsFileInfo]) -> Result<String, AnalysisError> {
+        let mut output = String::new();
+
+        for file in files {
+            let line = serde_json::to_string(file)?;
+            output.push_str(&line);
+            output.push('\n');
+        }
+
+        Ok(output)
+    }
+
+    fn format_bash_history(&self, entries: &[BashHistoryInfo]) -> Result<String, AnalysisError> {
+        let mut output = String::new();
+
+        for entry in entries {
+            let line = serde_json::to_string(entry)?;
+            output.push_str(&line);
+            output.push('\n');
+        }
+
+        Ok(output)
+    }
+
+    fn format_interfaces(&self, interfaces: &[InterfaceInfo]) -> Result<String, AnalysisError> {
+        let mut output = String::new();
+
+        for iface in interfaces {
+            let line = serde_json::to_string(iface)?;
+            output.push_str(&line);
+            output.push('\n');
+        }
+
+        Ok(output)
+    }
+
+    fn format_neighbours(&self, neighbours: &[NeighbourInfo]) -> Result<String, AnalysisError> {
+        let mut output = String::new();
+
+        for neigh in neighbours {
+            let line = serde_json::to_string(neigh)?;
+            output.push_str(&line);
+            output.push('\n');
+        }
+
+        Ok(output)
+    }
+
+    fn format_routes(&self, routes: &[RouteInfo]) -> Result<String, AnalysisError> {
+        let mut output = String::new();
+
+        for route in routes {
+            let line = serde_json::to_string(route)?;
+            output.push_str(&line);
+            output.push('\n');
+        }
+
+        Ok(output)
+    }
+
+    fn format_unix_sockets(&self, sockets: &[UnixSocketInfo]) -> Result<String, AnalysisError> {
+        let mut output = String::new();
+
+        for sock in sockets {
+            let line = serde_json::to_string(sock)?;
+            output.push_str(&line);
+            output.push('\n');
+        }
+
+        Ok(output)
+    }
+
+    fn format_raw_sockets(&self, sockets: &[RawSocketInfo]) -> Result<String, AnalysisError> {
+        let mut output = String::new();
+
+        for sock in sockets {
+            let line = serde_json::to_string(sock)?;
+            output.push_str(&line);
+            output.push('\n');
+        }
+
+        Ok(output)
+    }
+
+    fn format_bpf_programs(&self, programs: &[BpfProgInfo]) -> Result<String, AnalysisError> {
+        let mut output = String::new();
+
+        for prog in programs {
+            let line = serde_json::to_string(prog)?;
+            output.push_str(&line);
+            output.push('\n');
+        }
+
+        Ok(output)
+    }
+
+    fn format_bpf_maps(&self, maps: &[BpfMapInfo]) -> Result<String, AnalysisError> {
+        let mut output = String::new();
+
+        for map in maps {
+            let line = serde_json::to_string(map)?;
+            output.push_str(&line);
+            output.push('\n');
+        }
+
+        Ok(output)
+    }
+
+    fn format_inline_hooks(&self, hooks: &[InlineHookInfo]) -> Result<String, AnalysisError> {
+        let mut output = String::new();
+
+        for hook in hooks {
+            let line = serde_json::to_string(hook)?;
+            output.push_str(&line);
+            output.push('\n');
+        }
+
+        Ok(output)
+    }
+
+    fn format_trace_hooks(&self, hooks: &[TraceHookInfo]) -> Result<String, AnalysisError> {
+        let mut output = String::new();
+
+        for hook in hooks {
+            let line = serde_json::to_string(hook)?;
+            output.push_str(&line);
+            output.push('\n');
+        }
+
+        Ok(output)
+    }
+
+    fn format_netfilter_hooks(&self, hooks: &[NetfilterHookInfo]) -> Result<String, AnalysisError> {
+        let mut output = String::new();
+
+        for hook in hooks {
+            let line = serde_json::to_string(hook)?;
+            output.push_str(&line);
+            output.push('\n');
+        }
+
+        Ok(output)
+    }
+
+    fn format_notifiers(&self, notifiers: &[NotifierInfo]) -> Result<String, AnalysisError> {
+        let mut output = String::new();
+
+        for notifier in notifiers {
+            let line = serde_json::to_string(notifier)?;
+            output.push_str(&line);
+            output.push('\n');
+        }
+
+        Ok(output)
+    }
+
+    fn format_tty_hooks(&self, hooks: &[TtyHookInfo]) -> Result<String, AnalysisError> {
+        let mut output = String::new();
+
+        for hook in hooks {
+            let line = serde_json::to_string(hook)?;
+            output.push_str(&line);
+            output.push('\n');
+        }
+
+        Ok(output)
+    }
+
+    fn format_ops_pointers(&self, pointers: &[OpsPointerInfo]) -> Result<String, AnalysisError> {
+        let mut output = String::new();
+
+        for entry in pointers {
+            let line = serde_json::to_string(entry)?;
+            output.push_str(&line);
+            output.push('\n');
+        }
+
+        Ok(output)
+    }
+
+    fn format_process_namespaces(
+        &self,
+        processes: &[ProcessNamespaceInfo],
+    ) -> Result<String, AnalysisError> {
+        let mut output = String::new();
+
+        for process in processes {
+            let line = serde_json::to_string(process)?;
+            output.push_str(&line);
+            output.push('\n');
+        }
+
+        Ok(output)
+    }
+
+    fn format_containers(&self, containers: &[ContainerInfo]) -> Result<String, AnalysisError> {
+        let mut output = String::new();
+
+        for container in containers {
+            let line = serde_json::to_string(container)?;
+            output.push_str(&line);
+            output.push('\n');
+        }
+
+        Ok(output)
+    }
+
+    fn format_libraries(&self, libraries: &[LibraryInfo]) -> Result<String, AnalysisError> {
+        let mut output = String::new();
+
+        for lib in libraries {
+            let line = serde_json::to_string(lib)?;
+            output.push_str(&line);
+            output.push('\n');
+        }
+
+        Ok(output)
+    }
+
+    fn format_elfs(&self, elfs: &[ElfInfo]) -> Result<String, AnalysisError> {
+        let mut output = String::new();
+
+        for elf in elfs {
+            let line = serde_json::to_string(elf)?;
+            output.push_str(&line);
+            output.push('\n');
+        }
+
+        Ok(output)
+    }
+
+    fn format_scan_matches(&self, matches: &[ScanMatchInfo]) -> Result<String, AnalysisError> {
+        let mut output = String::new();
+
+        for m in matches {
+            let line = serde_json::to_string(m)?;
+            output.push_str(&line);
+            output.push('\n');
+        }
+
+        Ok(output)
+    }
+
+    fn format_strings(&self, strings: &[StringInfo]) -> Result<String, AnalysisError> {
+        let mut output = String::new();
+
+        for s in strings {
+            let line = serde_json::to_string(s)?;
+            output.push_str(&line);
+            output.push('\n');
+        }
+
+        Ok(output)
+    }
+
+    fn format_page_info(&self, pages: &[PageInfo]) -> Result<String, AnalysisError> {
+        let mut output = String::new();
+
+        for p in pages {
+            let line = serde_json::to_string(p)?;
+            output.push_str(&line);
+            output.push('\n');
+        }
+
+        Ok(output)
+    }
+
+    fn format_maps(&self, maps: &[MapsInfo]) -> Result<String, AnalysisError> {
+        let mut output = String::new();
+
+        for m in maps {
+            let line = serde_json::to_string(m)?;
+            output.push_str(&line);
+            output.push('\n');
+        }
+
+        Ok(output)
+    }
 }
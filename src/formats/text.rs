@@ -1,7 +1,13 @@
 //! Text (table) output formatter for the Linux Memory Parser tool
 use crate::error::AnalysisError;
 use crate::formats::traits::OutputFormatter;
-use crate::kernel::{format_start_time, ConnectionInfo, ModuleInfo, ProcessInfo};
+use crate::kernel::{
+    format_start_time, format_unix_time, join_display, BashHistoryInfo, BpfMapInfo, BpfProgInfo,
+    ConnectionInfo, ContainerInfo, ElfInfo, InlineHookInfo, InterfaceInfo, LibraryInfo, MapsInfo,
+    ModuleInfo, NeighbourInfo, NetfilterHookInfo, NotifierInfo, OpsPointerInfo, PageInfo,
+    ProcessInfo, ProcessNamespaceInfo, RawSocketInfo, RouteInfo, ScanMatchInfo, StringInfo,
+    TmpfsFileInfo, TraceHookInfo, TtyHookInfo, UnixSocketInfo,
+};
 use prettytable::{Cell, Row, Table};
 
 /// Text formatter that outputs data in a human-readable table format
@@ -54,6 +60,9 @@ fn format_connections(&self, connections: &[ConnectionInfo]) -> Result<String, A
             Cell::new("REMOTE_PORT").style_spec("c"),
             Cell::new("STATE").style_spec("c"),
             Cell::new("PID").style_spec("c"),
+            Cell::new("FD").style_spec("c"),
+            Cell::new("COMM").style_spec("c"),
+            Cell::new("INODE").style_spec("c"),
         ]));
 
         // Data rows
@@ -66,6 +75,9 @@ fn format_connections(&self, connections: &[ConnectionInfo]) -> Result<String, A
                 Cell::new(&conn.remote_port.to_string()),
                 Cell::new(&conn.state),
                 Cell::new(&conn.pid.to_string()),
+                Cell::new(&conn.fd.map_or("-".to_string(), |fd| fd.to_string())),
+                Cell::new(&conn.comm),
+                Cell::new(&conn.inode.to_string()),
             ]));
         }
 
@@ -96,4 +108,804 @@ fn format_modules(&self, modules: &[ModuleInfo]) -> Result<String, AnalysisError
 
         Ok(table.to_string())
     }
+
+    fn format_tmpfs_files(&self, files: &[TmpfsFileInfo]) -> Result<String, AnalysisError> {
+        let mut table = Table::new();
+        table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+
+        // Header
+        table.add_row(Row::new(vec![
+            Cell::new("FS").style_spec("c"),
+            Cell::new("MOUNT").style_spec("c"),
+            Cell::new("PATH").style_spec("c"),
+            Cell::new("ORIGIN").style_spec("c"),
+            Cell::new("INODE").style_spec("c"),
+            Cell::new("MODE").style_spec("c"),
+            Cell::new("UID").style_spec("c"),
+            Cell::new("GID").style_spec("c"),
+            Cell::new("SIZE").style_spec("c"),
+            Cell::new("PAGES").style_spec("c"),
+            Cell::new("MTIME").style_spec("c"),
+            Cell::new("ATIME").style_spec("c"),
+            Cell::new("CTIME").style_spec("c"),
+            Cell::new("EXPORTED").style_spec("c"),
+        ]));
+
+        // Data rows
+        for file in files {
+            table.add_row(Row::new(vec![
+                Cell::new(&file.fs_type),
+                Cell::new(&file.mount),
+                Cell::new(&file.path),
+                Cell::new(&file.origin),
+                Cell::new(&file.inode.to_string()),
+                Cell::new(&file.mode),
+                Cell::new(&file.uid.to_string()),
+                Cell::new(&file.gid.to_string()),
+                Cell::new(&file.size.to_string()),
+                Cell::new(&file.cached_pages.to_string()),
+                Cell::new(&format_unix_time(file.mtime)),
+                Cell::new(&format_unix_time(file.atime)),
+                Cell::new(&format_unix_time(file.ctime)),
+                Cell::new(file.exported.as_deref().unwrap_or("-")),
+            ]));
+        }
+
+        Ok(table.to_string())
+    }
+
+    fn format_bash_history(&self, entries: &[BashHistoryInfo]) -> Result<String, AnalysisError> {
+        let mut table = Table::new();
+        table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+
+        // Header
+        table.add_row(Row::new(vec![
+            Cell::new("PID").style_spec("c"),
+            Cell::new("UID").style_spec("c"),
+            Cell::new("COMM").style_spec("c"),
+            Cell::new("TIMESTAMP").style_spec("c"),
+            Cell::new("COMMAND").style_spec("c"),
+        ]));
+
+        // Data rows
+        for entry in entries {
+            table.add_row(Row::new(vec![
+                Cell::new(&entry.pid.to_string()),
+                Cell::new(&entry.uid.to_string()),
+                Cell::new(&entry.comm),
+                Cell::new(&format_unix_time(entry.timestamp)),
+                Cell::new(&entry.command),
+            ]));
+        }
+
+        Ok(table.to_string())
+    }
+
+    fn format_interfaces(&self, interfaces: &[InterfaceInfo]) -> Result<String, AnalysisError> {
+        let mut table = Table::new();
+        table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+
+        // Header
+        table.add_row(Row::new(vec![
+            Cell::new("NETNS").style_spec("c"),
+            Cell::new("NAME").style_spec("c"),
+            Cell::new("MAC").style_spec("c"),
+            Cell::new("MTU").style_spec("c"),
+            Cell::new("FLAGS").style_spec("c"),
+            Cell::new("PROMISC").style_spec("c"),
+            Cell::new("IPV4").style_spec("c"),
+            Cell::new("IPV6").style_spec("c"),
+        ]));
+
+        // Data rows
+        for iface in interfaces {
+            table.add_row(Row::new(vec![
+                Cell::new(&iface.net_namespace.to_string()),
+                Cell::new(&iface.name),
+                Cell::new(&iface.mac),
+                Cell::new(&iface.mtu.to_string()),
+                Cell::new(&iface.flags),
+                Cell::new(yes_no(iface.promiscuous)),
+                Cell::new(&iface.ipv4.join(", ")),
+                Cell::new(&iface.ipv6.join(", ")),
+            ]));
+        }
+
+        Ok(table.to_string())
+    }
+
+    fn format_neighbours(&self, neighbours: &[NeighbourInfo]) -> Result<String, AnalysisError> {
+        let mut table = Table::new();
+        table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+
+        // Header
+        table.add_row(Row::new(vec![
+            Cell::new("NETNS").style_spec("c"),
+            Cell::new("FAMILY").style_spec("c"),
+            Cell::new("ADDRESS").style_spec("c"),
+            Cell::new("MAC").style_spec("c"),
+            Cell::new("INTERFACE").style_spec("c"),
+            Cell::new("STATE").style_spec("c"),
+        ]));
+
+        // Data rows
+        for neigh in neighbours {
+            table.add_row(Row::new(vec![
+                Cell::new(&neigh.net_namespace.to_string()),
+                Cell::new(&neigh.family),
+                Cell::new(&neigh.address),
+                Cell::new(&neigh.mac),
+                Cell::new(&neigh.interface),
+                Cell::new(&neigh.state),
+            ]));
+        }
+
+        Ok(table.to_string())
+    }
+
+    fn format_routes(&self, routes: &[RouteInfo]) -> Result<String, AnalysisError> {
+        let mut table = Table::new();
+        table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+
+        // Header
+        table.add_row(Row::new(vec![
+            Cell::new("NETNS").style_spec("c"),
+            Cell::new("FAMILY").style_spec("c"),
+            Cell::new("TABLE").style_spec("c"),
+            Cell::new("DESTINATION").style_spec("c"),
+            Cell::new("GATEWAY").style_spec("c"),
+            Cell::new("INTERFACE").style_spec("c"),
+            Cell::new("METRIC").style_spec("c"),
+            Cell::new("TYPE").style_spec("c"),
+        ]));
+
+        // Data rows
+        for route in routes {
+            table.add_row(Row::new(vec![
+                Cell::new(&route.net_namespace.to_string()),
+                Cell::new(&route.family),
+                Cell::new(&route.table),
+                Cell::new(&route.destination),
+                Cell::new(&route.gateway),
+                Cell::new(&route.interface),
+                Cell::new(&route.metric.to_string()),
+                Cell::new(&route.route_type),
+            ]));
+        }
+
+        Ok(table.to_string())
+    }
+
+    fn format_unix_sockets(&self, sockets: &[UnixSocketInfo]) -> Result<String, AnalysisError> {
+        let mut table = Table::new();
+        table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+
+        // Header
+        table.add_row(Row::new(vec![
+            Cell::new("NETNS").style_spec("c"),
+            Cell::new("INODE").style_spec("c"),
+            Cell::new("TYPE").style_spec("c"),
+            Cell::new("STATE").style_spec("c"),
+            Cell::new("PATH").style_spec("c"),
+            Cell::new("OWNERS").style_spec("c"),
+            Cell::new("PEER INODE").style_spec("c"),
+            Cell::new("PEER PIDS").style_spec("c"),
+        ]));
+
+        // Data rows
+        for sock in sockets {
+            table.add_row(Row::new(vec![
+                Cell::new(&sock.net_namespace.to_string()),
+                Cell::new(&sock.inode.to_string()),
+                Cell::new(&sock.socket_type),
+                Cell::new(&sock.state),
+                Cell::new(&sock.path),
+                Cell::new(&join_display(&sock.owners)),
+                Cell::new(&sock.peer_inode.to_string()),
+                Cell::new(&join_display(&sock.peer_pids)),
+            ]));
+        }
+
+        Ok(table.to_string())
+    }
+
+    fn format_raw_sockets(&self, sockets: &[RawSocketInfo]) -> Result<String, AnalysisError> {
+        let mut table = Table::new();
+        table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+
+        // Header
+        table.add_row(Row::new(vec![
+            Cell::new("NETNS").style_spec("c"),
+            Cell::new("FAMILY").style_spec("c"),
+            Cell::new("PROTOCOL").style_spec("c"),
+            Cell::new("TYPE").style_spec("c"),
+            Cell::new("INTERFACE").style_spec("c"),
+            Cell::new("PORTID").style_spec("c"),
+            Cell::new("INODE").style_spec("c"),
+            Cell::new("OWNERS").style_spec("c"),
+            Cell::new("FILTER_INSNS").style_spec("c"),
+        ]));
+
+        // Data rows
+        for sock in sockets {
+            table.add_row(Row::new(vec![
+                Cell::new(&sock.net_namespace.to_string()),
+                Cell::new(&sock.family),
+                Cell::new(&sock.protocol),
+                Cell::new(&sock.socket_type),
+                Cell::new(&sock.interface),
+                Cell::new(&sock.portid.map_or("-".to_string(), |p| p.to_string())),
+                Cell::new(&sock.inode.to_string()),
+                Cell::new(&join_display(&sock.owners)),
+                Cell::new(&sock.filter_insns.to_string()),
+            ]));
+        }
+
+        Ok(table.to_string())
+    }
+
+    fn format_bpf_programs(&self, programs: &[BpfProgInfo]) -> Result<String, AnalysisError> {
+        let mut table = Table::new();
+        table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+
+        // Header
+        table.add_row(Row::new(vec![
+            Cell::new("ID").style_spec("c"),
+            Cell::new("TYPE").style_spec("c"),
+            Cell::new("NAME").style_spec("c"),
+            Cell::new("TAG").style_spec("c"),
+            Cell::new("LOADED").style_spec("c"),
+            Cell::new("UID").style_spec("c"),
+            Cell::new("ATTACH").style_spec("c"),
+            Cell::new("INSNS").style_spec("c"),
+            Cell::new("JIT ADDR").style_spec("c"),
+            Cell::new("JIT SIZE").style_spec("c"),
+            Cell::new("MAPS").style_spec("c"),
+        ]));
+
+        // Data rows
+        for prog in programs {
+            table.add_row(Row::new(vec![
+                Cell::new(&prog.id.to_string()),
+                Cell::new(&prog.prog_type),
+                Cell::new(&prog.name),
+                Cell::new(&prog.tag),
+                Cell::new(&format_start_time(prog.load_time)),
+                Cell::new(
+                    &prog
+                        .uid
+                        .map(|u| u.to_string())
+                        .unwrap_or_else(|| "-".to_string()),
+                ),
+                Cell::new(&prog.attach.join(", ")),
+                Cell::new(&prog.insns.to_string()),
+                Cell::new(&format!("{:#x}", prog.jit_addr)),
+                Cell::new(&prog.jit_size.to_string()),
+                Cell::new(&join_display(&prog.map_ids)),
+            ]));
+        }
+
+        Ok(table.to_string())
+    }
+
+    fn format_bpf_maps(&self, maps: &[BpfMapInfo]) -> Result<String, AnalysisError> {
+        let mut table = Table::new();
+        table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+
+        // Header
+        table.add_row(Row::new(vec![
+            Cell::new("ID").style_spec("c"),
+            Cell::new("TYPE").style_spec("c"),
+            Cell::new("NAME").style_spec("c"),
+            Cell::new("KEY").style_spec("c"),
+            Cell::new("VALUE").style_spec("c"),
+            Cell::new("MAX ENTRIES").style_spec("c"),
+            Cell::new("FLAGS").style_spec("c"),
+        ]));
+
+        // Data rows
+        for map in maps {
+            table.add_row(Row::new(vec![
+                Cell::new(&map.id.to_string()),
+                Cell::new(&map.map_type),
+                Cell::new(&map.name),
+                Cell::new(&map.key_size.to_string()),
+                Cell::new(&map.value_size.to_string()),
+                Cell::new(&map.max_entries.to_string()),
+                Cell::new(&format!("{:#x}", map.flags)),
+            ]));
+        }
+
+        Ok(table.to_string())
+    }
+
+    fn format_inline_hooks(&self, hooks: &[InlineHookInfo]) -> Result<String, AnalysisError> {
+        let mut table = Table::new();
+        table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+
+        // Header
+        table.add_row(Row::new(vec![
+            Cell::new("FUNCTION").style_spec("c"),
+            Cell::new("ADDRESS").style_spec("c"),
+            Cell::new("OFFSET").style_spec("c"),
+            Cell::new("KIND").style_spec("c"),
+            Cell::new("TARGET").style_spec("c"),
+            Cell::new("ORIGINAL").style_spec("c"),
+            Cell::new("CURRENT").style_spec("c"),
+        ]));
+
+        // Data rows
+        for hook in hooks {
+            table.add_row(Row::new(vec![
+                Cell::new(&hook.function),
+                Cell::new(&format!("{:#x}", hook.address)),
+                Cell::new(&format!("+{:#x}", hook.hook_offset)),
+                Cell::new(&hook.kind),
+                Cell::new(&hook.target),
+                Cell::new(&hook.original),
+                Cell::new(&hook.current),
+            ]));
+        }
+
+        Ok(table.to_string())
+    }
+
+    fn format_trace_hooks(&self, hooks: &[TraceHookInfo]) -> Result<String, AnalysisError> {
+        let mut table = Table::new();
+        table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+
+        // Header
+        table.add_row(Row::new(vec![
+            Cell::new("TYPE").style_spec("c"),
+            Cell::new("TARGET").style_spec("c"),
+            Cell::new("HANDLER").style_spec("c"),
+            Cell::new("CALLBACK").style_spec("c"),
+            Cell::new("SYMBOL").style_spec("c"),
+            Cell::new("OWNER").style_spec("c"),
+            Cell::new("DETAILS").style_spec("c"),
+        ]));
+
+        // Data rows
+        for hook in hooks {
+            table.add_row(Row::new(vec![
+                Cell::new(&hook.hook_type),
+                Cell::new(&hook.target),
+                Cell::new(&hook.handler),
+                Cell::new(&format!("{:#x}", hook.callback)),
+                Cell::new(&hook.symbol),
+                Cell::new(&hook.owner),
+                Cell::new(&hook.details),
+            ]));
+        }
+
+        Ok(table.to_string())
+    }
+
+    fn format_netfilter_hooks(&self, hooks: &[NetfilterHookInfo]) -> Result<String, AnalysisError> {
+        let mut table = Table::new();
+        table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+
+        // Header
+        table.add_row(Row::new(vec![
+            Cell::new("NETNS").style_spec("c"),
+            Cell::new("FAMILY").style_spec("c"),
+            Cell::new("HOOK").style_spec("c"),
+            Cell::new("PRIORITY").style_spec("c"),
+            Cell::new("CALLBACK").style_spec("c"),
+            Cell::new("SYMBOL").style_spec("c"),
+            Cell::new("OWNER").style_spec("c"),
+        ]));
+
+        // Data rows
+        for hook in hooks {
+            table.add_row(Row::new(vec![
+                Cell::new(&hook.net_namespace.to_string()),
+                Cell::new(&hook.family),
+                Cell::new(&hook.hook),
+                Cell::new(&hook.priority.to_string()),
+                Cell::new(&format!("{:#x}", hook.callback)),
+                Cell::new(&hook.symbol),
+                Cell::new(&hook.owner),
+            ]));
+        }
+
+        Ok(table.to_string())
+    }
+
+    fn format_notifiers(&self, notifiers: &[NotifierInfo]) -> Result<String, AnalysisError> {
+        let mut table = Table::new();
+        table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+
+        // Header
+        table.add_row(Row::new(vec![
+            Cell::new("CHAIN").style_spec("c"),
+            Cell::new("PRIORITY").style_spec("c"),
+            Cell::new("CALLBACK").style_spec("c"),
+            Cell::new("SYMBOL").style_spec("c"),
+            Cell::new("OWNER").style_spec("c"),
+        ]));
+
+        // Data rows
+        for notifier in notifiers {
+            table.add_row(Row::new(vec![
+                Cell::new(&notifier.chain),
+                Cell::new(&notifier.priority.to_string()),
+                Cell::new(&format!("{:#x}", notifier.callback)),
+                Cell::new(&notifier.symbol),
+                Cell::new(&notifier.owner),
+            ]));
+        }
+
+        Ok(table.to_string())
+    }
+
+    fn format_tty_hooks(&self, hooks: &[TtyHookInfo]) -> Result<String, AnalysisError> {
+        let mut table = Table::new();
+        table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+
+        // Header
+        table.add_row(Row::new(vec![
+            Cell::new("TTY").style_spec("c"),
+            Cell::new("DRIVER").style_spec("c"),
+            Cell::new("LDISC").style_spec("c"),
+            Cell::new("HANDLER").style_spec("c"),
+            Cell::new("CALLBACK").style_spec("c"),
+            Cell::new("SYMBOL").style_spec("c"),
+            Cell::new("OWNER").style_spec("c"),
+        ]));
+
+        // Data rows
+        for hook in hooks {
+            table.add_row(Row::new(vec![
+                Cell::new(&hook.tty),
+                Cell::new(&hook.driver),
+                Cell::new(&hook.ldisc),
+                Cell::new(&hook.handler),
+                Cell::new(&format!("{:#x}", hook.callback)),
+                Cell::new(&hook.symbol),
+                Cell::new(&hook.owner),
+            ]));
+        }
+
+        Ok(table.to_string())
+    }
+
+    fn format_ops_pointers(&self, pointers: &[OpsPointerInfo]) -> Result<String, AnalysisError> {
+        let mut table = Table::new();
+        table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+
+        // Header
+        table.add_row(Row::new(vec![
+            Cell::new("TABLE").style_spec("c"),
+            Cell::new("MEMBER").style_spec("c"),
+            Cell::new("POINTER").style_spec("c"),
+            Cell::new("SYMBOL").style_spec("c"),
+            Cell::new("OWNER").style_spec("c"),
+        ]));
+
+        // Data rows
+        for entry in pointers {
+            table.add_row(Row::new(vec![
+                Cell::new(&entry.table),
+                Cell::new(&entry.member),
+                Cell::new(&format!("{:#x}", entry.pointer)),
+                Cell::new(&entry.symbol),
+                Cell::new(&entry.owner),
+            ]));
+        }
+
+        Ok(table.to_string())
+    }
+
+    fn format_process_namespaces(
+        &self,
+        processes: &[ProcessNamespaceInfo],
+    ) -> Result<String, AnalysisError> {
+        let mut table = Table::new();
+        table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+
+        // Header
+        table.add_row(Row::new(vec![
+            Cell::new("PID").style_spec("c"),
+            Cell::new("NS PID").style_spec("c"),
+            Cell::new("COMM").style_spec("c"),
+            Cell::new("USER").style_spec("c"),
+            Cell::new("MNT").style_spec("c"),
+            Cell::new("UTS").style_spec("c"),
+            Cell::new("IPC").style_spec("c"),
+            Cell::new("PID NS").style_spec("c"),
+            Cell::new("NET").style_spec("c"),
+            Cell::new("CGROUP NS").style_spec("c"),
+            Cell::new("TIME").style_spec("c"),
+            Cell::new("HOSTNAME").style_spec("c"),
+            Cell::new("CGROUP").style_spec("c"),
+        ]));
+
+        // Data rows
+        for process in processes {
+            table.add_row(Row::new(vec![
+                Cell::new(&process.pid.to_string()),
+                Cell::new(&process.ns_pid.to_string()),
+                Cell::new(&process.comm),
+                Cell::new(&process.user_ns.to_string()),
+                Cell::new(&process.mnt_ns.to_string()),
+                Cell::new(&process.uts_ns.to_string()),
+                Cell::new(&process.ipc_ns.to_string()),
+                Cell::new(&process.pid_ns.to_string()),
+                Cell::new(&process.net_ns.to_string()),
+                Cell::new(&process.cgroup_ns.to_string()),
+                Cell::new(&process.time_ns.to_string()),
+                Cell::new(&process.hostname),
+                Cell::new(&process.cgroup),
+            ]));
+        }
+
+        Ok(table.to_string())
+    }
+
+    fn format_containers(&self, containers: &[ContainerInfo]) -> Result<String, AnalysisError> {
+        let mut table = Table::new();
+        table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+
+        // Header
+        table.add_row(Row::new(vec![
+            Cell::new("CONTAINER").style_spec("c"),
+            Cell::new("RUNTIME").style_spec("c"),
+            Cell::new("POD").style_spec("c"),
+            Cell::new("HOSTNAME").style_spec("c"),
+            Cell::new("PID NS").style_spec("c"),
+            Cell::new("NET NS").style_spec("c"),
+            Cell::new("PROCS").style_spec("c"),
+            Cell::new("PIDS").style_spec("c"),
+            Cell::new("CGROUP").style_spec("c"),
+        ]));
+
+        // Data rows
+        for container in containers {
+            table.add_row(Row::new(vec![
+                Cell::new(&container.container),
+                Cell::new(&container.runtime),
+                Cell::new(&container.pod),
+                Cell::new(&container.hostname),
+                Cell::new(&container.pid_ns.to_string()),
+                Cell::new(&container.net_ns.to_string()),
+                Cell::new(&container.processes.to_string()),
+                Cell::new(&join_display(&container.pids)),
+                Cell::new(&container.cgroup),
+            ]));
+        }
+
+        Ok(table.to_string())
+    }
+
+    fn format_libraries(&self, libraries: &[LibraryInfo]) -> Result<String, AnalysisError> {
+        let mut table = Table::new();
+        table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+
+        // Header
+        table.add_row(Row::new(vec![
+            Cell::new("PID").style_spec("c"),
+            Cell::new("COMM").style_spec("c"),
+            Cell::new("BASE").style_spec("c"),
+            Cell::new("MAPPED").style_spec("c"),
+            Cell::new("LINKED").style_spec("c"),
+            Cell::new("DELETED").style_spec("c"),
+            Cell::new("MEMFD").style_spec("c"),
+            Cell::new("PRELOAD").style_spec("c"),
+            Cell::new("PATH").style_spec("c"),
+        ]));
+
+        // Data rows
+        for lib in libraries {
+            table.add_row(Row::new(vec![
+                Cell::new(&lib.pid.to_string()),
+                Cell::new(&lib.comm),
+                Cell::new(&format!("{:#x}", lib.base)),
+                Cell::new(yes_no(lib.mapped)),
+                Cell::new(yes_no(lib.linked)),
+                Cell::new(yes_no(lib.deleted)),
+                Cell::new(yes_no(lib.memfd)),
+                Cell::new(yes_no(lib.preloaded)),
+                Cell::new(&lib.path),
+            ]));
+        }
+
+        Ok(table.to_string())
+    }
+
+    fn format_elfs(&self, elfs: &[ElfInfo]) -> Result<String, AnalysisError> {
+        let mut table = Table::new();
+        table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+
+        // Header
+        table.add_row(Row::new(vec![
+            Cell::new("PID").style_spec("c"),
+            Cell::new("COMM").style_spec("c"),
+            Cell::new("START").style_spec("c"),
+            Cell::new("END").style_spec("c"),
+            Cell::new("TYPE").style_spec("c"),
+            Cell::new("BUILD ID").style_spec("c"),
+            Cell::new("DELETED").style_spec("c"),
+            Cell::new("PATH").style_spec("c"),
+        ]));
+
+        // Data rows
+        for elf in elfs {
+            table.add_row(Row::new(vec![
+                Cell::new(&elf.pid.to_string()),
+                Cell::new(&elf.comm),
+                Cell::new(&format!("{:#x}", elf.start)),
+                Cell::new(&format!("{:#x}", elf.end)),
+                Cell::new(&elf.elf_type),
+                Cell::new(&elf.build_id),
+                Cell::new(yes_no(elf.deleted)),
+                Cell::new(&elf.path),
+            ]));
+        }
+
+        Ok(table.to_string())
+    }
+
+    fn format_scan_matches(&self, matches: &[ScanMatchInfo]) -> Result<String, AnalysisError> {
+        let mut table = Table::new();
+        table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+
+        // Header
+        table.add_row(Row::new(vec![
+            Cell::new("RULE").style_spec("c"),
+            Cell::new("STRING").style_spec("c"),
+            Cell::new("SPACE").style_spec("c"),
+            Cell::new("ADDRESS").style_spec("c"),
+            Cell::new("PID").style_spec("c"),
+            Cell::new("COMM").style_spec("c"),
+            Cell::new("CONTEXT").style_spec("c"),
+            Cell::new("DATA").style_spec("c"),
+        ]));
+
+        // Data rows
+        for m in matches {
+            table.add_row(Row::new(vec![
+                Cell::new(&m.rule),
+                Cell::new(&m.string),
+                Cell::new(&m.space),
+                Cell::new(&format!("{:#x}", m.address)),
+                Cell::new(
+                    &m.pid
+                        .map(|p| p.to_string())
+                        .unwrap_or_else(|| "-".to_string()),
+                ),
+                Cell::new(&m.comm),
+                Cell::new(&m.context),
+                Cell::new(&m.data),
+            ]));
+        }
+
+        Ok(table.to_string())
+    }
+
+    fn format_strings(&self, strings: &[StringInfo]) -> Result<String, AnalysisError> {
+        let mut table = Table::new();
+        table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+
+        // Header
+        table.add_row(Row::new(vec![
+            Cell::new("OFFSET").style_spec("c"),
+            Cell::new("PHYSICAL").style_spec("c"),
+            Cell::new("KERNEL").style_spec("c"),
+            Cell::new("OWNERS").style_spec("c"),
+            Cell::new("STRING").style_spec("c"),
+        ]));
+
+        // Data rows
+        for s in strings {
+            table.add_row(Row::new(vec![
+                Cell::new(&s.offset.to_string()),
+                Cell::new(
+                    &s.physical
+                        .map(|p| format!("{:#x}", p))
+                        .unwrap_or_else(|| "-".to_string()),
+                ),
+                Cell::new(
+                    &s.kernel
+                        .iter()
+                        .map(|a| format!("{:#x}", a))
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                ),
+                Cell::new(
+                    &s.owners
+                        .iter()
+                        .map(|o| format!("{}/{}@{:#x}", o.pid, o.comm, o.address))
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                ),
+                Cell::new(&s.string),
+            ]));
+        }
+
+        Ok(table.to_string())
+    }
+
+    fn format_page_info(&self, pages: &[PageInfo]) -> Result<String, AnalysisError> {
+        let mut table = Table::new();
+        table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+
+        // Header
+        table.add_row(Row::new(vec![
+            Cell::new("PHYSICAL").style_spec("c"),
+            Cell::new("PFN").style_spec("c"),
+            Cell::new("FLAGS").style_spec("c"),
+            Cell::new("MAPPING").style_spec("c"),
+            Cell::new("INDEX").style_spec("c"),
+            Cell::new("REF").style_spec("c"),
+            Cell::new("MAP").style_spec("c"),
+            Cell::new("FILE").style_spec("c"),
+            Cell::new("MAPPED BY").style_spec("c"),
+        ]));
+
+        // Data rows
+        for p in pages {
+            table.add_row(Row::new(vec![
+                Cell::new(&format!("{:#x}", p.physical)),
+                Cell::new(&format!("{:#x}", p.pfn)),
+                Cell::new(&format!("{:#x} {}", p.flags, p.flag_names.join("|"))),
+                Cell::new(&format!("{} {:#x}", p.mapping_type, p.mapping)),
+                Cell::new(&p.index.to_string()),
+                Cell::new(&p.refcount.to_string()),
+                Cell::new(&p.mapcount.to_string()),
+                Cell::new(
+                    &p.inode
+                        .map(|i| format!("{} {}", i, p.path))
+                        .unwrap_or_else(|| "-".to_string()),
+                ),
+                Cell::new(&p.mapped_by.join(", ")),
+            ]));
+        }
+
+        Ok(table.to_string())
+    }
+
+    fn format_maps(&self, maps: &[MapsInfo]) -> Result<String, AnalysisError> {
+        let mut table = Table::new();
+        table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+
+        // Header
+        table.add_row(Row::new(vec![
+            Cell::new("PID").style_spec("c"),
+            Cell::new("COMM").style_spec("c"),
+            Cell::new("START").style_spec("c"),
+            Cell::new("END").style_spec("c"),
+            Cell::new("PERMS").style_spec("c"),
+            Cell::new("RESIDENT").style_spec("c"),
+            Cell::new("SWAPPED").style_spec("c"),
+            Cell::new("UNAVAILABLE").style_spec("c"),
+            Cell::new("PATH").style_spec("c"),
+        ]));
+
+        // Data rows
+        for m in maps {
+            table.add_row(Row::new(vec![
+                Cell::new(&m.pid.to_string()),
+                Cell::new(&m.comm),
+                Cell::new(&format!("{:#x}", m.start)),
+                Cell::new(&format!("{:#x}", m.end)),
+                Cell::new(&m.perms),
+                Cell::new(&m.resident.to_string()),
+                Cell::new(&format!("{} ({} readable)", m.swapped, m.swap_readable)),
+                Cell::new(&m.unavailable.to_string()),
+                Cell::new(&m.path),
+            ]));
+        }
+
+        Ok(table.to_string())
+    }
+}
+
+/// Render a flag as "yes" or "no"
+fn yes_no(value: bool) -> &'static str {
+    if value {
+        "yes"
+    } else {
+        "no"
+    }
 }
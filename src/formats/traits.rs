@@ -1,12 +1,44 @@
 //! Output format traits for the Linux Memory Parser tool
 use crate::error::AnalysisError;
-use crate::kernel::{ConnectionInfo, ModuleInfo, ProcessInfo};
+use crate::kernel::{
+    BashHistoryInfo, BpfMapInfo, BpfProgInfo, ConnectionInfo, ContainerInfo, ElfInfo,
+    InlineHookInfo, InterfaceInfo, LibraryInfo, MapsInfo, ModuleInfo, NeighbourInfo,
+    NetfilterHookInfo, NotifierInfo, OpsPointerInfo, PageInfo, ProcessInfo, ProcessNamespaceInfo,
+    RawSocketInfo, RouteInfo, ScanMatchInfo, StringInfo, TmpfsFileInfo, TraceHookInfo, TtyHookInfo,
+    UnixSocketInfo,
+};
 
 /// Trait for output formatters
 pub trait OutputFormatter: Send + Sync {
     fn format_processes(&self, processes: &[ProcessInfo]) -> Result<String, AnalysisError>;
     fn format_connections(&self, connections: &[ConnectionInfo]) -> Result<String, AnalysisError>;
     fn format_modules(&self, modules: &[ModuleInfo]) -> Result<String, AnalysisError>;
+    fn format_tmpfs_files(&self, files: &[TmpfsFileInfo]) -> Result<String, AnalysisError>;
+    fn format_bash_history(&self, entries: &[BashHistoryInfo]) -> Result<String, AnalysisError>;
+    fn format_interfaces(&self, interfaces: &[InterfaceInfo]) -> Result<String, AnalysisError>;
+    fn format_neighbours(&self, neighbours: &[NeighbourInfo]) -> Result<String, AnalysisError>;
+    fn format_routes(&self, routes: &[RouteInfo]) -> Result<String, AnalysisError>;
+    fn format_unix_sockets(&self, sockets: &[UnixSocketInfo]) -> Result<String, AnalysisError>;
+    fn format_raw_sockets(&self, sockets: &[RawSocketInfo]) -> Result<String, AnalysisError>;
+    fn format_bpf_programs(&self, programs: &[BpfProgInfo]) -> Result<String, AnalysisError>;
+    fn format_bpf_maps(&self, maps: &[BpfMapInfo]) -> Result<String, AnalysisError>;
+    fn format_inline_hooks(&self, hooks: &[InlineHookInfo]) -> Result<String, AnalysisError>;
+    fn format_trace_hooks(&self, hooks: &[TraceHookInfo]) -> Result<String, AnalysisError>;
+    fn format_netfilter_hooks(&self, hooks: &[NetfilterHookInfo]) -> Result<String, AnalysisError>;
+    fn format_notifiers(&self, notifiers: &[NotifierInfo]) -> Result<String, AnalysisError>;
+    fn format_tty_hooks(&self, hooks: &[TtyHookInfo]) -> Result<String, AnalysisError>;
+    fn format_ops_pointers(&self, pointers: &[OpsPointerInfo]) -> Result<String, AnalysisError>;
+    fn format_process_namespaces(
+        &self,
+        processes: &[ProcessNamespaceInfo],
+    ) -> Result<String, AnalysisError>;
+    fn format_containers(&self, containers: &[ContainerInfo]) -> Result<String, AnalysisError>;
+    fn format_libraries(&self, libraries: &[LibraryInfo]) -> Result<String, AnalysisError>;
+    fn format_elfs(&self, elfs: &[ElfInfo]) -> Result<String, AnalysisError>;
+    fn format_scan_matches(&self, matches: &[ScanMatchInfo]) -> Result<String, AnalysisError>;
+    fn format_strings(&self, strings: &[StringInfo]) -> Result<String, AnalysisError>;
+    fn format_page_info(&self, pages: &[PageInfo]) -> Result<String, AnalysisError>;
+    fn format_maps(&self, maps: &[MapsInfo]) -> Result<String, AnalysisError>;
 }
 
 /// Enum for output format types
@@ -94,4 +126,375 @@ pub fn write_modules(&self, modules: &[ModuleInfo]) -> Result<(), AnalysisError>
 
         Ok(())
     }
+
+    /// Write recovered tmpfs files to the configured destination
+    pub fn write_tmpfs_files(&self, files: &[TmpfsFileInfo]) -> Result<(), AnalysisError> {
+        let content = self.formatter.format_tmpfs_files(files)?;
+
+        match &self.destination {
+            OutputDestination::Stdout => {
+                println!("{}", content);
+            }
+            OutputDestination::File(path) => {
+                std::fs::write(path, content)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Write bash history entries to the configured destination
+    pub fn write_bash_history(&self, entries: &[BashHistoryInfo]) -> Result<(), AnalysisError> {
+        let content = self.formatter.format_bash_history(entries)?;
+
+        match &self.destination {
+            OutputDestination::Stdout => {
+                println!("{}", content);
+            }
+            OutputDestination::File(path) => {
+                std::fs::write(path, content)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Write network interfaces to the configured destination
+    pub fn write_interfaces(&self, interfaces: &[InterfaceInfo]) -> Result<(), AnalysisError> {
+        let content = self.formatter.format_interfaces(interfaces)?;
+
+        match &self.destination {
+            OutputDestination::Stdout => {
+                println!("{}", content);
+            }
+            OutputDestination::File(path) => {
+                std::fs::write(path, content)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Write neighbour cache entries to the configured destination
+    pub fn write_neighbours(&self, neighbours: &[NeighbourInfo]) -> Result<(), AnalysisError> {
+        let content = self.formatter.format_neighbours(neighbours)?;
+
+        match &self.destination {
+            OutputDestination::Stdout => {
+                println!("{}", content);
+            }
+            OutputDestination::File(path) => {
+                std::fs::write(path, content)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Write routing table entries to the configured destination
+    pub fn write_routes(&self, routes: &[RouteInfo]) -> Result<(), AnalysisError> {
+        let content = self.formatter.format_routes(routes)?;
+
+        match &self.destination {
+            OutputDestination::Stdout => {
+                println!("{}", content);
+            }
+            OutputDestination::File(path) => {
+                std::fs::write(path, content)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Write Unix domain sockets to the configured destination
+    pub fn write_unix_sockets(&self, sockets: &[UnixSocketInfo]) -> Result<(), AnalysisError> {
+        let content = self.formatter.format_unix_sockets(sockets)?;
+
+        match &self.destination {
+            OutputDestination::Stdout => {
+                println!("{}", content);
+            }
+            OutputDestination::File(path) => {
+                std::fs::write(path, content)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Write packet, raw and netlink sockets to the configured destination
+    pub fn write_raw_sockets(&self, sockets: &[RawSocketInfo]) -> Result<(), AnalysisError> {
+        let content = self.formatter.format_raw_sockets(sockets)?;
+
+        match &self.destination {
+            OutputDestination::Stdout => {
+                println!("{}", content);
+            }
+            OutputDestination::File(path) => {
+                std::fs::write(path, content)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Write eBPF programs to the configured destination
+    pub fn write_bpf_programs(&self, programs: &[BpfProgInfo]) -> Result<(), AnalysisError> {
+        let content = self.formatter.format_bpf_programs(programs)?;
+
+        match &self.destination {
+            OutputDestination::Stdout => {
+                println!("{}", content);
+            }
+            OutputDestination::File(path) => {
+                std::fs::write(path, content)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Write eBPF maps to the configured destination
+    pub fn write_bpf_maps(&self, maps: &[BpfMapInfo]) -> Result<(), AnalysisError> {
+        let content = self.formatter.format_bpf_maps(maps)?;
+
+        match &self.destination {
+            OutputDestination::Stdout => {
+                println!("{}", content);
+            }
+            OutputDestination::File(path) => {
+                std::fs::write(path, content)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Write kernel text modifications to the configured destination
+    pub fn write_inline_hooks(&self, hooks: &[InlineHookInfo]) -> Result<(), AnalysisError> {
+        let content = self.formatter.format_inline_hooks(hooks)?;
+
+        match &self.destination {
+            OutputDestination::Stdout => {
+                println!("{}", content);
+            }
+            OutputDestination::File(path) => {
+                std::fs::write(path, content)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Write ftrace, kprobe and tracepoint callbacks to the configured destination
+    pub fn write_trace_hooks(&self, hooks: &[TraceHookInfo]) -> Result<(), AnalysisError> {
+        let content = self.formatter.format_trace_hooks(hooks)?;
+
+        match &self.destination {
+            OutputDestination::Stdout => {
+                println!("{}", content);
+            }
+            OutputDestination::File(path) => {
+                std::fs::write(path, content)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Write netfilter hooks to the configured destination
+    pub fn write_netfilter_hooks(&self, hooks: &[NetfilterHookInfo]) -> Result<(), AnalysisError> {
+        let content = self.formatter.format_netfilter_hooks(hooks)?;
+
+        match &self.destination {
+            OutputDestination::Stdout => {
+                println!("{}", content);
+            }
+            OutputDestination::File(path) => {
+                std::fs::write(path, content)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Write notifier chain callbacks to the configured destination
+    pub fn write_notifiers(&self, notifiers: &[NotifierInfo]) -> Result<(), AnalysisError> {
+        let content = self.formatter.format_notifiers(notifiers)?;
+
+        match &self.destination {
+            OutputDestination::Stdout => {
+                println!("{}", content);
+            }
+            OutputDestination::File(path) => {
+                std::fs::write(path, content)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Write tty handlers to the configured destination
+    pub fn write_tty_hooks(&self, hooks: &[TtyHookInfo]) -> Result<(), AnalysisError> {
+        let content = self.formatter.format_tty_hooks(hooks)?;
+
+        match &self.destination {
+            OutputDestination::Stdout => {
+                println!("{}", content);
+            }
+            OutputDestination::File(path) => {
+                std::fs::write(path, content)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Write ops structure pointers to the configured destination
+    pub fn write_ops_pointers(&self, pointers: &[OpsPointerInfo]) -> Result<(), AnalysisError> {
+        let content = self.formatter.format_ops_pointers(pointers)?;
+
+        match &self.destination {
+            OutputDestination::Stdout => {
+                println!("{}", content);
+            }
+            OutputDestination::File(path) => {
+                std::fs::write(path, content)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Write process namespaces to the configured destination
+    pub fn write_process_namespaces(
+        &self,
+        processes: &[ProcessNamespaceInfo],
+    ) -> Result<(), AnalysisError> {
+        let content = self.formatter.format_process_namespaces(processes)?;
+
+        match &self.destination {
+            OutputDestination::Stdout => {
+                println!("{}", content);
+            }
+            OutputDestination::File(path) => {
+                std::fs::write(path, content)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Write containers to the configured destination
+    pub fn write_containers(&self, containers: &[ContainerInfo]) -> Result<(), AnalysisError> {
+        let content = self.formatter.format_containers(containers)?;
+
+        match &self.destination {
+            OutputDestination::Stdout => {
+                println!("{}", content);
+            }
+            OutputDestination::File(path) => {
+                std::fs::write(path, content)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Write loaded libraries to the configured destination
+    pub fn write_libraries(&self, libraries: &[LibraryInfo]) -> Result<(), AnalysisError> {
+        let content = self.formatter.format_libraries(libraries)?;
+
+        match &self.destination {
+            OutputDestination::Stdout => {
+                println!("{}", content);
+            }
+            OutputDestination::File(path) => {
+                std::fs::write(path, content)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Write mapped ELF images to the configured destination
+    pub fn write_elfs(&self, elfs: &[ElfInfo]) -> Result<(), AnalysisError> {
+        let content = self.formatter.format_elfs(elfs)?;
+
+        match &self.destination {
+            OutputDestination::Stdout => {
+                println!("{}", content);
+            }
+            OutputDestination::File(path) => {
+                std::fs::write(path, content)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Write signature scan matches to the configured destination
+    pub fn write_scan_matches(&self, matches: &[ScanMatchInfo]) -> Result<(), AnalysisError> {
+        let content = self.formatter.format_scan_matches(matches)?;
+
+        match &self.destination {
+            OutputDestination::Stdout => {
+                println!("{}", content);
+            }
+            OutputDestination::File(path) => {
+                std::fs::write(path, content)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Write attributed strings to the configured destination
+    pub fn write_strings(&self, strings: &[StringInfo]) -> Result<(), AnalysisError> {
+        let content = self.formatter.format_strings(strings)?;
+
+        match &self.destination {
+            OutputDestination::Stdout => {
+                println!("{}", content);
+            }
+            OutputDestination::File(path) => {
+                std::fs::write(path, content)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Write decoded struct page entries to the configured destination
+    pub fn write_page_info(&self, pages: &[PageInfo]) -> Result<(), AnalysisError> {
+        let content = self.formatter.format_page_info(pages)?;
+
+        match &self.destination {
+            OutputDestination::Stdout => {
+                println!("{}", content);
+            }
+            OutputDestination::File(path) => {
+                std::fs::write(path, content)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Write process mappings with page residency to the configured destination
+    pub fn write_maps(&self, maps: &[MapsInfo]) -> Result<(), AnalysisError> {
+        let content = self.formatter.format_maps(maps)?;
+
+        match &self.destination {
+            OutputDestination::Stdout => {
+                println!("{}", content);
+            }
+            OutputDestination::File(path) => {
+                std::fs::write(path, content)?;
+            }
+        }
+
+        Ok(())
+    }
 }
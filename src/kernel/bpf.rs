@@ -0,0 +1,547 @@
+//! eBPF object helpers: prog_idr / map_idr / link_idr walking and type names
+use crate::error::AnalysisError;
+use crate::kernel::xarray::XArrayWalker;
+use crate::kernel::KernelParser;
+use crate::symbols::SymbolResolver;
+use crate::translation::MemoryTranslator;
+use std::collections::HashMap;
+
+// Macro for conditional warning output
+macro_rules! warn {
+    ($($arg:tt)*) => {
+        if std::env::var("LINMEMPARSER_VERBOSE").is_ok() {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+// BPF_OBJ_NAME_LEN
+const BPF_OBJ_NAME_LEN: usize = 16;
+const BPF_TAG_SIZE: usize = 8;
+const IFNAMSIZ: usize = 16;
+// Upper bound on maps referenced by a single program
+const MAX_USED_MAPS: u32 = 64;
+
+// enum bpf_link_type values whose attach target is decoded
+const BPF_LINK_TYPE_RAW_TRACEPOINT: u32 = 1;
+const BPF_LINK_TYPE_XDP: u32 = 6;
+
+/// Fields of a loaded eBPF program
+#[derive(Debug, Clone)]
+pub struct BpfProg {
+    pub prog: u64, // struct bpf_prog pointer
+    pub id: u32,
+    pub prog_type: u32,
+    pub name: String,
+    pub tag: [u8; BPF_TAG_SIZE],
+    pub load_time: u64, // ns since boot
+    pub insn_count: u32,
+    pub jit_addr: u64,
+    pub jit_size: u32,
+    pub uid: Option<u32>,
+    pub attach_func: Option<String>,
+    pub map_ids: Vec<u32>,
+}
+
+impl BpfProg {
+    /// A program listed in prog_idr whose fields could not be read
+    fn unreadable(prog: u64) -> Self {
+        BpfProg {
+            prog,
+            id: 0,
+            prog_type: 0,
+            name: "<unreadable>".to_string(),
+            tag: [0; BPF_TAG_SIZE],
+            load_time: 0,
+            insn_count: 0,
+            jit_addr: 0,
+            jit_size: 0,
+            uid: None,
+            attach_func: None,
+            map_ids: Vec::new(),
+        }
+    }
+}
+
+/// Fields of an eBPF map
+#[derive(Debug, Clone)]
+pub struct BpfMap {
+    pub map: u64, // struct bpf_map pointer
+    pub id: u32,
+    pub map_type: u32,
+    pub name: String,
+    pub key_size: u32,
+    pub value_size: u32,
+    pub max_entries: u32,
+    pub flags: u32,
+}
+
+/// Walker over the eBPF object IDRs
+pub struct BpfWalker<'a> {
+    mapped: &'a [u8],
+    translator: &'a MemoryTranslator,
+    symbol_resolver: &'a SymbolResolver,
+    xarray: XArrayWalker<'a>,
+    // bpf_prog
+    prog_type: usize,
+    prog_len: usize,
+    prog_jited_len: usize,
+    prog_tag: usize,
+    prog_aux: usize,
+    prog_bpf_func: usize,
+    prog_insnsi: usize,
+    // bpf_prog_aux
+    aux_id: usize,
+    aux_name: Option<usize>,
+    aux_load_time: Option<usize>,
+    aux_user: Option<usize>,
+    aux_attach_func_name: Option<usize>,
+    aux_used_maps: Option<usize>,
+    aux_used_map_cnt: Option<usize>,
+    // user_struct
+    user_uid: Option<usize>,
+}
+
+impl<'a> BpfWalker<'a> {
+    /// Resolve the bpf_prog / bpf_prog_aux offsets
+    pub fn new(
+        mapped: &'a [u8],
+        translator: &'a MemoryTranslator,
+        symbol_resolver: &'a SymbolResolver,
+    ) -> Result<Self, AnalysisError> {
+        let field = |s: &str, f: &str| symbol_resolver.require_field_offset(s, f);
+        // Owner, name and attach details come and go between kernel versions
+        let optional = |s: &str, f: &str| {
+            symbol_resolver
+                .get_struct_field_offset(s, f, None)
+                .map(|o| o as usize)
+        };
+
+        Ok(BpfWalker {
+            mapped,
+            translator,
+            symbol_resolver,
+            xarray: XArrayWalker::new(mapped, translator, symbol_resolver),
+            prog_type: field("bpf_prog", "type")?,
+            prog_len: field("bpf_prog", "len")?,
+            prog_jited_len: field("bpf_prog", "jited_len")?,
+            prog_tag: field("bpf_prog", "tag")?,
+            prog_aux: field("bpf_prog", "aux")?,
+            prog_bpf_func: field("bpf_prog", "bpf_func")?,
+            prog_insnsi: field("bpf_prog", "insnsi")?,
+            aux_id: field("bpf_prog_aux", "id")?,
+            aux_name: optional("bpf_prog_aux", "name"),
+            aux_load_time: optional("bpf_prog_aux", "load_time"),
+            aux_user: optional("bpf_prog_aux", "user"),
+            aux_attach_func_name: optional("bpf_prog_aux", "attach_func_name"),
+            aux_used_maps: optional("bpf_prog_aux", "used_maps"),
+            aux_used_map_cnt: optional("bpf_prog_aux", "used_map_cnt"),
+            user_uid: optional("user_struct", "uid"),
+        })
+    }
+
+    fn read_ptr(&self, addr: u64) -> u64 {
+        KernelParser::read_virt_pointer(self.mapped, self.translator, addr).unwrap_or(0)
+    }
+
+    fn read_u32(&self, addr: u64) -> u32 {
+        KernelParser::read_virt_u32(self.mapped, self.translator, addr).unwrap_or(0)
+    }
+
+    fn read_name(&self, addr: u64) -> String {
+        KernelParser::read_virt_cstring(self.mapped, self.translator, addr, BPF_OBJ_NAME_LEN)
+            .unwrap_or_default()
+    }
+
+    /// Return the objects stored in the IDR named by `symbol`
+    fn idr_entries(&self, symbol: &str) -> Result<Vec<u64>, AnalysisError> {
+        let idr = self
+            .symbol_resolver
+            .get_symbol_address(symbol)
+            .ok_or_else(|| AnalysisError::SymbolNotFound(symbol.to_string()))?;
+        let idr_rt = self
+            .symbol_resolver
+            .get_struct_field_offset("idr", "idr_rt", None)
+            .unwrap_or(0);
+        Ok(self
+            .xarray
+            .entries(idr + idr_rt)
+            .into_iter()
+            .map(|(_, entry)| entry)
+            .collect())
+    }
+
+    /// Enumerate loaded programs from prog_idr
+    ///
+    /// Programs whose bpf_prog_aux cannot be read (a vmalloc page missing from the
+    /// dump) are still listed, named "<unreadable>", so they are not silently lost.
+    pub fn programs(&self) -> Result<Vec<BpfProg>, AnalysisError> {
+        let mut programs = Vec::new();
+        for prog in self.idr_entries("prog_idr")? {
+            let aux = match KernelParser::read_virt_pointer(
+                self.mapped,
+                self.translator,
+                prog + self.prog_aux as u64,
+            ) {
+                Some(aux) if aux != 0 => aux,
+                _ => {
+                    warn!("[WARNING] bpf_prog 0x{:x} is unreadable", prog);
+                    programs.push(BpfProg::unreadable(prog));
+                    continue;
+                }
+            };
+
+            let mut tag = [0u8; BPF_TAG_SIZE];
+            if let Some(bytes) = KernelParser::read_virt_bytes(
+                self.mapped,
+                self.translator,
+                prog + self.prog_tag as u64,
+                BPF_TAG_SIZE,
+            ) {
+                tag.copy_from_slice(&bytes);
+            }
+
+            // aux->user was dropped in 5.11 when memlock accounting moved to memcg
+            let uid = match (self.aux_user, self.user_uid) {
+                (Some(user), Some(uid)) => match self.read_ptr(aux + user as u64) {
+                    0 => None,
+                    ptr => {
+                        KernelParser::read_virt_u32(self.mapped, self.translator, ptr + uid as u64)
+                    }
+                },
+                _ => None,
+            };
+
+            let attach_func = self.aux_attach_func_name.and_then(|offset| {
+                KernelParser::read_virt_cstring(
+                    self.mapped,
+                    self.translator,
+                    self.read_ptr(aux + offset as u64),
+                    256,
+                )
+            });
+
+            programs.push(BpfProg {
+                prog,
+                id: self.read_u32(aux + self.aux_id as u64),
+                prog_type: self.read_u32(prog + self.prog_type as u64),
+                name: self
+                    .aux_name
+                    .map(|offset| self.read_name(aux + offset as u64))
+                    .unwrap_or_default(),
+                tag,
+                load_time: self
+                    .aux_load_time
+                    .and_then(|offset| {
+                        KernelParser::read_virt_u64(
+                            self.mapped,
+                            self.translator,
+                            aux + offset as u64,
+                        )
+                    })
+                    .unwrap_or(0),
+                insn_count: self.read_u32(prog + self.prog_len as u64),
+                jit_addr: self.read_ptr(prog + self.prog_bpf_func as u64),
+                jit_size: self.read_u32(prog + self.prog_jited_len as u64),
+                uid,
+                attach_func,
+                map_ids: self.used_map_ids(aux),
+            });
+        }
+
+        programs.sort_by_key(|p| p.id);
+        Ok(programs)
+    }
+
+    /// Read the ids of the maps a program references through aux->used_maps
+    fn used_map_ids(&self, aux: u64) -> Vec<u32> {
+        let (maps, count) = match (self.aux_used_maps, self.aux_used_map_cnt) {
+            (Some(maps), Some(count)) => (
+                self.read_ptr(aux + maps as u64),
+                self.read_u32(aux + count as u64).min(MAX_USED_MAPS),
+            ),
+            _ => return Vec::new(),
+        };
+        let map_id = match self
+            .symbol_resolver
+            .get_struct_field_offset("bpf_map", "id", None)
+        {
+            Some(o) => o,
+            None => return Vec::new(),
+        };
+        if maps == 0 {
+            return Vec::new();
+        }
+
+        (0..count as u64)
+            .map(|i| self.read_ptr(maps + i * self.translator.pointer_size()))
+            .filter(|&map| map != 0)
+            .map(|map| self.read_u32(map + map_id))
+            .collect()
+    }
+
+    /// Enumerate maps from map_idr
+    pub fn maps(&self) -> Result<Vec<BpfMap>, AnalysisError> {
+        let field = |f: &str| {
+            self.symbol_resolver
+                .require_field_offset("bpf_map", f)
+                .map(|o| o as u64)
+        };
+        let map_type = field("map_type")?;
+        let key_size = field("key_size")?;
+        let value_size = field("value_size")?;
+        let max_entries = field("max_entries")?;
+        let map_flags = field("map_flags")?;
+        let id = field("id")?;
+        let name = self
+            .symbol_resolver
+            .get_struct_field_offset("bpf_map", "name", None);
+
+        let mut maps: Vec<BpfMap> = self
+            .idr_entries("map_idr")?
+            .into_iter()
+            .map(|map| BpfMap {
+                map,
+                id: self.read_u32(map + id),
+                map_type: self.read_u32(map + map_type),
+                name: name
+                    .map(|offset| self.read_name(map + offset))
+                    .unwrap_or_default(),
+                key_size: self.read_u32(map + key_size),
+                value_size: self.read_u32(map + value_size),
+                max_entries: self.read_u32(map + max_entries),
+                flags: self.read_u32(map + map_flags),
+            })
+            .collect();
+
+        maps.sort_by_key(|m| m.id);
+        Ok(maps)
+    }
+
+    /// Map each program to the attach points recorded by bpf_links (5.7+)
+    ///
+    /// Programs attached through the legacy perf_event/netlink interfaces have no
+    /// link and are not listed here.
+    pub fn link_attachments(&self) -> HashMap<u64, Vec<String>> {
+        let mut attachments: HashMap<u64, Vec<String>> = HashMap::new();
+        let resolver = self.symbol_resolver;
+        let (link_type, link_prog) = match (
+            resolver.get_struct_field_offset("bpf_link", "type", None),
+            resolver.get_struct_field_offset("bpf_link", "prog", None),
+        ) {
+            (Some(t), Some(p)) => (t, p),
+            _ => return attachments,
+        };
+        let links = match self.idr_entries("link_idr") {
+            Ok(links) => links,
+            Err(_) => return attachments,
+        };
+
+        for link in links {
+            let prog = self.read_ptr(link + link_prog);
+            if prog == 0 {
+                continue;
+            }
+            let kind = self.read_u32(link + link_type);
+            let target = match kind {
+                BPF_LINK_TYPE_RAW_TRACEPOINT => self.raw_tracepoint_name(link),
+                BPF_LINK_TYPE_XDP => self.xdp_device_name(link),
+                _ => None,
+            };
+            let point = match target {
+                Some(target) => format!("{}:{}", bpf_link_type_name(kind), target),
+                None => bpf_link_type_name(kind),
+            };
+            attachments.entry(prog).or_default().push(point);
+        }
+
+        attachments
+    }
+
+    /// Follow bpf_raw_tp_link -> bpf_raw_event_map -> tracepoint name
+    fn raw_tracepoint_name(&self, link: u64) -> Option<String> {
+        let resolver = self.symbol_resolver;
+        let btp = resolver.get_struct_field_offset("bpf_raw_tp_link", "btp", None)?;
+        let tp = resolver.get_struct_field_offset("bpf_raw_event_map", "tp", None)?;
+        let name = resolver.get_struct_field_offset("tracepoint", "name", None)?;
+
+        let event_map = self.read_ptr(link + btp);
+        if event_map == 0 {
+            return None;
+        }
+        let tracepoint = self.read_ptr(event_map + tp);
+        if tracepoint == 0 {
+            return None;
+        }
+        KernelParser::read_virt_cstring(
+            self.mapped,
+            self.translator,
+            self.read_ptr(tracepoint + name),
+            256,
+        )
+    }
+
+    /// Follow bpf_xdp_link -> net_device name
+    fn xdp_device_name(&self, link: u64) -> Option<String> {
+        let resolver = self.symbol_resolver;
+        let dev = resolver.get_struct_field_offset("bpf_xdp_link", "dev", None)?;
+        let name = resolver.get_struct_field_offset("net_device", "name", None)?;
+
+        let device = self.read_ptr(link + dev);
+        if device == 0 {
+            return None;
+        }
+        KernelParser::read_virt_cstring(self.mapped, self.translator, device + name, IFNAMSIZ)
+    }
+
+    /// Read a program's bytecode (struct bpf_insn, 8 bytes each)
+    pub fn bytecode(&self, prog: &BpfProg) -> Option<Vec<u8>> {
+        if prog.insn_count == 0 {
+            return None;
+        }
+        KernelParser::read_virt_bytes(
+            self.mapped,
+            self.translator,
+            prog.prog + self.prog_insnsi as u64,
+            prog.insn_count as usize * 8,
+        )
+    }
+
+    /// Read a program's JIT-compiled image
+    pub fn jit_image(&self, prog: &BpfProg) -> Option<Vec<u8>> {
+        if prog.jit_addr == 0 || prog.jit_size == 0 {
+            return None;
+        }
+        KernelParser::read_virt_bytes(
+            self.mapped,
+            self.translator,
+            prog.jit_addr,
+            prog.jit_size as usize,
+        )
+    }
+}
+
+/// Name an enum bpf_prog_type value
+pub fn bpf_prog_type_name(prog_type: u32) -> String {
+    const NAMES: [&str; 33] = [
+        "unspec",
+        "socket_filter",
+        "kprobe",
+        "sched_cls",
+        "sched_act",
+        "tracepoint",
+        "xdp",
+        "perf_event",
+        "cgroup_skb",
+        "cgroup_sock",
+        "lwt_in",
+        "lwt_out",
+        "lwt_xmit",
+        "sock_ops",
+        "sk_skb",
+        "cgroup_device",
+        "sk_msg",
+        "raw_tracepoint",
+        "cgroup_sock_addr",
+        "lwt_seg6local",
+        "lirc_mode2",
+        "sk_reuseport",
+        "flow_dissector",
+        "cgroup_sysctl",
+        "raw_tracepoint_writable",
+        "cgroup_sockopt",
+        "tracing",
+        "struct_ops",
+        "ext",
+        "lsm",
+        "sk_lookup",
+        "syscall",
+        "netfilter",
+    ];
+    NAMES
+        .get(prog_type as usize)
+        .map(|n| n.to_string())
+        .unwrap_or_else(|| format!("type_{}", prog_type))
+}
+
+/// Name an enum bpf_map_type value
+pub fn bpf_map_type_name(map_type: u32) -> String {
+    const NAMES: [&str; 34] = [
+        "unspec",
+        "hash",
+        "array",
+        "prog_array",
+        "perf_event_array",
+        "percpu_hash",
+        "percpu_array",
+        "stack_trace",
+        "cgroup_array",
+        "lru_hash",
+        "lru_percpu_hash",
+        "lpm_trie",
+        "array_of_maps",
+        "hash_of_maps",
+        "devmap",
+        "sockmap",
+        "cpumap",
+        "xskmap",
+        "sockhash",
+        "cgroup_storage",
+        "reuseport_sockarray",
+        "percpu_cgroup_storage",
+        "queue",
+        "stack",
+        "sk_storage",
+        "devmap_hash",
+        "struct_ops",
+        "ringbuf",
+        "inode_storage",
+        "task_storage",
+        "bloom_filter",
+        "user_ringbuf",
+        "cgrp_storage",
+        "arena",
+    ];
+    NAMES
+        .get(map_type as usize)
+        .map(|n| n.to_string())
+        .unwrap_or_else(|| format!("type_{}", map_type))
+}
+
+/// Name an enum bpf_link_type value
+fn bpf_link_type_name(link_type: u32) -> String {
+    const NAMES: [&str; 14] = [
+        "unspec",
+        "raw_tracepoint",
+        "tracing",
+        "cgroup",
+        "iter",
+        "netns",
+        "xdp",
+        "perf_event",
+        "kprobe_multi",
+        "struct_ops",
+        "netfilter",
+        "tcx",
+        "uprobe_multi",
+        "netkit",
+    ];
+    NAMES
+        .get(link_type as usize)
+        .map(|n| n.to_string())
+        .unwrap_or_else(|| format!("link_{}", link_type))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bpf_type_names() {
+        assert_eq!(bpf_prog_type_name(2), "kprobe");
+        assert_eq!(bpf_prog_type_name(29), "lsm");
+        assert_eq!(bpf_prog_type_name(99), "type_99");
+        assert_eq!(bpf_map_type_name(27), "ringbuf");
+        assert_eq!(bpf_link_type_name(6), "xdp");
+    }
+}
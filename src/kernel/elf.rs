@@ -0,0 +1,190 @@
+//! In-memory ELF image helpers: header parsing, build IDs and image reconstruction
+use crate::kernel::mm::ProcessMemory;
+use crate::kernel::target::Target;
+use goblin::container::{Container, Ctx};
+use goblin::elf::header::{et_to_str, Header};
+use goblin::elf::note::NT_GNU_BUILD_ID;
+use goblin::elf::program_header::{ProgramHeader, PT_LOAD, PT_NOTE};
+use goblin::elf::Elf;
+
+const PAGE_SIZE: u64 = 0x1000;
+// Safety limits
+const MAX_NOTE_BYTES: u64 = 0x1000;
+const MAX_IMAGE_SIZE: u64 = 256 * 1024 * 1024;
+
+/// An ELF image mapped into a process
+pub struct ElfImage {
+    pub base: u64, // Address of the ELF header
+    pub header: Header,
+    pub program_headers: Vec<ProgramHeader>,
+    bias: u64, // Load bias: runtime address minus p_vaddr
+}
+
+impl ElfImage {
+    /// Parse the ELF and program headers mapped at an address
+    ///
+    /// Returns None unless the page starts with the ELF magic and the program
+    /// headers are resident.
+    pub fn parse(memory: &ProcessMemory, base: u64) -> Option<Self> {
+        let page = memory.read_page(base)?;
+        if !page.starts_with(b"\x7fELF") {
+            return None;
+        }
+        let header = Elf::parse_header(&page).ok()?;
+        let ctx = Ctx::new(header.container().ok()?, header.endianness().ok()?);
+
+        let table_end = header.e_phoff + header.e_phentsize as u64 * header.e_phnum as u64;
+        let bytes = if table_end <= PAGE_SIZE {
+            page.to_vec()
+        } else {
+            memory.read_bytes(base, table_end as usize)?
+        };
+        let program_headers = ProgramHeader::parse(
+            &bytes,
+            header.e_phoff as usize,
+            header.e_phnum as usize,
+            ctx,
+        )
+        .ok()?;
+
+        // The header page is the page-aligned start of the first loadable segment
+        let first_load = program_headers.iter().find(|p| p.p_type == PT_LOAD)?;
+        let bias = base.wrapping_sub(first_load.p_vaddr & !(PAGE_SIZE - 1));
+
+        Some(ElfImage {
+            base,
+            header,
+            program_headers,
+            bias,
+        })
+    }
+
+    /// Name the object type ("EXEC", "DYN", ...)
+    pub fn type_name(&self) -> &'static str {
+        et_to_str(self.header.e_type)
+    }
+
+    /// Read the GNU build ID from the PT_NOTE segments
+    pub fn build_id(&self, memory: &ProcessMemory) -> Option<String> {
+        let target = Target::from_elf_header(&self.header.e_ident)?;
+        self.program_headers
+            .iter()
+            .filter(|p| p.p_type == PT_NOTE)
+            .find_map(|note| {
+                let bytes = memory.read_bytes(
+                    self.bias.wrapping_add(note.p_vaddr),
+                    note.p_memsz.min(MAX_NOTE_BYTES) as usize,
+                )?;
+                parse_build_id(&bytes, target)
+            })
+    }
+
+    /// Rebuild a file image by placing each loadable segment at its file offset
+    ///
+    /// Pages that are not resident are left zero-filled. Section headers are not
+    /// loaded at runtime, so the section header fields are cleared.
+    pub fn reconstruct(&self, memory: &ProcessMemory) -> Vec<u8> {
+        let loads: Vec<&ProgramHeader> = self
+            .program_headers
+            .iter()
+            .filter(|p| p.p_type == PT_LOAD)
+            .collect();
+        let size = loads
+            .iter()
+            .map(|p| p.p_offset + p.p_filesz)
+            .max()
+            .unwrap_or(0)
+            .min(MAX_IMAGE_SIZE);
+        let mut image = vec![0u8; size as usize];
+
+        for segment in loads {
+            let start = self.bias.wrapping_add(segment.p_vaddr);
+            let mut copied = 0u64;
+            while copied < segment.p_filesz {
+                let address = start + copied;
+                let page_offset = address & (PAGE_SIZE - 1);
+                let chunk = (PAGE_SIZE - page_offset).min(segment.p_filesz - copied);
+                let target = segment.p_offset + copied;
+                if target + chunk > size {
+                    break;
+                }
+                if let Some(page) = memory.read_page(address) {
+                    image[target as usize..(target + chunk) as usize].copy_from_slice(
+                        &page[page_offset as usize..(page_offset + chunk) as usize],
+                    );
+                }
+                copied += chunk;
+            }
+        }
+
+        // e_shoff, e_shnum and e_shstrndx
+        let fields: [(usize, usize); 3] = match self.header.container() {
+            Ok(Container::Big) => [(0x28, 8), (0x3c, 2), (0x3e, 2)],
+            _ => [(0x20, 4), (0x30, 2), (0x32, 2)],
+        };
+        for (offset, length) in fields {
+            if let Some(field) = image.get_mut(offset..offset + length) {
+                field.fill(0);
+            }
+        }
+
+        image
+    }
+}
+
+/// Find an NT_GNU_BUILD_ID note owned by "GNU" and format its descriptor as hex
+fn parse_build_id(notes: &[u8], target: Target) -> Option<String> {
+    let align = |n: usize| (n + 3) & !3;
+    let u32_at = |i: usize| Some(target.u32(notes.get(i..i + 4)?.try_into().ok()?));
+
+    let mut offset = 0;
+    while offset + 12 <= notes.len() {
+        let namesz = u32_at(offset)? as usize;
+        let descsz = u32_at(offset + 4)? as usize;
+        let note_type = u32_at(offset + 8)?;
+        let name_start = offset + 12;
+        let desc_start = name_start + align(namesz);
+        let desc = notes.get(desc_start..desc_start + descsz)?;
+        if note_type == NT_GNU_BUILD_ID && notes.get(name_start..name_start + namesz)? == b"GNU\0" {
+            return Some(desc.iter().map(|b| format!("{:02x}", b)).collect());
+        }
+        offset = desc_start + align(descsz);
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_build_id_skips_other_notes() {
+        let mut notes = Vec::new();
+        // NT_GNU_ABI_TAG (type 1) with a 16-byte descriptor
+        notes.extend_from_slice(&[4, 0, 0, 0, 16, 0, 0, 0, 1, 0, 0, 0]);
+        notes.extend_from_slice(b"GNU\0");
+        notes.extend_from_slice(&[0u8; 16]);
+        // NT_GNU_BUILD_ID with a 4-byte descriptor
+        notes.extend_from_slice(&[4, 0, 0, 0, 4, 0, 0, 0, 3, 0, 0, 0]);
+        notes.extend_from_slice(b"GNU\0");
+        notes.extend_from_slice(&[0xde, 0xad, 0xbe, 0xef]);
+
+        let target = Target::default();
+        assert_eq!(parse_build_id(&notes, target), Some("deadbeef".to_string()));
+        assert_eq!(parse_build_id(&notes[..32], target), None);
+
+        // Big-endian notes carry the same fields in the other byte order
+        let swapped: Vec<u8> = notes
+            .chunks(4)
+            .flat_map(|word| {
+                if word.starts_with(b"GNU") || word == [0xde, 0xad, 0xbe, 0xef] {
+                    word.to_vec()
+                } else {
+                    word.iter().rev().copied().collect()
+                }
+            })
+            .collect();
+        let be = Target::from_pointer_type(8, "big").unwrap();
+        assert_eq!(parse_build_id(&swapped, be), Some("deadbeef".to_string()));
+    }
+}
@@ -0,0 +1,192 @@
+//! Open file helpers: task fd tables and struct file fields
+use crate::error::AnalysisError;
+use crate::kernel::KernelParser;
+use crate::symbols::SymbolResolver;
+use crate::translation::MemoryTranslator;
+
+// Upper bound on fd table size (NR_OPEN default is 1048576)
+const MAX_FDS: u32 = 1 << 20;
+
+// S_IFMT / S_IFSOCK from include/uapi/linux/stat.h
+const S_IFMT: u16 = 0o170000;
+const S_IFSOCK: u16 = 0o140000;
+
+/// An open file descriptor of a task
+#[derive(Debug, Clone)]
+pub struct OpenFile {
+    pub fd: u32,
+    pub file: u64, // struct file pointer
+}
+
+/// Walker over task fd tables and struct file
+pub struct FileWalker<'a> {
+    mapped: &'a [u8],
+    translator: &'a MemoryTranslator,
+    // task_struct
+    task_files: usize,
+    // files_struct / fdtable
+    files_fdt: usize,
+    fdt_max_fds: usize,
+    fdt_fd: usize,
+    // file
+    f_inode: usize,
+    // inode
+    i_mode: usize,
+    i_ino: usize,
+}
+
+impl<'a> FileWalker<'a> {
+    /// Resolve the structure offsets needed for fd table walking
+    pub fn new(
+        mapped: &'a [u8],
+        translator: &'a MemoryTranslator,
+        symbol_resolver: &'a SymbolResolver,
+    ) -> Result<Self, AnalysisError> {
+        let field = |s: &str, f: &str| symbol_resolver.require_field_offset(s, f);
+
+        Ok(FileWalker {
+            mapped,
+            translator,
+            task_files: field("task_struct", "files")?,
+            files_fdt: field("files_struct", "fdt")?,
+            fdt_max_fds: field("fdtable", "max_fds")?,
+            fdt_fd: field("fdtable", "fd")?,
+            f_inode: field("file", "f_inode")?,
+            i_mode: field("inode", "i_mode")?,
+            i_ino: field("inode", "i_ino")?,
+        })
+    }
+
+    fn read_ptr(&self, addr: u64) -> u64 {
+        KernelParser::read_virt_pointer(self.mapped, self.translator, addr).unwrap_or(0)
+    }
+
+    /// List the open file descriptors of the task_struct at the given file offset
+    pub fn open_files(&self, task_file_offset: u64) -> Vec<OpenFile> {
+        let mut files = Vec::new();
+        let files_struct = KernelParser::read_pointer(
+            self.mapped,
+            task_file_offset as usize + self.task_files,
+            self.translator.target(),
+        )
+        .unwrap_or(0);
+        if files_struct == 0 {
+            return files; // Kernel threads and zombies have no fd table
+        }
+
+        let fdt = self.read_ptr(files_struct + self.files_fdt as u64);
+        if fdt == 0 {
+            return files;
+        }
+        let max_fds = KernelParser::read_virt_u32(
+            self.mapped,
+            self.translator,
+            fdt + self.fdt_max_fds as u64,
+        )
+        .unwrap_or(0)
+        .min(MAX_FDS);
+        let fd_array = self.read_ptr(fdt + self.fdt_fd as u64);
+        if fd_array == 0 {
+            return files;
+        }
+
+        // Read the pointer array in page-sized chunks
+        let target = self.translator.target();
+        let mut fd = 0u32;
+        while fd < max_fds {
+            let count = (max_fds - fd).min(512);
+            let bytes = match KernelParser::read_virt_bytes(
+                self.mapped,
+                self.translator,
+                fd_array + fd as u64 * target.pointer_size as u64,
+                count as usize * target.pointer_size,
+            ) {
+                Some(b) => b,
+                None => {
+                    // Skip an unmapped chunk, later descriptors may still be readable
+                    fd += count;
+                    continue;
+                }
+            };
+            for (i, chunk) in bytes.chunks_exact(target.pointer_size).enumerate() {
+                let file = target.pointer(chunk).unwrap_or(0);
+                if file != 0 {
+                    files.push(OpenFile {
+                        fd: fd + i as u32,
+                        file,
+                    });
+                }
+            }
+            fd += count;
+        }
+
+        files
+    }
+
+    /// Return the inode backing a struct file
+    pub fn file_inode(&self, file: u64) -> u64 {
+        self.read_ptr(file + self.f_inode as u64)
+    }
+
+    /// Read the inode number of a struct inode
+    pub fn inode_number(&self, inode: u64) -> u64 {
+        self.read_ptr(inode + self.i_ino as u64)
+    }
+
+    /// Check whether a struct inode is a socket (sockfs) inode
+    pub fn is_socket_inode(&self, inode: u64) -> bool {
+        KernelParser::read_virt_u16(self.mapped, self.translator, inode + self.i_mode as u64)
+            .map(|mode| mode & S_IFMT == S_IFSOCK)
+            .unwrap_or(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::MemoryRegion;
+    use crate::testutil::{Dump, DIRECT_MAP};
+
+    #[test]
+    fn test_open_files_skips_unreadable_chunks() {
+        let mut symbols = SymbolResolver::new();
+        symbols.add_struct("task_struct", 0x100, &[("files", 0x10)]);
+        symbols.add_struct("files_struct", 0x40, &[("fdt", 0x20)]);
+        symbols.add_struct("fdtable", 0x20, &[("max_fds", 0), ("fd", 8)]);
+        symbols.add_struct("file", 0x100, &[("f_inode", 0x20)]);
+        symbols.add_struct("inode", 0x100, &[("i_mode", 0), ("i_ino", 0x40)]);
+
+        // The fd array starts at physical 0x3000, but only its second
+        // 512-pointer chunk (physical 0x4000) is present in the dump
+        let mut dump = Dump::new(0x4000);
+        dump.ptr(0x110, 0x1000);
+        dump.ptr(0x1020, 0x1100);
+        dump.bytes(0x1100, &1024u32.to_le_bytes());
+        dump.ptr(0x1108, 0x3000);
+        dump.ptr(0x3000, 0x2000);
+        dump.ptr(0x3018, 0x2100);
+        let translator = MemoryTranslator::new(vec![
+            MemoryRegion {
+                start: 0,
+                end: 0x2fff,
+                file_offset: 0,
+            },
+            MemoryRegion {
+                start: 0x4000,
+                end: 0x4fff,
+                file_offset: 0x3000,
+            },
+        ]);
+
+        let walker = FileWalker::new(&dump.0, &translator, &symbols).unwrap();
+        let files: Vec<_> = walker
+            .open_files(0x100)
+            .into_iter()
+            .map(|f| (f.fd, f.file))
+            .collect();
+        assert_eq!(
+            files,
+            [(512, DIRECT_MAP + 0x2000), (515, DIRECT_MAP + 0x2100)]
+        );
+    }
+}
@@ -0,0 +1,112 @@
+//! Dynamic loader helpers: auxv, program headers and the link_map chain in user memory
+use crate::kernel::mm::ProcessMemory;
+use std::collections::HashSet;
+
+// Auxiliary vector tags (include/uapi/linux/auxvec.h)
+const AT_PHDR: u64 = 3;
+const AT_PHENT: u64 = 4;
+const AT_PHNUM: u64 = 5;
+// Program header types and dynamic tags (elf.h)
+const PT_LOAD: u32 = 1;
+const PT_DYNAMIC: u32 = 2;
+const PT_PHDR: u32 = 6;
+const DT_NULL: u64 = 0;
+const DT_DEBUG: u64 = 21;
+// sizeof(Elf32_Phdr) / sizeof(Elf64_Phdr) and the offset of p_vaddr in each
+const ELF32_PHDR_SIZE: u64 = 32;
+const ELF64_PHDR_SIZE: u64 = 56;
+const ELF32_P_VADDR: usize = 8;
+const ELF64_P_VADDR: usize = 16;
+// sizeof(Elf32_Ehdr), sizeof(Elf64_Ehdr)
+const ELF32_EHDR_SIZE: u64 = 52;
+const ELF64_EHDR_SIZE: u64 = 64;
+// Elf_Dyn, r_debug and link_map are made of word-sized fields:
+// struct r_debug: int r_version; struct link_map *r_map; ...
+// struct link_map: l_addr, l_name, l_ld, l_next, l_prev
+const R_DEBUG_R_MAP: u64 = 1;
+const LINK_MAP_L_NAME: u64 = 1;
+const LINK_MAP_L_NEXT: u64 = 3;
+// Safety limits
+const MAX_PHDRS: u64 = 128;
+const MAX_DYN_ENTRIES: u64 = 1024;
+const MAX_LINK_MAP_ENTRIES: usize = 4096;
+const MAX_PATH_LEN: usize = 4096;
+
+/// One object on the loader's link_map chain
+#[derive(Debug, Clone)]
+pub struct LinkMapEntry {
+    pub base: u64,    // l_addr: load bias of the object
+    pub name: String, // l_name: path the loader opened (empty for the main program)
+}
+
+/// Walk the loader's link_map chain starting from the main program's DT_DEBUG entry
+///
+/// Returns None for static executables, processes whose loader has not filled in
+/// r_debug yet, or when the program headers or dynamic section are not resident.
+pub fn link_map(memory: &ProcessMemory) -> Option<Vec<LinkMapEntry>> {
+    let target = memory.target();
+    let word = target.pointer_size as u64;
+    let (phdr_size, p_vaddr_offset, ehdr_size) = if word == 4 {
+        (ELF32_PHDR_SIZE, ELF32_P_VADDR, ELF32_EHDR_SIZE)
+    } else {
+        (ELF64_PHDR_SIZE, ELF64_P_VADDR, ELF64_EHDR_SIZE)
+    };
+
+    let auxv = memory.saved_auxv();
+    let aux = |tag: u64| auxv.iter().find(|(t, _)| *t == tag).map(|(_, v)| *v);
+    let phdr = aux(AT_PHDR)?;
+    let phent = aux(AT_PHENT).unwrap_or(phdr_size);
+    let phnum = aux(AT_PHNUM)?.min(MAX_PHDRS);
+
+    // Locate PT_DYNAMIC and the load bias of the main program
+    let mut dynamic = None;
+    let mut bias = None;
+    let mut first_load = None;
+    for i in 0..phnum {
+        let header = memory.read_bytes(phdr + i * phent, phdr_size as usize)?;
+        let p_type = target.u32(header[0..4].try_into().ok()?);
+        let p_vaddr = target.pointer(&header[p_vaddr_offset..])?;
+        match p_type {
+            PT_PHDR => bias = Some(phdr.wrapping_sub(p_vaddr)),
+            PT_DYNAMIC => dynamic = Some(p_vaddr),
+            PT_LOAD if first_load.is_none() => first_load = Some(p_vaddr),
+            _ => {}
+        }
+    }
+    // Without PT_PHDR, assume the headers follow the ELF header in the first segment
+    let bias = bias.or_else(|| Some(phdr.wrapping_sub(first_load? + ehdr_size)))?;
+    let dynamic = bias.wrapping_add(dynamic?);
+
+    let mut r_debug = 0;
+    for i in 0..MAX_DYN_ENTRIES {
+        // Elf_Dyn: d_tag, d_un
+        let tag = memory.read_pointer(dynamic + i * 2 * word)?;
+        if tag == DT_NULL {
+            break;
+        }
+        if tag == DT_DEBUG {
+            r_debug = memory.read_pointer(dynamic + (i * 2 + 1) * word)?;
+            break;
+        }
+    }
+    if r_debug == 0 {
+        return None;
+    }
+
+    let mut entries = Vec::new();
+    let mut seen = HashSet::new();
+    let mut current = memory.read_pointer(r_debug + R_DEBUG_R_MAP * word)?;
+    while current != 0 && entries.len() < MAX_LINK_MAP_ENTRIES && seen.insert(current) {
+        let base = memory.read_pointer(current).unwrap_or(0);
+        let name = memory
+            .read_pointer(current + LINK_MAP_L_NAME * word)
+            .and_then(|ptr| memory.read_cstring(ptr, MAX_PATH_LEN))
+            .unwrap_or_default();
+        entries.push(LinkMapEntry { base, name });
+        current = memory
+            .read_pointer(current + LINK_MAP_L_NEXT * word)
+            .unwrap_or(0);
+    }
+
+    Some(entries)
+}
@@ -0,0 +1,387 @@
+//! Process address space helpers: mm_struct, VMA enumeration and user memory reads
+use crate::error::AnalysisError;
+use crate::kernel::target::Target;
+use crate::kernel::KernelParser;
+use crate::symbols::SymbolResolver;
+use crate::translation::{MemoryTranslator, PageLocation};
+use std::borrow::Cow;
+
+// Macro for conditional debug output
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        if std::env::var("LINMEMPARSER_DEBUG").is_ok() {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+// Safety limits for VMA walks
+const MAX_VMAS: usize = 65536;
+const MAX_MAPLE_DEPTH: usize = 16;
+
+const PAGE_SIZE: u64 = 0x1000;
+
+// Maple tree node encoding (include/linux/maple_tree.h)
+const MAPLE_NODE_MASK: u64 = 0xff;
+const MAPLE_NODE_TYPE_SHIFT: u64 = 3;
+const MAPLE_NODE_TYPE_MASK: u64 = 0x0f;
+const MAPLE_DENSE: u64 = 0;
+const MAPLE_LEAF_64: u64 = 1;
+const MAPLE_RANGE_64: u64 = 2;
+const MAPLE_ARANGE_64: u64 = 3;
+const MAPLE_NODE_SLOTS: u64 = 31;
+const MAPLE_RANGE64_SLOTS: u64 = 16;
+const MAPLE_ARANGE64_SLOTS: u64 = 10;
+
+// AT_VECTOR_SIZE upper bound for mm_struct.saved_auxv
+const MAX_AUXV_ENTRIES: u64 = 64;
+
+// vm_flags bits
+pub const VM_READ: u64 = 0x1;
+pub const VM_WRITE: u64 = 0x2;
+pub const VM_EXEC: u64 = 0x4;
+pub const VM_SHARED: u64 = 0x8;
+
+/// A virtual memory area of a process
+#[derive(Debug, Clone)]
+#[allow(dead_code)]
+pub struct VmaInfo {
+    pub address: u64, // Virtual address of the vm_area_struct
+    pub start: u64,
+    pub end: u64,
+    pub flags: u64,
+    pub pgoff: u64,
+    pub file: u64, // struct file pointer (0 for anonymous mappings)
+}
+
+/// Address space of a single process
+pub struct ProcessMemory<'a> {
+    mapped: &'a [u8],
+    translator: &'a MemoryTranslator,
+    symbol_resolver: &'a SymbolResolver,
+    /// Virtual address of the mm_struct
+    pub mm: u64,
+    /// Physical address of the top-level page table
+    pub pgd_phys: u64,
+}
+
+impl<'a> ProcessMemory<'a> {
+    /// Build the address space for the task_struct at the given file offset
+    ///
+    /// Returns None for kernel threads (no mm) or when the page table is not captured.
+    pub fn for_task(
+        mapped: &'a [u8],
+        translator: &'a MemoryTranslator,
+        symbol_resolver: &'a SymbolResolver,
+        task_file_offset: u64,
+    ) -> Option<Self> {
+        let mm_offset = symbol_resolver
+            .get_struct_field_offset("task_struct", "mm", None)
+            .unwrap_or(0x350) as usize;
+        let mm = KernelParser::read_pointer(
+            mapped,
+            task_file_offset as usize + mm_offset,
+            translator.target(),
+        )?;
+        if mm == 0 {
+            return None;
+        }
+
+        let pgd_offset = symbol_resolver
+            .require_field_offset("mm_struct", "pgd")
+            .ok()?;
+        let pgd = KernelParser::read_virt_pointer(mapped, translator, mm + pgd_offset as u64)?;
+        let pgd_phys = translator.virtual_to_physical(pgd)?;
+
+        Some(ProcessMemory {
+            mapped,
+            translator,
+            symbol_resolver,
+            mm,
+            pgd_phys,
+        })
+    }
+
+    /// Read an unsigned long field of the mm_struct (start_brk, brk, start_stack, ...)
+    pub fn mm_field(&self, field_name: &str) -> Option<u64> {
+        let offset = self
+            .symbol_resolver
+            .get_struct_field_offset("mm_struct", field_name, None)?;
+        KernelParser::read_virt_pointer(self.mapped, self.translator, self.mm + offset)
+    }
+
+    /// Read the (type, value) pairs of the auxiliary vector saved at exec time
+    pub fn saved_auxv(&self) -> Vec<(u64, u64)> {
+        let mut auxv = Vec::new();
+        let offset =
+            match self
+                .symbol_resolver
+                .get_struct_field_offset("mm_struct", "saved_auxv", None)
+            {
+                Some(o) => o,
+                None => return auxv,
+            };
+        for i in 0..MAX_AUXV_ENTRIES {
+            // auxv entries are pairs of unsigned longs
+            let word = self.translator.pointer_size();
+            let entry = self.mm + offset + i * 2 * word;
+            // AT_NULL terminates the vector
+            let tag =
+                KernelParser::read_virt_pointer(self.mapped, self.translator, entry).unwrap_or(0);
+            if tag == 0 {
+                break;
+            }
+            let value = KernelParser::read_virt_pointer(self.mapped, self.translator, entry + word)
+                .unwrap_or(0);
+            auxv.push((tag, value));
+        }
+        auxv
+    }
+
+    /// Translate a user virtual address to a file offset
+    pub fn virtual_to_file_offset(&self, virtual_addr: u64) -> Option<u64> {
+        self.translator
+            .user_virtual_to_file_offset(self.mapped, self.pgd_phys, virtual_addr)
+    }
+
+    /// Find whether a user page is in the dump, swapped out or unavailable
+    pub fn locate_page(&self, page_addr: u64) -> PageLocation {
+        self.translator
+            .locate_user_page(self.mapped, self.pgd_phys, page_addr & !(PAGE_SIZE - 1))
+    }
+
+    /// Read one page of user memory from the dump, compressed swap or a loaded swap image
+    pub fn read_page(&self, page_addr: u64) -> Option<Cow<'a, [u8]>> {
+        match self.locate_page(page_addr) {
+            PageLocation::Memory(file_offset) => self
+                .mapped
+                .get(file_offset as usize..(file_offset + PAGE_SIZE) as usize)
+                .map(Cow::Borrowed),
+            PageLocation::Swapped(entry) => {
+                let slot = self.translator.read_swap_page(self.mapped, entry)?;
+                // Swap slots of 16K/64K kernel pages hold several 4K pages
+                let start = (page_addr as usize & (slot.len() - 1)) & !(PAGE_SIZE as usize - 1);
+                let range = start..start + PAGE_SIZE as usize;
+                match slot {
+                    Cow::Borrowed(bytes) => bytes.get(range).map(Cow::Borrowed),
+                    Cow::Owned(bytes) => bytes.get(range).map(|b| Cow::Owned(b.to_vec())),
+                }
+            }
+            PageLocation::Unavailable => None,
+        }
+    }
+
+    /// Read a buffer of user memory; fails if any page is unreadable
+    pub fn read_bytes(&self, virtual_addr: u64, length: usize) -> Option<Vec<u8>> {
+        let mut buffer = Vec::with_capacity(length);
+        let mut current = virtual_addr;
+
+        while buffer.len() < length {
+            let page_offset = (current & (PAGE_SIZE - 1)) as usize;
+            let chunk = (PAGE_SIZE as usize - page_offset).min(length - buffer.len());
+            let page = self.read_page(current)?;
+            buffer.extend_from_slice(&page[page_offset..page_offset + chunk]);
+            current = current.wrapping_add(chunk as u64);
+        }
+
+        Some(buffer)
+    }
+
+    /// Byte order and pointer size of the dumped kernel (and its user space)
+    pub fn target(&self) -> Target {
+        self.translator.target()
+    }
+
+    /// Read a pointer or unsigned long from user memory
+    pub fn read_pointer(&self, virtual_addr: u64) -> Option<u64> {
+        let target = self.target();
+        target.pointer(&self.read_bytes(virtual_addr, target.pointer_size)?)
+    }
+
+    /// Read a null-terminated string from user memory
+    pub fn read_cstring(&self, virtual_addr: u64, max_length: usize) -> Option<String> {
+        if virtual_addr == 0 {
+            return None;
+        }
+
+        let mut bytes = Vec::new();
+        let mut current = virtual_addr;
+        while bytes.len() < max_length {
+            let page = self.read_page(current)?;
+            let page_offset = (current & (PAGE_SIZE - 1)) as usize;
+            let chunk = &page[page_offset..];
+            let chunk = &chunk[..chunk.len().min(max_length - bytes.len())];
+            if let Some(nul_pos) = chunk.iter().position(|&c| c == 0) {
+                bytes.extend_from_slice(&chunk[..nul_pos]);
+                break;
+            }
+            bytes.extend_from_slice(chunk);
+            current = current.wrapping_add(chunk.len() as u64);
+        }
+
+        Some(String::from_utf8_lossy(&bytes).to_string())
+    }
+
+    /// Enumerate the VMAs of this address space
+    pub fn vmas(&self) -> Result<Vec<VmaInfo>, AnalysisError> {
+        enumerate_vmas(self.mapped, self.translator, self.symbol_resolver, self.mm)
+    }
+}
+
+/// Enumerate the VMAs of an mm_struct
+///
+/// Kernels before 6.1 link VMAs through mm->mmap/vm_next; newer kernels store
+/// them in the mm->mm_mt maple tree.
+pub fn enumerate_vmas(
+    mapped: &[u8],
+    translator: &MemoryTranslator,
+    symbol_resolver: &SymbolResolver,
+    mm: u64,
+) -> Result<Vec<VmaInfo>, AnalysisError> {
+    let mut addresses = Vec::new();
+
+    if symbol_resolver.has_field("mm_struct", "mmap") {
+        let mmap_offset = symbol_resolver.require_field_offset("mm_struct", "mmap")?;
+        let next_offset = symbol_resolver.require_field_offset("vm_area_struct", "vm_next")?;
+        let mut current =
+            KernelParser::read_virt_pointer(mapped, translator, mm + mmap_offset as u64)
+                .unwrap_or(0);
+        let mut visited = std::collections::HashSet::new();
+        while current != 0 && addresses.len() < MAX_VMAS && visited.insert(current) {
+            addresses.push(current);
+            current =
+                KernelParser::read_virt_pointer(mapped, translator, current + next_offset as u64)
+                    .unwrap_or(0);
+        }
+    } else {
+        let mt_offset = symbol_resolver.require_field_offset("mm_struct", "mm_mt")?;
+        let root_offset = symbol_resolver
+            .get_struct_field_offset("maple_tree", "ma_root", None)
+            .unwrap_or(8);
+        let root = KernelParser::read_virt_pointer(
+            mapped,
+            translator,
+            mm + (mt_offset as u64) + root_offset,
+        )
+        .unwrap_or(0);
+        collect_maple_entries(mapped, translator, symbol_resolver, root, 0, &mut addresses);
+    }
+
+    let start_offset = symbol_resolver.require_field_offset("vm_area_struct", "vm_start")?;
+    let end_offset = symbol_resolver.require_field_offset("vm_area_struct", "vm_end")?;
+    let flags_offset = symbol_resolver.require_field_offset("vm_area_struct", "vm_flags")?;
+    let pgoff_offset = symbol_resolver.require_field_offset("vm_area_struct", "vm_pgoff")?;
+    let file_offset = symbol_resolver.require_field_offset("vm_area_struct", "vm_file")?;
+    let mm_offset = symbol_resolver
+        .get_struct_field_offset("vm_area_struct", "vm_mm", None)
+        .map(|o| o as usize);
+
+    let read = |addr: u64| KernelParser::read_virt_pointer(mapped, translator, addr);
+    let user_space_end = translator.kernel_regions().user_space_end;
+    let mut vmas = Vec::new();
+
+    for address in addresses {
+        let (start, end) = match (
+            read(address + start_offset as u64),
+            read(address + end_offset as u64),
+        ) {
+            (Some(s), Some(e)) => (s, e),
+            _ => continue,
+        };
+
+        // Reject anything that does not look like a user-space VMA of this mm
+        if start >= end || end > user_space_end {
+            continue;
+        }
+        if let Some(off) = mm_offset {
+            if read(address + off as u64) != Some(mm) {
+                debug!(
+                    "[DEBUG] VMA 0x{:x} does not belong to mm 0x{:x}",
+                    address, mm
+                );
+                continue;
+            }
+        }
+
+        vmas.push(VmaInfo {
+            address,
+            start,
+            end,
+            flags: read(address + flags_offset as u64).unwrap_or(0),
+            pgoff: read(address + pgoff_offset as u64).unwrap_or(0),
+            file: read(address + file_offset as u64).unwrap_or(0),
+        });
+    }
+
+    vmas.sort_by_key(|vma| vma.start);
+    Ok(vmas)
+}
+
+/// Recursively collect the leaf entries of a maple tree
+fn collect_maple_entries(
+    mapped: &[u8],
+    translator: &MemoryTranslator,
+    symbol_resolver: &SymbolResolver,
+    entry: u64,
+    depth: usize,
+    entries: &mut Vec<u64>,
+) {
+    if entry == 0 || depth > MAX_MAPLE_DEPTH || entries.len() >= MAX_VMAS {
+        return;
+    }
+
+    // A non-node root is a single entry stored directly in ma_root
+    let is_node = entry & 3 == 2 && entry > 4096;
+    if !is_node {
+        if entry & 3 == 0 {
+            entries.push(entry);
+        }
+        return;
+    }
+
+    let node = entry & !MAPLE_NODE_MASK;
+    let node_type = (entry >> MAPLE_NODE_TYPE_SHIFT) & MAPLE_NODE_TYPE_MASK;
+    let (slots_offset, slot_count) = match node_type {
+        MAPLE_DENSE => (8, MAPLE_NODE_SLOTS),
+        MAPLE_LEAF_64 | MAPLE_RANGE_64 => (
+            symbol_resolver
+                .get_struct_field_offset("maple_range_64", "slot", None)
+                .unwrap_or(128),
+            MAPLE_RANGE64_SLOTS,
+        ),
+        MAPLE_ARANGE_64 => (
+            symbol_resolver
+                .get_struct_field_offset("maple_arange_64", "slot", None)
+                .unwrap_or(80),
+            MAPLE_ARANGE64_SLOTS,
+        ),
+        _ => return,
+    };
+    let is_leaf = node_type < MAPLE_RANGE_64;
+
+    for i in 0..slot_count {
+        let slot = match KernelParser::read_virt_pointer(
+            mapped,
+            translator,
+            node + slots_offset + i * translator.pointer_size(),
+        ) {
+            Some(s) if s != 0 => s,
+            _ => continue,
+        };
+        if is_leaf {
+            // Leaf slots hold the stored pointers; skip internal/value entries
+            if slot & 3 == 0 {
+                entries.push(slot);
+            }
+        } else {
+            collect_maple_entries(
+                mapped,
+                translator,
+                symbol_resolver,
+                slot,
+                depth + 1,
+                entries,
+            );
+        }
+    }
+}
@@ -1,5 +1,6 @@
 //! Kernel data structure parsing module
 use serde::Serialize;
+use target::Target;
 
 /// Structure to hold process information.
 #[derive(Debug, Serialize, Clone)]
@@ -25,7 +26,10 @@ pub struct ConnectionInfo {
     pub remote_addr: String,
     pub remote_port: u16,
     pub state: String,
-    pub pid: i32,
+    pub pid: i32, // Owning process (0 if no process holds the socket)
+    pub fd: Option<u32>,
+    pub comm: String,
+    pub inode: u64, // sockfs inode number
 }
 
 /// Structure to hold kernel module information.
@@ -38,38 +42,397 @@ pub struct ModuleInfo {
     pub init_address: u64,
 }
 
+/// Structure to hold a file recovered from a tmpfs/ramfs superblock or memfd.
+#[derive(Debug, Serialize, Clone)]
+pub struct TmpfsFileInfo {
+    pub offset: u64, // File offset where the inode is found
+    pub fs_type: String,
+    pub mount: String, // Mount point of the owning superblock
+    pub path: String,
+    pub origin: String, // "tree", "memfd", "unlinked" or "anonymous"
+    pub inode: u64,
+    pub file_type: String,
+    pub mode: String,
+    pub uid: u32,
+    pub gid: u32,
+    pub size: u64,
+    pub cached_pages: u64, // Pages present in the page cache
+    pub atime: i64,
+    pub mtime: i64,
+    pub ctime: i64,
+    pub exported: Option<String>, // Path the contents were written to
+}
+
+/// Structure to hold a command recovered from a shell's history list.
+#[derive(Debug, Serialize, Clone)]
+pub struct BashHistoryInfo {
+    pub offset: u64, // File offset where the HIST_ENTRY is found
+    pub pid: i32,
+    pub uid: u32,
+    pub comm: String,
+    pub timestamp: i64, // Seconds since the epoch (from the "#<epoch>" string)
+    pub command: String,
+}
+
+/// Structure to hold network interface information.
+#[derive(Debug, Serialize, Clone)]
+pub struct InterfaceInfo {
+    pub offset: u64,        // File offset where the net_device is found
+    pub net_namespace: u32, // Inode number of the owning network namespace
+    pub name: String,
+    pub mac: String,
+    pub mtu: u32,
+    pub flags: String,
+    pub promiscuous: bool,
+    pub ipv4: Vec<String>, // Addresses in CIDR notation
+    pub ipv6: Vec<String>,
+}
+
+/// Structure to hold an ARP/NDP neighbour cache entry.
+#[derive(Debug, Serialize, Clone)]
+pub struct NeighbourInfo {
+    pub offset: u64, // File offset where the neighbour is found
+    pub net_namespace: u32,
+    pub family: String, // "ipv4" (arp_tbl) or "ipv6" (nd_tbl)
+    pub address: String,
+    pub mac: String,
+    pub interface: String,
+    pub state: String, // NUD state (REACHABLE, STALE, ...)
+}
+
+/// Structure to hold a routing table entry.
+#[derive(Debug, Serialize, Clone)]
+pub struct RouteInfo {
+    pub offset: u64, // File offset where the fib_alias/fib6_info is found
+    pub net_namespace: u32,
+    pub family: String,
+    pub table: String,       // "main", "local", "default" or the numeric table id
+    pub destination: String, // Prefix in CIDR notation
+    pub gateway: String,
+    pub interface: String,
+    pub metric: u32,
+    pub route_type: String,
+}
+
+/// A process file descriptor referring to a socket.
+#[derive(Debug, Serialize, Clone)]
+pub struct SocketOwner {
+    pub pid: i32,
+    pub fd: u32,
+    pub comm: String,
+}
+
+impl std::fmt::Display for SocketOwner {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}/{}:{}", self.comm, self.pid, self.fd)
+    }
+}
+
+/// Structure to hold Unix domain socket information.
+#[derive(Debug, Serialize, Clone)]
+pub struct UnixSocketInfo {
+    pub offset: u64, // File offset where the unix_sock is found
+    pub net_namespace: u32,
+    pub inode: u64,
+    pub path: String, // Abstract names are prefixed with '@'
+    pub socket_type: String,
+    pub state: String,
+    pub owners: Vec<SocketOwner>,
+    pub peer_inode: u64,
+    pub peer_pids: Vec<i32>,
+}
+
+/// Structure to hold a packet, raw or netlink socket.
+#[derive(Debug, Serialize, Clone)]
+pub struct RawSocketInfo {
+    pub offset: u64, // File offset where the sock is found
+    pub net_namespace: u32,
+    pub family: String, // "packet", "raw", "raw6" or "netlink"
+    pub protocol: String,
+    pub socket_type: String,
+    pub interface: String,   // Bound device ("*" when unbound)
+    pub portid: Option<u32>, // Netlink port id
+    pub inode: u64,
+    pub owners: Vec<SocketOwner>,
+    pub filter_insns: u32, // Instructions in the attached socket filter
+    pub filter: String,    // Classic BPF program bytes (hex)
+}
+
+/// Structure to hold information about a loaded eBPF program
+#[derive(Debug, Serialize, Clone)]
+pub struct BpfProgInfo {
+    pub offset: u64, // File offset where the bpf_prog is found
+    pub id: u32,
+    pub prog_type: String,
+    pub name: String,
+    pub tag: String,         // Program tag (hex), as shown by bpftool
+    pub load_time: u64,      // Nanoseconds since boot
+    pub uid: Option<u32>,    // Loading user (kernels before 5.11)
+    pub attach: Vec<String>, // Attach function and bpf_link targets
+    pub insns: u32,
+    pub jit_addr: u64, // Address of the JIT image (0 if interpreted)
+    pub jit_size: u32,
+    pub map_ids: Vec<u32>,
+    pub exported: Vec<String>, // Paths the bytecode/JIT image were written to
+}
+
+/// Structure to hold information about an eBPF map
+#[derive(Debug, Serialize, Clone)]
+pub struct BpfMapInfo {
+    pub offset: u64, // File offset where the bpf_map is found
+    pub id: u32,
+    pub map_type: String,
+    pub name: String,
+    pub key_size: u32,
+    pub value_size: u32,
+    pub max_entries: u32,
+    pub flags: u32,
+}
+
+/// Structure to hold a modification found in kernel function text
+#[derive(Debug, Serialize, Clone)]
+pub struct InlineHookInfo {
+    pub offset: u64, // File offset of the modified bytes
+    pub function: String,
+    pub address: u64,     // Runtime address of the function
+    pub hook_offset: u64, // Offset of the modification within the function
+    pub kind: String,     // "jmp", "call", "movabs jmp", "push ret", "int3", "modified", ...
+    pub target: String,   // Trampoline destination (symbol+offset when in kernel text)
+    pub original: String, // Bytes from vmlinux (empty without a vmlinux image)
+    pub current: String,  // Bytes found in memory
+}
+
+/// Structure to hold a callback registered with ftrace, kprobes or a tracepoint
+#[derive(Debug, Serialize, Clone)]
+pub struct TraceHookInfo {
+    pub offset: u64,       // File offset of the ftrace_ops / kprobe / tracepoint
+    pub hook_type: String, // "ftrace", "kprobe", "kretprobe" or "tracepoint"
+    pub target: String,    // Probed function, filtered functions or tracepoint name
+    pub handler: String,   // Which callback of the hook ("pre", "post", "return", ...)
+    pub callback: u64,
+    pub symbol: String, // Callback symbol, or its address when unresolved
+    pub owner: String,  // "kernel", the owning module, or "unknown"
+    pub details: String,
+}
+
+/// Structure to hold a registered netfilter hook
+#[derive(Debug, Serialize, Clone)]
+pub struct NetfilterHookInfo {
+    pub offset: u64, // File offset of the nf_hook_entries / nf_hook_ops
+    pub net_namespace: u32,
+    pub family: String, // "ipv4", "ipv6", "arp", "bridge", ...
+    pub hook: String,   // Hook point, e.g. "PRE_ROUTING"
+    pub priority: i32,
+    pub callback: u64,
+    pub symbol: String, // Callback symbol, or its address when unresolved
+    pub owner: String,  // "kernel", the owning module, or "unknown"
+}
+
+/// Structure to hold a callback registered on a notifier chain
+#[derive(Debug, Serialize, Clone)]
+pub struct NotifierInfo {
+    pub offset: u64,   // File offset of the notifier_block
+    pub chain: String, // Chain head symbol, e.g. "keyboard_notifier_list"
+    pub priority: i32,
+    pub callback: u64,
+    pub symbol: String, // Callback symbol, or its address when unresolved
+    pub owner: String,  // "kernel", the owning module, or "unknown"
+}
+
+/// Structure to hold a tty handler pointer and where it resolves
+#[derive(Debug, Serialize, Clone)]
+pub struct TtyHookInfo {
+    pub offset: u64, // File offset of the tty_struct
+    pub tty: String,
+    pub driver: String,
+    pub ldisc: String,   // Line discipline name, e.g. "n_tty"
+    pub handler: String, // "receive_buf", "receive_buf2" or "write"
+    pub callback: u64,
+    pub symbol: String, // Handler symbol, or its address when unresolved
+    pub owner: String,  // "kernel", the owning module, or "unknown"
+}
+
+/// Function pointer slot in a checked ops structure or dispatch table
+#[derive(Debug, Serialize, Clone)]
+pub struct OpsPointerInfo {
+    pub offset: u64,    // File offset of the pointer slot
+    pub table: String,  // e.g. "sys_call_table", "idt_table", "tcp4_seq_ops"
+    pub member: String, // Table index or structure field name
+    pub pointer: u64,
+    pub symbol: String, // Target symbol, or its address when unresolved
+    pub owner: String,  // "kernel", the owning module, or "unknown"
+}
+
+/// Namespace membership of one process
+#[derive(Debug, Serialize, Clone)]
+pub struct ProcessNamespaceInfo {
+    pub offset: u64, // File offset of the task_struct
+    pub pid: i32,
+    pub ns_pid: i32, // PID inside the process's own PID namespace
+    pub comm: String,
+    // Namespace inode numbers, as shown in /proc/PID/ns/*
+    pub user_ns: u32,
+    pub mnt_ns: u32,
+    pub uts_ns: u32,
+    pub ipc_ns: u32,
+    pub pid_ns: u32,
+    pub net_ns: u32,
+    pub cgroup_ns: u32,
+    pub time_ns: u32,
+    pub hostname: String, // UTS namespace nodename
+    pub cgroup: String,
+}
+
+/// Processes sharing one set of namespaces
+#[derive(Debug, Serialize, Clone)]
+pub struct ContainerInfo {
+    pub container: String, // Container ID, or "host" for the initial namespaces
+    pub runtime: String,   // Runtime named by the cgroup path, "host", or empty
+    pub pod: String,       // Kubernetes pod UID, when the cgroup names one
+    pub hostname: String,
+    pub cgroup: String,
+    pub pid_ns: u32,
+    pub mnt_ns: u32,
+    pub net_ns: u32,
+    pub user_ns: u32,
+    pub processes: usize,
+    pub pids: Vec<i32>, // Host PIDs of the member processes
+}
+
+/// Shared object seen by the kernel (file mapping) or the dynamic loader (link_map)
+#[derive(Debug, Serialize, Clone)]
+pub struct LibraryInfo {
+    pub offset: u64, // File offset of the task_struct
+    pub pid: i32,
+    pub comm: String,
+    pub base: u64, // Lowest mapping address, or l_addr for loader-only entries
+    pub path: String,
+    pub mapped: bool,    // Has an executable file mapping (kernel view)
+    pub linked: bool,    // Present on the loader's link_map chain
+    pub deleted: bool,   // Backing file unlinked from disk
+    pub memfd: bool,     // Backed by a memfd_create file
+    pub preloaded: bool, // Named by the process's LD_PRELOAD
+}
+
+/// ELF image mapped into a process
+#[derive(Debug, Serialize, Clone)]
+pub struct ElfInfo {
+    pub offset: u64, // File offset of the task_struct
+    pub pid: i32,
+    pub comm: String,
+    pub start: u64, // Address of the ELF header
+    pub end: u64,   // End of the last mapping of the same file
+    pub path: String,
+    pub elf_type: String,
+    pub build_id: String, // GNU build ID from PT_NOTE (empty if not resident)
+    pub deleted: bool,    // Backing file unlinked from disk
+    pub exported: Option<String>, // Path the reconstructed image was written to
+}
+
+/// Signature match from a memory scan
+#[derive(Debug, Serialize, Clone)]
+pub struct ScanMatchInfo {
+    pub rule: String,
+    pub string: String, // Rule string identifier ($name)
+    pub space: String,  // physical, kernel or process
+    pub address: u64,   // Physical address for physical scans, virtual otherwise
+    pub pid: Option<i32>,
+    pub comm: String,
+    pub context: String, // LiME region, kernel symbol, or VMA range and path
+    pub data: String,    // Hex of the first matched bytes
+}
+
+/// Process mapping of a string's physical address
+#[derive(Debug, Serialize, Clone)]
+pub struct StringOwner {
+    pub pid: i32,
+    pub comm: String,
+    pub address: u64, // User virtual address of the string
+    pub vma_start: u64,
+    pub vma_end: u64,
+    pub path: String,
+}
+
+/// String from the dump attributed to its physical and virtual addresses
+#[derive(Debug, Serialize, Clone)]
+pub struct StringInfo {
+    pub offset: u64,           // File offset in the dump
+    pub physical: Option<u64>, // None when the offset is outside every LiME region
+    pub string: String,
+    pub kernel: Vec<u64>, // Kernel virtual aliases (direct map, kernel image)
+    pub owners: Vec<StringOwner>,
+}
+
+/// Decoded struct page for a physical page frame
+#[derive(Debug, Serialize, Clone)]
+pub struct PageInfo {
+    pub physical: u64, // Page-aligned physical address
+    pub pfn: u64,
+    pub page: u64, // Virtual address of the struct page in vmemmap
+    pub flags: u64,
+    pub flag_names: Vec<String>,
+    pub head: Option<u64>, // Head PFN for compound tail pages
+    pub mapping: u64,
+    pub mapping_type: String, // none, file, anon, movable or ksm
+    pub inode: Option<u64>,   // Inode number for page cache pages
+    pub path: String,         // File path for page cache pages
+    pub index: u64,
+    pub refcount: i32,
+    pub mapcount: i32,
+    pub mapped_by: Vec<String>, // pid/comm@address of each process mapping
+}
+
+/// Process memory mapping with where its pages are
+#[derive(Debug, Serialize, Clone)]
+pub struct MapsInfo {
+    pub pid: i32,
+    pub comm: String,
+    pub start: u64,
+    pub end: u64,
+    pub perms: String, // rwxp / rwxs
+    pub pgoff: u64,
+    pub path: String,
+    pub resident: u64,      // Pages captured in the dump
+    pub swapped: u64,       // Pages swapped out
+    pub swap_readable: u64, // Swapped pages readable from compressed swap or a swap image
+    pub unavailable: u64,   // Pages never faulted in, or whose page or page table was not captured
+}
+
 /// Helper functions for reading data from memory
+///
+/// Integers are decoded in the byte order of the dumped kernel, and pointers are
+/// read at its pointer size (see `Target`).
 pub struct KernelParser;
 
 impl KernelParser {
     /// Read a u64 (8 bytes) from the mapped memory at a given file offset.
-    pub fn read_u64(mapped: &[u8], offset: usize) -> Option<u64> {
+    pub fn read_u64(mapped: &[u8], offset: usize, target: Target) -> Option<u64> {
         if offset + 8 <= mapped.len() {
             let mut buf = [0u8; 8];
             buf.copy_from_slice(&mapped[offset..offset + 8]);
-            Some(u64::from_ne_bytes(buf))
+            Some(target.u64(buf))
         } else {
             None
         }
     }
 
     /// Read an i32 (4 bytes) from the mapped memory at a given file offset.
-    pub fn read_i32(mapped: &[u8], offset: usize) -> Option<i32> {
+    pub fn read_i32(mapped: &[u8], offset: usize, target: Target) -> Option<i32> {
         if offset + 4 <= mapped.len() {
             let mut buf = [0u8; 4];
             buf.copy_from_slice(&mapped[offset..offset + 4]);
-            Some(i32::from_ne_bytes(buf))
+            Some(target.u32(buf) as i32)
         } else {
             None
         }
     }
 
     /// Read a u32 (4 bytes) from the mapped memory at a given file offset.
-    pub fn read_u32(mapped: &[u8], offset: usize) -> Option<u32> {
+    pub fn read_u32(mapped: &[u8], offset: usize, target: Target) -> Option<u32> {
         if offset + 4 <= mapped.len() {
             let mut buf = [0u8; 4];
             buf.copy_from_slice(&mapped[offset..offset + 4]);
-            Some(u32::from_ne_bytes(buf))
+            Some(target.u32(buf))
         } else {
             None
         }
@@ -77,16 +440,25 @@ pub fn read_u32(mapped: &[u8], offset: usize) -> Option<u32> {
 
     /// Read a u16 (2 bytes) from the mapped memory at a given file offset.
     #[allow(dead_code)]
-    pub fn read_u16(mapped: &[u8], offset: usize) -> Option<u16> {
+    pub fn read_u16(mapped: &[u8], offset: usize, target: Target) -> Option<u16> {
         if offset + 2 <= mapped.len() {
             let mut buf = [0u8; 2];
             buf.copy_from_slice(&mapped[offset..offset + 2]);
-            Some(u16::from_ne_bytes(buf))
+            Some(target.u16(buf))
         } else {
             None
         }
     }
 
+    /// Read a pointer (4 or 8 bytes, per the target) from the mapped memory at a given file offset.
+    pub fn read_pointer(mapped: &[u8], offset: usize, target: Target) -> Option<u64> {
+        if target.pointer_size == 4 {
+            Self::read_u32(mapped, offset, target).map(u64::from)
+        } else {
+            Self::read_u64(mapped, offset, target)
+        }
+    }
+
     /// Read a string of fixed length from the mapped memory at a given file offset.
     pub fn read_string(mapped: &[u8], offset: usize, length: usize) -> Option<String> {
         if offset + length <= mapped.len() {
@@ -126,8 +498,9 @@ pub fn dereference_pointer(
             .virtual_to_file_offset(virtual_addr)
             .ok_or_else(|| crate::error::AnalysisError::AddressTranslationFailed(virtual_addr))?;
 
-        // Read the 8-byte pointer value at that file offset
-        let pointer_value = Self::read_u64(mapped, file_offset as usize).ok_or_else(|| {
+        // Read the pointer value at that file offset
+        let pointer_value = Self::read_pointer(mapped, file_offset as usize, translator.target())
+            .ok_or_else(|| {
             crate::error::AnalysisError::InvalidStructure(format!(
                 "Cannot read pointer at offset 0x{:x}",
                 file_offset
@@ -136,11 +509,273 @@ pub fn dereference_pointer(
 
         Ok(pointer_value)
     }
+
+    /// Read a u64 from a kernel virtual address
+    pub fn read_virt_u64(
+        mapped: &[u8],
+        translator: &crate::translation::MemoryTranslator,
+        virtual_addr: u64,
+    ) -> Option<u64> {
+        let file_offset = translator.virtual_to_file_offset(virtual_addr)?;
+        Self::read_u64(mapped, file_offset as usize, translator.target())
+    }
+
+    /// Read a pointer from a kernel virtual address
+    pub fn read_virt_pointer(
+        mapped: &[u8],
+        translator: &crate::translation::MemoryTranslator,
+        virtual_addr: u64,
+    ) -> Option<u64> {
+        let file_offset = translator.virtual_to_file_offset(virtual_addr)?;
+        Self::read_pointer(mapped, file_offset as usize, translator.target())
+    }
+
+    /// Read a u32 from a kernel virtual address
+    pub fn read_virt_u32(
+        mapped: &[u8],
+        translator: &crate::translation::MemoryTranslator,
+        virtual_addr: u64,
+    ) -> Option<u32> {
+        let file_offset = translator.virtual_to_file_offset(virtual_addr)?;
+        Self::read_u32(mapped, file_offset as usize, translator.target())
+    }
+
+    /// Read an i32 from a kernel virtual address
+    #[allow(dead_code)]
+    pub fn read_virt_i32(
+        mapped: &[u8],
+        translator: &crate::translation::MemoryTranslator,
+        virtual_addr: u64,
+    ) -> Option<i32> {
+        let file_offset = translator.virtual_to_file_offset(virtual_addr)?;
+        Self::read_i32(mapped, file_offset as usize, translator.target())
+    }
+
+    /// Read a u16 from a kernel virtual address
+    pub fn read_virt_u16(
+        mapped: &[u8],
+        translator: &crate::translation::MemoryTranslator,
+        virtual_addr: u64,
+    ) -> Option<u16> {
+        let file_offset = translator.virtual_to_file_offset(virtual_addr)?;
+        Self::read_u16(mapped, file_offset as usize, translator.target())
+    }
+
+    /// Read a byte buffer from a kernel virtual address
+    ///
+    /// Each page is translated separately so buffers spanning a page boundary
+    /// are read correctly even when the pages are not physically contiguous.
+    pub fn read_virt_bytes(
+        mapped: &[u8],
+        translator: &crate::translation::MemoryTranslator,
+        virtual_addr: u64,
+        length: usize,
+    ) -> Option<Vec<u8>> {
+        let mut buffer = Vec::with_capacity(length);
+        let mut current = virtual_addr;
+
+        while buffer.len() < length {
+            let page_remaining = (0x1000 - (current & 0xfff)) as usize;
+            let chunk = page_remaining.min(length - buffer.len());
+            let file_offset = translator.virtual_to_file_offset(current)? as usize;
+            if file_offset + chunk > mapped.len() {
+                return None;
+            }
+            buffer.extend_from_slice(&mapped[file_offset..file_offset + chunk]);
+            current = current.wrapping_add(chunk as u64);
+        }
+
+        Some(buffer)
+    }
+
+    /// Read a null-terminated string (up to max_length bytes) from a kernel virtual address
+    pub fn read_virt_cstring(
+        mapped: &[u8],
+        translator: &crate::translation::MemoryTranslator,
+        virtual_addr: u64,
+        max_length: usize,
+    ) -> Option<String> {
+        if virtual_addr == 0 {
+            return None;
+        }
+
+        let mut bytes = Vec::new();
+        let mut current = virtual_addr;
+
+        // Read page by page until we find the terminator
+        while bytes.len() < max_length {
+            let page_remaining = (0x1000 - (current & 0xfff)) as usize;
+            let chunk = page_remaining.min(max_length - bytes.len());
+            let data = Self::read_virt_bytes(mapped, translator, current, chunk)?;
+            if let Some(nul_pos) = data.iter().position(|&c| c == 0) {
+                bytes.extend_from_slice(&data[..nul_pos]);
+                break;
+            }
+            bytes.extend_from_slice(&data);
+            current = current.wrapping_add(chunk as u64);
+        }
+
+        Some(String::from_utf8_lossy(&bytes).to_string())
+    }
+
+    /// Walk a circular list_head and return the virtual address of every entry
+    ///
+    /// The returned addresses point at the embedded list_head; callers subtract the
+    /// member offset to recover the containing structure (container_of).
+    pub fn walk_list(
+        mapped: &[u8],
+        translator: &crate::translation::MemoryTranslator,
+        head_addr: u64,
+        max_entries: usize,
+    ) -> Vec<u64> {
+        let mut entries = Vec::new();
+        let mut visited = std::collections::HashSet::new();
+        let mut current = match Self::read_virt_pointer(mapped, translator, head_addr) {
+            Some(next) => next,
+            None => return entries,
+        };
+
+        while current != 0 && current != head_addr && entries.len() < max_entries {
+            if !visited.insert(current) {
+                break;
+            }
+            entries.push(current);
+            current = match Self::read_virt_pointer(mapped, translator, current) {
+                Some(next) => next,
+                None => break,
+            };
+        }
+
+        entries
+    }
+
+    /// Walk an hlist_nulls chain and return every node address
+    ///
+    /// The chain ends at a "nulls" marker (odd pointer value) rather than NULL.
+    pub fn walk_hlist_nulls(
+        mapped: &[u8],
+        translator: &crate::translation::MemoryTranslator,
+        first_node: u64,
+        max_entries: usize,
+    ) -> Vec<u64> {
+        let mut entries = Vec::new();
+        let mut visited = std::collections::HashSet::new();
+        let mut current = first_node;
+
+        while current != 0 && current & 1 == 0 && entries.len() < max_entries {
+            if !visited.insert(current) {
+                break;
+            }
+            entries.push(current);
+            current = match Self::read_virt_pointer(mapped, translator, current) {
+                Some(next) => next,
+                None => break,
+            };
+        }
+
+        entries
+    }
+
+    /// Walk an hlist starting at the first node and return every node address
+    pub fn walk_hlist(
+        mapped: &[u8],
+        translator: &crate::translation::MemoryTranslator,
+        first_node: u64,
+        max_entries: usize,
+    ) -> Vec<u64> {
+        let mut entries = Vec::new();
+        let mut visited = std::collections::HashSet::new();
+        let mut current = first_node;
+
+        while current != 0 && entries.len() < max_entries {
+            if !visited.insert(current) {
+                break;
+            }
+            entries.push(current);
+            current = match Self::read_virt_pointer(mapped, translator, current) {
+                Some(next) => next,
+                None => break,
+            };
+        }
+
+        entries
+    }
 }
 
+// Include the target data model (byte order / pointer size)
+pub mod target;
+
 // Include the process extractor module
 pub mod process_extractor;
 
+// Include the process address space helpers
+pub mod mm;
+
+// Include the dynamic loader (auxv / link_map) helpers
+pub mod loader;
+
+// Include the XArray / radix tree walker
+pub mod xarray;
+
+// Include the VFS (superblock/dentry/inode) helpers
+pub mod vfs;
+
+// Include the open file (fd table) helpers
+pub mod files;
+
+// Include the networking (namespace/device/address) helpers
+pub mod net;
+
+// Include the eBPF program/map helpers
+pub mod bpf;
+
+// Include the kernel text (vmlinux / patch site / trampoline) helpers
+pub mod text;
+
+// Include the loaded module list helpers
+pub mod modules;
+
+// Include the code pointer classifier (kernel text / module / unknown)
+pub mod pointers;
+
+// Include the namespace, PID namespace and cgroup helpers
+pub mod namespaces;
+
+// Include the user-space ELF image helpers
+pub mod elf;
+
+// Include the physical-to-virtual reverse mapping index
+pub mod rmap;
+
+// Include the struct page / vmemmap decoding helpers
+pub mod page;
+
+// Include the swap area (swap_info) helpers
+pub mod swap;
+
+// Include the zsmalloc object lookup helpers
+pub mod zsmalloc;
+
+/// Join a list of values with ", " for table and CSV output
+pub fn join_display<T: std::fmt::Display>(values: &[T]) -> String {
+    values
+        .iter()
+        .map(|v| v.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Format a Unix epoch timestamp (seconds) as an RFC 3339 UTC string
+pub fn format_unix_time(secs: i64) -> String {
+    if secs == 0 {
+        return "-".to_string();
+    }
+
+    chrono::DateTime::from_timestamp(secs, 0)
+        .map(|dt| dt.to_rfc3339())
+        .unwrap_or_else(|| secs.to_string())
+}
+
 /// Format start_time from nanoseconds since boot to human-readable elapsed time
 pub fn format_start_time(start_time_ns: u64) -> String {
     if start_time_ns == 0 {
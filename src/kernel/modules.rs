@@ -0,0 +1,229 @@
+//! Loaded module helpers: the `modules` list, module memory ranges and module symbols
+use crate::error::AnalysisError;
+use crate::kernel::KernelParser;
+use crate::symbols::SymbolResolver;
+use crate::translation::MemoryTranslator;
+
+// Safety limits
+const MAX_MODULES: usize = 4096;
+const MAX_MODULE_SYMBOLS: u32 = 200000;
+// MODULE_NAME_LEN (64 - sizeof(unsigned long))
+const MODULE_NAME_LEN: usize = 56;
+// MOD_MEM_NUM_TYPES (6.4+): text, data, rodata, ro_after_init, init text/data/rodata
+const MOD_MEM_NUM_TYPES: u64 = 7;
+// sizeof(Elf32_Sym) / sizeof(Elf64_Sym) and the offset of st_value in each
+const ELF32_SYM_SIZE: u64 = 16;
+const ELF64_SYM_SIZE: u64 = 24;
+const ELF32_ST_VALUE: usize = 4;
+const ELF64_ST_VALUE: usize = 8;
+
+/// A loaded module with its memory ranges and symbols
+#[derive(Debug, Clone)]
+pub struct LoadedModule {
+    pub name: String,
+    pub ranges: Vec<(u64, u64)>, // [start, end) of each allocated region
+    pub symbols: Vec<(u64, String)>, // Sorted by address
+}
+
+impl LoadedModule {
+    /// Check whether an address falls inside one of the module's regions
+    pub fn contains(&self, address: u64) -> bool {
+        self.ranges
+            .iter()
+            .any(|&(start, end)| address >= start && address < end)
+    }
+
+    /// Find the module symbol at or below an address
+    pub fn symbol_for(&self, address: u64) -> Option<(&str, u64)> {
+        let index = self.symbols.partition_point(|(a, _)| *a <= address);
+        let (start, name) = &self.symbols[index.checked_sub(1)?];
+        Some((name.as_str(), address - start))
+    }
+}
+
+/// Walker over the `modules` list
+pub struct ModuleWalker<'a> {
+    mapped: &'a [u8],
+    translator: &'a MemoryTranslator,
+    symbol_resolver: &'a SymbolResolver,
+    list: usize,
+    name: usize,
+}
+
+impl<'a> ModuleWalker<'a> {
+    /// Resolve the struct module offsets shared by every kernel version
+    pub fn new(
+        mapped: &'a [u8],
+        translator: &'a MemoryTranslator,
+        symbol_resolver: &'a SymbolResolver,
+    ) -> Result<Self, AnalysisError> {
+        Ok(ModuleWalker {
+            mapped,
+            translator,
+            symbol_resolver,
+            list: symbol_resolver.require_field_offset("module", "list")?,
+            name: symbol_resolver.require_field_offset("module", "name")?,
+        })
+    }
+
+    fn read_ptr(&self, addr: u64) -> u64 {
+        KernelParser::read_virt_pointer(self.mapped, self.translator, addr).unwrap_or(0)
+    }
+
+    fn read_u32(&self, addr: u64) -> u32 {
+        KernelParser::read_virt_u32(self.mapped, self.translator, addr).unwrap_or(0)
+    }
+
+    fn offset(&self, s: &str, f: &str) -> Option<u64> {
+        self.symbol_resolver.get_struct_field_offset(s, f, None)
+    }
+
+    /// Enumerate modules on the `modules` list
+    pub fn modules(&self) -> Result<Vec<LoadedModule>, AnalysisError> {
+        let head = self
+            .symbol_resolver
+            .get_symbol_address("modules")
+            .ok_or_else(|| AnalysisError::SymbolNotFound("modules".to_string()))?;
+
+        let mut modules = Vec::new();
+        for node in KernelParser::walk_list(self.mapped, self.translator, head, MAX_MODULES) {
+            let module = node - self.list as u64;
+            let name = KernelParser::read_virt_cstring(
+                self.mapped,
+                self.translator,
+                module + self.name as u64,
+                MODULE_NAME_LEN,
+            )
+            .unwrap_or_default();
+            let ranges = self.memory_ranges(module);
+            let symbols = self.module_symbols(module, &ranges);
+            modules.push(LoadedModule {
+                name,
+                ranges,
+                symbols,
+            });
+        }
+
+        Ok(modules)
+    }
+
+    /// Collect a module's allocated regions across the three layout generations
+    fn memory_ranges(&self, module: u64) -> Vec<(u64, u64)> {
+        let mut ranges = Vec::new();
+        let mut push = |base: u64, size: u64| {
+            if base != 0 && size != 0 {
+                ranges.push((base, base + size));
+            }
+        };
+
+        if let (Some(mem), Some(base), Some(size), Some(stride)) = (
+            self.offset("module", "mem"),
+            self.offset("module_memory", "base"),
+            self.offset("module_memory", "size"),
+            self.symbol_resolver.get_struct_size("module_memory"),
+        ) {
+            // 6.4+: struct module_memory mem[MOD_MEM_NUM_TYPES]
+            for i in 0..MOD_MEM_NUM_TYPES {
+                let entry = module + mem + i * stride as u64;
+                push(
+                    self.read_ptr(entry + base),
+                    self.read_u32(entry + size) as u64,
+                );
+            }
+        } else if let (Some(base), Some(size)) = (
+            self.offset("module_layout", "base"),
+            self.offset("module_layout", "size"),
+        ) {
+            // 4.5 - 6.3: core_layout / init_layout
+            for layout in ["core_layout", "init_layout"] {
+                if let Some(layout) = self.offset("module", layout) {
+                    push(
+                        self.read_ptr(module + layout + base),
+                        self.read_u32(module + layout + size) as u64,
+                    );
+                }
+            }
+        } else {
+            // Before 4.5: module_core / module_init with separate sizes
+            for (base, size) in [("module_core", "core_size"), ("module_init", "init_size")] {
+                if let (Some(base), Some(size)) =
+                    (self.offset("module", base), self.offset("module", size))
+                {
+                    push(
+                        self.read_ptr(module + base),
+                        self.read_u32(module + size) as u64,
+                    );
+                }
+            }
+        }
+
+        ranges
+    }
+
+    /// Read a module's symbol table through module->kallsyms
+    fn module_symbols(&self, module: u64, ranges: &[(u64, u64)]) -> Vec<(u64, String)> {
+        let mut symbols = Vec::new();
+        let (kallsyms, symtab, num_symtab, strtab) = match (
+            self.offset("module", "kallsyms"),
+            self.offset("mod_kallsyms", "symtab"),
+            self.offset("mod_kallsyms", "num_symtab"),
+            self.offset("mod_kallsyms", "strtab"),
+        ) {
+            (Some(k), Some(s), Some(n), Some(t)) => (k, s, n, t),
+            _ => return symbols,
+        };
+
+        let kallsyms = self.read_ptr(module + kallsyms);
+        if kallsyms == 0 {
+            return symbols;
+        }
+        let table = self.read_ptr(kallsyms + symtab);
+        let count = self.read_u32(kallsyms + num_symtab).min(MAX_MODULE_SYMBOLS);
+        let strings = self.read_ptr(kallsyms + strtab);
+        if table == 0 || strings == 0 {
+            return symbols;
+        }
+
+        let target = self.translator.target();
+        let (sym_size, value_offset) = if target.pointer_size == 4 {
+            (ELF32_SYM_SIZE, ELF32_ST_VALUE)
+        } else {
+            (ELF64_SYM_SIZE, ELF64_ST_VALUE)
+        };
+        let bytes = match KernelParser::read_virt_bytes(
+            self.mapped,
+            self.translator,
+            table,
+            (count as u64 * sym_size) as usize,
+        ) {
+            Some(b) => b,
+            None => return symbols,
+        };
+        for sym in bytes.chunks_exact(sym_size as usize) {
+            let st_name = target.u32(sym[0..4].try_into().unwrap());
+            let st_value = match target.pointer(&sym[value_offset..]) {
+                Some(v) => v,
+                None => continue,
+            };
+            let inside = ranges
+                .iter()
+                .any(|&(start, end)| st_value >= start && st_value < end);
+            if st_name == 0 || !inside {
+                continue;
+            }
+            if let Some(name) = KernelParser::read_virt_cstring(
+                self.mapped,
+                self.translator,
+                strings + st_name as u64,
+                256,
+            ) {
+                if !name.is_empty() {
+                    symbols.push((st_value, name));
+                }
+            }
+        }
+
+        symbols.sort();
+        symbols
+    }
+}
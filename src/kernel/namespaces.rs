@@ -0,0 +1,293 @@
+//! Namespace helpers: per-task nsproxy members, namespace-local PIDs, UTS hostnames and cgroups
+use crate::error::AnalysisError;
+use crate::kernel::KernelParser;
+use crate::symbols::SymbolResolver;
+use crate::translation::MemoryTranslator;
+
+// __NEW_UTS_LEN + 1
+const UTS_NAME_LEN: usize = 65;
+// Safety limits
+const MAX_PID_LEVEL: u32 = 32; // MAX_PID_NS_LEVEL
+const MAX_CGROUP_DEPTH: usize = 64;
+const MAX_CGROUP_SUBSYS: u64 = 16;
+// sizeof(struct upid): nr and the owning pid_namespace pointer
+const UPID_SIZE: u64 = 16;
+
+/// Namespaces and container context of one task
+#[derive(Debug, Clone, Default)]
+pub struct TaskNamespaces {
+    pub ns_pid: i32, // PID as seen inside the task's own PID namespace
+    // Namespace inode numbers, as shown in /proc/PID/ns/* (0 when unavailable)
+    pub user: u32,
+    pub mnt: u32,
+    pub uts: u32,
+    pub ipc: u32,
+    pub pid: u32,
+    pub net: u32,
+    pub cgroup: u32,
+    pub time: u32,
+    pub hostname: String,    // nodename of the UTS namespace
+    pub cgroup_path: String, // Path in the cgroup v2 hierarchy, or the deepest v1 path
+}
+
+/// Walker over task namespaces and cgroup membership
+pub struct NamespaceWalker<'a> {
+    mapped: &'a [u8],
+    translator: &'a MemoryTranslator,
+    symbol_resolver: &'a SymbolResolver,
+    task_nsproxy: usize,
+    task_cred: usize,
+}
+
+impl<'a> NamespaceWalker<'a> {
+    /// Resolve the task_struct offsets every lookup starts from
+    pub fn new(
+        mapped: &'a [u8],
+        translator: &'a MemoryTranslator,
+        symbol_resolver: &'a SymbolResolver,
+    ) -> Result<Self, AnalysisError> {
+        Ok(NamespaceWalker {
+            mapped,
+            translator,
+            symbol_resolver,
+            task_nsproxy: symbol_resolver.require_field_offset("task_struct", "nsproxy")?,
+            task_cred: symbol_resolver.require_field_offset("task_struct", "cred")?,
+        })
+    }
+
+    fn read_ptr(&self, addr: u64) -> u64 {
+        KernelParser::read_virt_pointer(self.mapped, self.translator, addr).unwrap_or(0)
+    }
+
+    fn read_u32(&self, addr: u64) -> u32 {
+        KernelParser::read_virt_u32(self.mapped, self.translator, addr).unwrap_or(0)
+    }
+
+    fn offset(&self, s: &str, f: &str) -> Option<u64> {
+        self.symbol_resolver.get_struct_field_offset(s, f, None)
+    }
+
+    /// Read a task_struct pointer field by file offset
+    fn task_ptr(&self, task_file_offset: u64, field: u64) -> u64 {
+        KernelParser::read_pointer(
+            self.mapped,
+            (task_file_offset + field) as usize,
+            self.translator.target(),
+        )
+        .unwrap_or(0)
+    }
+
+    /// Read the inode number of a namespace object
+    ///
+    /// 3.19+ embeds a struct ns_common as `ns`; earlier kernels store `proc_inum` directly.
+    fn inum(&self, ns: u64, struct_name: &str) -> u32 {
+        if ns == 0 {
+            return 0;
+        }
+        if let (Some(common), Some(inum)) = (
+            self.offset(struct_name, "ns"),
+            self.offset("ns_common", "inum"),
+        ) {
+            return self.read_u32(ns + common + inum);
+        }
+        match self.offset(struct_name, "proc_inum") {
+            Some(inum) => self.read_u32(ns + inum),
+            None => 0,
+        }
+    }
+
+    /// Collect the namespaces, local PID, hostname and cgroup of the task at a file offset
+    pub fn task_namespaces(&self, task_file_offset: u64) -> TaskNamespaces {
+        let mut result = TaskNamespaces::default();
+
+        // Exiting tasks have already dropped their nsproxy
+        let nsproxy = self.task_ptr(task_file_offset, self.task_nsproxy as u64);
+        if nsproxy != 0 {
+            let members = [
+                ("uts_ns", "uts_namespace", &mut result.uts),
+                ("ipc_ns", "ipc_namespace", &mut result.ipc),
+                ("mnt_ns", "mnt_namespace", &mut result.mnt),
+                ("net_ns", "net", &mut result.net),
+                ("cgroup_ns", "cgroup_namespace", &mut result.cgroup),
+                ("time_ns", "time_namespace", &mut result.time),
+            ];
+            for (field, struct_name, inum) in members {
+                if let Some(offset) = self.offset("nsproxy", field) {
+                    *inum = self.inum(self.read_ptr(nsproxy + offset), struct_name);
+                }
+            }
+            if let Some(offset) = self.offset("nsproxy", "uts_ns") {
+                result.hostname = self.hostname(self.read_ptr(nsproxy + offset));
+            }
+        }
+
+        let cred = self.task_ptr(task_file_offset, self.task_cred as u64);
+        if let (true, Some(user_ns)) = (cred != 0, self.offset("cred", "user_ns")) {
+            result.user = self.inum(self.read_ptr(cred + user_ns), "user_namespace");
+        }
+
+        let pid = self.task_pid(task_file_offset);
+        if let Some((nr, ns)) = self.innermost_upid(pid) {
+            result.ns_pid = nr;
+            result.pid = self.inum(ns, "pid_namespace");
+        }
+
+        result.cgroup_path = self.cgroup_path(task_file_offset);
+        result
+    }
+
+    /// Read the nodename of a UTS namespace
+    fn hostname(&self, uts_ns: u64) -> String {
+        let (name, nodename) = match (
+            self.offset("uts_namespace", "name"),
+            self.offset("new_utsname", "nodename"),
+        ) {
+            (Some(n), Some(o)) => (n, o),
+            (Some(n), None) => (n, UTS_NAME_LEN as u64),
+            _ => return String::new(),
+        };
+        if uts_ns == 0 {
+            return String::new();
+        }
+        KernelParser::read_virt_cstring(
+            self.mapped,
+            self.translator,
+            uts_ns + name + nodename,
+            UTS_NAME_LEN,
+        )
+        .unwrap_or_default()
+    }
+
+    /// Return the task's struct pid
+    fn task_pid(&self, task_file_offset: u64) -> u64 {
+        // 4.19+: task->thread_pid
+        if let Some(thread_pid) = self.offset("task_struct", "thread_pid") {
+            return self.task_ptr(task_file_offset, thread_pid);
+        }
+        // Earlier: task->pids[PIDTYPE_PID].pid, PIDTYPE_PID being the first slot
+        match (
+            self.offset("task_struct", "pids"),
+            self.offset("pid_link", "pid"),
+        ) {
+            (Some(pids), Some(pid)) => self.task_ptr(task_file_offset, pids + pid),
+            _ => 0,
+        }
+    }
+
+    /// Read numbers[level] of a struct pid: the PID in the innermost namespace
+    fn innermost_upid(&self, pid: u64) -> Option<(i32, u64)> {
+        if pid == 0 {
+            return None;
+        }
+        let level = self.read_u32(pid + self.offset("pid", "level")?);
+        if level > MAX_PID_LEVEL {
+            return None;
+        }
+        let stride = self
+            .symbol_resolver
+            .get_struct_size("upid")
+            .map(|s| s as u64)
+            .unwrap_or(UPID_SIZE);
+        let upid = pid + self.offset("pid", "numbers")? + level as u64 * stride;
+        let nr = KernelParser::read_virt_i32(
+            self.mapped,
+            self.translator,
+            upid + self.offset("upid", "nr")?,
+        )?;
+        Some((nr, self.read_ptr(upid + self.offset("upid", "ns")?)))
+    }
+
+    /// Build the cgroup path of a task
+    ///
+    /// The default (v2) hierarchy is preferred. On v1-only hosts the task sits in the
+    /// v2 root, so the deepest path across the v1 controllers is used instead.
+    fn cgroup_path(&self, task_file_offset: u64) -> String {
+        let css_set = match self.offset("task_struct", "cgroups") {
+            Some(o) => self.task_ptr(task_file_offset, o),
+            None => return String::new(),
+        };
+        if css_set == 0 {
+            return String::new();
+        }
+
+        let mut path = match self.offset("css_set", "dfl_cgrp") {
+            Some(o) => self.cgroup_kernfs_path(self.read_ptr(css_set + o)),
+            None => String::new(),
+        };
+        if path.len() > 1 {
+            return path;
+        }
+
+        // cgroup_subsys[] has one slot per compiled-in controller
+        let (subsys, css_cgroup) = match (
+            self.offset("css_set", "subsys"),
+            self.offset("cgroup_subsys_state", "cgroup"),
+        ) {
+            (Some(s), Some(c)) => (s, c),
+            _ => return path,
+        };
+        let count = self
+            .symbol_resolver
+            .get_symbol_address("cgroup_subsys")
+            .and_then(|base| {
+                let next = self.symbol_resolver.next_symbol_address(base)?;
+                Some((next - base) / 8)
+            })
+            .unwrap_or(MAX_CGROUP_SUBSYS)
+            .min(MAX_CGROUP_SUBSYS);
+        for i in 0..count {
+            let css = self.read_ptr(css_set + subsys + i * self.translator.pointer_size());
+            if css == 0 {
+                continue;
+            }
+            let candidate = self.cgroup_kernfs_path(self.read_ptr(css + css_cgroup));
+            if candidate.len() > path.len() {
+                path = candidate;
+            }
+        }
+        path
+    }
+
+    /// Build a cgroup's path by walking its kernfs_node parents
+    fn cgroup_kernfs_path(&self, cgroup: u64) -> String {
+        let (kn, name) = match (
+            self.offset("cgroup", "kn"),
+            self.offset("kernfs_node", "name"),
+        ) {
+            (Some(k), Some(n)) => (k, n),
+            _ => return String::new(),
+        };
+        // Renamed to __parent in 6.15
+        let parent = match self
+            .offset("kernfs_node", "parent")
+            .or_else(|| self.offset("kernfs_node", "__parent"))
+        {
+            Some(p) => p,
+            None => return String::new(),
+        };
+        if cgroup == 0 {
+            return String::new();
+        }
+
+        let mut components = Vec::new();
+        let mut node = self.read_ptr(cgroup + kn);
+        while node != 0 && components.len() < MAX_CGROUP_DEPTH {
+            let up = self.read_ptr(node + parent);
+            if up == 0 {
+                break; // The hierarchy root is not part of the path
+            }
+            components.push(
+                KernelParser::read_virt_cstring(
+                    self.mapped,
+                    self.translator,
+                    self.read_ptr(node + name),
+                    256,
+                )
+                .unwrap_or_default(),
+            );
+            node = up;
+        }
+        components.reverse();
+        format!("/{}", components.join("/"))
+    }
+}
@@ -0,0 +1,449 @@
+//! Networking helpers for walking network namespaces and devices
+use crate::error::AnalysisError;
+use crate::kernel::files::FileWalker;
+use crate::kernel::{KernelParser, ProcessInfo, SocketOwner};
+use crate::symbols::SymbolResolver;
+use crate::translation::MemoryTranslator;
+use std::collections::HashMap;
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+// Safety limits to prevent runaway walks over corrupted structures
+const MAX_NAMESPACES: usize = 4096;
+const MAX_DEVICES: usize = 65536;
+
+// IFNAMSIZ from include/uapi/linux/if.h
+const IFNAMSIZ: usize = 16;
+
+// Interface flags (include/uapi/linux/if.h)
+pub const IFF_PROMISC: u32 = 0x100;
+const INTERFACE_FLAGS: [(u32, &str); 15] = [
+    (0x1, "UP"),
+    (0x2, "BROADCAST"),
+    (0x4, "DEBUG"),
+    (0x8, "LOOPBACK"),
+    (0x10, "POINTOPOINT"),
+    (0x20, "NOTRAILERS"),
+    (0x40, "RUNNING"),
+    (0x80, "NOARP"),
+    (IFF_PROMISC, "PROMISC"),
+    (0x200, "ALLMULTI"),
+    (0x400, "MASTER"),
+    (0x800, "SLAVE"),
+    (0x1000, "MULTICAST"),
+    (0x2000, "PORTSEL"),
+    (0x4000, "AUTOMEDIA"),
+];
+
+/// Walker over network namespaces and their devices
+pub struct NetWalker<'a> {
+    mapped: &'a [u8],
+    translator: &'a MemoryTranslator,
+    symbol_resolver: &'a SymbolResolver,
+    // net
+    net_list: usize,
+    net_ns: Option<usize>,
+    dev_base_head: usize,
+    // ns_common
+    ns_inum: usize,
+    // net_device
+    dev_list: usize,
+    dev_name: usize,
+    dev_nd_net: Option<usize>,
+    dev_addr_len: Option<usize>,
+    dev_ifindex: Option<usize>,
+}
+
+impl<'a> NetWalker<'a> {
+    /// Resolve the structure offsets needed for namespace and device walking
+    pub fn new(
+        mapped: &'a [u8],
+        translator: &'a MemoryTranslator,
+        symbol_resolver: &'a SymbolResolver,
+    ) -> Result<Self, AnalysisError> {
+        let field = |s: &str, f: &str| symbol_resolver.require_field_offset(s, f);
+        let optional = |s: &str, f: &str| {
+            if symbol_resolver.has_field(s, f) {
+                symbol_resolver.require_field_offset(s, f).ok()
+            } else {
+                None
+            }
+        };
+
+        Ok(NetWalker {
+            mapped,
+            translator,
+            symbol_resolver,
+            net_list: field("net", "list")?,
+            net_ns: optional("net", "ns"),
+            dev_base_head: field("net", "dev_base_head")?,
+            ns_inum: symbol_resolver
+                .get_struct_field_offset("ns_common", "inum", None)
+                .unwrap_or(16) as usize,
+            dev_list: field("net_device", "dev_list")?,
+            dev_name: field("net_device", "name")?,
+            // possible_net_t holds a single struct net pointer
+            dev_nd_net: optional("net_device", "nd_net"),
+            dev_addr_len: optional("net_device", "addr_len"),
+            dev_ifindex: optional("net_device", "ifindex"),
+        })
+    }
+
+    /// Return the address of every network namespace, starting with init_net
+    pub fn namespaces(&self) -> Result<Vec<u64>, AnalysisError> {
+        let init_net = self
+            .symbol_resolver
+            .get_symbol_address("init_net")
+            .ok_or_else(|| AnalysisError::SymbolNotFound("init_net".to_string()))?;
+        let mut namespaces = vec![init_net];
+
+        // Every namespace (init_net included) is linked on net_namespace_list
+        if let Some(head) = self
+            .symbol_resolver
+            .get_symbol_address("net_namespace_list")
+        {
+            for entry in KernelParser::walk_list(self.mapped, self.translator, head, MAX_NAMESPACES)
+            {
+                let net = entry.wrapping_sub(self.net_list as u64);
+                if !namespaces.contains(&net) {
+                    namespaces.push(net);
+                }
+            }
+        }
+
+        Ok(namespaces)
+    }
+
+    /// Read the namespace inode number (as shown in /proc/PID/ns/net)
+    pub fn namespace_inum(&self, net: u64) -> u32 {
+        match self.net_ns {
+            Some(ns) => KernelParser::read_virt_u32(
+                self.mapped,
+                self.translator,
+                net + (ns + self.ns_inum) as u64,
+            )
+            .unwrap_or(0),
+            None => 0,
+        }
+    }
+
+    /// Return the address of every net_device registered in a namespace
+    pub fn devices(&self, net: u64) -> Vec<u64> {
+        let head = net + self.dev_base_head as u64;
+        KernelParser::walk_list(self.mapped, self.translator, head, MAX_DEVICES)
+            .into_iter()
+            .map(|entry| entry.wrapping_sub(self.dev_list as u64))
+            .collect()
+    }
+
+    /// Read the interface name of a net_device
+    pub fn device_name(&self, dev: u64) -> Option<String> {
+        KernelParser::read_virt_cstring(
+            self.mapped,
+            self.translator,
+            dev + self.dev_name as u64,
+            IFNAMSIZ,
+        )
+    }
+
+    /// Return the network namespace a net_device belongs to
+    pub fn device_namespace(&self, dev: u64) -> Option<u64> {
+        let offset = self.dev_nd_net?;
+        KernelParser::read_virt_pointer(self.mapped, self.translator, dev + offset as u64)
+            .filter(|&net| net != 0)
+    }
+
+    /// Map (namespace, ifindex) to interface name for every device
+    pub fn interface_names(&self) -> Result<HashMap<(u64, i32), String>, AnalysisError> {
+        let mut names = HashMap::new();
+        let offset = match self.dev_ifindex {
+            Some(o) => o as u64,
+            None => return Ok(names),
+        };
+        for net in self.namespaces()? {
+            for dev in self.devices(net) {
+                let ifindex =
+                    KernelParser::read_virt_u32(self.mapped, self.translator, dev + offset);
+                if let (Some(ifindex), Some(name)) = (ifindex, self.device_name(dev)) {
+                    names.insert((net, ifindex as i32), name);
+                }
+            }
+        }
+        Ok(names)
+    }
+
+    /// Return the hardware address length of a net_device (6 for Ethernet)
+    pub fn device_addr_len(&self, dev: u64) -> usize {
+        self.dev_addr_len
+            .and_then(|offset| {
+                KernelParser::read_virt_bytes(self.mapped, self.translator, dev + offset as u64, 1)
+            })
+            .map(|b| b[0] as usize)
+            .unwrap_or(6)
+    }
+}
+
+/// Reader for the protocol-independent parts of struct sock and struct socket
+pub struct SockReader<'a> {
+    mapped: &'a [u8],
+    translator: &'a MemoryTranslator,
+    // sock / sock_common
+    skc_family: usize,
+    skc_state: usize,
+    skc_net: Option<usize>,
+    sk_socket: usize,
+    sk_type: usize,
+    // socket / socket_alloc
+    socket_type: usize,
+    vfs_inode: usize,
+    // inode
+    i_ino: usize,
+}
+
+impl<'a> SockReader<'a> {
+    /// Resolve the struct sock offsets shared by every socket family
+    pub fn new(
+        mapped: &'a [u8],
+        translator: &'a MemoryTranslator,
+        symbol_resolver: &'a SymbolResolver,
+    ) -> Result<Self, AnalysisError> {
+        let field = |s: &str, f: &str| symbol_resolver.require_field_offset(s, f);
+        // struct sock starts with the embedded struct sock_common
+        let common = symbol_resolver
+            .get_struct_field_offset("sock", "__sk_common", None)
+            .unwrap_or(0) as usize;
+        let skc_net = if symbol_resolver.has_field("sock_common", "skc_net") {
+            Some(common + field("sock_common", "skc_net")?)
+        } else {
+            None
+        };
+
+        Ok(SockReader {
+            mapped,
+            translator,
+            skc_family: common + field("sock_common", "skc_family")?,
+            skc_state: common + field("sock_common", "skc_state")?,
+            skc_net,
+            sk_socket: field("sock", "sk_socket")?,
+            sk_type: field("sock", "sk_type")?,
+            socket_type: field("socket", "type")?,
+            vfs_inode: field("socket_alloc", "vfs_inode")?,
+            i_ino: field("inode", "i_ino")?,
+        })
+    }
+
+    /// Read the address family (AF_INET, AF_INET6, AF_UNIX, ...)
+    pub fn family(&self, sk: u64) -> u16 {
+        KernelParser::read_virt_u16(self.mapped, self.translator, sk + self.skc_family as u64)
+            .unwrap_or(0)
+    }
+
+    /// Read sk_state (TCP_* values, reused by every family)
+    pub fn state(&self, sk: u64) -> u8 {
+        KernelParser::read_virt_bytes(self.mapped, self.translator, sk + self.skc_state as u64, 1)
+            .map(|b| b[0])
+            .unwrap_or(0)
+    }
+
+    /// Return the network namespace a sock belongs to
+    pub fn net(&self, sk: u64) -> Option<u64> {
+        let offset = self.skc_net?;
+        KernelParser::read_virt_pointer(self.mapped, self.translator, sk + offset as u64)
+            .filter(|&net| net != 0)
+    }
+
+    /// Return the struct socket attached to a sock (0 for orphaned socks)
+    pub fn socket(&self, sk: u64) -> u64 {
+        KernelParser::read_virt_pointer(self.mapped, self.translator, sk + self.sk_socket as u64)
+            .unwrap_or(0)
+    }
+
+    /// Read the socket type (SOCK_STREAM, SOCK_DGRAM, ...)
+    ///
+    /// struct socket's short type is preferred because sk_type was a bitfield
+    /// before 5.6.
+    pub fn socket_type(&self, sk: u64) -> u16 {
+        let socket = self.socket(sk);
+        let addr = if socket != 0 {
+            socket + self.socket_type as u64
+        } else {
+            sk + self.sk_type as u64
+        };
+        KernelParser::read_virt_u16(self.mapped, self.translator, addr).unwrap_or(0)
+    }
+
+    /// Return the sockfs inode number of a sock (as shown in /proc/PID/fd)
+    pub fn inode_number(&self, sk: u64) -> u64 {
+        let socket = self.socket(sk);
+        if socket == 0 {
+            return 0;
+        }
+        // struct socket_alloc { struct socket socket; struct inode vfs_inode; }
+        let inode = socket + self.vfs_inode as u64;
+        KernelParser::read_virt_pointer(self.mapped, self.translator, inode + self.i_ino as u64)
+            .unwrap_or(0)
+    }
+}
+
+/// Index from socket inode number to the processes holding the socket open
+#[derive(Default)]
+pub struct SocketIndex {
+    owners: HashMap<u64, Vec<SocketOwner>>,
+}
+
+impl SocketIndex {
+    /// Walk the fd table of every process and record each sockfs file
+    pub fn build(
+        mapped: &[u8],
+        translator: &MemoryTranslator,
+        symbol_resolver: &SymbolResolver,
+        processes: &[ProcessInfo],
+    ) -> Result<Self, AnalysisError> {
+        let walker = FileWalker::new(mapped, translator, symbol_resolver)?;
+        let mut owners: HashMap<u64, Vec<SocketOwner>> = HashMap::new();
+
+        for process in processes {
+            for open_file in walker.open_files(process.offset) {
+                let inode = walker.file_inode(open_file.file);
+                if inode == 0 || !walker.is_socket_inode(inode) {
+                    continue;
+                }
+                owners
+                    .entry(walker.inode_number(inode))
+                    .or_default()
+                    .push(SocketOwner {
+                        pid: process.pid,
+                        fd: open_file.fd,
+                        comm: process.comm.clone(),
+                    });
+            }
+        }
+
+        Ok(SocketIndex { owners })
+    }
+
+    /// Return every (pid, fd) holding the socket with this inode number
+    pub fn owners(&self, inode_number: u64) -> Vec<SocketOwner> {
+        self.owners.get(&inode_number).cloned().unwrap_or_default()
+    }
+}
+
+/// Format a network-byte-order IPv4 address
+pub fn format_ipv4(bytes: &[u8]) -> String {
+    match <[u8; 4]>::try_from(bytes) {
+        Ok(octets) => Ipv4Addr::from(octets).to_string(),
+        Err(_) => "?".to_string(),
+    }
+}
+
+/// Format a network-byte-order IPv6 address
+pub fn format_ipv6(bytes: &[u8]) -> String {
+    match <[u8; 16]>::try_from(bytes) {
+        Ok(octets) => Ipv6Addr::from(octets).to_string(),
+        Err(_) => "?".to_string(),
+    }
+}
+
+/// Format a hardware address as colon-separated hex bytes
+pub fn format_mac(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+/// Format net_device flags like ip-link ("UP|BROADCAST|RUNNING")
+pub fn format_interface_flags(flags: u32) -> String {
+    let names: Vec<&str> = INTERFACE_FLAGS
+        .iter()
+        .filter(|(bit, _)| flags & bit != 0)
+        .map(|(_, name)| *name)
+        .collect();
+    if names.is_empty() {
+        "-".to_string()
+    } else {
+        names.join("|")
+    }
+}
+
+/// Name a TCP state (include/net/tcp_states.h)
+pub fn format_tcp_state(state: u8) -> String {
+    match state {
+        1 => "ESTABLISHED",
+        2 => "SYN_SENT",
+        3 => "SYN_RECV",
+        4 => "FIN_WAIT1",
+        5 => "FIN_WAIT2",
+        6 => "TIME_WAIT",
+        7 => "CLOSE",
+        8 => "CLOSE_WAIT",
+        9 => "LAST_ACK",
+        10 => "LISTEN",
+        11 => "CLOSING",
+        12 => "NEW_SYN_RECV",
+        _ => "UNKNOWN",
+    }
+    .to_string()
+}
+
+/// Name a socket type (include/linux/net.h)
+pub fn format_socket_type(socket_type: u16) -> String {
+    match socket_type {
+        1 => "STREAM",
+        2 => "DGRAM",
+        3 => "RAW",
+        4 => "RDM",
+        5 => "SEQPACKET",
+        6 => "DCCP",
+        10 => "PACKET",
+        _ => "UNKNOWN",
+    }
+    .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_addresses() {
+        assert_eq!(format_ipv4(&[192, 168, 1, 10]), "192.168.1.10");
+        let mut v6 = [0u8; 16];
+        v6[0] = 0xfe;
+        v6[1] = 0x80;
+        v6[15] = 1;
+        assert_eq!(format_ipv6(&v6), "fe80::1");
+        // IPv4-mapped IPv6 addresses (dual-stack sockets) keep the dotted quad
+        let mut mapped = [0u8; 16];
+        mapped[10] = 0xff;
+        mapped[11] = 0xff;
+        mapped[12..].copy_from_slice(&[10, 1, 2, 3]);
+        assert_eq!(format_ipv6(&mapped), "::ffff:10.1.2.3");
+        assert_eq!(format_ipv6(&[0u8; 16]), "::");
+        assert_eq!(format_ipv4(&[1, 2, 3]), "?");
+        assert_eq!(
+            format_mac(&[0x52, 0x54, 0x00, 0xab, 0xcd, 0xef]),
+            "52:54:00:ab:cd:ef"
+        );
+    }
+
+    #[test]
+    fn test_format_tcp_state() {
+        assert_eq!(format_tcp_state(1), "ESTABLISHED");
+        assert_eq!(format_tcp_state(6), "TIME_WAIT");
+        assert_eq!(format_tcp_state(7), "CLOSE");
+        assert_eq!(format_tcp_state(10), "LISTEN");
+        assert_eq!(format_tcp_state(12), "NEW_SYN_RECV");
+        assert_eq!(format_tcp_state(0), "UNKNOWN");
+        assert_eq!(format_tcp_state(13), "UNKNOWN");
+    }
+
+    #[test]
+    fn test_format_interface_flags() {
+        assert_eq!(
+            format_interface_flags(0x1143),
+            "UP|BROADCAST|RUNNING|PROMISC|MULTICAST"
+        );
+        assert_eq!(format_interface_flags(0), "-");
+    }
+}
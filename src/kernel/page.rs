@@ -0,0 +1,324 @@
+//! struct page helpers: vmemmap location, PFN <-> page conversion and page descriptor decoding
+use crate::kernel::KernelParser;
+use crate::symbols::SymbolResolver;
+use crate::translation::MemoryTranslator;
+
+// struct page layout on x86-64 (64 bytes), used when dwarf2json cannot name a
+// member of the anonymous unions
+const DEFAULT_PAGE_SIZE: u64 = 64;
+const DEFAULT_COMPOUND_HEAD: u64 = 8;
+const DEFAULT_MAPPING: u64 = 24;
+const DEFAULT_INDEX: u64 = 32;
+const DEFAULT_MAPCOUNT: u64 = 48;
+const DEFAULT_REFCOUNT: u64 = 52;
+
+// Low bits of page->mapping (include/linux/page-flags.h)
+const PAGE_MAPPING_ANON: u64 = 0x1;
+const PAGE_MAPPING_MOVABLE: u64 = 0x2;
+const PAGE_MAPPING_FLAGS: u64 = 0x3;
+
+// Bound on the pageflag_names table
+const MAX_PAGE_FLAGS: u64 = 64;
+
+/// What page->mapping points to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MappingKind {
+    None,    // Free, slab or page-table pages
+    File,    // struct address_space of a file (page cache)
+    Anon,    // struct anon_vma of an anonymous mapping
+    Movable, // Driver-managed movable page (zsmalloc, balloon)
+    Ksm,     // KSM-merged page (stable tree node)
+}
+
+impl MappingKind {
+    /// Classify a raw page->mapping value by its low tag bits
+    pub fn from_mapping(mapping: u64) -> Self {
+        match mapping & PAGE_MAPPING_FLAGS {
+            _ if mapping == 0 => MappingKind::None,
+            PAGE_MAPPING_ANON => MappingKind::Anon,
+            PAGE_MAPPING_MOVABLE => MappingKind::Movable,
+            PAGE_MAPPING_FLAGS => MappingKind::Ksm,
+            _ => MappingKind::File,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            MappingKind::None => "none",
+            MappingKind::File => "file",
+            MappingKind::Anon => "anon",
+            MappingKind::Movable => "movable",
+            MappingKind::Ksm => "ksm",
+        }
+    }
+}
+
+/// Decoded struct page
+#[derive(Debug, Clone)]
+pub struct PageDescriptor {
+    pub pfn: u64,
+    pub page: u64, // Virtual address of the struct page
+    pub flags: u64,
+    pub flag_names: Vec<String>,
+    pub head: Option<u64>, // Head PFN when this is a compound tail page
+    pub mapping: u64,      // page->mapping of the head page, low bits cleared
+    pub mapping_kind: MappingKind,
+    pub index: u64,    // Page offset within the file, or the anon linear index
+    pub refcount: i32, // _refcount
+    pub mapcount: i32, // _mapcount + 1 (page-table mappings)
+}
+
+/// Location of the vmemmap array and the struct page layout
+#[derive(Debug, Clone, Copy)]
+pub struct PageLayout {
+    pub vmemmap_base: u64, // Address of the (possibly absent) struct page of PFN 0
+    pub size: u64,         // sizeof(struct page)
+    page_shift: u64,
+    compound_head: u64,
+    mapping: u64,
+    pub index: u64, // page->index, also the next-page link of zsmalloc zspages
+    mapcount: u64,
+    refcount: u64,
+}
+
+impl PageLayout {
+    /// Find the vmemmap array and read the struct page field offsets
+    pub fn new(
+        mapped: &[u8],
+        translator: &MemoryTranslator,
+        symbol_resolver: &SymbolResolver,
+    ) -> Self {
+        let (vmemmap_start, _) = vmemmap_range(mapped, translator, symbol_resolver);
+        let size = symbol_resolver
+            .get_struct_size("page")
+            .map_or(DEFAULT_PAGE_SIZE, |s| s as u64);
+        // On AArch64 the first struct page describes PHYS_OFFSET, not PFN 0
+        let first_pfn = translator.kernel_regions().vmemmap_first_pfn;
+        let field = |name: &str, default: u64| {
+            if symbol_resolver.has_field("page", name) {
+                symbol_resolver
+                    .get_struct_field_offset("page", name, None)
+                    .unwrap_or(default)
+            } else {
+                default
+            }
+        };
+
+        PageLayout {
+            vmemmap_base: vmemmap_start.wrapping_sub(first_pfn * size),
+            size,
+            page_shift: translator.page_size().trailing_zeros() as u64,
+            compound_head: field("compound_head", DEFAULT_COMPOUND_HEAD),
+            mapping: field("mapping", DEFAULT_MAPPING),
+            index: field("index", DEFAULT_INDEX),
+            mapcount: field("_mapcount", DEFAULT_MAPCOUNT),
+            refcount: field("_refcount", DEFAULT_REFCOUNT),
+        }
+    }
+
+    /// Default x86-64 struct page layout at a vmemmap base
+    #[cfg(test)]
+    pub fn with_vmemmap_base(vmemmap_base: u64) -> Self {
+        PageLayout {
+            vmemmap_base,
+            size: DEFAULT_PAGE_SIZE,
+            page_shift: 12,
+            compound_head: DEFAULT_COMPOUND_HEAD,
+            mapping: DEFAULT_MAPPING,
+            index: DEFAULT_INDEX,
+            mapcount: DEFAULT_MAPCOUNT,
+            refcount: DEFAULT_REFCOUNT,
+        }
+    }
+
+    /// Get the virtual address of a PFN's struct page
+    pub fn pfn_to_page(&self, pfn: u64) -> u64 {
+        self.vmemmap_base.wrapping_add(pfn * self.size)
+    }
+
+    /// Get the PFN a struct page pointer describes
+    pub fn page_to_pfn(&self, page: u64) -> Option<u64> {
+        if self.size == 0 {
+            return None;
+        }
+        Some(page.wrapping_sub(self.vmemmap_base) / self.size)
+    }
+}
+
+/// Find [VMEMMAP_START, end of the vmemmap area)
+///
+/// x86-64 KASLR records the start in vmemmap_base. Otherwise the architecture's
+/// candidates are tried for one whose struct page of the first dumped page is
+/// mapped, which needs the kernel page table to be loaded.
+pub fn vmemmap_range(
+    mapped: &[u8],
+    translator: &MemoryTranslator,
+    symbol_resolver: &SymbolResolver,
+) -> (u64, u64) {
+    let regions = translator.kernel_regions();
+    let start = symbol_resolver
+        .get_symbol_address("vmemmap_base")
+        .and_then(|addr| KernelParser::read_virt_pointer(mapped, translator, addr))
+        .filter(|&base| base != 0)
+        .or_else(|| {
+            let first_pfn = translator.get_regions().first()?.start / translator.page_size();
+            let offset = first_pfn.saturating_sub(regions.vmemmap_first_pfn) * DEFAULT_PAGE_SIZE;
+            regions
+                .vmemmap_starts
+                .iter()
+                .copied()
+                .find(|&start| translator.virtual_to_physical(start + offset).is_some())
+        })
+        .unwrap_or(regions.vmemmap_starts[0]);
+    (start, start.saturating_add(regions.vmemmap_size))
+}
+
+/// Locates the vmemmap array and decodes the struct page of any PFN
+pub struct PageMap<'a> {
+    mapped: &'a [u8],
+    translator: &'a MemoryTranslator,
+    layout: PageLayout,
+    flag_names: Vec<(u64, String)>, // (mask, name) from pageflag_names
+}
+
+impl<'a> PageMap<'a> {
+    /// Read the vmemmap location, struct page layout and page flag names
+    pub fn new(
+        mapped: &'a [u8],
+        translator: &'a MemoryTranslator,
+        symbol_resolver: &SymbolResolver,
+    ) -> Self {
+        PageMap {
+            mapped,
+            translator,
+            layout: PageLayout::new(mapped, translator, symbol_resolver),
+            flag_names: read_flag_names(mapped, translator, symbol_resolver),
+        }
+    }
+
+    /// Get the virtual address of a PFN's struct page
+    pub fn pfn_to_page(&self, pfn: u64) -> u64 {
+        self.layout.pfn_to_page(pfn)
+    }
+
+    /// Get the PFN a struct page pointer describes
+    pub fn page_to_pfn(&self, page: u64) -> Option<u64> {
+        self.layout.page_to_pfn(page)
+    }
+
+    /// Convert a struct page pointer to the physical address of the page
+    pub fn page_to_physical(&self, page: u64) -> Option<u64> {
+        Some(self.page_to_pfn(page)? << self.layout.page_shift)
+    }
+
+    /// Read an unsigned long or pointer field
+    fn read_ulong(&self, addr: u64) -> Option<u64> {
+        KernelParser::read_virt_pointer(self.mapped, self.translator, addr)
+    }
+
+    fn read_i32(&self, addr: u64) -> Option<i32> {
+        KernelParser::read_virt_u32(self.mapped, self.translator, addr).map(|v| v as i32)
+    }
+
+    /// Decode the struct page describing a physical address
+    ///
+    /// Returns None when the vmemmap page holding the descriptor was not captured.
+    pub fn describe(&self, physical: u64) -> Option<PageDescriptor> {
+        let pfn = physical >> self.layout.page_shift;
+        let page = self.pfn_to_page(pfn);
+        let flags = self.read_ulong(page)?;
+
+        // Tail pages of a compound page point at the head with bit 0 set; the
+        // mapping and index live in the head
+        let compound_head = self
+            .read_ulong(page + self.layout.compound_head)
+            .unwrap_or(0);
+        let head_page = if compound_head & 1 != 0 {
+            compound_head - 1
+        } else {
+            page
+        };
+        let head = (head_page != page)
+            .then(|| self.page_to_pfn(head_page))
+            .flatten();
+
+        let raw_mapping = self
+            .read_ulong(head_page + self.layout.mapping)
+            .unwrap_or(0);
+
+        Some(PageDescriptor {
+            pfn,
+            page,
+            flags,
+            flag_names: self
+                .flag_names
+                .iter()
+                .filter(|(mask, _)| flags & mask == *mask)
+                .map(|(_, name)| name.clone())
+                .collect(),
+            head,
+            mapping: raw_mapping & !PAGE_MAPPING_FLAGS,
+            mapping_kind: MappingKind::from_mapping(raw_mapping),
+            index: self.read_ulong(head_page + self.layout.index).unwrap_or(0),
+            refcount: self.read_i32(head_page + self.layout.refcount).unwrap_or(0),
+            mapcount: self
+                .read_i32(page + self.layout.mapcount)
+                .map_or(0, |c| c + 1),
+        })
+    }
+}
+
+/// Read the kernel's own page flag names from pageflag_names (mm/debug.c)
+///
+/// Bit numbers depend on the kernel version and config, so names are taken from
+/// the dump rather than hardcoded.
+fn read_flag_names(
+    mapped: &[u8],
+    translator: &MemoryTranslator,
+    symbol_resolver: &SymbolResolver,
+) -> Vec<(u64, String)> {
+    let table = match symbol_resolver.get_symbol_address("pageflag_names") {
+        Some(t) => t,
+        None => return Vec::new(),
+    };
+    let mut names = Vec::new();
+    for i in 0..MAX_PAGE_FLAGS {
+        // struct trace_print_flags { unsigned long mask; const char *name; }
+        let word = translator.pointer_size();
+        let entry = table + i * 2 * word;
+        let mask = KernelParser::read_virt_pointer(mapped, translator, entry).unwrap_or(0);
+        let name = KernelParser::read_virt_pointer(mapped, translator, entry + word)
+            .and_then(|ptr| KernelParser::read_virt_cstring(mapped, translator, ptr, 32));
+        match name {
+            Some(name) if mask != 0 => names.push((mask, name)),
+            _ => break,
+        }
+    }
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mapping_kind_from_tag_bits() {
+        assert_eq!(MappingKind::from_mapping(0), MappingKind::None);
+        assert_eq!(
+            MappingKind::from_mapping(0xffff_8880_1234_5600),
+            MappingKind::File
+        );
+        assert_eq!(
+            MappingKind::from_mapping(0xffff_8880_1234_5601),
+            MappingKind::Anon
+        );
+        assert_eq!(
+            MappingKind::from_mapping(0xffff_8880_1234_5602),
+            MappingKind::Movable
+        );
+        assert_eq!(
+            MappingKind::from_mapping(0xffff_8880_1234_5603),
+            MappingKind::Ksm
+        );
+    }
+}
@@ -0,0 +1,194 @@
+//! Code pointer classification: map pointers to core kernel text, loaded modules or neither
+use crate::error::AnalysisError;
+use crate::kernel::modules::{LoadedModule, ModuleWalker};
+use crate::symbols::SymbolResolver;
+use crate::translation::MemoryTranslator;
+use std::fmt;
+
+// Macro for conditional warning output
+macro_rules! warn {
+    ($($arg:tt)*) => {
+        if std::env::var("LINMEMPARSER_VERBOSE").is_ok() {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+/// Which code region a pointer lands in
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PointerClass {
+    Kernel,         // Core kernel text (_stext.._etext)
+    Module(String), // A module on the `modules` list
+    Unknown,        // Heap, vmalloc or an unlinked module
+}
+
+impl fmt::Display for PointerClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PointerClass::Kernel => write!(f, "kernel"),
+            PointerClass::Module(name) => write!(f, "{}", name),
+            PointerClass::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+/// Where a code pointer lands
+#[derive(Debug, Clone)]
+pub struct ResolvedPointer {
+    pub symbol: String, // symbol+offset, or the raw address when no symbol covers it
+    pub class: PointerClass,
+}
+
+/// Address-range index over core kernel text and the loaded modules
+///
+/// Built once per plugin run from the `SymbolResolver` and the module list, then
+/// shared by every check that asks whether a function pointer is legitimate.
+pub struct PointerIndex {
+    stext: u64,
+    etext: u64,
+    kernel_symbols: Vec<(u64, String)>,
+    modules: Vec<LoadedModule>,
+}
+
+impl PointerIndex {
+    /// Index kernel text symbols and walk the module list
+    ///
+    /// A missing module list is not fatal: every non-kernel pointer then classifies
+    /// as unknown.
+    pub fn new(
+        mapped: &[u8],
+        translator: &MemoryTranslator,
+        symbol_resolver: &SymbolResolver,
+    ) -> Result<Self, AnalysisError> {
+        let text = |name: &str| {
+            symbol_resolver
+                .get_symbol_address(name)
+                .ok_or_else(|| AnalysisError::SymbolNotFound(name.to_string()))
+        };
+        let stext = text("_stext")?;
+        let etext = text("_etext")?;
+
+        let mut kernel_symbols: Vec<(u64, String)> = symbol_resolver
+            .get_symbols()
+            .iter()
+            .filter(|(_, &address)| address >= stext && address < etext)
+            .map(|(name, &address)| (address, name.clone()))
+            .collect();
+        kernel_symbols.sort();
+        kernel_symbols.dedup_by_key(|(address, _)| *address);
+
+        let modules = match ModuleWalker::new(mapped, translator, symbol_resolver)
+            .and_then(|walker| walker.modules())
+        {
+            Ok(modules) => modules,
+            Err(e) => {
+                warn!("[WARNING] Module list unavailable: {}", e);
+                Vec::new()
+            }
+        };
+
+        Ok(PointerIndex {
+            stext,
+            etext,
+            kernel_symbols,
+            modules,
+        })
+    }
+
+    /// Build an index from known text bounds and modules (synthetic dumps in unit tests)
+    #[cfg(test)]
+    pub fn from_parts(
+        stext: u64,
+        etext: u64,
+        mut kernel_symbols: Vec<(u64, String)>,
+        modules: Vec<LoadedModule>,
+    ) -> Self {
+        kernel_symbols.sort();
+        PointerIndex {
+            stext,
+            etext,
+            kernel_symbols,
+            modules,
+        }
+    }
+
+    /// Check whether an address lies in core kernel text
+    pub fn in_kernel_text(&self, address: u64) -> bool {
+        address >= self.stext && address < self.etext
+    }
+
+    /// Classify a code pointer and name the symbol it points into
+    pub fn classify(&self, address: u64) -> ResolvedPointer {
+        if self.in_kernel_text(address) {
+            let index = self.kernel_symbols.partition_point(|(a, _)| *a <= address);
+            let symbol = match index.checked_sub(1).map(|i| &self.kernel_symbols[i]) {
+                Some((start, name)) => format_symbol(name, address - start),
+                None => format!("{:#x}", address),
+            };
+            return ResolvedPointer {
+                symbol,
+                class: PointerClass::Kernel,
+            };
+        }
+
+        if let Some(module) = self.modules.iter().find(|m| m.contains(address)) {
+            let symbol = match module.symbol_for(address) {
+                Some((name, offset)) => format_symbol(name, offset),
+                None => format!("{:#x}", address),
+            };
+            return ResolvedPointer {
+                symbol,
+                class: PointerClass::Module(module.name.clone()),
+            };
+        }
+
+        ResolvedPointer {
+            symbol: format!("{:#x}", address),
+            class: PointerClass::Unknown,
+        }
+    }
+}
+
+/// Format a symbol with an optional offset
+fn format_symbol(name: &str, offset: u64) -> String {
+    if offset == 0 {
+        name.to_string()
+    } else {
+        format!("{}+{:#x}", name, offset)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_kernel_module_and_unknown() {
+        let index = PointerIndex {
+            stext: 0x1000,
+            etext: 0x2000,
+            kernel_symbols: vec![
+                (0x1000, "start".to_string()),
+                (0x1800, "sys_read".to_string()),
+            ],
+            modules: vec![LoadedModule {
+                name: "evil".to_string(),
+                ranges: vec![(0x9000, 0xa000)],
+                symbols: vec![(0x9100, "hook_read".to_string())],
+            }],
+        };
+
+        let kernel = index.classify(0x1810);
+        assert_eq!(kernel.class, PointerClass::Kernel);
+        assert_eq!(kernel.symbol, "sys_read+0x10");
+
+        let module = index.classify(0x9100);
+        assert_eq!(module.class, PointerClass::Module("evil".to_string()));
+        assert_eq!(module.symbol, "hook_read");
+        assert_eq!(module.class.to_string(), "evil");
+
+        let unknown = index.classify(0xdead0000);
+        assert_eq!(unknown.class, PointerClass::Unknown);
+        assert_eq!(unknown.class.to_string(), "unknown");
+    }
+}
@@ -71,8 +71,12 @@ pub fn extract_process_info(
             .unwrap_or(0x0) as usize; // Use standard offset as fallback
 
         // Read PID
-        let pid =
-            KernelParser::read_i32(mapped, (task_struct_offset as usize) + pid_offset).unwrap_or(0);
+        let pid = KernelParser::read_i32(
+            mapped,
+            (task_struct_offset as usize) + pid_offset,
+            translator.target(),
+        )
+        .unwrap_or(0);
 
         // Read process name
         let comm = KernelParser::read_string(
@@ -83,16 +87,23 @@ pub fn extract_process_info(
         .unwrap_or_else(|| "<unknown>".to_string());
 
         // Read parent PID by dereferencing the parent pointer
-        let parent_ptr =
-            KernelParser::read_u64(mapped, (task_struct_offset as usize) + parent_offset)
-                .unwrap_or(0);
+        let parent_ptr = KernelParser::read_pointer(
+            mapped,
+            (task_struct_offset as usize) + parent_offset,
+            translator.target(),
+        )
+        .unwrap_or(0);
 
         let ppid = if parent_ptr != 0 {
             // Translate the virtual address of the parent task_struct to file offset
             if let Some(parent_file_offset) = translator.virtual_to_file_offset(parent_ptr) {
                 // Read the PID from the parent task_struct
-                KernelParser::read_i32(mapped, parent_file_offset as usize + pid_offset)
-                    .unwrap_or(0)
+                KernelParser::read_i32(
+                    mapped,
+                    parent_file_offset as usize + pid_offset,
+                    translator.target(),
+                )
+                .unwrap_or(0)
             } else {
                 0 // Default to 0 if translation fails
             }
@@ -101,13 +112,20 @@ pub fn extract_process_info(
         };
 
         // Read start time
-        let start_time =
-            KernelParser::read_u64(mapped, (task_struct_offset as usize) + start_time_offset)
-                .unwrap_or(0);
+        let start_time = KernelParser::read_u64(
+            mapped,
+            (task_struct_offset as usize) + start_time_offset,
+            translator.target(),
+        )
+        .unwrap_or(0);
 
         // Read credential information by dereferencing the cred pointer
-        let cred_ptr = KernelParser::read_u64(mapped, (task_struct_offset as usize) + cred_offset)
-            .unwrap_or(0);
+        let cred_ptr = KernelParser::read_pointer(
+            mapped,
+            (task_struct_offset as usize) + cred_offset,
+            translator.target(),
+        )
+        .unwrap_or(0);
 
         let (uid, gid) = if cred_ptr != 0 {
             // Translate the virtual address of the cred structure to file offset
@@ -119,10 +137,18 @@ pub fn extract_process_info(
                     .get_struct_field_offset("cred", "gid", kernel_version.as_ref())
                     .unwrap_or(0x4) as usize;
 
-                let uid = KernelParser::read_u32(mapped, cred_file_offset as usize + uid_offset)
-                    .unwrap_or(0);
-                let gid = KernelParser::read_u32(mapped, cred_file_offset as usize + gid_offset)
-                    .unwrap_or(0);
+                let uid = KernelParser::read_u32(
+                    mapped,
+                    cred_file_offset as usize + uid_offset,
+                    translator.target(),
+                )
+                .unwrap_or(0);
+                let gid = KernelParser::read_u32(
+                    mapped,
+                    cred_file_offset as usize + gid_offset,
+                    translator.target(),
+                )
+                .unwrap_or(0);
                 (uid, gid)
             } else {
                 (0, 0) // Default to 0 if translation fails
@@ -132,9 +158,12 @@ pub fn extract_process_info(
         };
 
         // Read process state
-        let state_val =
-            KernelParser::read_i32(mapped, (task_struct_offset as usize) + state_offset)
-                .unwrap_or(0);
+        let state_val = KernelParser::read_i32(
+            mapped,
+            (task_struct_offset as usize) + state_offset,
+            translator.target(),
+        )
+        .unwrap_or(0);
         let state = match state_val {
             0 => "Running".to_string(),
             1 => "Sleeping".to_string(),
@@ -148,8 +177,12 @@ pub fn extract_process_info(
         let mm_offset = symbol_resolver
             .get_struct_field_offset("task_struct", "mm", kernel_version.as_ref())
             .unwrap_or(0x350) as usize;
-        let mm_ptr =
-            KernelParser::read_u64(mapped, (task_struct_offset as usize) + mm_offset).unwrap_or(0);
+        let mm_ptr = KernelParser::read_pointer(
+            mapped,
+            (task_struct_offset as usize) + mm_offset,
+            translator.target(),
+        )
+        .unwrap_or(0);
 
         debug!(
             "[DEBUG] PID {}: mm_offset=0x{:x}, mm_ptr=0x{:x}",
@@ -175,12 +208,18 @@ pub fn extract_process_info(
                 );
 
                 // Read arg_start and arg_end pointers
-                let arg_start =
-                    KernelParser::read_u64(mapped, mm_file_offset as usize + arg_start_offset)
-                        .unwrap_or(0);
-                let arg_end =
-                    KernelParser::read_u64(mapped, mm_file_offset as usize + arg_end_offset)
-                        .unwrap_or(0);
+                let arg_start = KernelParser::read_pointer(
+                    mapped,
+                    mm_file_offset as usize + arg_start_offset,
+                    translator.target(),
+                )
+                .unwrap_or(0);
+                let arg_end = KernelParser::read_pointer(
+                    mapped,
+                    mm_file_offset as usize + arg_end_offset,
+                    translator.target(),
+                )
+                .unwrap_or(0);
 
                 debug!(
                     "[DEBUG] PID {}: arg_start=0x{:x}, arg_end=0x{:x}, len={}",
@@ -365,7 +404,11 @@ pub fn walk_process_list(
             // The tasks field is a list_head structure, so we need to read the next pointer
             // The list_head structure contains: next (first field) and prev (second field)
             // So the next pointer is at the same offset as the tasks field
-            let next_ptr = match KernelParser::read_u64(mapped, current_offset + tasks_offset) {
+            let next_ptr = match KernelParser::read_pointer(
+                mapped,
+                current_offset + tasks_offset,
+                translator.target(),
+            ) {
                 Some(n) => {
                     debug!("[DEBUG] Read next_ptr: 0x{:x} from file_offset 0x{:x} (current=0x{:x} + tasks_offset=0x{:x})",
                               n, current_offset + tasks_offset, current_offset, tasks_offset);
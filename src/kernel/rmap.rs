@@ -0,0 +1,307 @@
+//! Reverse mapping: from a physical address to the process and kernel virtual addresses that map it
+use crate::kernel::mm::ProcessMemory;
+use crate::kernel::page::vmemmap_range;
+use crate::kernel::vfs::VfsWalker;
+use crate::kernel::{KernelParser, ProcessInfo};
+use crate::memory::MemoryRegion;
+use crate::symbols::SymbolResolver;
+use crate::translation::MemoryTranslator;
+use std::collections::HashMap;
+
+// Macro for conditional warning output
+macro_rules! warn {
+    ($($arg:tt)*) => {
+        if std::env::var("LINMEMPARSER_VERBOSE").is_ok() {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+/// A VMA that maps a physical address
+#[derive(Debug, Clone)]
+pub struct PageOwner {
+    pub pid: i32,
+    pub comm: String,
+    pub address: u64, // User virtual address of the queried physical address
+    pub vma_start: u64,
+    pub vma_end: u64,
+    pub path: String, // Backing file, or "[anonymous]"
+}
+
+/// A VMA mappings point back to
+struct OwnerVma {
+    pid: i32,
+    comm: String,
+    start: u64,
+    end: u64,
+    path: String,
+}
+
+/// Everything known to map one physical address
+#[derive(Debug, Clone)]
+pub struct PhysicalOwners {
+    pub physical: u64,
+    pub region: Option<MemoryRegion>, // LiME region holding the page
+    pub kernel: Vec<u64>,             // Direct map, kernel image, vmalloc and module aliases
+    pub processes: Vec<PageOwner>,
+}
+
+/// One leaf page table entry
+struct Mapping {
+    physical: u64, // Page-aligned to `size`
+    size: u64,
+    virtual_addr: u64,
+    vma: Option<u32>, // Index into `vmas`, or None for a kernel page table entry
+}
+
+/// Index from physical pages to the virtual addresses that map them
+///
+/// Built by walking the page table of every process over each of its VMAs, so
+/// shared libraries, shared memory and copy-on-write pages report every owner,
+/// and the kernel page table over the vmalloc and module areas. The direct map
+/// and kernel image aliases are computed rather than stored.
+pub struct ReverseMap<'a> {
+    translator: &'a MemoryTranslator,
+    vmas: Vec<OwnerVma>,
+    mappings: Vec<Mapping>,           // Sorted by physical address
+    kernel_image: Option<(u64, u64)>, // [_text, _end)
+}
+
+/// Find the physical address of the kernel's top-level page table
+pub fn kernel_pgd_phys(
+    translator: &MemoryTranslator,
+    symbol_resolver: &SymbolResolver,
+) -> Option<u64> {
+    translator
+        .kernel_regions()
+        .pgd_symbols
+        .iter()
+        .find_map(|name| symbol_resolver.get_symbol_address(name))
+        .and_then(|pgd| translator.virtual_to_physical(pgd))
+}
+
+/// Kernel ranges without a fixed virtual-to-physical offset, translated through
+/// the kernel page table: vmalloc, vmemmap and modules
+pub fn kernel_page_table_ranges(
+    mapped: &[u8],
+    translator: &MemoryTranslator,
+    symbol_resolver: &SymbolResolver,
+) -> Vec<(u64, u64)> {
+    vec![
+        vmalloc_range(mapped, translator, symbol_resolver),
+        vmemmap_range(mapped, translator, symbol_resolver),
+        translator.kernel_regions().modules,
+    ]
+}
+
+impl<'a> ReverseMap<'a> {
+    /// Walk the kernel page table and the page tables of the given processes
+    pub fn build(
+        mapped: &'a [u8],
+        translator: &'a MemoryTranslator,
+        symbol_resolver: &'a SymbolResolver,
+        processes: &[ProcessInfo],
+    ) -> Self {
+        let vfs = match VfsWalker::new(mapped, translator, symbol_resolver) {
+            Ok(v) => Some(v),
+            Err(e) => {
+                warn!("[WARNING] File paths unavailable: {}", e);
+                None
+            }
+        };
+        let mut paths: HashMap<u64, String> = HashMap::new();
+        let mut vmas = Vec::new();
+        let mut mappings = Vec::new();
+
+        for process in processes {
+            // Kernel threads have no address space
+            let memory = match ProcessMemory::for_task(
+                mapped,
+                translator,
+                symbol_resolver,
+                process.offset,
+            ) {
+                Some(m) => m,
+                None => continue,
+            };
+            let process_vmas = match memory.vmas() {
+                Ok(v) => v,
+                Err(e) => {
+                    warn!("[WARNING] PID {}: VMAs unavailable: {}", process.pid, e);
+                    continue;
+                }
+            };
+
+            for vma in process_vmas {
+                let index = vmas.len() as u32;
+                let path = if vma.file == 0 {
+                    "[anonymous]".to_string()
+                } else {
+                    paths
+                        .entry(vma.file)
+                        .or_insert_with(|| {
+                            vfs.as_ref()
+                                .and_then(|v| v.file_path(vma.file))
+                                .unwrap_or_else(|| format!("{:#x}", vma.file))
+                        })
+                        .clone()
+                };
+                translator.walk_page_table(
+                    mapped,
+                    memory.pgd_phys,
+                    vma.start,
+                    vma.end,
+                    &mut |virtual_addr, physical, size| {
+                        mappings.push(Mapping {
+                            physical,
+                            size,
+                            virtual_addr,
+                            vma: Some(index),
+                        })
+                    },
+                );
+                vmas.push(OwnerVma {
+                    pid: process.pid,
+                    comm: process.comm.clone(),
+                    start: vma.start,
+                    end: vma.end,
+                    path,
+                });
+            }
+        }
+
+        match kernel_pgd_phys(translator, symbol_resolver) {
+            Some(pgd_phys) => {
+                let vmalloc = vmalloc_range(mapped, translator, symbol_resolver);
+                for (start, end) in [vmalloc, translator.kernel_regions().modules] {
+                    translator.walk_page_table(
+                        mapped,
+                        pgd_phys,
+                        start,
+                        end,
+                        &mut |virtual_addr, physical, size| {
+                            mappings.push(Mapping {
+                                physical,
+                                size,
+                                virtual_addr,
+                                vma: None,
+                            })
+                        },
+                    );
+                }
+            }
+            None => warn!("[WARNING] Kernel page table not found; vmalloc aliases unavailable"),
+        }
+        mappings.sort_by_key(|m| m.physical);
+
+        let kernel_image = symbol_resolver
+            .get_symbol_address("_text")
+            .zip(symbol_resolver.get_symbol_address("_end"));
+
+        ReverseMap {
+            translator,
+            vmas,
+            mappings,
+            kernel_image,
+        }
+    }
+
+    /// Collect everything that maps a physical address
+    pub fn lookup(&self, physical: u64) -> PhysicalOwners {
+        PhysicalOwners {
+            physical,
+            region: self.translator.physical_region(physical).cloned(),
+            kernel: self.kernel_aliases(physical),
+            processes: self.process_owners(physical),
+        }
+    }
+
+    /// Collect everything that maps the byte at a dump file offset
+    ///
+    /// Returns None when the offset lies outside every LiME region (headers or
+    /// trailing data).
+    pub fn lookup_file_offset(&self, file_offset: u64) -> Option<PhysicalOwners> {
+        Some(self.lookup(self.translator.file_offset_to_physical(file_offset)?))
+    }
+
+    /// List every process VMA that maps a physical address
+    pub fn process_owners(&self, physical: u64) -> Vec<PageOwner> {
+        let mut owners: Vec<PageOwner> = self
+            .mappings_of(physical)
+            .filter_map(|(mapping, address)| {
+                let vma = &self.vmas[mapping.vma? as usize];
+                // Large pages can extend past the VMA
+                if address < vma.start || address >= vma.end {
+                    return None;
+                }
+                Some(PageOwner {
+                    pid: vma.pid,
+                    comm: vma.comm.clone(),
+                    address,
+                    vma_start: vma.start,
+                    vma_end: vma.end,
+                    path: vma.path.clone(),
+                })
+            })
+            .collect();
+        owners.sort_by_key(|o| (o.pid, o.address));
+        owners
+    }
+
+    /// List the kernel virtual addresses of a physical address: the direct map, the
+    /// kernel image mapping when it falls inside the image, and vmalloc/module mappings
+    pub fn kernel_aliases(&self, physical: u64) -> Vec<u64> {
+        let mut aliases = Vec::new();
+        if let Some(address) = self.translator.physical_to_direct_map(physical) {
+            aliases.push(address);
+        }
+        if let Some((text, end)) = self.kernel_image {
+            let address = self.translator.physical_to_kernel_image(physical);
+            if address >= text && address < end {
+                aliases.push(address);
+            }
+        }
+        let mut vmalloc: Vec<u64> = self
+            .mappings_of(physical)
+            .filter(|(mapping, _)| mapping.vma.is_none())
+            .map(|(_, address)| address)
+            .collect();
+        vmalloc.sort_unstable();
+        // The AArch64 vmalloc walk also covers the kernel image
+        vmalloc.retain(|address| !aliases.contains(address));
+        aliases.extend(vmalloc);
+        aliases
+    }
+
+    /// Find the leaf entries of any page size covering a physical address, with the
+    /// virtual address each gives it
+    fn mappings_of(&self, physical: u64) -> impl Iterator<Item = (&Mapping, u64)> + '_ {
+        self.translator
+            .leaf_page_sizes()
+            .into_iter()
+            .flat_map(move |size| {
+                let base = physical & !(size - 1);
+                let first = self.mappings.partition_point(|m| m.physical < base);
+                self.mappings[first..]
+                    .iter()
+                    .take_while(move |m| m.physical == base)
+                    .filter(move |m| m.size == size)
+                    .map(move |m| (m, m.virtual_addr + (physical - base)))
+            })
+    }
+}
+
+/// Find [VMALLOC_START, VMALLOC_END), reading vmalloc_base when KASLR randomizes it
+fn vmalloc_range(
+    mapped: &[u8],
+    translator: &MemoryTranslator,
+    symbol_resolver: &SymbolResolver,
+) -> (u64, u64) {
+    let (default_start, default_end) = translator.kernel_regions().vmalloc;
+    let start = symbol_resolver
+        .get_symbol_address("vmalloc_base")
+        .and_then(|addr| KernelParser::read_virt_pointer(mapped, translator, addr))
+        .filter(|&base| base != 0)
+        .unwrap_or(default_start);
+    (start, start.saturating_add(default_end - default_start))
+}
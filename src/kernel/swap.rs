@@ -0,0 +1,589 @@
+//! Swap area helpers: the kernel's swap_info table and compressed swap (zram, zswap)
+use crate::compress::decompress_page;
+use crate::kernel::page::PageLayout;
+use crate::kernel::vfs::VfsWalker;
+use crate::kernel::xarray::XArrayWalker;
+use crate::kernel::{zsmalloc, KernelParser};
+use crate::symbols::SymbolResolver;
+use crate::translation::{MemoryTranslator, SwapEntry};
+use std::collections::{HashMap, HashSet};
+
+// Macro for conditional debug output
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        if std::env::var("LINMEMPARSER_DEBUG").is_ok() {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+// MAX_SWAPFILES upper bound (1 << MAX_SWAPFILES_SHIFT)
+const MAX_SWAP_TYPES: u64 = 32;
+// swap_info_struct.flags: SWP_USED
+const SWP_USED: u64 = 1 << 0;
+const PAGE_SIZE: u64 = 0x1000;
+
+// zram_table_entry: handle, then flags with the compressed size in the low bits
+// (ZRAM_FLAG_SHIFT is PAGE_SHIFT + 1, or 24 on old kernels)
+const ZRAM_SIZE_MASK: u64 = (1 << 13) - 1;
+// sizeof(zram_table_entry) without and with CONFIG_ZRAM_MEMORY_TRACKING
+const ZRAM_ENTRY_SIZES: [u64; 2] = [16, 24];
+const ZRAM_SAMPLE_ENTRIES: u64 = 64;
+
+// zswap_entry.swpentry is a generic swp_entry_t: the offset sits below
+// SWP_TYPE_SHIFT = BITS_PER_XA_VALUE - MAX_SWAPFILES_SHIFT
+const MAX_SWAPFILES_SHIFT: u64 = 5;
+// sizeof(struct xarray), for the per-64MB zswap trees of 6.10+
+const XARRAY_SIZE: u64 = 16;
+// Safety limit on zswap rb-tree walks
+const MAX_ZSWAP_ENTRIES: usize = 1 << 24;
+
+/// An active swap area from swap_info[]
+#[derive(Debug, Clone)]
+pub struct SwapArea {
+    pub swap_type: u8,
+    pub path: String, // Swap partition or swap file path
+    pub pages: u64,   // Usable pages in the area
+    pub bdev: u64,    // struct block_device of the swap device
+    pub zeromap: u64, // Bitmap of zero-filled slots that were never written (6.12+)
+}
+
+/// List the swap areas in use when the dump was taken
+///
+/// Returns an empty list when swap_info_struct offsets are unavailable.
+pub fn swap_areas(
+    mapped: &[u8],
+    translator: &MemoryTranslator,
+    symbol_resolver: &SymbolResolver,
+) -> Vec<SwapArea> {
+    let mut areas = Vec::new();
+    let table = match symbol_resolver.get_symbol_address("swap_info") {
+        Some(t) => t,
+        None => return areas,
+    };
+    let field = |name: &str| {
+        if symbol_resolver.has_field("swap_info_struct", name) {
+            symbol_resolver.get_struct_field_offset("swap_info_struct", name, None)
+        } else {
+            None
+        }
+    };
+    let (flags_off, file_off, pages_off) =
+        match (field("flags"), field("swap_file"), field("pages")) {
+            (Some(f), Some(s), Some(p)) => (f, s, p),
+            _ => {
+                debug!("[DEBUG] swap_info_struct offsets not available");
+                return areas;
+            }
+        };
+    let count = symbol_resolver
+        .get_symbol_address("nr_swapfiles")
+        .and_then(|addr| KernelParser::read_virt_u32(mapped, translator, addr))
+        .map_or(MAX_SWAP_TYPES, |n| (n as u64).min(MAX_SWAP_TYPES));
+    let vfs = VfsWalker::new(mapped, translator, symbol_resolver).ok();
+
+    for swap_type in 0..count {
+        let info = match KernelParser::read_virt_pointer(
+            mapped,
+            translator,
+            table + swap_type * translator.pointer_size(),
+        ) {
+            Some(i) if i != 0 => i,
+            _ => continue,
+        };
+        let flags =
+            KernelParser::read_virt_pointer(mapped, translator, info + flags_off).unwrap_or(0);
+        if flags & SWP_USED == 0 {
+            continue;
+        }
+        let file =
+            KernelParser::read_virt_pointer(mapped, translator, info + file_off).unwrap_or(0);
+        let pointer = |name: &str| {
+            field(name)
+                .and_then(|off| KernelParser::read_virt_pointer(mapped, translator, info + off))
+                .unwrap_or(0)
+        };
+        areas.push(SwapArea {
+            swap_type: swap_type as u8,
+            path: vfs
+                .as_ref()
+                .and_then(|v| v.file_path(file))
+                .unwrap_or_else(|| format!("{:#x}", file)),
+            pages: KernelParser::read_virt_u32(mapped, translator, info + pages_off).unwrap_or(0)
+                as u64,
+            bdev: pointer("bdev"),
+            zeromap: pointer("zeromap"),
+        });
+    }
+    areas
+}
+
+/// A zram device used as a swap area
+struct ZramDevice {
+    swap_type: u8,
+    table: u64,      // zram->table
+    entry_size: u64, // sizeof(struct zram_table_entry)
+}
+
+/// A page held by zswap
+struct ZswapObject {
+    handle: u64, // zsmalloc handle, or the fill value of a same-filled page
+    length: u64, // Compressed length; 0 for same-filled pages
+    algorithm: usize,
+}
+
+/// Reader for swapped-out pages that are still in kernel memory
+///
+/// zswap pages are compressed in a zsmalloc pool in front of the swap device; zram
+/// devices keep every slot compressed in memory. Offsets are resolved up front so
+/// the translator can own the reader.
+pub struct CompressedSwap {
+    pages: PageLayout,
+    zram: Vec<ZramDevice>,
+    zswap: HashMap<(u8, u64), ZswapObject>,
+    zswap_algorithms: Vec<String>, // Compressor names of zswap pools
+    zeromaps: Vec<(u8, u64)>,      // (swap type, zeromap bitmap)
+}
+
+impl CompressedSwap {
+    /// Find the zram devices and index the zswap trees of the given swap areas
+    pub fn new(
+        mapped: &[u8],
+        translator: &MemoryTranslator,
+        symbol_resolver: &SymbolResolver,
+        areas: &[SwapArea],
+    ) -> Self {
+        let mut swap = CompressedSwap {
+            pages: PageLayout::new(mapped, translator, symbol_resolver),
+            zram: Vec::new(),
+            zswap: HashMap::new(),
+            zswap_algorithms: Vec::new(),
+            zeromaps: areas
+                .iter()
+                .filter(|a| a.zeromap != 0)
+                .map(|a| (a.swap_type, a.zeromap))
+                .collect(),
+        };
+        for area in areas {
+            if let Some(device) = find_zram(mapped, translator, symbol_resolver, area) {
+                debug!(
+                    "[DEBUG] Swap type {} is zram (table {:#x}, {}-byte entries)",
+                    area.swap_type, device.table, device.entry_size
+                );
+                swap.zram.push(device);
+            }
+        }
+        swap.index_zswap(mapped, translator, symbol_resolver);
+        swap
+    }
+
+    /// Check whether any compressed swap was found
+    pub fn is_empty(&self) -> bool {
+        self.zram.is_empty() && self.zswap.is_empty() && self.zeromaps.is_empty()
+    }
+
+    /// Number of zram swap devices
+    pub fn zram_devices(&self) -> usize {
+        self.zram.len()
+    }
+
+    /// Number of pages held by zswap
+    pub fn zswap_pages(&self) -> usize {
+        self.zswap.len()
+    }
+
+    /// Check whether a swap entry can be read from kernel memory
+    pub fn contains(&self, mapped: &[u8], translator: &MemoryTranslator, entry: SwapEntry) -> bool {
+        self.is_zero_slot(mapped, translator, entry)
+            || self.zswap.contains_key(&(entry.swap_type, entry.offset))
+            || self.zram.iter().any(|d| d.swap_type == entry.swap_type)
+    }
+
+    /// Read and decompress a swapped-out page held in zswap or zram
+    pub fn read_page(
+        &self,
+        mapped: &[u8],
+        translator: &MemoryTranslator,
+        entry: SwapEntry,
+    ) -> Option<Vec<u8>> {
+        if self.is_zero_slot(mapped, translator, entry) {
+            return Some(vec![0; PAGE_SIZE as usize]);
+        }
+        if let Some(object) = self.zswap.get(&(entry.swap_type, entry.offset)) {
+            let algorithm = self.zswap_algorithms.get(object.algorithm);
+            return self.read_object(
+                mapped,
+                translator,
+                object.handle,
+                object.length,
+                algorithm.map(String::as_str),
+            );
+        }
+
+        let device = self.zram.iter().find(|d| d.swap_type == entry.swap_type)?;
+        let slot = device.table + entry.offset * device.entry_size;
+        let handle = KernelParser::read_virt_pointer(mapped, translator, slot)?;
+        let flags =
+            KernelParser::read_virt_pointer(mapped, translator, slot + translator.pointer_size())?;
+        // zram picks its compressor per device; the decoder is detected from the data.
+        // Slots written back to a backing device (CONFIG_ZRAM_WRITEBACK) also have a
+        // zero size and are not told apart from same-filled pages.
+        self.read_object(mapped, translator, handle, flags & ZRAM_SIZE_MASK, None)
+    }
+
+    /// Read one zsmalloc object, expanding same-filled pages and decompressing the rest
+    fn read_object(
+        &self,
+        mapped: &[u8],
+        translator: &MemoryTranslator,
+        handle: u64,
+        length: u64,
+        algorithm: Option<&str>,
+    ) -> Option<Vec<u8>> {
+        if length == 0 {
+            // Same-filled page: the handle holds the repeated word
+            let target = translator.target();
+            let word = if target.big_endian {
+                handle.to_be_bytes()[8 - target.pointer_size..].to_vec()
+            } else {
+                handle.to_le_bytes()[..target.pointer_size].to_vec()
+            };
+            return Some(word.repeat(PAGE_SIZE as usize / target.pointer_size));
+        }
+        let data = zsmalloc::read_object(mapped, translator, &self.pages, handle, length)?;
+        if length == PAGE_SIZE {
+            // Incompressible pages are stored as is
+            return Some(data);
+        }
+        decompress_page(algorithm, &data, PAGE_SIZE as usize)
+    }
+
+    /// Check the zeromap bit of a slot (6.12+ skips writing zero-filled pages)
+    fn is_zero_slot(&self, mapped: &[u8], translator: &MemoryTranslator, entry: SwapEntry) -> bool {
+        self.zeromaps
+            .iter()
+            .find(|(t, _)| *t == entry.swap_type)
+            .and_then(|(_, bitmap)| {
+                let bits = translator.pointer_size() * 8;
+                KernelParser::read_virt_pointer(
+                    mapped,
+                    translator,
+                    bitmap + entry.offset / bits * bits / 8,
+                )
+                .map(|word| word & (1 << (entry.offset % bits)) != 0)
+            })
+            .unwrap_or(false)
+    }
+
+    /// Index every zswap entry by (swap type, offset)
+    ///
+    /// Handles the single rb-tree per swap type (before 6.8), the array of rb-trees
+    /// per 64MB of swap (6.8-6.9) and the array of xarrays (6.10+).
+    fn index_zswap(
+        &mut self,
+        mapped: &[u8],
+        translator: &MemoryTranslator,
+        symbol_resolver: &SymbolResolver,
+    ) {
+        let trees = match symbol_resolver.get_symbol_address("zswap_trees") {
+            Some(t) => t,
+            None => return,
+        };
+        let field = |s: &str, f: &str| {
+            if symbol_resolver.has_field(s, f) {
+                symbol_resolver.get_struct_field_offset(s, f, None)
+            } else {
+                None
+            }
+        };
+        let (length_off, handle_off, pool_off, tfm_off) = match (
+            field("zswap_entry", "length"),
+            field("zswap_entry", "handle"),
+            field("zswap_entry", "pool"),
+            field("zswap_pool", "tfm_name"),
+        ) {
+            (Some(l), Some(h), Some(p), Some(t)) => (l, h, p, t),
+            _ => {
+                debug!("[DEBUG] zswap_entry offsets not available; zswap pages unreadable");
+                return;
+            }
+        };
+        let rb_tree = field("zswap_tree", "rbroot").zip(field("zswap_entry", "rbnode"));
+        // Before 6.6 entries were keyed by a pgoff_t offset
+        let key = field("zswap_entry", "swpentry")
+            .map(|off| (off, (1u64 << swp_type_shift(translator)) - 1))
+            .or_else(|| field("zswap_entry", "offset").map(|off| (off, u64::MAX)));
+        let tree_counts = symbol_resolver.get_symbol_address("nr_zswap_trees");
+        let tree_size = if rb_tree.is_some() {
+            symbol_resolver
+                .get_struct_size("zswap_tree")
+                .map_or(16, |s| s as u64)
+        } else {
+            symbol_resolver
+                .get_struct_size("xarray")
+                .map_or(XARRAY_SIZE, |s| s as u64)
+        };
+        let xarray = XArrayWalker::new(mapped, translator, symbol_resolver);
+        let read = |addr: u64| KernelParser::read_virt_pointer(mapped, translator, addr);
+        let mut pools: HashMap<u64, usize> = HashMap::new();
+
+        for swap_type in 0..MAX_SWAP_TYPES {
+            let base = match read(trees + swap_type * translator.pointer_size()) {
+                Some(b) if b != 0 => b,
+                _ => continue,
+            };
+            let count = match tree_counts {
+                Some(counts) => {
+                    KernelParser::read_virt_u32(mapped, translator, counts + swap_type * 4)
+                        .unwrap_or(0) as u64
+                }
+                None => 1,
+            };
+
+            let mut entries = Vec::new();
+            for tree in (0..count).map(|i| base + i * tree_size) {
+                match (rb_tree, key) {
+                    (Some((root_off, node_off)), Some((key_off, key_mask))) => {
+                        for node in rb_inorder(mapped, translator, read(tree + root_off)) {
+                            let entry = node.wrapping_sub(node_off);
+                            if let Some(k) = read(entry + key_off) {
+                                entries.push((k & key_mask, entry));
+                            }
+                        }
+                    }
+                    (None, _) => entries.extend(xarray.entries(tree)),
+                    _ => {}
+                }
+            }
+
+            for (offset, entry) in entries {
+                let length = KernelParser::read_virt_u32(mapped, translator, entry + length_off)
+                    .unwrap_or(0) as u64;
+                let pool = read(entry + pool_off).unwrap_or(0);
+                let next = self.zswap_algorithms.len();
+                let algorithm = *pools.entry(pool).or_insert_with(|| {
+                    let name =
+                        KernelParser::read_virt_cstring(mapped, translator, pool + tfm_off, 128)
+                            .unwrap_or_default();
+                    self.zswap_algorithms.push(name);
+                    next
+                });
+                self.zswap.insert(
+                    (swap_type as u8, offset),
+                    ZswapObject {
+                        handle: read(entry + handle_off).unwrap_or(0),
+                        length,
+                        algorithm,
+                    },
+                );
+            }
+        }
+    }
+}
+
+/// Find the zram device behind a swap area: bdev->bd_disk->private_data
+fn find_zram(
+    mapped: &[u8],
+    translator: &MemoryTranslator,
+    symbol_resolver: &SymbolResolver,
+    area: &SwapArea,
+) -> Option<ZramDevice> {
+    let offset = |s: &str, f: &str| symbol_resolver.get_struct_field_offset(s, f, None);
+    let read = |addr: u64| KernelParser::read_virt_pointer(mapped, translator, addr);
+    if area.bdev == 0 {
+        return None;
+    }
+    let disk = read(area.bdev + offset("block_device", "bd_disk")?)?;
+    let name = KernelParser::read_virt_cstring(
+        mapped,
+        translator,
+        disk + offset("gendisk", "disk_name")?,
+        32,
+    )?;
+    if !name.starts_with("zram") {
+        return None;
+    }
+    // struct zram starts with the slot table (zram is usually a module, so its
+    // layout is not in the vmlinux type information)
+    let zram = read(disk + offset("gendisk", "private_data")?)?;
+    let table = read(zram)?;
+    if table == 0 {
+        return None;
+    }
+
+    let entry_size = symbol_resolver
+        .get_struct_size("zram_table_entry")
+        .map(|s| s as u64)
+        .or_else(|| zram_entry_size(mapped, translator, table))?;
+
+    Some(ZramDevice {
+        swap_type: area.swap_type,
+        table,
+        entry_size,
+    })
+}
+
+/// Pick the zram_table_entry stride under which the first slots look like zram
+/// entries: a size of at most a page, and a kernel pointer for stored objects
+fn zram_entry_size(mapped: &[u8], translator: &MemoryTranslator, table: u64) -> Option<u64> {
+    let read = |addr: u64| KernelParser::read_virt_pointer(mapped, translator, addr);
+    ZRAM_ENTRY_SIZES.into_iter().find(|&size| {
+        (0..ZRAM_SAMPLE_ENTRIES).all(|i| {
+            let slot = table + i * size;
+            match (read(slot), read(slot + translator.pointer_size())) {
+                (Some(handle), Some(flags)) => {
+                    let length = flags & ZRAM_SIZE_MASK;
+                    length <= PAGE_SIZE
+                        && (length == 0 || handle >= translator.kernel_space_start())
+                }
+                _ => false,
+            }
+        })
+    })
+}
+
+/// Bit position of the swap type in a generic swp_entry_t
+///
+/// BITS_PER_XA_VALUE is one less than the bits in a long.
+fn swp_type_shift(translator: &MemoryTranslator) -> u64 {
+    translator.pointer_size() * 8 - 1 - MAX_SWAPFILES_SHIFT
+}
+
+/// Walk an rb-tree in order, returning the rb_node addresses
+fn rb_inorder(mapped: &[u8], translator: &MemoryTranslator, root: Option<u64>) -> Vec<u64> {
+    // struct rb_node: __rb_parent_color, rb_right, rb_left
+    let word = translator.pointer_size();
+    let child = |node: u64, off: u64| {
+        KernelParser::read_virt_pointer(mapped, translator, node + off).filter(|&c| c != 0)
+    };
+    let mut nodes = Vec::new();
+    let mut stack = Vec::new();
+    // A node seen twice means a corrupt or cyclic tree: treat it as a leaf edge
+    let mut visited = HashSet::new();
+    let mut current = root.filter(|&r| r != 0);
+    while nodes.len() < MAX_ZSWAP_ENTRIES {
+        while let Some(node) = current.filter(|&n| visited.insert(n)) {
+            stack.push(node);
+            current = child(node, 2 * word);
+        }
+        let node = match stack.pop() {
+            Some(n) => n,
+            None => break,
+        };
+        nodes.push(node);
+        current = child(node, word);
+    }
+    nodes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::MemoryRegion;
+    use crate::testutil::{Dump, DIRECT_MAP};
+
+    #[test]
+    fn test_swap_areas_lists_used_entries() {
+        let mut symbols = SymbolResolver::new();
+        symbols.add_struct(
+            "swap_info_struct",
+            0x100,
+            &[
+                ("flags", 0),
+                ("swap_file", 0x10),
+                ("pages", 0x18),
+                ("bdev", 0x20),
+            ],
+        );
+        symbols.add_symbol("swap_info".to_string(), DIRECT_MAP + 0x100);
+        symbols.add_symbol("nr_swapfiles".to_string(), DIRECT_MAP + 0x180);
+
+        // swap_info[0] is in use, swap_info[1] was swapoff'd and swap_info[2]
+        // is past nr_swapfiles
+        let mut dump = Dump::new(0x1000);
+        dump.bytes(0x180, &2u32.to_le_bytes());
+        for (slot, info, flags) in [(0, 0x200, SWP_USED), (1, 0x300, 0), (2, 0x400, SWP_USED)] {
+            dump.ptr(0x100 + slot * 8, info);
+            dump.word(info, flags);
+            dump.ptr(info + 0x10, 0x800);
+            dump.bytes(info + 0x18, &0x3ffffu32.to_le_bytes());
+            dump.ptr(info + 0x20, 0x900);
+        }
+        let translator = dump.translator();
+
+        let areas = swap_areas(&dump.0, &translator, &symbols);
+        assert_eq!(areas.len(), 1);
+        assert_eq!(areas[0].swap_type, 0);
+        assert_eq!(areas[0].pages, 0x3ffff);
+        assert_eq!(areas[0].bdev, DIRECT_MAP + 0x900);
+        assert_eq!(areas[0].zeromap, 0);
+        // Without dentry/vfsmount offsets the struct file address stands in for the path
+        assert_eq!(areas[0].path, format!("{:#x}", DIRECT_MAP + 0x800));
+    }
+
+    #[test]
+    fn test_rb_inorder_stops_on_cycles() {
+        // rb_node at 0x100 (root), 0x200 (left) and 0x300 (right)
+        let mut mapped = vec![0u8; 0x400];
+        let mut link = |node: usize, off: usize, to: u64| {
+            mapped[node + off..node + off + 8].copy_from_slice(&(DIRECT_MAP + to).to_le_bytes());
+        };
+        link(0x100, 16, 0x200);
+        link(0x100, 8, 0x300);
+        let translator = MemoryTranslator::new(vec![MemoryRegion {
+            start: 0,
+            end: 0x3ff,
+            file_offset: 0,
+        }]);
+        let root = Some(DIRECT_MAP + 0x100);
+        assert_eq!(
+            rb_inorder(&mapped, &translator, root),
+            vec![DIRECT_MAP + 0x200, DIRECT_MAP + 0x100, DIRECT_MAP + 0x300]
+        );
+
+        // The left child's rb_left points back at the root and the right
+        // child's rb_right at itself
+        let mut cyclic = mapped.clone();
+        cyclic[0x210..0x218].copy_from_slice(&(DIRECT_MAP + 0x100).to_le_bytes());
+        cyclic[0x308..0x310].copy_from_slice(&(DIRECT_MAP + 0x300).to_le_bytes());
+        assert_eq!(
+            rb_inorder(&cyclic, &translator, root),
+            vec![DIRECT_MAP + 0x200, DIRECT_MAP + 0x100, DIRECT_MAP + 0x300]
+        );
+        assert!(rb_inorder(&mapped, &translator, Some(0)).is_empty());
+    }
+
+    #[test]
+    fn test_zram_stride_and_swap_type_shift_follow_the_target() {
+        use crate::kernel::target::Target;
+        use crate::translation::aarch64::Aarch64Layout;
+        use crate::translation::Architecture;
+
+        // AArch64 with 39-bit VAs: zsmalloc handles live in the linear map at
+        // 0xffff_ff80_0000_0000
+        let layout = Aarch64Layout::new(39, 12, true);
+        let linear = layout.page_offset;
+        let mut translator = MemoryTranslator::new(vec![MemoryRegion {
+            start: 0,
+            end: 0x1fff,
+            file_offset: 0,
+        }]);
+        translator.set_architecture(Architecture::Aarch64(layout));
+
+        // 24-byte slots (CONFIG_ZRAM_MEMORY_TRACKING): handle, flags, ac_time
+        let mut mapped = vec![0u8; 0x2000];
+        for i in 0..ZRAM_SAMPLE_ENTRIES as usize {
+            let slot = i * 24;
+            let handle = linear + 0x10_0000 + i as u64 * 0x40;
+            mapped[slot..slot + 8].copy_from_slice(&handle.to_le_bytes());
+            mapped[slot + 8..slot + 16].copy_from_slice(&(0x600u64 | 1 << 13).to_le_bytes());
+            mapped[slot + 16..slot + 24].copy_from_slice(&0x6000_0000u64.to_le_bytes());
+        }
+        assert_eq!(zram_entry_size(&mapped, &translator, linear), Some(24));
+        assert_eq!(swp_type_shift(&translator), 58);
+
+        let mut be32 = MemoryTranslator::new(Vec::new());
+        be32.set_target(Target::from_pointer_type(4, "big").unwrap());
+        assert_eq!(swp_type_shift(&be32), 26);
+    }
+}
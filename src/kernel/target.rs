@@ -0,0 +1,229 @@
+//! Target data model: byte order and pointer size of the dumped kernel
+
+// ELF identification (e_ident)
+const ELF_MAGIC: &[u8; 4] = b"\x7fELF";
+const ELFCLASS32: u8 = 1;
+const ELFCLASS64: u8 = 2;
+const ELFDATA2LSB: u8 = 1;
+const ELFDATA2MSB: u8 = 2;
+
+// Architecture part of a GNU target triplet, with pointer size and byte order.
+// Longer names come first so "aarch64_be" is not taken for "aarch64".
+const TRIPLET_ARCHES: [(&str, usize, bool); 19] = [
+    ("aarch64_be", 8, true),
+    ("aarch64", 8, false),
+    ("x86_64", 8, false),
+    ("riscv64", 8, false),
+    ("powerpc64le", 8, false),
+    ("powerpc64", 8, true),
+    ("mips64el", 8, false),
+    ("mips64", 8, true),
+    ("s390x", 8, true),
+    ("i386", 4, false),
+    ("i486", 4, false),
+    ("i586", 4, false),
+    ("i686", 4, false),
+    ("armeb", 4, true),
+    ("arm", 4, false),
+    ("mipsel", 4, false),
+    ("mips", 4, true),
+    ("powerpc", 4, true),
+    ("riscv32", 4, false),
+];
+
+/// Byte order and pointer size of the dumped kernel
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Target {
+    pub big_endian: bool,
+    pub pointer_size: usize, // 4 or 8
+}
+
+impl Default for Target {
+    /// 64-bit little-endian (x86-64, AArch64)
+    fn default() -> Self {
+        Target {
+            big_endian: false,
+            pointer_size: 8,
+        }
+    }
+}
+
+impl Target {
+    /// Read the data model from an ELF header (EI_CLASS and EI_DATA)
+    pub fn from_elf_header(header: &[u8]) -> Option<Self> {
+        if !header.starts_with(ELF_MAGIC) || header.len() < 6 {
+            return None;
+        }
+        let pointer_size = match header[4] {
+            ELFCLASS32 => 4,
+            ELFCLASS64 => 8,
+            _ => return None,
+        };
+        let big_endian = match header[5] {
+            ELFDATA2LSB => false,
+            ELFDATA2MSB => true,
+            _ => return None,
+        };
+        Some(Target {
+            big_endian,
+            pointer_size,
+        })
+    }
+
+    /// Read the data model from dwarf2json's "pointer" base type
+    pub fn from_pointer_type(size: u64, endian: &str) -> Option<Self> {
+        let big_endian = match endian {
+            "little" => false,
+            "big" => true,
+            _ => return None,
+        };
+        matches!(size, 4 | 8).then_some(Target {
+            big_endian,
+            pointer_size: size as usize,
+        })
+    }
+
+    /// Guess the data model from the compiler triplet in a linux_banner
+    ///
+    /// Cross-compiled kernels name their compiler "<arch>-linux-gnu-gcc" (or
+    /// similar); native distribution builds usually just say "gcc" and give nothing.
+    pub fn from_banner(banner: &str) -> Option<Self> {
+        banner
+            .split(|c: char| c.is_whitespace() || c == '(' || c == ')')
+            .filter(|word| word.contains("-linux"))
+            .find_map(|word| {
+                let arch = word.split('-').next()?;
+                TRIPLET_ARCHES
+                    .iter()
+                    .find(|(name, _, _)| arch.starts_with(name))
+                    .map(|&(_, pointer_size, big_endian)| Target {
+                        big_endian,
+                        pointer_size,
+                    })
+            })
+    }
+
+    pub fn u16(self, bytes: [u8; 2]) -> u16 {
+        if self.big_endian {
+            u16::from_be_bytes(bytes)
+        } else {
+            u16::from_le_bytes(bytes)
+        }
+    }
+
+    pub fn u32(self, bytes: [u8; 4]) -> u32 {
+        if self.big_endian {
+            u32::from_be_bytes(bytes)
+        } else {
+            u32::from_le_bytes(bytes)
+        }
+    }
+
+    pub fn u64(self, bytes: [u8; 8]) -> u64 {
+        if self.big_endian {
+            u64::from_be_bytes(bytes)
+        } else {
+            u64::from_le_bytes(bytes)
+        }
+    }
+
+    /// Decode a pointer from the first `pointer_size` bytes of a slice
+    pub fn pointer(self, bytes: &[u8]) -> Option<u64> {
+        if self.pointer_size == 4 {
+            Some(u64::from(self.u32(bytes.get(..4)?.try_into().ok()?)))
+        } else {
+            Some(self.u64(bytes.get(..8)?.try_into().ok()?))
+        }
+    }
+
+    /// Human-readable name, e.g. "64-bit little-endian"
+    pub fn describe(self) -> String {
+        format!(
+            "{}-bit {}-endian",
+            self.pointer_size * 8,
+            if self.big_endian { "big" } else { "little" }
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_target_detection_and_decoding() {
+        let be32 = Target {
+            big_endian: true,
+            pointer_size: 4,
+        };
+        assert_eq!(Target::from_elf_header(b"\x7fELF\x01\x02\x01"), Some(be32));
+        assert_eq!(
+            Target::from_elf_header(b"\x7fELF\x02\x01\x01"),
+            Some(Target::default())
+        );
+        assert_eq!(Target::from_elf_header(b"EMiL"), None);
+        assert_eq!(Target::from_pointer_type(4, "big"), Some(be32));
+
+        let banner = "Linux version 5.10.110 (builder@host) (arm-linux-gnueabihf-gcc (GCC) 9.3.0, GNU ld 2.34) #1 SMP";
+        assert_eq!(
+            Target::from_banner(banner).map(|t| (t.pointer_size, t.big_endian)),
+            Some((4, false))
+        );
+        let banner = "Linux version 6.1.0 (root@host) (aarch64_be-linux-gnu-gcc 12.2.0) #1";
+        assert_eq!(
+            Target::from_banner(banner),
+            Some(Target::from_pointer_type(8, "big").unwrap())
+        );
+        assert_eq!(
+            Target::from_banner("Linux version 6.8.0 (buildd@lcy02) (x86_64-linux-gnu-gcc-13 (Ubuntu 13.2.0) 13.2.0)"),
+            Some(Target::default())
+        );
+        assert_eq!(
+            Target::from_banner("Linux version 6.8.0 (gcc (Debian 12.2.0) 12.2.0)"),
+            None
+        );
+
+        assert_eq!(be32.u32([0x12, 0x34, 0x56, 0x78]), 0x1234_5678);
+        assert_eq!(Target::default().u32([0x12, 0x34, 0x56, 0x78]), 0x7856_3412);
+        assert_eq!(be32.u16([0xab, 0xcd]), 0xabcd);
+    }
+
+    #[test]
+    fn test_reads_follow_the_translator_target() {
+        use crate::kernel::KernelParser;
+        use crate::memory::MemoryRegion;
+        use crate::translation::MemoryTranslator;
+
+        let mapped = [0x12, 0x34, 0x56, 0x78, 0x9a, 0xbc, 0xde, 0xf0];
+        let be32 = Target::from_pointer_type(4, "big").unwrap();
+        assert_eq!(be32.pointer(&mapped), Some(0x1234_5678));
+        assert_eq!(
+            Target::default().pointer(&mapped),
+            Some(0xf0de_bc9a_7856_3412)
+        );
+        assert_eq!(Target::default().pointer(&mapped[..4]), None);
+
+        // Two translators over the same dump do not share a data model
+        let regions = vec![MemoryRegion {
+            start: 0,
+            end: 0xfff,
+            file_offset: 0,
+        }];
+        let mut translator = MemoryTranslator::new(regions.clone());
+        translator.set_target(be32);
+        let default = MemoryTranslator::new(regions);
+        let address = 0xffff_8880_0000_0000;
+        assert_eq!(
+            KernelParser::read_virt_pointer(&mapped, &translator, address),
+            Some(0x1234_5678)
+        );
+        assert_eq!(
+            KernelParser::read_virt_u16(&mapped, &translator, address + 4),
+            Some(0x9abc)
+        );
+        assert_eq!(
+            KernelParser::read_virt_pointer(&mapped, &default, address),
+            Some(0xf0de_bc9a_7856_3412)
+        );
+    }
+}
@@ -0,0 +1,431 @@
+//! Kernel text helpers: vmlinux image parsing, runtime patch sites and trampoline decoding
+use crate::error::AnalysisError;
+use crate::symbols::SymbolResolver;
+use goblin::elf::section_header::SHT_NOBITS;
+use goblin::elf::Elf;
+use memmap2::Mmap;
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::path::Path;
+
+// Longest instruction sequence the kernel patches in place
+const MAX_PATCH_LENGTH: u64 = 64;
+
+/// A function symbol from the vmlinux symbol table (link-time address)
+#[derive(Debug, Clone)]
+pub struct KernelFunction {
+    pub name: String,
+    pub address: u64,
+    pub size: u64,
+}
+
+/// An executable section of the vmlinux image
+#[derive(Debug, Clone)]
+struct TextSection {
+    address: u64,
+    offset: u64,
+    size: u64,
+}
+
+/// How a patch table records the address of each site
+#[derive(Clone, Copy)]
+enum SiteEncoding {
+    Relative, // s32 relative to the field itself
+    Absolute, // u64 link-time address
+}
+
+/// A section listing code the kernel rewrites at boot or runtime
+struct PatchTable {
+    section: &'static str,
+    entry_size: usize,
+    encoding: SiteEncoding,
+    length: u64,                 // Bytes patched at each site
+    length_field: Option<usize>, // Per-entry u8 length, when the table records one
+}
+
+/// Fixed-layout patch tables: jump labels, static calls, retpoline/return thunks,
+/// IBT seals, ftrace call sites, SMP lock prefixes and paravirt sites
+const PATCH_TABLES: [PatchTable; 8] = [
+    PatchTable {
+        section: ".static_call_sites",
+        entry_size: 8,
+        encoding: SiteEncoding::Relative,
+        length: 5,
+        length_field: None,
+    },
+    PatchTable {
+        section: ".retpoline_sites",
+        entry_size: 4,
+        encoding: SiteEncoding::Relative,
+        length: 6,
+        length_field: None,
+    },
+    PatchTable {
+        section: ".return_sites",
+        entry_size: 4,
+        encoding: SiteEncoding::Relative,
+        length: 5,
+        length_field: None,
+    },
+    PatchTable {
+        section: ".call_sites",
+        entry_size: 4,
+        encoding: SiteEncoding::Relative,
+        length: 5,
+        length_field: None,
+    },
+    PatchTable {
+        section: ".ibt_endbr_seal",
+        entry_size: 4,
+        encoding: SiteEncoding::Relative,
+        length: 4,
+        length_field: None,
+    },
+    PatchTable {
+        section: ".smp_locks",
+        entry_size: 4,
+        encoding: SiteEncoding::Relative,
+        length: 1,
+        length_field: None,
+    },
+    PatchTable {
+        section: "__mcount_loc",
+        entry_size: 8,
+        encoding: SiteEncoding::Absolute,
+        length: 5,
+        length_field: None,
+    },
+    PatchTable {
+        section: ".parainstructions",
+        entry_size: 16,
+        encoding: SiteEncoding::Absolute,
+        length: 0,
+        length_field: Some(9),
+    },
+];
+
+/// A parsed vmlinux image
+///
+/// Holds the function symbols, executable section contents and the set of byte
+/// ranges the kernel legitimately rewrites (alternatives, static keys, ftrace, ...).
+pub struct VmlinuxImage {
+    _file: File,
+    data: Mmap,
+    functions: Vec<KernelFunction>,
+    sections: Vec<TextSection>,
+    symbols: BTreeMap<String, u64>,
+    // Patch site start -> end (link-time addresses)
+    patch_sites: BTreeMap<u64, u64>,
+}
+
+impl VmlinuxImage {
+    /// Map and parse a vmlinux ELF image
+    ///
+    /// Struct layouts that vary between versions (alt_instr, jump_entry) are taken
+    /// from the loaded dwarf2json profile when available.
+    pub fn open(path: &Path, symbol_resolver: &SymbolResolver) -> Result<Self, AnalysisError> {
+        let file = File::open(path)?;
+        let data = unsafe { Mmap::map(&file)? };
+
+        let (functions, sections, symbols, patch_sites) = {
+            let elf = Elf::parse(&data).map_err(|e| {
+                AnalysisError::ParseError(format!(
+                    "{}: not a valid ELF image: {}",
+                    path.display(),
+                    e
+                ))
+            })?;
+
+            let mut symbols = BTreeMap::new();
+            let mut functions = Vec::new();
+            for sym in elf.syms.iter() {
+                let name = match elf.strtab.get_at(sym.st_name) {
+                    Some(n) if !n.is_empty() => n,
+                    _ => continue,
+                };
+                if sym.is_function() && sym.st_size > 0 {
+                    functions.push(KernelFunction {
+                        name: name.to_string(),
+                        address: sym.st_value,
+                        size: sym.st_size,
+                    });
+                }
+                symbols.insert(name.to_string(), sym.st_value);
+            }
+            if functions.is_empty() {
+                return Err(AnalysisError::ParseError(format!(
+                    "{}: no function symbols (stripped image?)",
+                    path.display()
+                )));
+            }
+            functions.sort_by_key(|f| f.address);
+
+            // Only sections inside [_stext, _etext) stay mapped after boot
+            let stext = symbols.get("_stext").copied().unwrap_or(0);
+            let etext = symbols.get("_etext").copied().unwrap_or(u64::MAX);
+            let sections: Vec<TextSection> = elf
+                .section_headers
+                .iter()
+                .filter(|sh| sh.is_executable() && sh.sh_type != SHT_NOBITS)
+                .filter(|sh| sh.sh_addr >= stext && sh.sh_addr + sh.sh_size <= etext)
+                .map(|sh| TextSection {
+                    address: sh.sh_addr,
+                    offset: sh.sh_offset,
+                    size: sh.sh_size,
+                })
+                .collect();
+
+            let section_data = |name: &str| {
+                elf.section_headers
+                    .iter()
+                    .find(|sh| elf.shdr_strtab.get_at(sh.sh_name) == Some(name))
+                    .filter(|sh| sh.sh_type != SHT_NOBITS)
+                    .and_then(|sh| {
+                        let start = sh.sh_offset as usize;
+                        data.get(start..start + sh.sh_size as usize)
+                            .map(|bytes| (sh.sh_addr, bytes))
+                    })
+            };
+
+            let mut patch_sites = BTreeMap::new();
+            let mut add_site = |start: u64, length: u64| {
+                if length > 0 && length <= MAX_PATCH_LENGTH {
+                    let end = patch_sites.entry(start).or_insert(start);
+                    *end = (*end).max(start + length);
+                }
+            };
+
+            for table in PATCH_TABLES.iter() {
+                if let Some((base, bytes)) = section_data(table.section) {
+                    for (i, entry) in bytes.chunks_exact(table.entry_size).enumerate() {
+                        let site = decode_site(
+                            table.encoding,
+                            base + (i * table.entry_size) as u64,
+                            entry,
+                        );
+                        let length = match table.length_field {
+                            Some(field) => entry[field] as u64,
+                            None => table.length,
+                        };
+                        add_site(site, length);
+                    }
+                }
+            }
+
+            // Jump labels: relative entries (5.0+) or absolute code/target/key triples
+            if let Some((base, bytes)) = section_data("__jump_table") {
+                let entry_size = symbol_resolver.get_struct_size("jump_entry").unwrap_or(16);
+                let encoding = if entry_size == 16 {
+                    SiteEncoding::Relative
+                } else {
+                    SiteEncoding::Absolute
+                };
+                for (i, entry) in bytes.chunks_exact(entry_size).enumerate() {
+                    add_site(
+                        decode_site(encoding, base + (i * entry_size) as u64, entry),
+                        5,
+                    );
+                }
+            }
+
+            // Alternatives: struct alt_instr grew from 12/13 to 14 bytes in 6.3
+            if let Some((base, bytes)) = section_data(".altinstructions") {
+                let layouts = match (
+                    symbol_resolver.get_struct_size("alt_instr"),
+                    symbol_resolver.get_struct_field_offset("alt_instr", "instrlen", None),
+                ) {
+                    (Some(size), Some(instrlen)) => vec![(size, instrlen as usize)],
+                    _ => vec![(14, 12), (13, 10), (12, 10)],
+                };
+                let in_text = |addr: u64| {
+                    sections
+                        .iter()
+                        .any(|s| addr >= s.address && addr < s.address + s.size)
+                };
+                let layout = layouts.into_iter().find(|&(size, instrlen)| {
+                    bytes.len() % size == 0
+                        && bytes.chunks_exact(size).enumerate().all(|(i, entry)| {
+                            let site = decode_site(
+                                SiteEncoding::Relative,
+                                base + (i * size) as u64,
+                                entry,
+                            );
+                            in_text(site) && entry[instrlen] as u64 <= MAX_PATCH_LENGTH
+                        })
+                });
+                if let Some((size, instrlen)) = layout {
+                    for (i, entry) in bytes.chunks_exact(size).enumerate() {
+                        let site =
+                            decode_site(SiteEncoding::Relative, base + (i * size) as u64, entry);
+                        add_site(site, entry[instrlen] as u64);
+                    }
+                }
+            }
+
+            (functions, sections, symbols, patch_sites)
+        };
+
+        Ok(VmlinuxImage {
+            _file: file,
+            data,
+            functions,
+            sections,
+            symbols,
+            patch_sites,
+        })
+    }
+
+    /// Function symbols that live in kernel text, sorted by address
+    pub fn functions(&self) -> impl Iterator<Item = &KernelFunction> {
+        self.functions
+            .iter()
+            .filter(|f| self.bytes(f.address, 1).is_some())
+    }
+
+    /// Link-time address of a symbol
+    pub fn symbol_address(&self, name: &str) -> Option<u64> {
+        self.symbols.get(name).copied()
+    }
+
+    /// On-disk bytes at a link-time address
+    pub fn bytes(&self, address: u64, length: usize) -> Option<&[u8]> {
+        let section = self
+            .sections
+            .iter()
+            .find(|s| address >= s.address && address < s.address + s.size)?;
+        if address + length as u64 > section.address + section.size {
+            return None;
+        }
+        let start = (section.offset + address - section.address) as usize;
+        self.data.get(start..start + length)
+    }
+
+    /// Check whether a link-time address falls inside a recorded patch site
+    pub fn is_patch_site(&self, address: u64) -> bool {
+        self.patch_sites
+            .range(address.saturating_sub(MAX_PATCH_LENGTH)..=address)
+            .any(|(_, &end)| address < end)
+    }
+}
+
+/// Decode the site address from a patch table entry located at `entry_address`
+fn decode_site(encoding: SiteEncoding, entry_address: u64, entry: &[u8]) -> u64 {
+    match encoding {
+        SiteEncoding::Relative => {
+            let rel = i32::from_le_bytes(entry[0..4].try_into().unwrap());
+            entry_address.wrapping_add(rel as i64 as u64)
+        }
+        SiteEncoding::Absolute => u64::from_le_bytes(entry[0..8].try_into().unwrap()),
+    }
+}
+
+/// A control-transfer instruction sequence decoded from kernel text
+#[derive(Debug, Clone, PartialEq)]
+pub struct Trampoline {
+    pub kind: &'static str,
+    pub length: usize,
+    pub target: Option<u64>, // Destination, or the pointer slot for indirect jumps
+    pub indirect: bool,
+}
+
+/// ENDBR64, emitted at function entry when IBT is enabled
+pub const ENDBR64: [u8; 4] = [0xf3, 0x0f, 0x1e, 0xfa];
+/// The 5-byte NOP ftrace and jump labels leave at disabled sites
+pub const NOP5: [u8; 5] = [0x0f, 0x1f, 0x44, 0x00, 0x00];
+
+/// Decode an x86-64 jump/call/trampoline sequence at the start of `bytes`
+///
+/// Recognises jmp/call rel32, jmp rel8, `movabs reg, imm64; jmp reg`,
+/// `push imm32; ret`, `jmp [rip+disp32]` and int3.
+pub fn decode_trampoline(bytes: &[u8], address: u64) -> Option<Trampoline> {
+    let rel32 = |at: usize| {
+        bytes
+            .get(at..at + 4)
+            .map(|b| i32::from_le_bytes(b.try_into().unwrap()) as i64 as u64)
+    };
+    let relative = |kind: &'static str, length: usize, rel: u64| Trampoline {
+        kind,
+        length,
+        target: Some(address.wrapping_add(length as u64).wrapping_add(rel)),
+        indirect: false,
+    };
+
+    match *bytes.first()? {
+        0xe9 => Some(relative("jmp", 5, rel32(1)?)),
+        0xe8 => Some(relative("call", 5, rel32(1)?)),
+        0xeb => Some(relative("jmp", 2, *bytes.get(1)? as i8 as i64 as u64)),
+        0xcc => Some(Trampoline {
+            kind: "int3",
+            length: 1,
+            target: None,
+            indirect: false,
+        }),
+        0x68 if bytes.get(5) == Some(&0xc3) => Some(Trampoline {
+            kind: "push ret",
+            length: 6,
+            // push imm32 sign-extends into the kernel half of the address space
+            target: Some(rel32(1)?),
+            indirect: false,
+        }),
+        0xff if bytes.get(1) == Some(&0x25) => Some(Trampoline {
+            kind: "indirect jmp",
+            length: 6,
+            target: Some(address.wrapping_add(6).wrapping_add(rel32(2)?)),
+            indirect: true,
+        }),
+        // movabs rN, imm64 (REX.W B8+r) followed by jmp rN (FF E0+r)
+        rex @ (0x48 | 0x49) if (0xb8..=0xbf).contains(bytes.get(1)?) => {
+            let reg = bytes[1] - 0xb8;
+            let imm = u64::from_le_bytes(bytes.get(2..10)?.try_into().unwrap());
+            let jmp: &[u8] = if rex == 0x49 {
+                &[0x41, 0xff, 0xe0 + reg]
+            } else {
+                &[0xff, 0xe0 + reg]
+            };
+            if bytes.get(10..10 + jmp.len())? == jmp {
+                Some(Trampoline {
+                    kind: "movabs jmp",
+                    length: 10 + jmp.len(),
+                    target: Some(imm),
+                    indirect: false,
+                })
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Format bytes as space-separated hex
+pub fn format_bytes(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_trampoline() {
+        let base = 0xffffffff81000000;
+        let jmp = decode_trampoline(&[0xe9, 0x0b, 0x00, 0x00, 0x00], base).unwrap();
+        assert_eq!(jmp.kind, "jmp");
+        assert_eq!(jmp.target, Some(base + 0x10));
+
+        let mut movabs = vec![0x48, 0xb8];
+        movabs.extend_from_slice(&0xffffffffc0001000u64.to_le_bytes());
+        movabs.extend_from_slice(&[0xff, 0xe0]);
+        let hook = decode_trampoline(&movabs, base).unwrap();
+        assert_eq!(hook.kind, "movabs jmp");
+        assert_eq!(hook.length, 12);
+        assert_eq!(hook.target, Some(0xffffffffc0001000));
+
+        assert!(decode_trampoline(&[0x55, 0x48, 0x89, 0xe5], base).is_none());
+        assert!(decode_trampoline(&NOP5, base).is_none());
+    }
+}
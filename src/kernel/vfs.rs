@@ -0,0 +1,616 @@
+//! VFS helpers for walking superblocks, dentries, inodes and the page cache
+use crate::error::AnalysisError;
+use crate::kernel::page::PageMap;
+use crate::kernel::xarray::XArrayWalker;
+use crate::kernel::KernelParser;
+use crate::symbols::SymbolResolver;
+use crate::translation::MemoryTranslator;
+
+// Macro for conditional debug output
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        if std::env::var("LINMEMPARSER_DEBUG").is_ok() {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+// Safety limits to prevent runaway walks over corrupted structures
+const MAX_SUPER_BLOCKS: usize = 4096;
+const MAX_DENTRY_CHILDREN: usize = 100000;
+const MAX_SB_INODES: usize = 1000000;
+const MAX_PATH_DEPTH: usize = 256;
+
+const PAGE_SIZE: u64 = 0x1000;
+
+/// Inode attributes read from a struct inode
+#[derive(Debug, Clone)]
+pub struct InodeDetails {
+    pub ino: u64,
+    pub mode: u16,
+    pub uid: u32,
+    pub gid: u32,
+    pub nlink: u32,
+    pub size: u64,
+    pub atime: i64,
+    pub mtime: i64,
+    pub ctime: i64,
+    pub mapping: u64,
+}
+
+/// Which kind of child list a dentry uses
+enum ChildList {
+    /// d_subdirs/d_child list_heads (before 6.8)
+    Subdirs { subdirs: usize, child: usize },
+    /// d_children/d_sib hlists (6.8+)
+    Children { children: usize, sib: usize },
+}
+
+/// Walker over VFS structures in kernel memory
+pub struct VfsWalker<'a> {
+    mapped: &'a [u8],
+    translator: &'a MemoryTranslator,
+    symbol_resolver: &'a SymbolResolver,
+    // super_block
+    sb_list: usize,
+    sb_type: usize,
+    sb_root: usize,
+    sb_inodes: usize,
+    sb_mounts: Option<usize>,
+    // file_system_type
+    fs_type_name: usize,
+    // dentry
+    d_parent: usize,
+    d_name: usize,
+    d_inode: usize,
+    d_alias: usize,
+    child_list: ChildList,
+    // qstr
+    qstr_name: usize,
+    // inode
+    i_mode: usize,
+    i_uid: usize,
+    i_gid: usize,
+    i_ino: usize,
+    i_size: usize,
+    i_nlink: Option<usize>,
+    i_mapping: usize,
+    i_sb_list: usize,
+    i_dentry: usize,
+    // address_space
+    i_pages: Option<usize>,
+    xarray: XArrayWalker<'a>,
+    pages: PageMap<'a>,
+}
+
+impl<'a> VfsWalker<'a> {
+    /// Resolve every structure offset needed for VFS walking
+    pub fn new(
+        mapped: &'a [u8],
+        translator: &'a MemoryTranslator,
+        symbol_resolver: &'a SymbolResolver,
+    ) -> Result<Self, AnalysisError> {
+        let field = |s: &str, f: &str| symbol_resolver.require_field_offset(s, f);
+        let optional = |s: &str, f: &str| {
+            if symbol_resolver.has_field(s, f) {
+                symbol_resolver.require_field_offset(s, f).ok()
+            } else {
+                None
+            }
+        };
+
+        // Kernel 6.8 replaced d_subdirs/d_child with d_children/d_sib
+        let child_list = if symbol_resolver.has_field("dentry", "d_children") {
+            ChildList::Children {
+                children: field("dentry", "d_children")?,
+                sib: field("dentry", "d_sib")?,
+            }
+        } else {
+            ChildList::Subdirs {
+                subdirs: field("dentry", "d_subdirs")?,
+                child: field("dentry", "d_child")?,
+            }
+        };
+
+        // d_alias lives at the start of the d_u union on modern kernels
+        let d_alias = optional("dentry", "d_alias").map_or_else(|| field("dentry", "d_u"), Ok)?;
+
+        Ok(VfsWalker {
+            mapped,
+            translator,
+            symbol_resolver,
+            sb_list: field("super_block", "s_list")?,
+            sb_type: field("super_block", "s_type")?,
+            sb_root: field("super_block", "s_root")?,
+            sb_inodes: field("super_block", "s_inodes")?,
+            sb_mounts: optional("super_block", "s_mounts"),
+            fs_type_name: field("file_system_type", "name")?,
+            d_parent: field("dentry", "d_parent")?,
+            d_name: field("dentry", "d_name")?,
+            d_inode: field("dentry", "d_inode")?,
+            d_alias,
+            child_list,
+            qstr_name: optional("qstr", "name").unwrap_or(8),
+            i_mode: field("inode", "i_mode")?,
+            i_uid: field("inode", "i_uid")?,
+            i_gid: field("inode", "i_gid")?,
+            i_ino: field("inode", "i_ino")?,
+            i_size: field("inode", "i_size")?,
+            i_nlink: optional("inode", "i_nlink").or_else(|| optional("inode", "__i_nlink")),
+            i_mapping: field("inode", "i_mapping")?,
+            i_sb_list: field("inode", "i_sb_list")?,
+            i_dentry: field("inode", "i_dentry")?,
+            i_pages: optional("address_space", "i_pages"),
+            xarray: XArrayWalker::new(mapped, translator, symbol_resolver),
+            pages: PageMap::new(mapped, translator, symbol_resolver),
+        })
+    }
+
+    fn read_ptr(&self, addr: u64) -> Option<u64> {
+        KernelParser::read_virt_pointer(self.mapped, self.translator, addr)
+    }
+
+    /// Enumerate every superblock on the global super_blocks list
+    pub fn super_blocks(&self) -> Result<Vec<u64>, AnalysisError> {
+        let head = self
+            .symbol_resolver
+            .get_symbol_address("super_blocks")
+            .ok_or_else(|| AnalysisError::SymbolNotFound("super_blocks".to_string()))?;
+
+        Ok(
+            KernelParser::walk_list(self.mapped, self.translator, head, MAX_SUPER_BLOCKS)
+                .into_iter()
+                .map(|entry| entry.wrapping_sub(self.sb_list as u64))
+                .collect(),
+        )
+    }
+
+    /// Get the filesystem type name ("tmpfs", "ramfs", ...) of a superblock
+    pub fn fs_type_name(&self, sb: u64) -> Option<String> {
+        let fs_type = self.read_ptr(sb + self.sb_type as u64)?;
+        let name_ptr = self.read_ptr(fs_type + self.fs_type_name as u64)?;
+        KernelParser::read_virt_cstring(self.mapped, self.translator, name_ptr, 64)
+    }
+
+    /// Get the root dentry of a superblock
+    pub fn root_dentry(&self, sb: u64) -> Option<u64> {
+        self.read_ptr(sb + self.sb_root as u64).filter(|&d| d != 0)
+    }
+
+    /// Read the name of a dentry
+    pub fn dentry_name(&self, dentry: u64) -> Option<String> {
+        let name_ptr = self.read_ptr(dentry + (self.d_name + self.qstr_name) as u64)?;
+        KernelParser::read_virt_cstring(self.mapped, self.translator, name_ptr, 256)
+    }
+
+    /// Get the inode a dentry refers to (None for negative dentries)
+    pub fn dentry_inode(&self, dentry: u64) -> Option<u64> {
+        self.read_ptr(dentry + self.d_inode as u64)
+            .filter(|&i| i != 0)
+    }
+
+    /// Get the parent of a dentry
+    pub fn dentry_parent(&self, dentry: u64) -> Option<u64> {
+        self.read_ptr(dentry + self.d_parent as u64)
+    }
+
+    /// Enumerate the child dentries of a directory dentry
+    pub fn dentry_children(&self, dentry: u64) -> Vec<u64> {
+        match self.child_list {
+            ChildList::Subdirs { subdirs, child } => KernelParser::walk_list(
+                self.mapped,
+                self.translator,
+                dentry + subdirs as u64,
+                MAX_DENTRY_CHILDREN,
+            )
+            .into_iter()
+            .map(|entry| entry.wrapping_sub(child as u64))
+            .collect(),
+            ChildList::Children { children, sib } => {
+                let first = self.read_ptr(dentry + children as u64).unwrap_or(0);
+                KernelParser::walk_hlist(self.mapped, self.translator, first, MAX_DENTRY_CHILDREN)
+                    .into_iter()
+                    .map(|node| node.wrapping_sub(sib as u64))
+                    .collect()
+            }
+        }
+    }
+
+    /// Build the path of a dentry relative to the root of its filesystem
+    pub fn dentry_path(&self, dentry: u64) -> String {
+        let mut components = Vec::new();
+        let mut current = dentry;
+
+        for _ in 0..MAX_PATH_DEPTH {
+            let parent = match self.dentry_parent(current) {
+                Some(p) if p != 0 => p,
+                _ => break,
+            };
+            if parent == current {
+                break;
+            }
+            components.push(self.dentry_name(current).unwrap_or_else(|| "?".to_string()));
+            current = parent;
+        }
+
+        components.reverse();
+        format!("/{}", components.join("/"))
+    }
+
+    /// Enumerate every inode attached to a superblock (s_inodes list)
+    pub fn sb_inodes(&self, sb: u64) -> Vec<u64> {
+        KernelParser::walk_list(
+            self.mapped,
+            self.translator,
+            sb + self.sb_inodes as u64,
+            MAX_SB_INODES,
+        )
+        .into_iter()
+        .map(|entry| entry.wrapping_sub(self.i_sb_list as u64))
+        .collect()
+    }
+
+    /// Get the first dentry aliasing an inode, if any
+    pub fn inode_alias(&self, inode: u64) -> Option<u64> {
+        let first = self.read_ptr(inode + self.i_dentry as u64)?;
+        if first == 0 {
+            return None;
+        }
+        Some(first.wrapping_sub(self.d_alias as u64))
+    }
+
+    /// Read one inode timestamp, handling the layout changes across kernel versions
+    fn inode_time(&self, inode: u64, which: &str) -> i64 {
+        // 6.11+ splits timestamps into i_Xtime_sec/i_Xtime_nsec
+        let sec_field = format!("i_{}time_sec", which);
+        let candidates = [
+            sec_field,
+            format!("__i_{}time", which), // 6.6/6.7
+            format!("i_{}time", which),   // timespec64, tv_sec first
+        ];
+
+        for name in candidates.iter() {
+            if self.symbol_resolver.has_field("inode", name) {
+                if let Ok(offset) = self.symbol_resolver.require_field_offset("inode", name) {
+                    // time64_t seconds on every architecture
+                    return KernelParser::read_virt_u64(
+                        self.mapped,
+                        self.translator,
+                        inode + offset as u64,
+                    )
+                    .map(|v| v as i64)
+                    .unwrap_or(0);
+                }
+            }
+        }
+
+        0
+    }
+
+    /// Read the attributes of an inode
+    pub fn read_inode(&self, inode: u64) -> Option<InodeDetails> {
+        let mode =
+            KernelParser::read_virt_u16(self.mapped, self.translator, inode + self.i_mode as u64)?;
+        let uid =
+            KernelParser::read_virt_u32(self.mapped, self.translator, inode + self.i_uid as u64)?;
+        let gid =
+            KernelParser::read_virt_u32(self.mapped, self.translator, inode + self.i_gid as u64)?;
+        let ino = self.read_ptr(inode + self.i_ino as u64)?;
+        // loff_t is 64 bits even on 32-bit kernels
+        let size =
+            KernelParser::read_virt_u64(self.mapped, self.translator, inode + self.i_size as u64)?;
+        let nlink = self
+            .i_nlink
+            .and_then(|off| {
+                KernelParser::read_virt_u32(self.mapped, self.translator, inode + off as u64)
+            })
+            .unwrap_or(0);
+        let mapping = self.read_ptr(inode + self.i_mapping as u64).unwrap_or(0);
+
+        Some(InodeDetails {
+            ino,
+            mode,
+            uid,
+            gid,
+            nlink,
+            size,
+            atime: self.inode_time(inode, "a"),
+            mtime: self.inode_time(inode, "m"),
+            ctime: self.inode_time(inode, "c"),
+            mapping,
+        })
+    }
+
+    /// Get the mount point path of a superblock
+    ///
+    /// Walks struct mount (via s_mounts/mnt_instance) up through its parents.
+    /// Internal kernel mounts (such as the shm_mnt used by memfd) have no
+    /// namespace and are reported as "<internal>".
+    pub fn mount_path(&self, sb: u64) -> Option<String> {
+        let sb_mounts = self.sb_mounts?;
+        let mnt_instance = self
+            .symbol_resolver
+            .require_field_offset("mount", "mnt_instance")
+            .ok()?;
+
+        let mounts = KernelParser::walk_list(
+            self.mapped,
+            self.translator,
+            sb + sb_mounts as u64,
+            MAX_SUPER_BLOCKS,
+        );
+        let mount = mounts.first()?.wrapping_sub(mnt_instance as u64);
+
+        if self.is_internal_mount(mount)? {
+            return Some("<internal>".to_string());
+        }
+        self.mount_chain_path(mount)
+    }
+
+    /// Check whether a struct mount belongs to no mount namespace
+    fn is_internal_mount(&self, mount: u64) -> Option<bool> {
+        let mnt_ns = self
+            .symbol_resolver
+            .require_field_offset("mount", "mnt_ns")
+            .ok()?;
+        Some(self.read_ptr(mount + mnt_ns as u64).unwrap_or(0) == 0)
+    }
+
+    /// Build the path a struct mount is mounted on, up to its namespace root
+    fn mount_chain_path(&self, mount: u64) -> Option<String> {
+        let resolver = self.symbol_resolver;
+        let mnt_parent = resolver.require_field_offset("mount", "mnt_parent").ok()?;
+        let mnt_mountpoint = resolver
+            .require_field_offset("mount", "mnt_mountpoint")
+            .ok()?;
+
+        let mut components: Vec<String> = Vec::new();
+        let mut current = mount;
+        for _ in 0..MAX_PATH_DEPTH {
+            let parent = self.read_ptr(current + mnt_parent as u64)?;
+            if parent == current || parent == 0 {
+                break;
+            }
+            let mountpoint = self.read_ptr(current + mnt_mountpoint as u64)?;
+            let path = self.dentry_path(mountpoint);
+            if path != "/" {
+                components.push(path);
+            }
+            current = parent;
+        }
+
+        components.reverse();
+        let joined = components.concat();
+        Some(if joined.is_empty() {
+            "/".to_string()
+        } else {
+            joined
+        })
+    }
+
+    /// Get the absolute path of an open struct file through its f_path
+    ///
+    /// Files on internal mounts (memfd, shared anonymous memory) are named by
+    /// their dentry alone, the way /proc/PID/maps shows them.
+    pub fn file_path(&self, file: u64) -> Option<String> {
+        let resolver = self.symbol_resolver;
+        let f_path = resolver.require_field_offset("file", "f_path").ok()? as u64;
+        let path_mnt = resolver
+            .get_struct_field_offset("path", "mnt", None)
+            .unwrap_or(0);
+        let path_dentry = resolver
+            .get_struct_field_offset("path", "dentry", None)
+            .unwrap_or(8);
+        let mount_mnt = resolver.require_field_offset("mount", "mnt").ok()? as u64;
+
+        let vfsmount = self.read_ptr(file + f_path + path_mnt)?;
+        let dentry = self.read_ptr(file + f_path + path_dentry)?;
+        if vfsmount == 0 || dentry == 0 {
+            return None;
+        }
+        let mount = vfsmount.wrapping_sub(mount_mnt);
+
+        if self.is_internal_mount(mount)? {
+            return Some(format!("/{}", self.dentry_name(dentry)?));
+        }
+        let prefix = self.mount_chain_path(mount)?;
+        let relative = self.dentry_path(dentry);
+        Some(match (prefix.as_str(), relative.as_str()) {
+            ("/", _) => relative,
+            (_, "/") => prefix,
+            _ => prefix + &relative,
+        })
+    }
+
+    /// Check whether the inode behind an open struct file has been unlinked
+    pub fn file_deleted(&self, file: u64) -> bool {
+        let f_inode = match self.symbol_resolver.require_field_offset("file", "f_inode") {
+            Ok(o) => o as u64,
+            Err(_) => return false,
+        };
+        match self.read_ptr(file + f_inode) {
+            Some(inode) if inode != 0 => self
+                .read_inode(inode)
+                .map(|details| details.nlink == 0)
+                .unwrap_or(false),
+            _ => false,
+        }
+    }
+
+    /// Enumerate the pages cached for an address_space as (index, page pointer) pairs
+    pub fn cached_pages(&self, mapping: u64) -> Vec<(u64, u64)> {
+        match self.i_pages {
+            Some(off) => self.xarray.entries(mapping + off as u64),
+            None => {
+                debug!("[DEBUG] address_space::i_pages not available, cannot walk page cache");
+                Vec::new()
+            }
+        }
+    }
+
+    /// Convert a struct page pointer to the physical address of the page
+    pub fn page_to_physical(&self, page: u64) -> Option<u64> {
+        self.pages.page_to_physical(page)
+    }
+
+    /// Read the contents of a cached page
+    pub fn read_page(&self, page: u64) -> Option<&'a [u8]> {
+        let physical = self.page_to_physical(page)?;
+        let file_offset = self.translator.physical_to_file_offset(physical)? as usize;
+        self.mapped
+            .get(file_offset..file_offset + PAGE_SIZE as usize)
+    }
+}
+
+/// Describe the file type encoded in an inode mode
+pub fn file_type_name(mode: u16) -> &'static str {
+    match mode & 0o170000 {
+        0o140000 => "socket",
+        0o120000 => "symlink",
+        0o100000 => "file",
+        0o060000 => "block",
+        0o040000 => "dir",
+        0o020000 => "char",
+        0o010000 => "fifo",
+        _ => "unknown",
+    }
+}
+
+/// Format an inode mode as an ls-style permission string (e.g. "-rwxr-xr-x")
+pub fn format_mode(mode: u16) -> String {
+    let type_char = match mode & 0o170000 {
+        0o140000 => 's',
+        0o120000 => 'l',
+        0o060000 => 'b',
+        0o040000 => 'd',
+        0o020000 => 'c',
+        0o010000 => 'p',
+        _ => '-',
+    };
+
+    let mut out = String::with_capacity(10);
+    out.push(type_char);
+    let bits = [
+        (0o400, 'r'),
+        (0o200, 'w'),
+        (0o100, 'x'),
+        (0o040, 'r'),
+        (0o020, 'w'),
+        (0o010, 'x'),
+        (0o004, 'r'),
+        (0o002, 'w'),
+        (0o001, 'x'),
+    ];
+    for (bit, ch) in bits.iter() {
+        out.push(if mode & bit != 0 { *ch } else { '-' });
+    }
+
+    // setuid/setgid/sticky overlay the execute bits
+    let mut chars: Vec<char> = out.chars().collect();
+    if mode & 0o4000 != 0 {
+        chars[3] = if mode & 0o100 != 0 { 's' } else { 'S' };
+    }
+    if mode & 0o2000 != 0 {
+        chars[6] = if mode & 0o010 != 0 { 's' } else { 'S' };
+    }
+    if mode & 0o1000 != 0 {
+        chars[9] = if mode & 0o001 != 0 { 't' } else { 'T' };
+    }
+    chars.into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_mode_and_file_type_name() {
+        assert_eq!(format_mode(0o100644), "-rw-r--r--");
+        assert_eq!(format_mode(0o040755), "drwxr-xr-x");
+        assert_eq!(format_mode(0o120777), "lrwxrwxrwx");
+        // setuid, setgid and sticky with and without the execute bit
+        assert_eq!(format_mode(0o104755), "-rwsr-xr-x");
+        assert_eq!(format_mode(0o102644), "-rw-r-Sr--");
+        assert_eq!(format_mode(0o041777), "drwxrwxrwt");
+        assert_eq!(format_mode(0o041776), "drwxrwxrwT");
+        assert_eq!(format_mode(0o020620), "crw--w----");
+
+        assert_eq!(file_type_name(0o100644), "file");
+        assert_eq!(file_type_name(0o040755), "dir");
+        assert_eq!(file_type_name(0o140777), "socket");
+        assert_eq!(file_type_name(0o010600), "fifo");
+        assert_eq!(file_type_name(0o060660), "block");
+        assert_eq!(file_type_name(0o000644), "unknown");
+    }
+
+    #[test]
+    fn test_read_inode_on_a_32bit_big_endian_target() {
+        use crate::kernel::target::Target;
+        use crate::memory::MemoryRegion;
+
+        let mut symbols = SymbolResolver::new();
+        symbols.add_struct(
+            "super_block",
+            0x100,
+            &[
+                ("s_list", 0),
+                ("s_type", 8),
+                ("s_root", 12),
+                ("s_inodes", 16),
+            ],
+        );
+        symbols.add_struct("file_system_type", 0x20, &[("name", 0)]);
+        symbols.add_struct(
+            "dentry",
+            0x80,
+            &[
+                ("d_parent", 0),
+                ("d_name", 4),
+                ("d_inode", 16),
+                ("d_alias", 20),
+                ("d_children", 28),
+                ("d_sib", 32),
+            ],
+        );
+        symbols.add_struct(
+            "inode",
+            0x100,
+            &[
+                ("i_mode", 0),
+                ("i_uid", 4),
+                ("i_gid", 8),
+                ("i_ino", 0x10),
+                ("i_size", 0x18),
+                ("i_nlink", 0x20),
+                ("i_mtime_sec", 0x28),
+                ("i_mapping", 0x30),
+                ("i_sb_list", 0x34),
+                ("i_dentry", 0x3c),
+            ],
+        );
+
+        let mut mapped = vec![0u8; 0x100];
+        mapped[0..2].copy_from_slice(&0o100640u16.to_be_bytes());
+        mapped[4..8].copy_from_slice(&1000u32.to_be_bytes());
+        mapped[0x10..0x14].copy_from_slice(&4242u32.to_be_bytes());
+        // A 5 GiB file and a timestamp past 2038 need all 64 bits
+        mapped[0x18..0x20].copy_from_slice(&0x1_4000_0000u64.to_be_bytes());
+        mapped[0x20..0x24].copy_from_slice(&2u32.to_be_bytes());
+        mapped[0x28..0x30].copy_from_slice(&0x1_0000_0000i64.to_be_bytes());
+        mapped[0x30..0x34].copy_from_slice(&0xc000_0080u32.to_be_bytes());
+
+        let mut translator = MemoryTranslator::new(vec![MemoryRegion {
+            start: 0,
+            end: 0xff,
+            file_offset: 0,
+        }]);
+        translator.set_target(Target::from_pointer_type(4, "big").unwrap());
+        let vfs = VfsWalker::new(&mapped, &translator, &symbols).unwrap();
+
+        let inode = vfs.read_inode(0xffff_8880_0000_0000).unwrap();
+        assert_eq!((inode.mode, inode.uid, inode.ino), (0o100640, 1000, 4242));
+        assert_eq!((inode.size, inode.nlink), (0x1_4000_0000, 2));
+        assert_eq!((inode.mtime, inode.atime), (0x1_0000_0000, 0));
+        assert_eq!(inode.mapping, 0xc000_0080);
+    }
+}
@@ -0,0 +1,117 @@
+//! XArray / radix tree walking (page cache, IDRs)
+use crate::kernel::KernelParser;
+use crate::symbols::SymbolResolver;
+use crate::translation::MemoryTranslator;
+
+// Safety limit on tree depth (64-bit indices with 6-bit chunks)
+const MAX_XARRAY_DEPTH: usize = 11;
+// XA_CHUNK_SIZE / RADIX_TREE_MAP_SIZE
+const XA_CHUNK_SIZE: u64 = 64;
+
+/// Walker over struct xarray (4.20+) and the radix_tree_root it replaced
+///
+/// Both use the same entry encoding, so IDRs from older kernels walk the same way.
+pub struct XArrayWalker<'a> {
+    mapped: &'a [u8],
+    translator: &'a MemoryTranslator,
+    head: usize,
+    node_shift: usize,
+    node_slots: usize,
+}
+
+impl<'a> XArrayWalker<'a> {
+    /// Resolve the xarray/xa_node offsets, falling back to radix_tree_root/radix_tree_node
+    pub fn new(
+        mapped: &'a [u8],
+        translator: &'a MemoryTranslator,
+        symbol_resolver: &'a SymbolResolver,
+    ) -> Self {
+        let offset = |s: &str, f: &str, old_s: &str, old_f: &str, default: u64| {
+            symbol_resolver
+                .get_struct_field_offset(s, f, None)
+                .or_else(|| symbol_resolver.get_struct_field_offset(old_s, old_f, None))
+                .unwrap_or(default) as usize
+        };
+
+        XArrayWalker {
+            mapped,
+            translator,
+            head: offset("xarray", "xa_head", "radix_tree_root", "rnode", 8),
+            node_shift: offset("xa_node", "shift", "radix_tree_node", "shift", 0),
+            node_slots: offset("xa_node", "slots", "radix_tree_node", "slots", 40),
+        }
+    }
+
+    fn read_ptr(&self, addr: u64) -> Option<u64> {
+        KernelParser::read_virt_pointer(self.mapped, self.translator, addr)
+    }
+
+    /// Enumerate the entries of the xarray at the given address as (index, entry) pairs
+    ///
+    /// Internal and value entries are skipped; only stored pointers are returned.
+    pub fn entries(&self, xarray: u64) -> Vec<(u64, u64)> {
+        let mut entries = Vec::new();
+        let head = match self.read_ptr(xarray + self.head as u64) {
+            Some(h) if h != 0 => h,
+            _ => return entries,
+        };
+
+        if xa_is_node(head) {
+            self.walk_node(head & !3, 0, 0, &mut entries);
+        } else if !xa_is_internal(head) && !xa_is_value(head) {
+            entries.push((0, head));
+        }
+
+        entries
+    }
+
+    fn walk_node(&self, node: u64, base: u64, depth: usize, entries: &mut Vec<(u64, u64)>) {
+        if depth > MAX_XARRAY_DEPTH {
+            return;
+        }
+
+        let shift = match KernelParser::read_virt_bytes(
+            self.mapped,
+            self.translator,
+            node + self.node_shift as u64,
+            1,
+        ) {
+            Some(b) => b[0] as u64,
+            None => return,
+        };
+        if shift > 60 {
+            return;
+        }
+
+        for slot in 0..XA_CHUNK_SIZE {
+            let entry = match self
+                .read_ptr(node + self.node_slots as u64 + slot * self.translator.pointer_size())
+            {
+                Some(e) if e != 0 => e,
+                _ => continue,
+            };
+            let index = base + (slot << shift);
+
+            if xa_is_node(entry) && shift > 0 {
+                self.walk_node(entry & !3, index, depth + 1, entries);
+            } else if !xa_is_internal(entry) && !xa_is_value(entry) {
+                entries.push((index, entry));
+            }
+        }
+    }
+}
+
+/// XArray internal entries have the low two bits set to 0b10
+fn xa_is_internal(entry: u64) -> bool {
+    entry & 3 == 2
+}
+
+/// Node pointers are internal entries above the reserved range
+fn xa_is_node(entry: u64) -> bool {
+    xa_is_internal(entry) && entry > 4096
+}
+
+/// Value entries (shadow/swap entries) have the low bit set
+fn xa_is_value(entry: u64) -> bool {
+    entry & 1 == 1
+}
@@ -0,0 +1,287 @@
+//! zsmalloc object lookup: from an allocator handle to the bytes of the object (mm/zsmalloc.c)
+use crate::kernel::page::PageLayout;
+use crate::kernel::KernelParser;
+use crate::translation::MemoryTranslator;
+use std::sync::OnceLock;
+
+const PAGE_SIZE: u64 = 0x1000;
+const PAGE_SHIFT: u64 = 12;
+
+// Size class geometry: 32-byte minimum, PAGE_SIZE >> CLASS_BITS steps
+const ZS_MIN_ALLOC_SIZE: u64 = 32;
+const ZS_SIZE_CLASS_DELTA: u64 = PAGE_SIZE >> 8;
+const ZS_SIZE_CLASSES: u64 = (PAGE_SIZE - ZS_MIN_ALLOC_SIZE).div_ceil(ZS_SIZE_CLASS_DELTA) + 1;
+// Non-huge objects start with their handle | OBJ_ALLOCATED_TAG
+const ZS_HANDLE_SIZE: u64 = 8;
+
+// Encoded object locations put the PFN in the top _PFN_BITS bits
+// (MAX_POSSIBLE_PHYSMEM_BITS - PAGE_SHIFT for 4-level and 5-level kernels) and the
+// object index below it, shifted by 0-2 tag bits depending on the kernel version
+const PFN_BITS: [u64; 2] = [34, 40];
+const TAG_BITS: [u64; 3] = [0, 1, 2];
+// Pages per zspage: ZS_MAX_PAGES_PER_ZSPAGE before 6.3, CONFIG_ZSMALLOC_CHAIN_SIZE after
+const CHAIN_SIZES: [u64; 2] = [4, 8];
+
+/// A zsmalloc size class after merging
+#[derive(Debug, Clone, Copy)]
+struct SizeClass {
+    size: u64,
+    huge: bool, // One object per single-page zspage, stored without a handle header
+}
+
+/// Build the size class table zs_create_pool() builds for a zspage chain size
+///
+/// Classes with the same pages-per-zspage and objects-per-zspage are merged into
+/// the largest of them.
+fn size_classes(chain: u64) -> Vec<SizeClass> {
+    let mut classes = vec![
+        SizeClass {
+            size: 0,
+            huge: false
+        };
+        ZS_SIZE_CLASSES as usize
+    ];
+    let mut previous: Option<(u64, u64, SizeClass)> = None;
+    for i in (0..ZS_SIZE_CLASSES).rev() {
+        let size = (ZS_MIN_ALLOC_SIZE + i * ZS_SIZE_CLASS_DELTA).min(PAGE_SIZE);
+        let pages = chain_length(size, chain);
+        let objects = pages * PAGE_SIZE / size;
+        let class = match previous {
+            Some((p, o, class)) if p == pages && o == objects => class,
+            _ => SizeClass {
+                size,
+                huge: pages == 1 && objects == 1,
+            },
+        };
+        previous = Some((pages, objects, class));
+        classes[i as usize] = class;
+    }
+    classes
+}
+
+/// Pick the zspage length (in pages) that wastes the least space for a class size
+fn chain_length(size: u64, chain: u64) -> u64 {
+    let mut best = (0, 1);
+    for pages in 1..=chain {
+        let zspage = pages * PAGE_SIZE;
+        let used = (zspage - zspage % size) * 100 / zspage;
+        if used > best.0 {
+            best = (used, pages);
+        }
+    }
+    best.1
+}
+
+/// Find the size class an allocation of `length` bytes was served from
+fn class_for(chain_index: usize, length: u64) -> SizeClass {
+    static CLASSES: OnceLock<Vec<Vec<SizeClass>>> = OnceLock::new();
+    let classes = CLASSES.get_or_init(|| CHAIN_SIZES.iter().map(|&c| size_classes(c)).collect());
+    let size = length + ZS_HANDLE_SIZE;
+    let index = if size <= ZS_MIN_ALLOC_SIZE {
+        0
+    } else {
+        (size - ZS_MIN_ALLOC_SIZE).div_ceil(ZS_SIZE_CLASS_DELTA)
+    };
+    classes[chain_index][index.min(ZS_SIZE_CLASSES - 1) as usize]
+}
+
+/// Read `length` bytes of physical memory, which must not cross a page
+fn read_physical<'a>(
+    mapped: &'a [u8],
+    translator: &MemoryTranslator,
+    physical: u64,
+    length: u64,
+) -> Option<&'a [u8]> {
+    let offset = translator.physical_to_file_offset(physical)? as usize;
+    mapped.get(offset..offset + length as usize)
+}
+
+/// Read the object behind a zsmalloc handle
+///
+/// The location encoding and zspage chain size vary between kernel versions, so
+/// each variant is tried and accepted only when the object records the same
+/// handle (in its header, or in page->index for huge objects).
+pub fn read_object(
+    mapped: &[u8],
+    translator: &MemoryTranslator,
+    pages: &PageLayout,
+    handle: u64,
+    length: u64,
+) -> Option<Vec<u8>> {
+    if length == 0 || length > PAGE_SIZE {
+        return None;
+    }
+    let location = KernelParser::read_virt_pointer(mapped, translator, handle)?;
+    let records_handle = |value: u64| value & !(ZS_HANDLE_SIZE - 1) == handle;
+
+    for pfn_bits in PFN_BITS {
+        let pfn = location >> (64 - pfn_bits);
+        for tag_bits in TAG_BITS {
+            let index_bits = 64 - pfn_bits - tag_bits;
+            let object = (location >> tag_bits) & ((1u64 << index_bits) - 1);
+            for chain_index in 0..CHAIN_SIZES.len() {
+                let class = class_for(chain_index, length);
+
+                if class.huge {
+                    let index = KernelParser::read_virt_pointer(
+                        mapped,
+                        translator,
+                        pages.pfn_to_page(pfn) + pages.index,
+                    );
+                    if object == 0 && index.is_some_and(records_handle) {
+                        return read_physical(mapped, translator, pfn << PAGE_SHIFT, length)
+                            .map(<[u8]>::to_vec);
+                    }
+                    continue;
+                }
+
+                let offset = (object * class.size) % PAGE_SIZE;
+                let header =
+                    match read_physical(mapped, translator, (pfn << PAGE_SHIFT) + offset, 8) {
+                        Some(bytes) => translator.target().u64(bytes.try_into().ok()?),
+                        None => continue,
+                    };
+                if !records_handle(header) {
+                    continue;
+                }
+                return read_spanning(
+                    mapped,
+                    translator,
+                    pages,
+                    pfn,
+                    offset + ZS_HANDLE_SIZE,
+                    length,
+                );
+            }
+        }
+    }
+    None
+}
+
+/// Read an object that may continue on the next page of its zspage
+fn read_spanning(
+    mapped: &[u8],
+    translator: &MemoryTranslator,
+    pages: &PageLayout,
+    pfn: u64,
+    offset: u64,
+    length: u64,
+) -> Option<Vec<u8>> {
+    let first = length.min(PAGE_SIZE - offset);
+    let mut data = read_physical(mapped, translator, (pfn << PAGE_SHIFT) + offset, first)?.to_vec();
+    if first < length {
+        // Pages of a zspage are linked through page->index
+        let next = KernelParser::read_virt_pointer(
+            mapped,
+            translator,
+            pages.pfn_to_page(pfn) + pages.index,
+        )?;
+        let next_pfn = pages.page_to_pfn(next)?;
+        data.extend_from_slice(read_physical(
+            mapped,
+            translator,
+            next_pfn << PAGE_SHIFT,
+            length - first,
+        )?);
+    }
+    Some(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::MemoryRegion;
+
+    use crate::testutil::DIRECT_MAP;
+
+    const VMEMMAP_BASE: u64 = 0xffff_ea00_0000_0000;
+
+    fn put(mapped: &mut [u8], at: u64, value: u64) {
+        mapped[at as usize..at as usize + 8].copy_from_slice(&value.to_le_bytes());
+    }
+
+    /// 128KB of physical memory with struct pages for PFNs 0-63 in the vmemmap page
+    /// at 0x5000, mapped through a kernel page table at 0x1000
+    fn synthetic_memory() -> (Vec<u8>, MemoryTranslator, PageLayout) {
+        let mut mapped = vec![0u8; 0x20000];
+        let index = |level: u64| (VMEMMAP_BASE >> (12 + 9 * level)) & 0x1ff;
+        put(&mut mapped, 0x1000 + index(3) * 8, 0x2001);
+        put(&mut mapped, 0x2000 + index(2) * 8, 0x3001);
+        put(&mut mapped, 0x3000 + index(1) * 8, 0x4001);
+        put(&mut mapped, 0x4000 + index(0) * 8, 0x5001);
+        let mut translator = MemoryTranslator::new(vec![MemoryRegion {
+            start: 0,
+            end: 0x1ffff,
+            file_offset: 0,
+        }]);
+        translator.load_kernel_page_table(
+            &mapped,
+            0x1000,
+            &[(VMEMMAP_BASE, VMEMMAP_BASE + (1 << 40))],
+        );
+        (
+            mapped,
+            translator,
+            PageLayout::with_vmemmap_base(VMEMMAP_BASE),
+        )
+    }
+
+    #[test]
+    fn test_read_object_spanning_and_huge() {
+        let (mut mapped, translator, pages) = synthetic_memory();
+        let page_index = |pfn: u64| 0x5000 + pfn * 64 + pages.index;
+
+        // Object 1 of a multi-page class starts on PFN 0x10 and continues on PFN 0x12,
+        // linked through page->index
+        let length = 3000;
+        let class = class_for(0, length);
+        assert!(!class.huge);
+        let handle = DIRECT_MAP + 0x6000;
+        put(&mut mapped, 0x6000, (0x10 << 30) | 1);
+        put(&mut mapped, page_index(0x10), pages.pfn_to_page(0x12));
+        put(&mut mapped, 0x10000 + class.size, handle | 1);
+        let data: Vec<u8> = (0..length).map(|i| i as u8).collect();
+        let first = (PAGE_SIZE - class.size - ZS_HANDLE_SIZE) as usize;
+        let start = (0x10000 + class.size + ZS_HANDLE_SIZE) as usize;
+        mapped[start..start + first].copy_from_slice(&data[..first]);
+        mapped[0x12000..0x12000 + length as usize - first].copy_from_slice(&data[first..]);
+        assert_eq!(
+            read_object(&mapped, &translator, &pages, handle, length),
+            Some(data)
+        );
+
+        // A huge object fills PFN 0x14 with no header; page->index records the handle
+        let length = 4000;
+        assert!(class_for(0, length).huge);
+        let handle = DIRECT_MAP + 0x6100;
+        put(&mut mapped, 0x6100, 0x14 << 30);
+        put(&mut mapped, page_index(0x14), handle);
+        mapped[0x14000..0x14000 + length as usize].fill(0xaa);
+        assert_eq!(
+            read_object(&mapped, &translator, &pages, handle, length),
+            Some(vec![0xaa; length as usize])
+        );
+        // A handle the page does not record is rejected
+        put(&mut mapped, page_index(0x14), handle + 0x40);
+        assert_eq!(
+            read_object(&mapped, &translator, &pages, handle, length),
+            None
+        );
+    }
+
+    #[test]
+    fn test_size_classes_merge_and_huge() {
+        let classes = size_classes(4);
+        assert_eq!(classes.len(), 255);
+        assert_eq!(classes[0].size, 32);
+        assert!(!classes[0].huge);
+        // The largest classes hold one object per page and are merged into PAGE_SIZE
+        assert_eq!(classes[254].size, PAGE_SIZE);
+        assert!(classes[254].huge);
+        assert_eq!(classes[253].size, PAGE_SIZE);
+        // Every class is at least as large as the sizes it serves
+        for (i, class) in classes.iter().enumerate() {
+            assert!(class.size >= ZS_MIN_ALLOC_SIZE + i as u64 * ZS_SIZE_CLASS_DELTA);
+        }
+    }
+}
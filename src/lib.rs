@@ -14,12 +14,17 @@
 #![allow(clippy::needless_borrows_for_generic_args)]
 #![allow(clippy::unwrap_or_default)]
 
+pub mod compress;
 pub mod error;
 pub mod kernel;
 pub mod memory;
+pub mod scan;
 pub mod symbols;
 pub mod translation;
 
+#[cfg(test)]
+mod testutil;
+
 // Core modules
 pub mod core {
     pub mod dwarf;
@@ -33,12 +38,35 @@ pub mod cli {
 
 // Plugin modules
 pub mod plugins {
+    pub mod arp;
+    pub mod bash;
+    pub mod bpf;
+    pub mod bpfmaps;
+    pub mod containers;
+    pub mod elfs;
     pub mod files;
+    pub mod ifconfig;
+    pub mod inlinehooks;
+    pub mod ldrmodules;
+    pub mod maps;
     pub mod modules;
+    pub mod namespaces;
+    pub mod netfilter;
     pub mod netstat;
+    pub mod notifiers;
+    pub mod opscheck;
+    pub mod pageinfo;
     pub mod plugin_trait;
     pub mod pslist;
     pub mod pstree;
+    pub mod rawsockets;
+    pub mod routes;
+    pub mod strings;
+    pub mod tmpfs;
+    pub mod tracehooks;
+    pub mod ttycheck;
+    pub mod unix;
+    pub mod yarascan;
 }
 
 // Format modules
@@ -15,25 +15,38 @@
 #![allow(clippy::unwrap_or_default)]
 
 use clap::Parser;
+use std::cell::OnceCell;
 
 mod cli;
+mod compress;
 mod core;
 mod error;
 mod formats;
 mod kernel;
 mod memory;
 mod plugins;
+mod scan;
 mod symbols;
+#[cfg(test)]
+mod testutil;
 mod translation;
 
-use cli::args::{Cli, OutputFormatArg, PluginCommand};
+use cli::args::{ArchArg, Cli, OutputFormatArg, PluginCommand, ScanScopeArg};
 use error::AnalysisError;
 use formats::traits::{OutputDestination, OutputFormat, OutputWriter};
+use kernel::target::Target;
 use memory::MemoryMap;
 use plugins::plugin_trait::{AnalysisContext, ForensicPlugin, PluginOutput};
-use plugins::{FilesPlugin, ModulesPlugin, NetStatPlugin, PsListPlugin, PsTreePlugin};
+use plugins::{
+    ArpPlugin, BashPlugin, BpfMapsPlugin, BpfPlugin, ContainersPlugin, ElfsPlugin, FilesPlugin,
+    IfconfigPlugin, InlineHooksPlugin, LdrModulesPlugin, MapsPlugin, ModulesPlugin,
+    NamespacesPlugin, NetStatPlugin, NetfilterPlugin, NotifiersPlugin, OpsCheckPlugin,
+    PageInfoPlugin, PsListPlugin, PsTreePlugin, RawSocketsPlugin, RoutesPlugin, StringsPlugin,
+    TmpfsPlugin, TraceHooksPlugin, TtyCheckPlugin, UnixPlugin, YaraScanPlugin,
+};
+use scan::ScanScope;
 use symbols::SymbolResolver;
-use translation::MemoryTranslator;
+use translation::{Architecture, MemoryTranslator};
 
 // Macro for conditional debug output
 macro_rules! debug {
@@ -74,6 +87,41 @@ fn main() -> Result<(), AnalysisError> {
         println!("  netstat - Extract network connections");
         println!("  modules - List loaded kernel modules");
         println!("  files - List open file handles (not yet implemented)");
+        println!("  tmpfs - Reconstruct tmpfs/ramfs filesystems and memfd files");
+        println!("  bash - Recover bash command history from process memory");
+        println!("  ifconfig - List network interfaces, addresses and promiscuous mode");
+        println!("  arp - Extract the ARP and IPv6 neighbour caches");
+        println!("  routes - Extract the IPv4 and IPv6 routing tables");
+        println!("  unix - List Unix domain sockets with owning and peer processes");
+        println!("  rawsockets - List packet, raw and netlink sockets with attached BPF filters");
+        println!("  bpf - List loaded eBPF programs with attach points and JIT images");
+        println!("  bpfmaps - List eBPF maps with key/value sizes and capacity");
+        println!("  inlinehooks - Detect inline hooks by checking kernel function text integrity");
+        println!(
+            "  tracehooks - List ftrace ops, kprobes and tracepoint probes with resolved callbacks"
+        );
+        println!("  netfilter - List netfilter hooks with priorities and resolved callbacks");
+        println!(
+            "  notifiers - List keyboard, module, netdev, reboot and panic notifier callbacks"
+        );
+        println!("  ttycheck - Check tty line discipline receive_buf and driver write handlers");
+        println!("  opscheck - Check syscall tables, IDT, /proc and network ops structures for hijacked pointers");
+        println!("  namespaces - List per-process namespaces, namespace-local PIDs, hostnames and cgroups");
+        println!(
+            "  containers - Group processes into containers by namespace set, hostname and cgroup"
+        );
+        println!(
+            "  ldrmodules - Compare executable file mappings with the dynamic loader's link_map"
+        );
+        println!("  elfs - Carve ELF images from process memory with build IDs");
+        println!("  yarascan - Scan physical, kernel or process memory with YARA-style rules");
+        println!(
+            "  strings - Attribute strings in the dump to physical, kernel and process addresses"
+        );
+        println!(
+            "  pageinfo - Decode the struct page (flags, mapping, counts) of a physical address"
+        );
+        println!("  maps - List process memory mappings with resident, swapped and unavailable page counts");
         return Ok(());
     }
 
@@ -195,6 +243,62 @@ fn main() -> Result<(), AnalysisError> {
         }
     }
 
+    // Identify the data model (byte order, pointer size) KernelParser reads with:
+    // an ELF core header, then dwarf2json's pointer type, then the banner's compiler.
+    // A banner naming a 32-bit compiler is ignored when the symbols are 64-bit.
+    let symbols_64bit = symbol_resolver
+        .get_symbol_address("init_task")
+        .is_some_and(|addr| addr > u32::MAX as u64);
+    let target = Target::from_elf_header(mapped)
+        .or_else(|| symbol_resolver.target())
+        .or_else(|| {
+            SymbolResolver::find_banner(mapped)
+                .and_then(|banner| Target::from_banner(&banner))
+                .filter(|t| t.pointer_size == 8 || !symbols_64bit)
+        })
+        .unwrap_or_default();
+    translator.set_target(target);
+    if target == Target::default() {
+        debug!("[DEBUG] Target data model: {}", target.describe());
+    } else {
+        warn!("✓ Detected {} kernel", target.describe());
+        if target.pointer_size == 4 {
+            warn!("[WARNING] Kernel reads use 4-byte pointers, but address translation only supports x86-64 and AArch64 layouts");
+        }
+    }
+
+    // STEP 0: Detect the architecture. AArch64 kernels record their memory layout
+    // (kimage_voffset, PHYS_OFFSET, VA_BITS) in the image, which also gives the
+    // virtual KASLR slide, so the x86-64 phys_base / PAGE_OFFSET steps are skipped.
+    let aarch64 = match cli.arch {
+        ArchArg::Auto => symbol_resolver.is_aarch64_kernel(),
+        ArchArg::X86_64 => false,
+        ArchArg::Aarch64 => true,
+    };
+    if aarch64 {
+        let (layout, slide) = symbol_resolver
+            .detect_aarch64_layout(mapped, &translator)
+            .ok_or_else(|| {
+                AnalysisError::SymbolNotFound(
+                    "Could not determine the AArch64 memory layout - linux_banner and init_task \
+                     were not found through the kernel image mapping."
+                        .to_string(),
+                )
+            })?;
+        warn!(
+            "✓ Detected AArch64 kernel: {}-bit VA, {}K pages, kimage_voffset 0x{:x}, PHYS_OFFSET 0x{:x}",
+            layout.va_bits,
+            layout.page_size() / 1024,
+            layout.kimage_voffset,
+            layout.phys_offset
+        );
+        if slide != 0 {
+            warn!("✓ Detected virtual KASLR slide: 0x{:x}", slide);
+            symbol_resolver.apply_kaslr_slide(slide);
+        }
+        translator.set_architecture(Architecture::Aarch64(layout));
+    }
+
     // STEP 1: Detect KASLR offset and find init_task (with temp phys_base)
     // This uses heuristic search if needed and finds the CORRECT init_task location
     debug!("[DEBUG] Detecting KASLR offset to find actual init_task location...");
@@ -206,442 +310,543 @@ fn main() -> Result<(), AnalysisError> {
 
     warn!("Found init_task at file offset: 0x{:x}", init_task_offset);
 
-    // STEP 2: Now recalculate phys_base using the CORRECT init_task location
-    // This is critical - we need phys_base to translate virtual addresses in the process list
-    let phys_base_candidates = symbol_resolver.calculate_phys_base_candidates();
+    if !aarch64 {
+        // STEP 2: Now recalculate phys_base using the CORRECT init_task location
+        // This is critical - we need phys_base to translate virtual addresses in the process list
+        let phys_base_candidates = symbol_resolver.calculate_phys_base_candidates();
 
-    if phys_base_candidates.is_empty() {
-        warn!("[WARNING] Could not calculate phys_base candidates from _text symbol");
-        warn!(
-            "Using default phys_base: 0x{:x}",
-            translator.get_phys_base()
-        );
-        warn!("This may cause incorrect address translation - ensure symbol file contains _text");
-    } else {
-        warn!("Recalculating phys_base using found init_task location...");
-        warn!(
-            "Testing {} phys_base candidate(s)...",
-            phys_base_candidates.len()
-        );
+        if phys_base_candidates.is_empty() {
+            warn!("[WARNING] Could not calculate phys_base candidates from _text symbol");
+            warn!(
+                "Using default phys_base: 0x{:x}",
+                translator.get_phys_base()
+            );
+            warn!(
+                "This may cause incorrect address translation - ensure symbol file contains _text"
+            );
+        } else {
+            warn!("Recalculating phys_base using found init_task location...");
+            warn!(
+                "Testing {} phys_base candidate(s)...",
+                phys_base_candidates.len()
+            );
 
-        let mut found_valid_phys_base = false;
+            let mut found_valid_phys_base = false;
 
-        // Get the virtual address of init_task from symbols (if available)
-        if let Some(init_task_vaddr) = symbol_resolver.get_symbol_address("init_task") {
-            // Get PID offset from structure definitions
-            let pid_offset = symbol_resolver
-                .get_struct_field_offset("task_struct", "pid", None)
-                .unwrap_or(2384); // Fallback to common offset if not in symbols
+            // Get the virtual address of init_task from symbols (if available)
+            if let Some(init_task_vaddr) = symbol_resolver.get_symbol_address("init_task") {
+                // Get PID offset from structure definitions
+                let pid_offset = symbol_resolver
+                    .get_struct_field_offset("task_struct", "pid", None)
+                    .unwrap_or(2384); // Fallback to common offset if not in symbols
 
-            debug!("[DEBUG] Validating phys_base candidates:");
-            debug!(
-                "[DEBUG]   init_task vaddr from symbols: 0x{:x}",
-                init_task_vaddr
-            );
-            debug!(
-                "[DEBUG]   init_task file offset found: 0x{:x}",
-                init_task_offset
-            );
-            debug!("[DEBUG]   PID offset in task_struct: 0x{:x}", pid_offset);
+                debug!("[DEBUG] Validating phys_base candidates:");
+                debug!(
+                    "[DEBUG]   init_task vaddr from symbols: 0x{:x}",
+                    init_task_vaddr
+                );
+                debug!(
+                    "[DEBUG]   init_task file offset found: 0x{:x}",
+                    init_task_offset
+                );
+                debug!("[DEBUG]   PID offset in task_struct: 0x{:x}", pid_offset);
 
-            // Validate each candidate by checking if it correctly translates init_task vaddr to the found file offset
-            for (i, &candidate) in phys_base_candidates.iter().enumerate() {
-                translator.set_phys_base(candidate);
+                // Validate each candidate by checking if it correctly translates init_task vaddr to the found file offset
+                for (i, &candidate) in phys_base_candidates.iter().enumerate() {
+                    translator.set_phys_base(candidate);
 
-                // Try to translate init_task virtual address with this phys_base
-                if let Some(translated_offset) = translator.virtual_to_file_offset(init_task_vaddr)
-                {
-                    debug!(
-                        "[DEBUG]   Candidate {}: phys_base=0x{:x} translates to file_offset=0x{:x}",
-                        i + 1,
-                        candidate,
-                        translated_offset
-                    );
+                    // Try to translate init_task virtual address with this phys_base
+                    if let Some(translated_offset) =
+                        translator.virtual_to_file_offset(init_task_vaddr)
+                    {
+                        debug!(
+                            "[DEBUG]   Candidate {}: phys_base=0x{:x} translates to file_offset=0x{:x}",
+                            i + 1,
+                            candidate,
+                            translated_offset
+                        );
 
-                    // Check if this translation matches the init_task offset we found via heuristic search
-                    // Allow a small tolerance for structure alignment
-                    let offset_diff = translated_offset.abs_diff(init_task_offset as u64);
-
-                    if offset_diff < 0x1000 {
-                        // Within 4KB tolerance
-                        // Verify by reading PID at the found location
-                        let pid_file_offset = init_task_offset + pid_offset as usize;
-                        if pid_file_offset + 4 <= mapped.len() {
-                            let pid_bytes = &mapped[pid_file_offset..pid_file_offset + 4];
-                            let pid = i32::from_le_bytes([
-                                pid_bytes[0],
-                                pid_bytes[1],
-                                pid_bytes[2],
-                                pid_bytes[3],
-                            ]);
-
-                            debug!("[DEBUG]     Translation matches found init_task (offset_diff=0x{:x}), PID={}",
-                                     offset_diff, pid);
-
-                            if pid == 0 {
-                                warn!("✓ Found valid phys_base: 0x{:x} (translates correctly to found init_task with PID 0)", candidate);
-                                found_valid_phys_base = true;
-                                break;
+                        // Check if this translation matches the init_task offset we found via heuristic search
+                        // Allow a small tolerance for structure alignment
+                        let offset_diff = translated_offset.abs_diff(init_task_offset as u64);
+
+                        if offset_diff < 0x1000 {
+                            // Within 4KB tolerance
+                            // Verify by reading PID at the found location
+                            let pid_file_offset = init_task_offset + pid_offset as usize;
+                            if let Some(pid) =
+                                kernel::KernelParser::read_i32(mapped, pid_file_offset, target)
+                            {
+                                debug!("[DEBUG]     Translation matches found init_task (offset_diff=0x{:x}), PID={}",
+                                         offset_diff, pid);
+
+                                if pid == 0 {
+                                    warn!("✓ Found valid phys_base: 0x{:x} (translates correctly to found init_task with PID 0)", candidate);
+                                    found_valid_phys_base = true;
+                                    break;
+                                }
                             }
+                        } else {
+                            debug!(
+                                "[DEBUG]     Translation doesn't match (offset_diff=0x{:x})",
+                                offset_diff
+                            );
                         }
                     } else {
                         debug!(
-                            "[DEBUG]     Translation doesn't match (offset_diff=0x{:x})",
-                            offset_diff
+                            "[DEBUG]   Candidate {}: phys_base=0x{:x} - translation failed",
+                            i + 1,
+                            candidate
                         );
                     }
-                } else {
-                    debug!(
-                        "[DEBUG]   Candidate {}: phys_base=0x{:x} - translation failed",
-                        i + 1,
-                        candidate
+                }
+
+                if !found_valid_phys_base {
+                    warn!(
+                        "[WARNING] None of the phys_base candidates correctly translate init_task"
                     );
+                    warn!("This likely means:");
+                    warn!("  1. The memory regions in LIME header may be incomplete");
+                    warn!("  2. The _text symbol address may be incorrect");
+                    warn!("Attempting to calculate phys_base directly from found init_task...");
+
+                    // Try to calculate phys_base directly from the found init_task location
+                    // We know: file_offset = (vaddr - region.start) + region.file_offset
+                    // Rearranging: region.start = vaddr - (file_offset - region.file_offset)
+                    // For kernel: phys_base ≈ region.start (for main kernel region)
+
+                    // Find which region contains our init_task file offset
+                    for region in translator.get_regions() {
+                        if init_task_offset >= region.file_offset as usize
+                            && init_task_offset
+                                < (region.file_offset + (region.end - region.start)) as usize
+                        {
+                            // Calculate the physical address of init_task from the file offset
+                            let offset_in_region = init_task_offset as u64 - region.file_offset;
+                            let physical_addr = region.start + offset_in_region;
+
+                            debug!(
+                                "[DEBUG] Found init_task in region: start=0x{:x}, end=0x{:x}",
+                                region.start, region.end
+                            );
+                            debug!("[DEBUG]   File offset in region: 0x{:x}", offset_in_region);
+                            debug!(
+                                "[DEBUG]   Physical address of init_task: 0x{:x}",
+                                physical_addr
+                            );
+                            debug!(
+                                "[DEBUG]   Virtual address from symbols: 0x{:x}",
+                                init_task_vaddr
+                            );
+
+                            // Calculate phys_base using the kernel mapping formula:
+                            // For kernel text (0xffffffff80000000+): physical = phys_base + (virtual - 0xffffffff80000000)
+                            // Rearranging: phys_base = physical - (virtual - 0xffffffff80000000)
+                            const KERNEL_MAP_BASE: u64 = 0xffffffff80000000;
+                            let calculated_phys_base =
+                                physical_addr.wrapping_sub(init_task_vaddr - KERNEL_MAP_BASE);
+
+                            debug!(
+                                "[DEBUG]   Calculated phys_base: 0x{:x}",
+                                calculated_phys_base
+                            );
+                            translator.set_phys_base(calculated_phys_base);
+
+                            warn!(
+                                "✓ Calculated phys_base from memory region: 0x{:x}",
+                                calculated_phys_base
+                            );
+                            found_valid_phys_base = true;
+                            break;
+                        }
+                    }
+
+                    if !found_valid_phys_base {
+                        warn!("[WARNING] Could not calculate phys_base from init_task location");
+                        warn!("Using first candidate: 0x{:x}", phys_base_candidates[0]);
+                        translator.set_phys_base(phys_base_candidates[0]);
+                    }
                 }
+            } else {
+                warn!("[WARNING] init_task symbol not found, cannot validate phys_base");
+                warn!("Using first candidate: 0x{:x}", phys_base_candidates[0]);
+                translator.set_phys_base(phys_base_candidates[0]);
             }
+        }
 
-            if !found_valid_phys_base {
-                warn!("[WARNING] None of the phys_base candidates correctly translate init_task");
-                warn!("This likely means:");
-                warn!("  1. The memory regions in LIME header may be incomplete");
-                warn!("  2. The _text symbol address may be incorrect");
-                warn!("Attempting to calculate phys_base directly from found init_task...");
+        // STEP 3: Detect and validate PAGE_OFFSET for direct mapping translations
+        debug!("[DEBUG] Detecting PAGE_OFFSET using candidate validation approach...");
+
+        // Get structure field offsets
+        let tasks_offset = symbol_resolver
+            .get_struct_field_offset("task_struct", "tasks", None)
+            .unwrap_or(0xa00) as usize;
+        let pid_offset = symbol_resolver
+            .get_struct_field_offset("task_struct", "pid", None)
+            .unwrap_or(0xad0) as usize;
+        let comm_offset = symbol_resolver
+            .get_struct_field_offset("task_struct", "comm", None)
+            .unwrap_or(0xcf0) as usize;
+        let state_offset = symbol_resolver
+            .get_struct_field_offset("task_struct", "__state", None)
+            .or_else(|| symbol_resolver.get_struct_field_offset("task_struct", "state", None))
+            .unwrap_or(0x18) as usize;
+
+        // Read tasks.next pointer from init_task
+        let tasks_next_ptr =
+            kernel::KernelParser::read_pointer(mapped, init_task_offset + tasks_offset, target)
+                .ok_or_else(|| {
+                    AnalysisError::ParseError(
+                        "Failed to read tasks.next from init_task".to_string(),
+                    )
+                })?;
+
+        debug!("[DEBUG] tasks.next from init_task: 0x{:x}", tasks_next_ptr);
+
+        // Check if tasks.next is in direct mapping range
+        if tasks_next_ptr >= 0xffff000000000000 && tasks_next_ptr < 0xffffffff00000000 {
+            debug!("[DEBUG] tasks.next is in direct mapping range");
+
+            // Helper function to generate PAGE_OFFSET candidates
+            let generate_page_offset_candidates = || -> Vec<u64> {
+                let mut candidates = Vec::new();
+
+                // Standard values (try these first)
+                candidates.push(0xffff880000000000); // 4-level standard
+                candidates.push(0xffff888000000000); // 5-level standard
+
+                // KASLR variations in 1GB increments
+                let start = 0xffff800000000000u64;
+                let end = 0xffffb00000000000u64;
+                let step = 0x40000000u64; // 1GB
+
+                let mut addr = start;
+                while addr <= end {
+                    if !candidates.contains(&addr) {
+                        candidates.push(addr);
+                    }
+                    addr = addr.saturating_add(step);
+                }
 
-                // Try to calculate phys_base directly from the found init_task location
-                // We know: file_offset = (vaddr - region.start) + region.file_offset
-                // Rearranging: region.start = vaddr - (file_offset - region.file_offset)
-                // For kernel: phys_base ≈ region.start (for main kernel region)
+                candidates
+            };
 
-                // Find which region contains our init_task file offset
+            // Helper function to translate physical address to file offset
+            let phys_to_file_offset = |phys_addr: u64| -> Option<usize> {
                 for region in translator.get_regions() {
-                    if init_task_offset >= region.file_offset as usize
-                        && init_task_offset
-                            < (region.file_offset + (region.end - region.start)) as usize
-                    {
-                        // Calculate the physical address of init_task from the file offset
-                        let offset_in_region = init_task_offset as u64 - region.file_offset;
-                        let physical_addr = region.start + offset_in_region;
+                    if phys_addr >= region.start && phys_addr < region.end {
+                        let offset_in_region = phys_addr - region.start;
+                        return Some((region.file_offset + offset_in_region) as usize);
+                    }
+                }
+                None
+            };
 
-                        debug!(
-                            "[DEBUG] Found init_task in region: start=0x{:x}, end=0x{:x}",
-                            region.start, region.end
-                        );
-                        debug!("[DEBUG]   File offset in region: 0x{:x}", offset_in_region);
-                        debug!(
-                            "[DEBUG]   Physical address of init_task: 0x{:x}",
-                            physical_addr
-                        );
-                        debug!(
-                            "[DEBUG]   Virtual address from symbols: 0x{:x}",
-                            init_task_vaddr
-                        );
+            // Helper function to validate task_struct at given offset
+            let validate_next_task_candidate = |file_offset: usize| -> Option<(u32, String)> {
+                // Boundary check
+                if file_offset + comm_offset + 16 > mapped.len() {
+                    return None;
+                }
 
-                        // Calculate phys_base using the kernel mapping formula:
-                        // For kernel text (0xffffffff80000000+): physical = phys_base + (virtual - 0xffffffff80000000)
-                        // Rearranging: phys_base = physical - (virtual - 0xffffffff80000000)
-                        const KERNEL_MAP_BASE: u64 = 0xffffffff80000000;
-                        let calculated_phys_base =
-                            physical_addr.wrapping_sub(init_task_vaddr - KERNEL_MAP_BASE);
+                let mut score = 0u32;
 
-                        debug!(
-                            "[DEBUG]   Calculated phys_base: 0x{:x}",
-                            calculated_phys_base
-                        );
-                        translator.set_phys_base(calculated_phys_base);
+                // 1. Validate PID
+                let pid = kernel::KernelParser::read_i32(mapped, file_offset + pid_offset, target)?;
+                if pid <= 0 || pid >= 1000000 {
+                    return None;
+                }
+                score += 20;
 
-                        warn!(
-                            "✓ Calculated phys_base from memory region: 0x{:x}",
-                            calculated_phys_base
-                        );
-                        found_valid_phys_base = true;
-                        break;
-                    }
+                // 2. Validate comm
+                let comm = kernel::KernelParser::read_string(mapped, file_offset + comm_offset, 16)
+                    .unwrap_or_default();
+                if comm.len() < 2 {
+                    return None;
                 }
+                let has_alpha = comm.chars().any(|c| c.is_alphanumeric());
+                if !has_alpha {
+                    return None;
+                }
+                if !comm
+                    .chars()
+                    .all(|c| c.is_ascii_graphic() || c.is_whitespace())
+                {
+                    return None;
+                }
+                score += comm.len() as u32 * 10;
+                score += comm.chars().filter(|c| c.is_alphanumeric()).count() as u32 * 5;
+
+                // 3. Validate state
+                let state =
+                    kernel::KernelParser::read_i32(mapped, file_offset + state_offset, target)?;
+                if state < -1 || state > 1024 {
+                    return None;
+                }
+                score += 30;
 
-                if !found_valid_phys_base {
-                    warn!("[WARNING] Could not calculate phys_base from init_task location");
-                    warn!("Using first candidate: 0x{:x}", phys_base_candidates[0]);
-                    translator.set_phys_base(phys_base_candidates[0]);
+                // 4. Validate tasks.next pointer
+                let tasks_next =
+                    kernel::KernelParser::read_pointer(mapped, file_offset + tasks_offset, target)?;
+                if tasks_next < 0xffff800000000000 || tasks_next == 0xffffffffffffffff {
+                    return None;
                 }
-            }
-        } else {
-            warn!("[WARNING] init_task symbol not found, cannot validate phys_base");
-            warn!("Using first candidate: 0x{:x}", phys_base_candidates[0]);
-            translator.set_phys_base(phys_base_candidates[0]);
-        }
-    }
+                score += 20;
 
-    // STEP 3: Detect and validate PAGE_OFFSET for direct mapping translations
-    debug!("[DEBUG] Detecting PAGE_OFFSET using candidate validation approach...");
-
-    // Get structure field offsets
-    let tasks_offset = symbol_resolver
-        .get_struct_field_offset("task_struct", "tasks", None)
-        .unwrap_or(0xa00) as usize;
-    let pid_offset = symbol_resolver
-        .get_struct_field_offset("task_struct", "pid", None)
-        .unwrap_or(0xad0) as usize;
-    let comm_offset = symbol_resolver
-        .get_struct_field_offset("task_struct", "comm", None)
-        .unwrap_or(0xcf0) as usize;
-    let state_offset = symbol_resolver
-        .get_struct_field_offset("task_struct", "__state", None)
-        .or_else(|| symbol_resolver.get_struct_field_offset("task_struct", "state", None))
-        .unwrap_or(0x18) as usize;
-
-    // Read tasks.next pointer from init_task
-    let tasks_next_ptr = kernel::KernelParser::read_u64(mapped, init_task_offset + tasks_offset)
-        .ok_or_else(|| {
-            AnalysisError::ParseError("Failed to read tasks.next from init_task".to_string())
-        })?;
-
-    debug!("[DEBUG] tasks.next from init_task: 0x{:x}", tasks_next_ptr);
-
-    // Check if tasks.next is in direct mapping range
-    if tasks_next_ptr >= 0xffff000000000000 && tasks_next_ptr < 0xffffffff00000000 {
-        debug!("[DEBUG] tasks.next is in direct mapping range");
-
-        // Helper function to generate PAGE_OFFSET candidates
-        let generate_page_offset_candidates = || -> Vec<u64> {
-            let mut candidates = Vec::new();
-
-            // Standard values (try these first)
-            candidates.push(0xffff880000000000); // 4-level standard
-            candidates.push(0xffff888000000000); // 5-level standard
-
-            // KASLR variations in 1GB increments
-            let start = 0xffff800000000000u64;
-            let end = 0xffffb00000000000u64;
-            let step = 0x40000000u64; // 1GB
-
-            let mut addr = start;
-            while addr <= end {
-                if !candidates.contains(&addr) {
-                    candidates.push(addr);
+                // Bonus: Check for known good process names
+                if comm.starts_with("systemd") || comm.starts_with("kthreadd") {
+                    score += 100;
                 }
-                addr = addr.saturating_add(step);
-            }
 
-            candidates
-        };
+                Some((score, comm))
+            };
 
-        // Helper function to translate physical address to file offset
-        let phys_to_file_offset = |phys_addr: u64| -> Option<usize> {
-            for region in translator.get_regions() {
-                if phys_addr >= region.start && phys_addr < region.end {
-                    let offset_in_region = phys_addr - region.start;
-                    return Some((region.file_offset + offset_in_region) as usize);
-                }
-            }
-            None
-        };
+            // Generate PAGE_OFFSET candidates
+            let candidates = generate_page_offset_candidates();
+            let candidates_count = candidates.len();
+            debug!(
+                "[DEBUG] Testing {} PAGE_OFFSET candidates...",
+                candidates_count
+            );
 
-        // Helper function to validate task_struct at given offset
-        let validate_next_task_candidate = |file_offset: usize| -> Option<(u32, String)> {
-            // Boundary check
-            if file_offset + comm_offset + 16 > mapped.len() {
-                return None;
+            struct ValidCandidate {
+                page_offset: u64,
+                #[allow(dead_code)] // Reserved for future use
+                file_offset: usize,
+                score: u32,
+                comm: String,
+                is_4level: bool,
             }
 
-            let mut score = 0u32;
+            let mut valid_candidates: Vec<ValidCandidate> = Vec::new();
+            let mut rejections_logged = 0;
 
-            // 1. Validate PID
-            let pid = kernel::KernelParser::read_i32(mapped, file_offset + pid_offset)?;
-            if pid <= 0 || pid >= 1000000 {
-                return None;
-            }
-            score += 20;
+            // Test each PAGE_OFFSET candidate
+            for candidate_page_offset in candidates {
+                // Calculate expected physical address of next task
+                let next_task_phys = tasks_next_ptr.wrapping_sub(candidate_page_offset);
 
-            // 2. Validate comm
-            let comm = kernel::KernelParser::read_string(mapped, file_offset + comm_offset, 16)
-                .unwrap_or_default();
-            if comm.len() < 2 {
-                return None;
-            }
-            let has_alpha = comm.chars().any(|c| c.is_alphanumeric());
-            if !has_alpha {
-                return None;
-            }
-            if !comm
-                .chars()
-                .all(|c| c.is_ascii_graphic() || c.is_whitespace())
-            {
-                return None;
-            }
-            score += comm.len() as u32 * 10;
-            score += comm.chars().filter(|c| c.is_alphanumeric()).count() as u32 * 5;
+                // Check if physical address is within ANY captured memory region
+                let is_within_regions = translator
+                    .get_regions()
+                    .iter()
+                    .any(|region| next_task_phys >= region.start && next_task_phys <= region.end);
 
-            // 3. Validate state
-            let state = kernel::KernelParser::read_i32(mapped, file_offset + state_offset)?;
-            if state < -1 || state > 1024 {
-                return None;
-            }
-            score += 30;
+                if !is_within_regions {
+                    // Only log first 10 rejections to avoid spam
+                    if rejections_logged < 10 {
+                        debug!(
+                            "[DEBUG] Skipping candidate 0x{:x}: physical 0x{:x} not in any region",
+                            candidate_page_offset, next_task_phys
+                        );
+                        rejections_logged += 1;
+                    }
+                    continue;
+                }
 
-            // 4. Validate tasks.next pointer
-            let tasks_next = kernel::KernelParser::read_u64(mapped, file_offset + tasks_offset)?;
-            if tasks_next < 0xffff800000000000 || tasks_next == 0xffffffffffffffff {
-                return None;
+                // Translate to file offset
+                let file_offset = match phys_to_file_offset(next_task_phys) {
+                    Some(offset) => offset,
+                    None => continue,
+                };
+
+                // tasks.next points to the 'tasks' field within task_struct, not the base
+                // Subtract tasks_offset to get the actual struct base for validation
+                let task_struct_base = file_offset.saturating_sub(tasks_offset);
+
+                // Validate task_struct at this location
+                if let Some((score, comm)) = validate_next_task_candidate(task_struct_base) {
+                    // Determine paging level (4-level vs 5-level)
+                    let diff_4level =
+                        (candidate_page_offset as i64 - 0xffff880000000000u64 as i64).abs();
+                    let diff_5level =
+                        (candidate_page_offset as i64 - 0xffff888000000000u64 as i64).abs();
+                    let is_4level = diff_4level < diff_5level;
+
+                    debug!("[DEBUG] ✓ Valid candidate: PAGE_OFFSET=0x{:x}, task_base=0x{:x}, comm='{}', score={}",
+                             candidate_page_offset, task_struct_base, comm, score);
+
+                    valid_candidates.push(ValidCandidate {
+                        page_offset: candidate_page_offset,
+                        file_offset: task_struct_base, // Store the base, not the tasks field offset
+                        score,
+                        comm,
+                        is_4level,
+                    });
+                }
             }
-            score += 20;
 
-            // Bonus: Check for known good process names
-            if comm.starts_with("systemd") || comm.starts_with("kthreadd") {
-                score += 100;
+            // Log summary of candidate validation
+            if rejections_logged >= 10 {
+                debug!(
+                    "[DEBUG] Tested {} candidates, found {} valid (showing only first 10 rejections)",
+                    candidates_count,
+                    valid_candidates.len()
+                );
             }
 
-            Some((score, comm))
-        };
+            // Choose best candidate by score
+            if !valid_candidates.is_empty() {
+                valid_candidates.sort_by_key(|b| std::cmp::Reverse(b.score));
 
-        // Generate PAGE_OFFSET candidates
-        let candidates = generate_page_offset_candidates();
-        let candidates_count = candidates.len();
-        debug!(
-            "[DEBUG] Testing {} PAGE_OFFSET candidates...",
-            candidates_count
-        );
-
-        struct ValidCandidate {
-            page_offset: u64,
-            #[allow(dead_code)] // Reserved for future use
-            file_offset: usize,
-            score: u32,
-            comm: String,
-            is_4level: bool,
-        }
-
-        let mut valid_candidates: Vec<ValidCandidate> = Vec::new();
-        let mut rejections_logged = 0;
+                debug!(
+                    "[DEBUG] Found {} valid PAGE_OFFSET candidates:",
+                    valid_candidates.len()
+                );
+                for (i, cand) in valid_candidates.iter().take(5).enumerate() {
+                    debug!(
+                        "[DEBUG]   {}. PAGE_OFFSET=0x{:x}, comm='{}', score={}",
+                        i + 1,
+                        cand.page_offset,
+                        cand.comm,
+                        cand.score
+                    );
+                }
 
-        // Test each PAGE_OFFSET candidate
-        for candidate_page_offset in candidates {
-            // Calculate expected physical address of next task
-            let next_task_phys = tasks_next_ptr.wrapping_sub(candidate_page_offset);
+                let best = &valid_candidates[0];
+                debug!(
+                    "[DEBUG] ✓ Selected best candidate: PAGE_OFFSET=0x{:x}, comm='{}'",
+                    best.page_offset, best.comm
+                );
 
-            // Check if physical address is within ANY captured memory region
-            let is_within_regions = translator
-                .get_regions()
-                .iter()
-                .any(|region| next_task_phys >= region.start && next_task_phys <= region.end);
+                // Set PAGE_OFFSET based on paging level
+                if best.is_4level {
+                    debug!("[DEBUG] ✓ Detected 4-level paging");
+                    translator.set_page_offset_4level(best.page_offset);
+                } else {
+                    debug!("[DEBUG] ✓ Detected 5-level paging");
+                    translator.set_page_offset_5level(best.page_offset);
+                }
 
-            if !is_within_regions {
-                // Only log first 10 rejections to avoid spam
-                if rejections_logged < 10 {
+                warn!(
+                    "✓ Successfully detected PAGE_OFFSET: 0x{:x}",
+                    best.page_offset
+                );
+            } else {
+                warn!("[WARNING] Could not detect PAGE_OFFSET using candidate validation");
+                debug!("[DEBUG] Attempting to derive PAGE_OFFSET from init_task...");
+
+                // Stage 2: Try derivation method
+                if let Some(derived_offset) = symbol_resolver.derive_page_offset_from_init_task(
+                    mapped,
+                    &translator,
+                    init_task_offset,
+                    tasks_offset,
+                ) {
                     debug!(
-                        "[DEBUG] Skipping candidate 0x{:x}: physical 0x{:x} not in any region",
-                        candidate_page_offset, next_task_phys
+                        "[DEBUG] ✓ Successfully derived PAGE_OFFSET: 0x{:x}",
+                        derived_offset
                     );
-                    rejections_logged += 1;
+                    translator.set_page_offset_5level(derived_offset);
+                    translator.set_page_offset_4level(derived_offset); // Set both to same value for KASLR
+                    warn!(
+                        "✓ Derived PAGE_OFFSET from init_task: 0x{:x}",
+                        derived_offset
+                    );
+                } else {
+                    warn!("[WARNING] Could not derive PAGE_OFFSET - using defaults");
+                    warn!("[WARNING] Direct mapping translations may be incorrect");
                 }
-                continue;
             }
+        } else {
+            debug!("[DEBUG] tasks.next not in direct mapping range (likely kernel text mapping)");
+            debug!("[DEBUG] PAGE_OFFSET detection not needed for this kernel configuration");
+        }
 
-            // Translate to file offset
-            let file_offset = match phys_to_file_offset(next_task_phys) {
-                Some(offset) => offset,
-                None => continue,
-            };
-
-            // tasks.next points to the 'tasks' field within task_struct, not the base
-            // Subtract tasks_offset to get the actual struct base for validation
-            let task_struct_base = file_offset.saturating_sub(tasks_offset);
-
-            // Validate task_struct at this location
-            if let Some((score, comm)) = validate_next_task_candidate(task_struct_base) {
-                // Determine paging level (4-level vs 5-level)
-                let diff_4level =
-                    (candidate_page_offset as i64 - 0xffff880000000000u64 as i64).abs();
-                let diff_5level =
-                    (candidate_page_offset as i64 - 0xffff888000000000u64 as i64).abs();
-                let is_4level = diff_4level < diff_5level;
-
-                debug!("[DEBUG] ✓ Valid candidate: PAGE_OFFSET=0x{:x}, task_base=0x{:x}, comm='{}', score={}",
-                         candidate_page_offset, task_struct_base, comm, score);
-
-                valid_candidates.push(ValidCandidate {
-                    page_offset: candidate_page_offset,
-                    file_offset: task_struct_base, // Store the base, not the tasks field offset
-                    score,
-                    comm,
-                    is_4level,
-                });
+        // STEP 4: Detect the virtual KASLR slide so pointers into the kernel image can be followed
+        // Symbol addresses are static, but pointers read from memory hold runtime addresses.
+        // Relocating the symbols and the translator lets both be used interchangeably.
+        match symbol_resolver.detect_virtual_kaslr_slide(mapped, &translator, init_task_offset) {
+            Some(slide) if slide != 0 => {
+                warn!("✓ Detected virtual KASLR slide: 0x{:x}", slide);
+                symbol_resolver.apply_kaslr_slide(slide);
+                translator.set_phys_base(translator.get_phys_base().wrapping_sub(slide as u64));
+            }
+            Some(_) => {
+                debug!("[DEBUG] No virtual KASLR slide detected");
+            }
+            None => {
+                debug!("[DEBUG] Could not determine virtual KASLR slide, assuming none");
             }
         }
 
-        // Log summary of candidate validation
-        if rejections_logged >= 10 {
-            debug!(
-                "[DEBUG] Tested {} candidates, found {} valid (showing only first 10 rejections)",
-                candidates_count,
-                valid_candidates.len()
-            );
+        // STEP 5: Detect 5-level paging (LA57) for process page table walks
+        if let Some(l5_addr) = symbol_resolver.get_symbol_address("__pgtable_l5_enabled") {
+            if let Some(offset) = translator.virtual_to_file_offset(l5_addr) {
+                if kernel::KernelParser::read_u32(mapped, offset as usize, target) == Some(1) {
+                    warn!("✓ Detected 5-level paging");
+                    translator.set_paging_levels(5);
+                }
+            }
         }
+    }
 
-        // Choose best candidate by score
-        if !valid_candidates.is_empty() {
-            valid_candidates.sort_by(|a, b| b.score.cmp(&a.score));
-
+    // STEP 6: Load the kernel page table so vmalloc, vmemmap and module addresses
+    // (BPF programs, struct page, zsmalloc pages, module data) translate
+    match kernel::rmap::kernel_pgd_phys(&translator, &symbol_resolver) {
+        Some(pgd_phys) => {
+            let ranges =
+                kernel::rmap::kernel_page_table_ranges(mapped, &translator, &symbol_resolver);
+            translator.load_kernel_page_table(mapped, pgd_phys, &ranges);
             debug!(
-                "[DEBUG] Found {} valid PAGE_OFFSET candidates:",
-                valid_candidates.len()
+                "[DEBUG] Loaded {} kernel page table mappings",
+                translator.kernel_mapping_count()
             );
-            for (i, cand) in valid_candidates.iter().take(5).enumerate() {
-                debug!(
-                    "[DEBUG]   {}. PAGE_OFFSET=0x{:x}, comm='{}', score={}",
-                    i + 1,
-                    cand.page_offset,
-                    cand.comm,
-                    cand.score
-                );
-            }
+        }
+        None => {
+            warn!("[WARNING] Kernel page table not found; vmalloc, vmemmap and module memory is unreadable");
+        }
+    }
 
-            let best = &valid_candidates[0];
-            debug!(
-                "[DEBUG] ✓ Selected best candidate: PAGE_OFFSET=0x{:x}, comm='{}'",
-                best.page_offset, best.comm
+    // STEP 7: Find compressed swap (zram / zswap) and attach swap area images so
+    // swapped-out user pages can be read
+    let areas = kernel::swap::swap_areas(mapped, &translator, &symbol_resolver);
+    for area in &areas {
+        debug!(
+            "[DEBUG] Swap type {}: {} ({} pages)",
+            area.swap_type, area.path, area.pages
+        );
+    }
+    if !areas.is_empty() {
+        let compressed =
+            kernel::swap::CompressedSwap::new(mapped, &translator, &symbol_resolver, &areas);
+        if !compressed.is_empty() {
+            warn!(
+                "✓ Compressed swap: {} zram device(s), {} zswap page(s)",
+                compressed.zram_devices(),
+                compressed.zswap_pages()
             );
-
-            // Set PAGE_OFFSET based on paging level
-            if best.is_4level {
-                debug!("[DEBUG] ✓ Detected 4-level paging");
-                translator.set_page_offset_4level(best.page_offset);
-            } else {
-                debug!("[DEBUG] ✓ Detected 5-level paging");
-                translator.set_page_offset_5level(best.page_offset);
-            }
-
+            translator.set_compressed_swap(compressed);
+        }
+    }
+    for (index, image) in cli.swap.iter().enumerate() {
+        // Without an explicit type, match the file name against the dump's swap
+        // areas, then fall back to command-line order
+        let file_name = image.path.file_name();
+        let swap_type = image
+            .swap_type
+            .or_else(|| {
+                areas
+                    .iter()
+                    .find(|a| std::path::Path::new(&a.path).file_name() == file_name)
+                    .map(|a| a.swap_type)
+            })
+            .or_else(|| areas.get(index).map(|a| a.swap_type))
+            .unwrap_or(index as u8);
+        let swap_map = MemoryMap::new(&image.path.to_string_lossy())?;
+        if !swap_map.is_swap() {
             warn!(
-                "✓ Successfully detected PAGE_OFFSET: 0x{:x}",
-                best.page_offset
+                "[WARNING] {} has no swap signature; reading it as swap type {} anyway",
+                image.path.display(),
+                swap_type
             );
-        } else {
-            warn!("[WARNING] Could not detect PAGE_OFFSET using candidate validation");
-            debug!("[DEBUG] Attempting to derive PAGE_OFFSET from init_task...");
-
-            // Stage 2: Try derivation method
-            if let Some(derived_offset) = symbol_resolver.derive_page_offset_from_init_task(
-                mapped,
-                &translator,
-                init_task_offset,
-                tasks_offset,
-            ) {
-                debug!(
-                    "[DEBUG] ✓ Successfully derived PAGE_OFFSET: 0x{:x}",
-                    derived_offset
-                );
-                translator.set_page_offset_5level(derived_offset);
-                translator.set_page_offset_4level(derived_offset); // Set both to same value for KASLR
-                warn!(
-                    "✓ Derived PAGE_OFFSET from init_task: 0x{:x}",
-                    derived_offset
-                );
-            } else {
-                warn!("[WARNING] Could not derive PAGE_OFFSET - using defaults");
-                warn!("[WARNING] Direct mapping translations may be incorrect");
-            }
         }
-    } else {
-        debug!("[DEBUG] tasks.next not in direct mapping range (likely kernel text mapping)");
-        debug!("[DEBUG] PAGE_OFFSET detection not needed for this kernel configuration");
+        warn!(
+            "✓ Loaded swap image {} as swap type {}",
+            image.path.display(),
+            swap_type
+        );
+        translator.add_swap_image(swap_type, swap_map);
     }
 
     // Create analysis context
@@ -650,6 +855,7 @@ struct ValidCandidate {
         translator: &translator,
         symbol_resolver: &symbol_resolver,
         init_task_offset, // Pass the KASLR-adjusted init_task offset
+        reverse_map: OnceCell::new(),
     };
 
     // Determine output format and destination
@@ -700,9 +906,9 @@ fn run_plugin(
             let plugin = PsTreePlugin;
             execute_plugin(&plugin, context, output_writer, None, None)?;
         }
-        PluginCommand::Netstat { pid: _ } => {
+        PluginCommand::Netstat { pid } => {
             let plugin = NetStatPlugin;
-            execute_plugin(&plugin, context, output_writer, None, None)?;
+            execute_plugin(&plugin, context, output_writer, *pid, None)?;
         }
         PluginCommand::Modules => {
             let plugin = ModulesPlugin;
@@ -712,6 +918,129 @@ fn run_plugin(
             let plugin = FilesPlugin;
             execute_plugin(&plugin, context, output_writer, None, None)?;
         }
+        PluginCommand::Bash { pid, shell } => {
+            let plugin = BashPlugin {
+                shells: shell.clone(),
+                pid: *pid,
+            };
+            execute_plugin(&plugin, context, output_writer, None, None)?;
+        }
+        PluginCommand::Ifconfig => {
+            let plugin = IfconfigPlugin;
+            execute_plugin(&plugin, context, output_writer, None, None)?;
+        }
+        PluginCommand::Arp => {
+            let plugin = ArpPlugin;
+            execute_plugin(&plugin, context, output_writer, None, None)?;
+        }
+        PluginCommand::Routes => {
+            let plugin = RoutesPlugin;
+            execute_plugin(&plugin, context, output_writer, None, None)?;
+        }
+        PluginCommand::Unix => {
+            let plugin = UnixPlugin;
+            execute_plugin(&plugin, context, output_writer, None, None)?;
+        }
+        PluginCommand::Rawsockets => {
+            let plugin = RawSocketsPlugin;
+            execute_plugin(&plugin, context, output_writer, None, None)?;
+        }
+        PluginCommand::Bpf { dump_dir } => {
+            let plugin = BpfPlugin {
+                export_dir: dump_dir.clone(),
+            };
+            execute_plugin(&plugin, context, output_writer, None, None)?;
+        }
+        PluginCommand::Bpfmaps => {
+            let plugin = BpfMapsPlugin;
+            execute_plugin(&plugin, context, output_writer, None, None)?;
+        }
+        PluginCommand::Inlinehooks { vmlinux } => {
+            let plugin = InlineHooksPlugin {
+                vmlinux: vmlinux.clone(),
+            };
+            execute_plugin(&plugin, context, output_writer, None, None)?;
+        }
+        PluginCommand::Tracehooks => {
+            let plugin = TraceHooksPlugin;
+            execute_plugin(&plugin, context, output_writer, None, None)?;
+        }
+        PluginCommand::Netfilter => {
+            let plugin = NetfilterPlugin;
+            execute_plugin(&plugin, context, output_writer, None, None)?;
+        }
+        PluginCommand::Notifiers => {
+            let plugin = NotifiersPlugin;
+            execute_plugin(&plugin, context, output_writer, None, None)?;
+        }
+        PluginCommand::Ttycheck => {
+            let plugin = TtyCheckPlugin;
+            execute_plugin(&plugin, context, output_writer, None, None)?;
+        }
+        PluginCommand::Opscheck { all_entries } => {
+            let plugin = OpsCheckPlugin {
+                all_entries: *all_entries,
+            };
+            execute_plugin(&plugin, context, output_writer, None, None)?;
+        }
+        PluginCommand::Namespaces => {
+            let plugin = NamespacesPlugin;
+            execute_plugin(&plugin, context, output_writer, None, None)?;
+        }
+        PluginCommand::Containers => {
+            let plugin = ContainersPlugin;
+            execute_plugin(&plugin, context, output_writer, None, None)?;
+        }
+        PluginCommand::Ldrmodules { pid } => {
+            let plugin = LdrModulesPlugin { pid: *pid };
+            execute_plugin(&plugin, context, output_writer, None, None)?;
+        }
+        PluginCommand::Elfs { pid, dump_dir } => {
+            let plugin = ElfsPlugin {
+                pid: *pid,
+                export_dir: dump_dir.clone(),
+            };
+            execute_plugin(&plugin, context, output_writer, None, None)?;
+        }
+        PluginCommand::Yarascan { rules, scope, pid } => {
+            let plugin = YaraScanPlugin {
+                rules_path: rules.clone(),
+                scope: match scope {
+                    ScanScopeArg::Physical => ScanScope::Physical,
+                    ScanScopeArg::Kernel => ScanScope::Kernel,
+                    ScanScopeArg::Process => ScanScope::Process,
+                },
+                pid: *pid,
+            };
+            execute_plugin(&plugin, context, output_writer, None, None)?;
+        }
+        PluginCommand::Strings {
+            strings_file,
+            min_length,
+        } => {
+            let plugin = StringsPlugin {
+                strings_file: strings_file.clone(),
+                min_length: *min_length,
+            };
+            execute_plugin(&plugin, context, output_writer, None, None)?;
+        }
+        PluginCommand::Maps { pid } => {
+            let plugin = MapsPlugin { pid: *pid };
+            execute_plugin(&plugin, context, output_writer, None, None)?;
+        }
+        PluginCommand::Pageinfo { address, count } => {
+            let plugin = PageInfoPlugin {
+                address: *address,
+                count: *count,
+            };
+            execute_plugin(&plugin, context, output_writer, None, None)?;
+        }
+        PluginCommand::Tmpfs { dump_dir } => {
+            let plugin = TmpfsPlugin {
+                export_dir: dump_dir.clone(),
+            };
+            execute_plugin(&plugin, context, output_writer, None, None)?;
+        }
     }
     Ok(())
 }
@@ -724,14 +1053,43 @@ fn run_all_plugins(
     let plugins: Vec<Box<dyn ForensicPlugin>> = vec![
         Box::new(PsListPlugin),
         Box::new(PsTreePlugin),
+        Box::new(NamespacesPlugin),
+        Box::new(ContainersPlugin),
+        Box::new(LdrModulesPlugin { pid: None }),
+        Box::new(ElfsPlugin {
+            pid: None,
+            export_dir: None,
+        }),
+        Box::new(MapsPlugin { pid: None }),
         Box::new(NetStatPlugin),
+        Box::new(IfconfigPlugin),
+        Box::new(ArpPlugin),
+        Box::new(RoutesPlugin),
+        Box::new(UnixPlugin),
+        Box::new(RawSocketsPlugin),
         Box::new(ModulesPlugin),
+        Box::new(BpfPlugin { export_dir: None }),
+        Box::new(BpfMapsPlugin),
+        Box::new(InlineHooksPlugin { vmlinux: None }),
+        Box::new(TraceHooksPlugin),
+        Box::new(NetfilterPlugin),
+        Box::new(NotifiersPlugin),
+        Box::new(TtyCheckPlugin),
+        Box::new(OpsCheckPlugin { all_entries: false }),
+        Box::new(TmpfsPlugin { export_dir: None }),
+        Box::new(BashPlugin {
+            shells: vec!["bash".to_string()],
+            pid: None,
+        }),
         // Skip FilesPlugin as it's not implemented
     ];
 
+    // A plugin whose symbols or offsets are missing must not stop the rest
     for plugin in plugins {
         println!("\n=== Running plugin: {} ===", plugin.name());
-        execute_plugin(plugin.as_ref(), context, output_writer, None, None)?;
+        if let Err(e) = execute_plugin(plugin.as_ref(), context, output_writer, None, None) {
+            eprintln!("[ERROR] Plugin {} failed: {}", plugin.name(), e);
+        }
     }
 
     Ok(())
@@ -767,7 +1125,11 @@ fn execute_plugin(
                 output_writer.write_processes(&processes)?;
             }
         }
-        PluginOutput::Connections(connections) => {
+        PluginOutput::Connections(mut connections) => {
+            if let Some(pid) = filter_pid {
+                plugins::netstat::filter_by_pid(&mut connections, pid);
+            }
+
             if connections.is_empty() {
                 println!("No network connections found.");
             } else {
@@ -781,6 +1143,167 @@ fn execute_plugin(
                 output_writer.write_modules(&modules)?;
             }
         }
+        PluginOutput::TmpfsFiles(files) => {
+            if files.is_empty() {
+                println!("No tmpfs/ramfs files found.");
+            } else {
+                output_writer.write_tmpfs_files(&files)?;
+            }
+        }
+        PluginOutput::BashHistory(entries) => {
+            if entries.is_empty() {
+                println!("No bash history entries found.");
+            } else {
+                output_writer.write_bash_history(&entries)?;
+            }
+        }
+        PluginOutput::Interfaces(interfaces) => {
+            if interfaces.is_empty() {
+                println!("No network interfaces found.");
+            } else {
+                output_writer.write_interfaces(&interfaces)?;
+            }
+        }
+        PluginOutput::Neighbours(neighbours) => {
+            if neighbours.is_empty() {
+                println!("No neighbour entries found.");
+            } else {
+                output_writer.write_neighbours(&neighbours)?;
+            }
+        }
+        PluginOutput::Routes(routes) => {
+            if routes.is_empty() {
+                println!("No routes found.");
+            } else {
+                output_writer.write_routes(&routes)?;
+            }
+        }
+        PluginOutput::UnixSockets(sockets) => {
+            if sockets.is_empty() {
+                println!("No Unix sockets found.");
+            } else {
+                output_writer.write_unix_sockets(&sockets)?;
+            }
+        }
+        PluginOutput::RawSockets(sockets) => {
+            if sockets.is_empty() {
+                println!("No packet, raw or netlink sockets found.");
+            } else {
+                output_writer.write_raw_sockets(&sockets)?;
+            }
+        }
+        PluginOutput::BpfPrograms(programs) => {
+            if programs.is_empty() {
+                println!("No eBPF programs found.");
+            } else {
+                output_writer.write_bpf_programs(&programs)?;
+            }
+        }
+        PluginOutput::BpfMaps(maps) => {
+            if maps.is_empty() {
+                println!("No eBPF maps found.");
+            } else {
+                output_writer.write_bpf_maps(&maps)?;
+            }
+        }
+        PluginOutput::InlineHooks(hooks) => {
+            if hooks.is_empty() {
+                println!("No kernel text modifications found.");
+            } else {
+                output_writer.write_inline_hooks(&hooks)?;
+            }
+        }
+        PluginOutput::TraceHooks(hooks) => {
+            if hooks.is_empty() {
+                println!("No tracing hooks found.");
+            } else {
+                output_writer.write_trace_hooks(&hooks)?;
+            }
+        }
+        PluginOutput::NetfilterHooks(hooks) => {
+            if hooks.is_empty() {
+                println!("No netfilter hooks found.");
+            } else {
+                output_writer.write_netfilter_hooks(&hooks)?;
+            }
+        }
+        PluginOutput::Notifiers(notifiers) => {
+            if notifiers.is_empty() {
+                println!("No notifier callbacks found.");
+            } else {
+                output_writer.write_notifiers(&notifiers)?;
+            }
+        }
+        PluginOutput::TtyHooks(hooks) => {
+            if hooks.is_empty() {
+                println!("No tty handlers found.");
+            } else {
+                output_writer.write_tty_hooks(&hooks)?;
+            }
+        }
+        PluginOutput::OpsPointers(pointers) => {
+            if pointers.is_empty() {
+                println!("No hijacked ops pointers found.");
+            } else {
+                output_writer.write_ops_pointers(&pointers)?;
+            }
+        }
+        PluginOutput::ProcessNamespaces(processes) => {
+            if processes.is_empty() {
+                println!("No processes found.");
+            } else {
+                output_writer.write_process_namespaces(&processes)?;
+            }
+        }
+        PluginOutput::Containers(containers) => {
+            if containers.is_empty() {
+                println!("No processes found.");
+            } else {
+                output_writer.write_containers(&containers)?;
+            }
+        }
+        PluginOutput::Libraries(libraries) => {
+            if libraries.is_empty() {
+                println!("No libraries found.");
+            } else {
+                output_writer.write_libraries(&libraries)?;
+            }
+        }
+        PluginOutput::Elfs(elfs) => {
+            if elfs.is_empty() {
+                println!("No ELF images found.");
+            } else {
+                output_writer.write_elfs(&elfs)?;
+            }
+        }
+        PluginOutput::ScanMatches(matches) => {
+            if matches.is_empty() {
+                println!("No matches found.");
+            } else {
+                output_writer.write_scan_matches(&matches)?;
+            }
+        }
+        PluginOutput::Strings(strings) => {
+            if strings.is_empty() {
+                println!("No strings found.");
+            } else {
+                output_writer.write_strings(&strings)?;
+            }
+        }
+        PluginOutput::PageInfo(pages) => {
+            if pages.is_empty() {
+                println!("No page descriptors found.");
+            } else {
+                output_writer.write_page_info(&pages)?;
+            }
+        }
+        PluginOutput::Maps(maps) => {
+            if maps.is_empty() {
+                println!("No mappings found.");
+            } else {
+                output_writer.write_maps(&maps)?;
+            }
+        }
         PluginOutput::Tree(tree_str) => {
             // Tree output is already formatted, just print it
             println!("{}", tree_str);
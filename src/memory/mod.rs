@@ -65,6 +65,11 @@ pub fn is_lime(&self) -> bool {
         magic == 0x4C694D45
     }
 
+    /// Check for the mkswap signature ("SWAPSPACE2") at the end of the first page
+    pub fn is_swap(&self) -> bool {
+        self.mapped.get(0xff6..0x1000) == Some(b"SWAPSPACE2".as_slice())
+    }
+
     /// Parse the LIME header from the memory capture and return memory regions.
     /// LIME format structure (32 bytes per segment header):
     /// - Magic: 4 bytes (0x4C694D45 when bytes [0x45, 0x4D, 0x69, 0x4C] read as LE u32)
@@ -0,0 +1,352 @@
+//! ARP plugin - extracts the ARP (IPv4) and NDP (IPv6) neighbour caches
+use crate::error::AnalysisError;
+use crate::kernel::net::{format_ipv4, format_ipv6, format_mac, NetWalker};
+use crate::kernel::{KernelParser, NeighbourInfo};
+use crate::plugins::plugin_trait::{AnalysisContext, ForensicPlugin, PluginOutput};
+use crate::symbols::SymbolResolver;
+use crate::translation::MemoryTranslator;
+
+// Macro for conditional warning output
+macro_rules! warn {
+    ($($arg:tt)*) => {
+        if std::env::var("LINMEMPARSER_VERBOSE").is_ok() {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+// Safety limits for hash table walks
+const MAX_HASH_SHIFT: u32 = 24;
+const MAX_CHAIN_LENGTH: usize = 4096;
+// MAX_ADDR_LEN from include/linux/netdevice.h
+const MAX_ADDR_LEN: usize = 32;
+
+// Neighbour Unreachability Detection states (include/uapi/linux/neighbour.h)
+const NUD_STATES: [(u8, &str); 8] = [
+    (0x01, "INCOMPLETE"),
+    (0x02, "REACHABLE"),
+    (0x04, "STALE"),
+    (0x08, "DELAY"),
+    (0x10, "PROBE"),
+    (0x20, "FAILED"),
+    (0x40, "NOARP"),
+    (0x80, "PERMANENT"),
+];
+
+pub struct ArpPlugin;
+
+impl ForensicPlugin for ArpPlugin {
+    fn name(&self) -> &str {
+        "arp"
+    }
+
+    fn description(&self) -> &str {
+        "Extract the ARP and IPv6 neighbour caches"
+    }
+
+    fn run(&self, context: &AnalysisContext) -> Result<PluginOutput, AnalysisError> {
+        let reader = NeighbourReader::new(
+            &context.memory_map.mapped[..],
+            context.translator,
+            context.symbol_resolver,
+        )?;
+        let mut neighbours = Vec::new();
+
+        for (table_name, family, key_len) in [("arp_tbl", "ipv4", 4), ("nd_tbl", "ipv6", 16)] {
+            let table = match context.symbol_resolver.get_symbol_address(table_name) {
+                Some(addr) => addr,
+                None => {
+                    warn!("[WARNING] Symbol {} not found, skipping", table_name);
+                    continue;
+                }
+            };
+            neighbours.extend(reader.read_table(table_name, table, family, key_len));
+        }
+
+        Ok(PluginOutput::Neighbours(neighbours))
+    }
+}
+
+/// Resolved neigh_table / neigh_hash_table / neighbour offsets
+struct NeighbourReader<'a> {
+    mapped: &'a [u8],
+    translator: &'a MemoryTranslator,
+    walker: NetWalker<'a>,
+    nht: u64,
+    hash_shift: u64,
+    primary_key: u64,
+    ha: u64,
+    nud_state: u64,
+    dev: u64,
+    // neigh_hash_table bucket array and the neighbour link chained from it
+    buckets: u64,
+    link: u64,
+}
+
+impl<'a> NeighbourReader<'a> {
+    fn new(
+        mapped: &'a [u8],
+        translator: &'a MemoryTranslator,
+        resolver: &'a SymbolResolver,
+    ) -> Result<Self, AnalysisError> {
+        let field = |s: &str, f: &str| resolver.require_field_offset(s, f).map(|o| o as u64);
+
+        // Kernel 6.13 moved the buckets from neighbour->next chains to hlists
+        let (buckets, link) = if resolver.has_field("neigh_hash_table", "hash_heads") {
+            (
+                field("neigh_hash_table", "hash_heads")?,
+                field("neighbour", "hash")?,
+            )
+        } else {
+            (
+                field("neigh_hash_table", "hash_buckets")?,
+                field("neighbour", "next")?,
+            )
+        };
+
+        Ok(NeighbourReader {
+            mapped,
+            translator,
+            walker: NetWalker::new(mapped, translator, resolver)?,
+            nht: field("neigh_table", "nht")?,
+            hash_shift: field("neigh_hash_table", "hash_shift")?,
+            primary_key: field("neighbour", "primary_key")?,
+            ha: field("neighbour", "ha")?,
+            nud_state: field("neighbour", "nud_state")?,
+            dev: field("neighbour", "dev")?,
+            buckets,
+            link,
+        })
+    }
+
+    fn read_ptr(&self, addr: u64) -> u64 {
+        KernelParser::read_virt_pointer(self.mapped, self.translator, addr).unwrap_or(0)
+    }
+
+    fn read_bytes(&self, addr: u64, len: usize) -> Option<Vec<u8>> {
+        KernelParser::read_virt_bytes(self.mapped, self.translator, addr, len)
+    }
+
+    /// Walk every hash bucket of one neigh_table
+    fn read_table(
+        &self,
+        table_name: &str,
+        table: u64,
+        family: &str,
+        key_len: usize,
+    ) -> Vec<NeighbourInfo> {
+        let mut neighbours = Vec::new();
+
+        let nht = self.read_ptr(table + self.nht);
+        if nht == 0 {
+            return neighbours;
+        }
+        let hash_shift =
+            KernelParser::read_virt_u32(self.mapped, self.translator, nht + self.hash_shift)
+                .unwrap_or(0);
+        if hash_shift > MAX_HASH_SHIFT {
+            warn!(
+                "[WARNING] {} hash_shift {} is implausible, skipping",
+                table_name, hash_shift
+            );
+            return neighbours;
+        }
+        let buckets = self.read_ptr(nht + self.buckets);
+        if buckets == 0 {
+            return neighbours;
+        }
+
+        for bucket in 0..(1u64 << hash_shift) {
+            let first = self.read_ptr(buckets + bucket * self.translator.pointer_size());
+            // Both layouts are singly linked through the first pointer of the link
+            let chain =
+                KernelParser::walk_hlist(self.mapped, self.translator, first, MAX_CHAIN_LENGTH);
+            for link in chain {
+                let neigh = link.wrapping_sub(self.link);
+                neighbours.push(self.read_neighbour(neigh, family, key_len));
+            }
+        }
+
+        neighbours
+    }
+
+    fn read_neighbour(&self, neigh: u64, family: &str, key_len: usize) -> NeighbourInfo {
+        let dev = self.read_ptr(neigh + self.dev);
+
+        let address = self
+            .read_bytes(neigh + self.primary_key, key_len)
+            .map(|key| {
+                if key_len == 4 {
+                    format_ipv4(&key)
+                } else {
+                    format_ipv6(&key)
+                }
+            })
+            .unwrap_or_else(|| "?".to_string());
+
+        let ha_len = self.walker.device_addr_len(dev).min(MAX_ADDR_LEN);
+        let mac = self
+            .read_bytes(neigh + self.ha, ha_len)
+            .map(|ha| format_mac(&ha))
+            .unwrap_or_else(|| "?".to_string());
+
+        let state = self
+            .read_bytes(neigh + self.nud_state, 1)
+            .map(|b| format_nud_state(b[0]))
+            .unwrap_or_else(|| "?".to_string());
+
+        NeighbourInfo {
+            offset: self.translator.virtual_to_file_offset(neigh).unwrap_or(0),
+            net_namespace: self
+                .walker
+                .device_namespace(dev)
+                .map(|net| self.walker.namespace_inum(net))
+                .unwrap_or(0),
+            family: family.to_string(),
+            address,
+            mac,
+            interface: self
+                .walker
+                .device_name(dev)
+                .unwrap_or_else(|| "?".to_string()),
+            state,
+        }
+    }
+}
+
+/// Format a NUD state bitmask ("REACHABLE", "NONE", ...)
+fn format_nud_state(state: u8) -> String {
+    if state == 0 {
+        return "NONE".to_string();
+    }
+    NUD_STATES
+        .iter()
+        .filter(|(bit, _)| state & bit != 0)
+        .map(|(_, name)| *name)
+        .collect::<Vec<_>>()
+        .join("|")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testutil::{Dump, DIRECT_MAP};
+
+    const TABLE: u64 = 0x1000;
+    const NHT: u64 = 0x1100;
+    const BUCKETS: u64 = 0x1200;
+    const DEV: u64 = 0x3000;
+    const NET: u64 = 0x3400;
+
+    /// An arp_tbl with two neighbours chained in bucket 1 of 2, through
+    /// neighbour->next or (6.13+) neighbour->hash
+    fn arp_table(hash_heads: bool) -> (Dump, SymbolResolver) {
+        let mut symbols = SymbolResolver::new();
+        symbols.add_struct(
+            "net",
+            0x100,
+            &[("list", 0), ("dev_base_head", 0x10), ("ns", 0x20)],
+        );
+        symbols.add_struct("ns_common", 0x18, &[("inum", 0x10)]);
+        symbols.add_struct(
+            "net_device",
+            0x100,
+            &[
+                ("dev_list", 0),
+                ("name", 0x10),
+                ("nd_net", 0x30),
+                ("addr_len", 0x38),
+            ],
+        );
+        symbols.add_struct("neigh_table", 0x40, &[("nht", 0x10)]);
+        let link = if hash_heads {
+            symbols.add_struct(
+                "neigh_hash_table",
+                0x20,
+                &[("hash_shift", 8), ("hash_heads", 0x18)],
+            );
+            0x8
+        } else {
+            symbols.add_struct(
+                "neigh_hash_table",
+                0x20,
+                &[("hash_buckets", 0), ("hash_shift", 8)],
+            );
+            0
+        };
+        symbols.add_struct(
+            "neighbour",
+            0x80,
+            &[
+                ("next", 0),
+                ("hash", 0x8),
+                ("dev", 0x10),
+                ("nud_state", 0x18),
+                ("ha", 0x20),
+                ("primary_key", 0x40),
+            ],
+        );
+
+        let mut dump = Dump::new(0x4000);
+        dump.ptr(TABLE + 0x10, NHT);
+        dump.bytes(NHT + 8, &1u32.to_le_bytes());
+        dump.ptr(NHT + if hash_heads { 0x18 } else { 0 }, BUCKETS);
+        dump.ptr(BUCKETS + 8, 0x2000 + link);
+        dump.ptr(0x2000 + link, 0x2100 + link);
+
+        for (neigh, ip, state) in [(0x2000, [10, 0, 0, 1], 0x02), (0x2100, [10, 0, 0, 2], 0x04)] {
+            dump.ptr(neigh + 0x10, DEV);
+            dump.bytes(neigh + 0x18, &[state]);
+            dump.bytes(neigh + 0x20, &[0x52, 0x54, 0, 0, 0, ip[3]]);
+            dump.bytes(neigh + 0x40, &ip);
+        }
+        dump.bytes(DEV + 0x10, b"eth0\0");
+        dump.ptr(DEV + 0x30, NET);
+        dump.bytes(DEV + 0x38, &[6]);
+        dump.bytes(NET + 0x30, &4026531992u32.to_le_bytes());
+
+        (dump, symbols)
+    }
+
+    fn read(hash_heads: bool) -> Vec<NeighbourInfo> {
+        let (dump, symbols) = arp_table(hash_heads);
+        let translator = dump.translator();
+        let reader = NeighbourReader::new(&dump.0, &translator, &symbols).unwrap();
+        reader.read_table("arp_tbl", DIRECT_MAP + TABLE, "ipv4", 4)
+    }
+
+    #[test]
+    fn test_read_table_walks_hash_buckets() {
+        let neighbours = read(false);
+        assert_eq!(neighbours.len(), 2);
+
+        let first = &neighbours[0];
+        assert_eq!(first.offset, 0x2000);
+        assert_eq!(first.net_namespace, 4026531992);
+        assert_eq!(first.family, "ipv4");
+        assert_eq!(first.address, "10.0.0.1");
+        assert_eq!(first.mac, "52:54:00:00:00:01");
+        assert_eq!(first.interface, "eth0");
+        assert_eq!(first.state, "REACHABLE");
+
+        assert_eq!(neighbours[1].address, "10.0.0.2");
+        assert_eq!(neighbours[1].state, "STALE");
+    }
+
+    #[test]
+    fn test_read_table_follows_hash_heads_on_6_13() {
+        let neighbours = read(true);
+        let addresses: Vec<_> = neighbours.iter().map(|n| n.address.as_str()).collect();
+        assert_eq!(addresses, ["10.0.0.1", "10.0.0.2"]);
+        // Entries are rebased from neighbour->hash back to the neighbour
+        assert_eq!(neighbours[0].offset, 0x2000);
+        assert_eq!(neighbours[1].mac, "52:54:00:00:00:02");
+    }
+
+    #[test]
+    fn test_format_nud_state() {
+        assert_eq!(format_nud_state(0), "NONE");
+        assert_eq!(format_nud_state(0x02), "REACHABLE");
+        assert_eq!(format_nud_state(0x84), "STALE|PERMANENT");
+    }
+}
@@ -0,0 +1,391 @@
+//! Bash plugin - recovers shell command history from bash process heaps
+use crate::error::AnalysisError;
+use crate::kernel::mm::{ProcessMemory, VmaInfo, VM_SHARED, VM_WRITE};
+use crate::kernel::process_extractor::ProcessExtractor;
+use crate::kernel::target::Target;
+use crate::kernel::{BashHistoryInfo, ProcessInfo};
+use crate::plugins::plugin_trait::{AnalysisContext, ForensicPlugin, PluginOutput};
+use regex::bytes::Regex;
+use std::collections::{HashMap, HashSet};
+
+// Macro for conditional debug output
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        if std::env::var("LINMEMPARSER_DEBUG").is_ok() {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+// Macro for conditional warning output
+macro_rules! warn {
+    ($($arg:tt)*) => {
+        if std::env::var("LINMEMPARSER_VERBOSE").is_ok() {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+const PAGE_SIZE: u64 = 0x1000;
+const MAX_COMMAND_LENGTH: usize = 8192;
+// HISTTIMEFORMAT timestamps are stored as "#<epoch>" strings
+const TIMESTAMP_PATTERN: &str = r"#([0-9]{9,11})\x00";
+// Upper bound on memory scanned per process (256MB)
+const MAX_SCAN_BYTES: u64 = 256 * 1024 * 1024;
+
+pub struct BashPlugin {
+    /// Process names treated as bash-compatible shells
+    pub shells: Vec<String>,
+    /// Only scan this PID
+    pub pid: Option<i32>,
+}
+
+impl ForensicPlugin for BashPlugin {
+    fn name(&self) -> &str {
+        "bash"
+    }
+
+    fn description(&self) -> &str {
+        "Recover bash command history from process memory"
+    }
+
+    fn run(&self, context: &AnalysisContext) -> Result<PluginOutput, AnalysisError> {
+        let process_extractor = ProcessExtractor::new();
+        let processes = process_extractor.walk_process_list(
+            context.memory_map,
+            context.translator,
+            context.symbol_resolver,
+            context.init_task_offset as u64,
+        )?;
+
+        let timestamp_re = Regex::new(TIMESTAMP_PATTERN)?;
+        let mut history = Vec::new();
+
+        for process in processes.iter().filter(|p| self.matches(p)) {
+            let memory = match ProcessMemory::for_task(
+                &context.memory_map.mapped,
+                context.translator,
+                context.symbol_resolver,
+                process.offset,
+            ) {
+                Some(m) => m,
+                None => {
+                    warn!(
+                        "[WARNING] PID {}: address space not available, skipping",
+                        process.pid
+                    );
+                    continue;
+                }
+            };
+
+            match scan_process(&memory, process, &timestamp_re) {
+                Ok(mut entries) => {
+                    debug!(
+                        "[DEBUG] PID {}: recovered {} history entries",
+                        process.pid,
+                        entries.len()
+                    );
+                    history.append(&mut entries);
+                }
+                Err(e) => {
+                    warn!("[WARNING] PID {}: history scan failed: {}", process.pid, e);
+                }
+            }
+        }
+
+        Ok(PluginOutput::BashHistory(history))
+    }
+}
+
+impl BashPlugin {
+    fn matches(&self, process: &ProcessInfo) -> bool {
+        if let Some(pid) = self.pid {
+            if process.pid != pid {
+                return false;
+            }
+        }
+        self.shells.contains(&process.comm)
+    }
+}
+
+/// Select the VMAs that can hold readline's history list: the brk heap plus
+/// private anonymous writable mappings used by malloc for larger arenas
+fn heap_vmas(memory: &ProcessMemory, vmas: &[VmaInfo]) -> Vec<VmaInfo> {
+    let start_brk = memory.mm_field("start_brk").unwrap_or(0);
+    let brk = memory.mm_field("brk").unwrap_or(0);
+
+    vmas.iter()
+        .filter(|vma| {
+            let is_brk_heap = brk > start_brk && vma.start < brk && vma.end > start_brk;
+            let is_anon_rw =
+                vma.file == 0 && vma.flags & VM_WRITE != 0 && vma.flags & VM_SHARED == 0;
+            is_brk_heap || is_anon_rw
+        })
+        .cloned()
+        .collect()
+}
+
+/// Scan the heap of one shell process for HIST_ENTRY structures
+///
+/// Readline stores each entry as { char *line; char *timestamp; histdata_t data; }
+/// with the timestamp formatted as "#<epoch>". We locate the timestamp strings
+/// first, then look for pointers to them; the preceding pointer is the command.
+fn scan_process(
+    memory: &ProcessMemory,
+    process: &ProcessInfo,
+    timestamp_re: &Regex,
+) -> Result<Vec<BashHistoryInfo>, AnalysisError> {
+    let vmas = memory.vmas()?;
+    let mut pages = Vec::new();
+    let mut scanned = 0u64;
+
+    for vma in heap_vmas(memory, &vmas) {
+        let mut page_addr = vma.start;
+        while page_addr < vma.end && scanned < MAX_SCAN_BYTES {
+            if let Some(page) = memory.read_page(page_addr) {
+                pages.push((page_addr, page));
+            }
+            page_addr += PAGE_SIZE;
+            scanned += PAGE_SIZE;
+        }
+    }
+
+    let records = find_history(
+        &pages,
+        memory.target(),
+        timestamp_re,
+        |addr| memory.read_pointer(addr),
+        |addr| memory.read_cstring(addr, MAX_COMMAND_LENGTH),
+    );
+    Ok(records
+        .into_iter()
+        .map(|record| BashHistoryInfo {
+            offset: memory.virtual_to_file_offset(record.address).unwrap_or(0),
+            pid: process.pid,
+            uid: process.uid,
+            comm: process.comm.clone(),
+            timestamp: record.timestamp,
+            command: record.command,
+        })
+        .collect())
+}
+
+/// A HIST_ENTRY recovered from the heap
+#[derive(Debug)]
+struct HistoryRecord {
+    address: u64, // Address of the HIST_ENTRY
+    timestamp: i64,
+    command: String,
+}
+
+/// Find HIST_ENTRY structures in heap pages, ordered by timestamp
+///
+/// `read_pointer` and `read_string` read the process's memory, so the command a
+/// record points to may live on a page outside `pages`.
+fn find_history<P: AsRef<[u8]>>(
+    pages: &[(u64, P)],
+    target: Target,
+    timestamp_re: &Regex,
+    read_pointer: impl Fn(u64) -> Option<u64>,
+    read_string: impl Fn(u64) -> Option<String>,
+) -> Vec<HistoryRecord> {
+    // Pass 1: find "#<epoch>" timestamp strings
+    let mut timestamps: HashMap<u64, i64> = HashMap::new();
+    for (page_addr, page) in pages {
+        for caps in timestamp_re.captures_iter(page.as_ref()) {
+            let whole = caps.get(0).unwrap();
+            let digits = String::from_utf8_lossy(&caps[1]).to_string();
+            if let Ok(epoch) = digits.parse::<i64>() {
+                timestamps.insert(page_addr + whole.start() as u64, epoch);
+            }
+        }
+    }
+
+    if timestamps.is_empty() {
+        return Vec::new();
+    }
+
+    // Pass 2: find HIST_ENTRY.timestamp pointers referencing those strings
+    let word = target.pointer_size as u64;
+    let mut seen = HashSet::new();
+    let mut records = Vec::new();
+    for (page_addr, page) in pages {
+        for (i, chunk) in page.as_ref().chunks_exact(target.pointer_size).enumerate() {
+            let value = match target.pointer(chunk) {
+                Some(v) => v,
+                None => continue,
+            };
+            let epoch = match timestamps.get(&value) {
+                Some(e) => *e,
+                None => continue,
+            };
+
+            // HIST_ENTRY.line is the word before HIST_ENTRY.timestamp
+            let entry_addr = match (page_addr + i as u64 * word).checked_sub(word) {
+                Some(a) => a,
+                None => continue,
+            };
+            if !seen.insert(entry_addr) {
+                continue;
+            }
+
+            let line_ptr = match read_pointer(entry_addr) {
+                Some(p) if p != 0 => p,
+                _ => continue,
+            };
+            let command = match read_string(line_ptr) {
+                Some(c) if is_plausible_command(&c) => c,
+                _ => continue,
+            };
+
+            records.push(HistoryRecord {
+                address: entry_addr,
+                timestamp: epoch,
+                command,
+            });
+        }
+    }
+
+    records.sort_by_key(|r| r.timestamp);
+    records
+}
+
+/// Reject pointers that landed on binary data rather than a command line
+fn is_plausible_command(command: &str) -> bool {
+    if command.is_empty() {
+        return false;
+    }
+    let printable = command
+        .chars()
+        .filter(|c| !c.is_control() || *c == '\t' || *c == '\n')
+        .count();
+    printable == command.chars().count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HEAP: u64 = 0x5555_5555_a000;
+
+    /// A heap page holding HIST_ENTRY records and their strings
+    struct Heap {
+        base: u64,
+        page: Vec<u8>,
+        target: Target,
+    }
+
+    impl Heap {
+        fn new(base: u64, target: Target) -> Self {
+            Heap {
+                base,
+                page: vec![0u8; PAGE_SIZE as usize],
+                target,
+            }
+        }
+
+        fn put_string(&mut self, at: usize, text: &str) -> u64 {
+            self.page[at..at + text.len()].copy_from_slice(text.as_bytes());
+            self.page[at + text.len()] = 0;
+            self.base + at as u64
+        }
+
+        fn put_pointer(&mut self, at: usize, value: u64) {
+            let size = self.target.pointer_size;
+            let bytes = if self.target.big_endian {
+                value.to_be_bytes()[8 - size..].to_vec()
+            } else {
+                value.to_le_bytes()[..size].to_vec()
+            };
+            self.page[at..at + size].copy_from_slice(&bytes);
+        }
+
+        /// HIST_ENTRY { char *line; char *timestamp; histdata_t data; }
+        fn put_entry(&mut self, at: usize, line: u64, timestamp: u64) {
+            let word = self.target.pointer_size;
+            self.put_pointer(at, line);
+            self.put_pointer(at + word, timestamp);
+            self.put_pointer(at + 2 * word, 0);
+        }
+
+        fn scan(&self) -> Vec<(u64, i64, String)> {
+            let read_pointer = |addr: u64| {
+                self.target
+                    .pointer(self.page.get((addr.checked_sub(self.base)?) as usize..)?)
+            };
+            let read_string = |addr: u64| {
+                let bytes = self.page.get((addr.checked_sub(self.base)?) as usize..)?;
+                let end = bytes.iter().position(|&b| b == 0)?;
+                Some(String::from_utf8_lossy(&bytes[..end]).to_string())
+            };
+            let timestamp_re = Regex::new(TIMESTAMP_PATTERN).unwrap();
+            find_history(
+                &[(self.base, &self.page[..])],
+                self.target,
+                &timestamp_re,
+                read_pointer,
+                read_string,
+            )
+            .into_iter()
+            .map(|r| (r.address, r.timestamp, r.command))
+            .collect()
+        }
+    }
+
+    #[test]
+    fn test_history_entries_are_ordered_by_timestamp() {
+        let mut heap = Heap::new(HEAP, Target::default());
+        let ls = heap.put_string(0x800, "ls -la /tmp");
+        let curl = heap.put_string(0x820, "curl http://example.com/x | sh");
+        let binary = heap.put_string(0x850, "\x01\x02");
+        let late = heap.put_string(0x900, "#1700000300");
+        let early = heap.put_string(0x910, "#1700000100");
+        let lone = heap.put_string(0x920, "#1700000200");
+        // Entries are stored out of order on the heap
+        heap.put_entry(0x100, ls, late);
+        heap.put_entry(0x200, curl, early);
+        // A timestamp pointer whose line is not text is not a history entry
+        heap.put_entry(0x300, binary, lone);
+
+        assert_eq!(
+            heap.scan(),
+            vec![
+                (
+                    HEAP + 0x200,
+                    1700000100,
+                    "curl http://example.com/x | sh".to_string()
+                ),
+                (HEAP + 0x100, 1700000300, "ls -la /tmp".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_history_timestamps_need_a_terminated_epoch() {
+        let mut heap = Heap::new(HEAP, Target::default());
+        let cmd = heap.put_string(0x800, "id");
+        // Too few digits to be an epoch
+        let short = heap.put_string(0x900, "#12345");
+        heap.put_entry(0x100, cmd, short);
+        assert!(heap.scan().is_empty());
+        // Not NUL-terminated right after the digits
+        let mut heap = Heap::new(HEAP, Target::default());
+        let cmd = heap.put_string(0x800, "id");
+        let ts = heap.put_string(0x900, "#1700000000x");
+        heap.put_entry(0x100, cmd, ts);
+        assert!(heap.scan().is_empty());
+    }
+
+    #[test]
+    fn test_history_on_a_32_bit_big_endian_heap() {
+        let target = Target::from_pointer_type(4, "big").unwrap();
+        let mut heap = Heap::new(0x0804_c000, target);
+        let cmd = heap.put_string(0x800, "uname -a");
+        let ts = heap.put_string(0x900, "#1600000000");
+        heap.put_entry(0x104, cmd, ts);
+        assert_eq!(
+            heap.scan(),
+            vec![(0x0804_c104, 1600000000, "uname -a".to_string())]
+        );
+    }
+}
@@ -0,0 +1,119 @@
+//! Bpf plugin - enumerates loaded eBPF programs
+use crate::error::AnalysisError;
+use crate::kernel::bpf::{bpf_prog_type_name, BpfProg, BpfWalker};
+use crate::kernel::BpfProgInfo;
+use crate::plugins::plugin_trait::{AnalysisContext, ForensicPlugin, PluginOutput};
+use std::path::{Path, PathBuf};
+
+// Macro for conditional warning output
+macro_rules! warn {
+    ($($arg:tt)*) => {
+        if std::env::var("LINMEMPARSER_VERBOSE").is_ok() {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+pub struct BpfPlugin {
+    /// Directory to write program bytecode and JIT images into
+    pub export_dir: Option<PathBuf>,
+}
+
+impl ForensicPlugin for BpfPlugin {
+    fn name(&self) -> &str {
+        "bpf"
+    }
+
+    fn description(&self) -> &str {
+        "List loaded eBPF programs with attach points and JIT images"
+    }
+
+    fn run(&self, context: &AnalysisContext) -> Result<PluginOutput, AnalysisError> {
+        let mapped = &context.memory_map.mapped[..];
+        let walker = BpfWalker::new(mapped, context.translator, context.symbol_resolver)?;
+        let links = walker.link_attachments();
+
+        if let Some(dir) = &self.export_dir {
+            std::fs::create_dir_all(dir)?;
+        }
+
+        let mut programs = Vec::new();
+        for prog in walker.programs()? {
+            let mut attach: Vec<String> = prog
+                .attach_func
+                .iter()
+                .map(|func| format!("func:{}", func))
+                .collect();
+            if let Some(points) = links.get(&prog.prog) {
+                attach.extend(points.iter().cloned());
+            }
+
+            let exported = match &self.export_dir {
+                Some(dir) => export_program(&walker, &prog, dir),
+                None => Vec::new(),
+            };
+
+            programs.push(BpfProgInfo {
+                offset: context
+                    .translator
+                    .virtual_to_file_offset(prog.prog)
+                    .unwrap_or(0),
+                id: prog.id,
+                prog_type: bpf_prog_type_name(prog.prog_type),
+                name: prog.name.clone(),
+                tag: prog.tag.iter().map(|b| format!("{:02x}", b)).collect(),
+                load_time: prog.load_time,
+                uid: prog.uid,
+                attach,
+                insns: prog.insn_count,
+                jit_addr: prog.jit_addr,
+                jit_size: prog.jit_size,
+                map_ids: prog.map_ids.clone(),
+                exported,
+            });
+        }
+
+        Ok(PluginOutput::BpfPrograms(programs))
+    }
+}
+
+/// Write a program's bytecode (`.bpf`) and JIT image (`.jit`) into the export directory
+fn export_program(walker: &BpfWalker, prog: &BpfProg, dir: &Path) -> Vec<String> {
+    let stem = if prog.name.is_empty() {
+        format!("prog_{}", prog.id)
+    } else {
+        let name: String = prog
+            .name
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        format!("prog_{}_{}", prog.id, name)
+    };
+
+    let mut exported = Vec::new();
+    let images = [
+        ("bpf", walker.bytecode(prog)),
+        ("jit", walker.jit_image(prog)),
+    ];
+    for (extension, image) in images {
+        let bytes = match image {
+            Some(b) => b,
+            None => {
+                if extension == "bpf" || prog.jit_addr != 0 {
+                    warn!(
+                        "[WARNING] {} image of BPF program {} is unreadable",
+                        extension, prog.id
+                    );
+                }
+                continue;
+            }
+        };
+        let target = dir.join(format!("{}.{}", stem, extension));
+        match std::fs::write(&target, bytes) {
+            Ok(()) => exported.push(target.display().to_string()),
+            Err(e) => warn!("[WARNING] Failed to export {}: {}", target.display(), e),
+        }
+    }
+
+    exported
+}
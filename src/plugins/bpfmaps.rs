@@ -0,0 +1,42 @@
+//! BpfMaps plugin - enumerates eBPF maps
+use crate::error::AnalysisError;
+use crate::kernel::bpf::{bpf_map_type_name, BpfWalker};
+use crate::kernel::BpfMapInfo;
+use crate::plugins::plugin_trait::{AnalysisContext, ForensicPlugin, PluginOutput};
+
+pub struct BpfMapsPlugin;
+
+impl ForensicPlugin for BpfMapsPlugin {
+    fn name(&self) -> &str {
+        "bpfmaps"
+    }
+
+    fn description(&self) -> &str {
+        "List eBPF maps with key/value sizes and capacity"
+    }
+
+    fn run(&self, context: &AnalysisContext) -> Result<PluginOutput, AnalysisError> {
+        let mapped = &context.memory_map.mapped[..];
+        let walker = BpfWalker::new(mapped, context.translator, context.symbol_resolver)?;
+
+        let maps = walker
+            .maps()?
+            .into_iter()
+            .map(|map| BpfMapInfo {
+                offset: context
+                    .translator
+                    .virtual_to_file_offset(map.map)
+                    .unwrap_or(0),
+                id: map.id,
+                map_type: bpf_map_type_name(map.map_type),
+                name: map.name,
+                key_size: map.key_size,
+                value_size: map.value_size,
+                max_entries: map.max_entries,
+                flags: map.flags,
+            })
+            .collect();
+
+        Ok(PluginOutput::BpfMaps(maps))
+    }
+}
@@ -0,0 +1,223 @@
+//! Containers plugin - groups processes by namespace set and names the container runtime
+use crate::error::AnalysisError;
+use crate::kernel::{ContainerInfo, ProcessNamespaceInfo};
+use crate::plugins::namespaces::process_namespaces;
+use crate::plugins::plugin_trait::{AnalysisContext, ForensicPlugin, PluginOutput};
+
+// Length docker and crictl abbreviate container IDs to
+const SHORT_ID_LEN: usize = 12;
+
+// Scope prefixes systemd cgroup drivers give container cgroups
+const SCOPE_PREFIXES: [(&str, &str); 5] = [
+    ("docker-", "docker"),
+    ("cri-containerd-", "containerd"),
+    ("crio-", "cri-o"),
+    ("libpod-", "podman"),
+    ("lxc.payload.", "lxc"),
+];
+
+pub struct ContainersPlugin;
+
+impl ForensicPlugin for ContainersPlugin {
+    fn name(&self) -> &str {
+        "containers"
+    }
+
+    fn description(&self) -> &str {
+        "Group processes into containers by namespace set, hostname and cgroup"
+    }
+
+    fn run(&self, context: &AnalysisContext) -> Result<PluginOutput, AnalysisError> {
+        let processes = process_namespaces(context)?;
+
+        // Group by the full namespace set, in process list order
+        let mut groups: Vec<(NamespaceSet, Vec<&ProcessNamespaceInfo>)> = Vec::new();
+        for process in &processes {
+            let set = NamespaceSet::of(process);
+            match groups.iter_mut().find(|(s, _)| *s == set) {
+                Some((_, members)) => members.push(process),
+                None => groups.push((set, vec![process])),
+            }
+        }
+
+        // The swapper task (first on the list) runs in the initial namespaces
+        let host = processes.first().map(NamespaceSet::of);
+        let host_hostname = processes
+            .first()
+            .map(|p| p.hostname.clone())
+            .unwrap_or_default();
+
+        let containers = groups
+            .into_iter()
+            .map(|(set, members)| {
+                let first = members[0];
+                let is_host = Some(set) == host;
+                let identity = members
+                    .iter()
+                    .find_map(|p| classify_cgroup(&p.cgroup))
+                    .filter(|_| !is_host);
+                let (runtime, id, pod) = match identity {
+                    Some(identity) => identity,
+                    None if is_host => ("host".to_string(), "host".to_string(), String::new()),
+                    None => (String::new(), String::new(), String::new()),
+                };
+                let container = if !id.is_empty() {
+                    id
+                } else if !first.hostname.is_empty() && first.hostname != host_hostname {
+                    first.hostname.clone()
+                } else {
+                    format!("mnt:[{}]", set.mnt)
+                };
+                ContainerInfo {
+                    container,
+                    runtime,
+                    pod,
+                    hostname: first.hostname.clone(),
+                    cgroup: first.cgroup.clone(),
+                    pid_ns: set.pid,
+                    mnt_ns: set.mnt,
+                    net_ns: set.net,
+                    user_ns: set.user,
+                    processes: members.len(),
+                    pids: members.iter().map(|p| p.pid).collect(),
+                }
+            })
+            .collect();
+
+        Ok(PluginOutput::Containers(containers))
+    }
+}
+
+/// The namespace inode numbers that define a container
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct NamespaceSet {
+    user: u32,
+    mnt: u32,
+    uts: u32,
+    ipc: u32,
+    pid: u32,
+    net: u32,
+    cgroup: u32,
+    time: u32,
+}
+
+impl NamespaceSet {
+    fn of(process: &ProcessNamespaceInfo) -> Self {
+        NamespaceSet {
+            user: process.user_ns,
+            mnt: process.mnt_ns,
+            uts: process.uts_ns,
+            ipc: process.ipc_ns,
+            pid: process.pid_ns,
+            net: process.net_ns,
+            cgroup: process.cgroup_ns,
+            time: process.time_ns,
+        }
+    }
+}
+
+/// Derive (runtime, container ID, pod UID) from a cgroup path
+///
+/// Understands both the systemd driver layout
+/// (`/kubepods.slice/kubepods-burstable-pod<uid>.slice/cri-containerd-<id>.scope`,
+/// `/system.slice/docker-<id>.scope`) and the cgroupfs layout
+/// (`/kubepods/burstable/pod<uid>/<id>`, `/docker/<id>`, `/lxc/<name>`).
+fn classify_cgroup(path: &str) -> Option<(String, String, String)> {
+    let components: Vec<&str> = path.split('/').filter(|c| !c.is_empty()).collect();
+    let mut pod = String::new();
+    let mut kubernetes = false;
+
+    for (i, component) in components.iter().enumerate() {
+        let component = component
+            .strip_suffix(".scope")
+            .or_else(|| component.strip_suffix(".slice"))
+            .unwrap_or(component);
+        let parent = i.checked_sub(1).map(|p| components[p]);
+
+        if component.starts_with("kubepods") {
+            kubernetes = true;
+        }
+        if kubernetes {
+            // "pod<uid>" (cgroupfs) or "kubepods-<qos>-pod<uid_with_underscores>" (systemd)
+            if let Some(uid) = component.rsplit_once("pod").map(|(_, uid)| uid) {
+                if uid.len() >= 32 {
+                    pod = uid.replace('_', "-");
+                    continue;
+                }
+            }
+        }
+
+        for (prefix, runtime) in SCOPE_PREFIXES {
+            if let Some(id) = component.strip_prefix(prefix) {
+                return Some((runtime.to_string(), short_id(id), pod));
+            }
+        }
+        if let Some(name) = component.strip_prefix("systemd-nspawn@") {
+            let name = name.strip_suffix(".service").unwrap_or(name);
+            return Some(("systemd-nspawn".to_string(), name.to_string(), pod));
+        }
+        match parent {
+            Some("docker") if is_container_id(component) => {
+                return Some(("docker".to_string(), short_id(component), pod));
+            }
+            Some("lxc") => return Some(("lxc".to_string(), component.to_string(), pod)),
+            _ if kubernetes && is_container_id(component) => {
+                return Some(("kubernetes".to_string(), short_id(component), pod));
+            }
+            _ => {}
+        }
+    }
+
+    None
+}
+
+/// Check for a 64-character hex container ID
+fn is_container_id(s: &str) -> bool {
+    s.len() == 64 && s.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+fn short_id(id: &str) -> String {
+    id.chars().take(SHORT_ID_LEN).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_cgroup() {
+        let id = "4f1e1c2d9a8b7c6d5e4f3a2b1c0d9e8f7a6b5c4d3e2f1a0b9c8d7e6f5a4b3c2d";
+
+        let docker = classify_cgroup(&format!("/system.slice/docker-{}.scope", id));
+        assert_eq!(
+            docker,
+            Some(("docker".into(), "4f1e1c2d9a8b".into(), String::new()))
+        );
+
+        let pod = classify_cgroup(&format!(
+            "/kubepods.slice/kubepods-burstable.slice/\
+             kubepods-burstable-pod0b5c8e2a_1d3f_4e5a_9b7c_6d8e0f1a2b3c.slice/\
+             cri-containerd-{}.scope",
+            id
+        ));
+        assert_eq!(
+            pod,
+            Some((
+                "containerd".into(),
+                "4f1e1c2d9a8b".into(),
+                "0b5c8e2a-1d3f-4e5a-9b7c-6d8e0f1a2b3c".into()
+            ))
+        );
+
+        let cgroupfs = classify_cgroup(&format!(
+            "/kubepods/besteffort/pod0b5c8e2a-1d3f-4e5a-9b7c-6d8e0f1a2b3c/{}",
+            id
+        ));
+        assert_eq!(cgroupfs.map(|c| c.0), Some("kubernetes".to_string()));
+
+        assert_eq!(
+            classify_cgroup("/user.slice/user-1000.slice/session-2.scope"),
+            None
+        );
+    }
+}
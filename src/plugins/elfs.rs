@@ -0,0 +1,160 @@
+//! Elfs plugin - finds ELF images mapped into each process and optionally rebuilds them
+use crate::error::AnalysisError;
+use crate::kernel::elf::ElfImage;
+use crate::kernel::mm::ProcessMemory;
+use crate::kernel::process_extractor::ProcessExtractor;
+use crate::kernel::vfs::VfsWalker;
+use crate::kernel::{ElfInfo, ProcessInfo};
+use crate::plugins::plugin_trait::{AnalysisContext, ForensicPlugin, PluginOutput};
+use std::path::{Path, PathBuf};
+
+// Macro for conditional warning output
+macro_rules! warn {
+    ($($arg:tt)*) => {
+        if std::env::var("LINMEMPARSER_VERBOSE").is_ok() {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+pub struct ElfsPlugin {
+    pub pid: Option<i32>,
+    /// Directory to write reconstructed ELF images into
+    pub export_dir: Option<PathBuf>,
+}
+
+impl ForensicPlugin for ElfsPlugin {
+    fn name(&self) -> &str {
+        "elfs"
+    }
+
+    fn description(&self) -> &str {
+        "Find ELF images mapped into processes, with build IDs and optional reconstruction"
+    }
+
+    fn run(&self, context: &AnalysisContext) -> Result<PluginOutput, AnalysisError> {
+        let mapped = &context.memory_map.mapped[..];
+        let translator = context.translator;
+        let resolver = context.symbol_resolver;
+        let processes = ProcessExtractor::new().walk_process_list(
+            context.memory_map,
+            translator,
+            resolver,
+            context.init_task_offset as u64,
+        )?;
+        let vfs = VfsWalker::new(mapped, translator, resolver)?;
+
+        if let Some(dir) = &self.export_dir {
+            std::fs::create_dir_all(dir)?;
+        }
+
+        let mut elfs = Vec::new();
+        for process in &processes {
+            if self.pid.is_some_and(|pid| pid != process.pid) {
+                continue;
+            }
+            // Kernel threads have no address space
+            let memory = match ProcessMemory::for_task(mapped, translator, resolver, process.offset)
+            {
+                Some(m) => m,
+                None => continue,
+            };
+            match self.process_elfs(&vfs, &memory, process) {
+                Ok(found) => elfs.extend(found),
+                Err(e) => warn!("[WARNING] PID {}: VMAs unavailable: {}", process.pid, e),
+            }
+        }
+
+        Ok(PluginOutput::Elfs(elfs))
+    }
+}
+
+impl ElfsPlugin {
+    fn process_elfs(
+        &self,
+        vfs: &VfsWalker,
+        memory: &ProcessMemory,
+        process: &ProcessInfo,
+    ) -> Result<Vec<ElfInfo>, AnalysisError> {
+        let vmas = memory.vmas()?;
+        let mut elfs = Vec::new();
+
+        for (i, vma) in vmas.iter().enumerate() {
+            // An ELF header sits at file offset 0, or anywhere in anonymous memory
+            if vma.file != 0 && vma.pgoff != 0 {
+                continue;
+            }
+            let image = match ElfImage::parse(memory, vma.start) {
+                Some(image) => image,
+                None => continue,
+            };
+
+            // The image extends over the following mappings of the same file
+            let end = if vma.file == 0 {
+                vma.end
+            } else {
+                vmas[i..]
+                    .iter()
+                    .take_while(|v| v.file == vma.file)
+                    .last()
+                    .map_or(vma.end, |v| v.end)
+            };
+            let path = if vma.file == 0 {
+                "[anonymous]".to_string()
+            } else {
+                vfs.file_path(vma.file)
+                    .unwrap_or_else(|| format!("{:#x}", vma.file))
+            };
+            let exported = self
+                .export_dir
+                .as_deref()
+                .and_then(|dir| export_image(&image, memory, process, &path, dir));
+
+            elfs.push(ElfInfo {
+                offset: process.offset,
+                pid: process.pid,
+                comm: process.comm.clone(),
+                start: vma.start,
+                end,
+                elf_type: image.type_name().to_string(),
+                build_id: image.build_id(memory).unwrap_or_default(),
+                deleted: vma.file != 0 && vfs.file_deleted(vma.file),
+                path,
+                exported,
+            });
+        }
+
+        Ok(elfs)
+    }
+}
+
+/// Write a reconstructed image as `pid_<pid>_<base>_<name>` into the export directory
+fn export_image(
+    image: &ElfImage,
+    memory: &ProcessMemory,
+    process: &ProcessInfo,
+    path: &str,
+    dir: &Path,
+) -> Option<String> {
+    let name: String = path
+        .rsplit('/')
+        .next()
+        .unwrap_or(path)
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '.' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let target = dir.join(format!("pid_{}_{:x}_{}", process.pid, image.base, name));
+    match std::fs::write(&target, image.reconstruct(memory)) {
+        Ok(()) => Some(target.display().to_string()),
+        Err(e) => {
+            warn!("[WARNING] Failed to export {}: {}", target.display(), e);
+            None
+        }
+    }
+}
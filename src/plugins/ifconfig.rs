@@ -0,0 +1,339 @@
+//! Ifconfig plugin - lists network interfaces, addresses and promiscuous mode
+use crate::error::AnalysisError;
+use crate::kernel::net::{
+    format_interface_flags, format_ipv4, format_ipv6, format_mac, NetWalker, IFF_PROMISC,
+};
+use crate::kernel::{InterfaceInfo, KernelParser};
+use crate::plugins::plugin_trait::{AnalysisContext, ForensicPlugin, PluginOutput};
+use crate::symbols::SymbolResolver;
+use crate::translation::MemoryTranslator;
+
+// Macro for conditional warning output
+macro_rules! warn {
+    ($($arg:tt)*) => {
+        if std::env::var("LINMEMPARSER_VERBOSE").is_ok() {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+// Safety limit on addresses per interface
+const MAX_ADDRESSES: usize = 4096;
+// MAX_ADDR_LEN from include/linux/netdevice.h
+const MAX_ADDR_LEN: usize = 32;
+
+pub struct IfconfigPlugin;
+
+impl ForensicPlugin for IfconfigPlugin {
+    fn name(&self) -> &str {
+        "ifconfig"
+    }
+
+    fn description(&self) -> &str {
+        "List network interfaces, addresses and promiscuous mode"
+    }
+
+    fn run(&self, context: &AnalysisContext) -> Result<PluginOutput, AnalysisError> {
+        let mapped = &context.memory_map.mapped[..];
+        let walker = NetWalker::new(mapped, context.translator, context.symbol_resolver)?;
+        let reader = DeviceReader::new(mapped, context.translator, context.symbol_resolver)?;
+        let mut interfaces = Vec::new();
+
+        for net in walker.namespaces()? {
+            let net_namespace = walker.namespace_inum(net);
+            for dev in walker.devices(net) {
+                let name = match walker.device_name(dev) {
+                    Some(n) if !n.is_empty() => n,
+                    _ => {
+                        warn!("[WARNING] Unreadable net_device at 0x{:x}, skipping", dev);
+                        continue;
+                    }
+                };
+                interfaces.push(reader.read(dev, net_namespace, name));
+            }
+        }
+
+        Ok(PluginOutput::Interfaces(interfaces))
+    }
+}
+
+/// Reads net_device attributes and the IPv4/IPv6 address lists hanging off it
+struct DeviceReader<'a> {
+    mapped: &'a [u8],
+    translator: &'a MemoryTranslator,
+    // net_device
+    mtu: usize,
+    flags: usize,
+    promiscuity: Option<usize>,
+    dev_addr: usize,
+    addr_len: usize,
+    ip_ptr: usize,
+    // in_device / in_ifaddr
+    ifa_list: usize,
+    ifa_next: usize,
+    ifa_local: usize,
+    ifa_prefixlen: usize,
+    inet6: Option<Inet6Layout>,
+}
+
+/// net_device.ip6_ptr, inet6_dev and inet6_ifaddr offsets (IPv6 may be compiled out)
+struct Inet6Layout {
+    ip6_ptr: usize,
+    addr_list: usize,
+    addr: usize,
+    prefix_len: usize,
+    if_list: usize,
+}
+
+impl<'a> DeviceReader<'a> {
+    fn new(
+        mapped: &'a [u8],
+        translator: &'a MemoryTranslator,
+        symbol_resolver: &'a SymbolResolver,
+    ) -> Result<Self, AnalysisError> {
+        let field = |s: &str, f: &str| symbol_resolver.require_field_offset(s, f);
+        let optional = |s: &str, f: &str| {
+            if symbol_resolver.has_field(s, f) {
+                symbol_resolver.require_field_offset(s, f).ok()
+            } else {
+                None
+            }
+        };
+
+        Ok(DeviceReader {
+            mapped,
+            translator,
+            mtu: field("net_device", "mtu")?,
+            flags: field("net_device", "flags")?,
+            promiscuity: optional("net_device", "promiscuity"),
+            dev_addr: field("net_device", "dev_addr")?,
+            addr_len: field("net_device", "addr_len")?,
+            ip_ptr: field("net_device", "ip_ptr")?,
+            ifa_list: field("in_device", "ifa_list")?,
+            ifa_next: field("in_ifaddr", "ifa_next")?,
+            ifa_local: field("in_ifaddr", "ifa_local")?,
+            ifa_prefixlen: field("in_ifaddr", "ifa_prefixlen")?,
+            inet6: (|| {
+                Some(Inet6Layout {
+                    ip6_ptr: optional("net_device", "ip6_ptr")?,
+                    addr_list: optional("inet6_dev", "addr_list")?,
+                    addr: optional("inet6_ifaddr", "addr")?,
+                    prefix_len: optional("inet6_ifaddr", "prefix_len")?,
+                    if_list: optional("inet6_ifaddr", "if_list")?,
+                })
+            })(),
+        })
+    }
+
+    fn read_u32(&self, addr: u64) -> u32 {
+        KernelParser::read_virt_u32(self.mapped, self.translator, addr).unwrap_or(0)
+    }
+
+    fn read_ptr(&self, addr: u64) -> u64 {
+        KernelParser::read_virt_pointer(self.mapped, self.translator, addr).unwrap_or(0)
+    }
+
+    fn read_u8(&self, addr: u64) -> u8 {
+        KernelParser::read_virt_bytes(self.mapped, self.translator, addr, 1)
+            .map(|b| b[0])
+            .unwrap_or(0)
+    }
+
+    fn read(&self, dev: u64, net_namespace: u32, name: String) -> InterfaceInfo {
+        let flags = self.read_u32(dev + self.flags as u64);
+        // The promiscuity counter catches sniffers even if IFF_PROMISC was masked
+        let promiscuity = self
+            .promiscuity
+            .map(|off| self.read_u32(dev + off as u64))
+            .unwrap_or(0);
+
+        InterfaceInfo {
+            offset: self.translator.virtual_to_file_offset(dev).unwrap_or(0),
+            net_namespace,
+            name,
+            mac: self.read_mac(dev),
+            mtu: self.read_u32(dev + self.mtu as u64),
+            flags: format_interface_flags(flags),
+            promiscuous: flags & IFF_PROMISC != 0 || promiscuity > 0,
+            ipv4: self.read_ipv4(dev),
+            ipv6: self.read_ipv6(dev),
+        }
+    }
+
+    fn read_mac(&self, dev: u64) -> String {
+        let length = (self.read_u8(dev + self.addr_len as u64) as usize).min(MAX_ADDR_LEN);
+        let dev_addr = self.read_ptr(dev + self.dev_addr as u64);
+        if length == 0 || dev_addr == 0 {
+            return "-".to_string();
+        }
+        KernelParser::read_virt_bytes(self.mapped, self.translator, dev_addr, length)
+            .map(|bytes| format_mac(&bytes))
+            .unwrap_or_else(|| "?".to_string())
+    }
+
+    /// Walk in_device->ifa_list (a singly linked list of in_ifaddr)
+    fn read_ipv4(&self, dev: u64) -> Vec<String> {
+        let mut addresses = Vec::new();
+        let in_dev = self.read_ptr(dev + self.ip_ptr as u64);
+        if in_dev == 0 {
+            return addresses;
+        }
+
+        let mut ifa = self.read_ptr(in_dev + self.ifa_list as u64);
+        while ifa != 0 && addresses.len() < MAX_ADDRESSES {
+            if let Some(local) = KernelParser::read_virt_bytes(
+                self.mapped,
+                self.translator,
+                ifa + self.ifa_local as u64,
+                4,
+            ) {
+                let prefix = self.read_u8(ifa + self.ifa_prefixlen as u64);
+                addresses.push(format!("{}/{}", format_ipv4(&local), prefix));
+            }
+            ifa = self.read_ptr(ifa + self.ifa_next as u64);
+        }
+
+        addresses
+    }
+
+    /// Walk inet6_dev->addr_list (a list_head of inet6_ifaddr.if_list)
+    fn read_ipv6(&self, dev: u64) -> Vec<String> {
+        let inet6 = match &self.inet6 {
+            Some(layout) => layout,
+            None => return Vec::new(),
+        };
+        let in6_dev = self.read_ptr(dev + inet6.ip6_ptr as u64);
+        if in6_dev == 0 {
+            return Vec::new();
+        }
+
+        let head = in6_dev + inet6.addr_list as u64;
+        KernelParser::walk_list(self.mapped, self.translator, head, MAX_ADDRESSES)
+            .into_iter()
+            .filter_map(|entry| {
+                let ifa = entry.wrapping_sub(inet6.if_list as u64);
+                let addr = KernelParser::read_virt_bytes(
+                    self.mapped,
+                    self.translator,
+                    ifa + inet6.addr as u64,
+                    16,
+                )?;
+                let prefix = self.read_u32(ifa + inet6.prefix_len as u64);
+                Some(format!("{}/{}", format_ipv6(&addr), prefix))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testutil::{Dump, DIRECT_MAP};
+
+    const DEV: u64 = 0x1000;
+    const MAC: u64 = 0x1400;
+    const IN_DEV: u64 = 0x2000;
+    const IN6_DEV: u64 = 0x3000;
+    const IFA6: u64 = 0x3100;
+
+    fn symbols(with_ipv6_prefix: bool) -> SymbolResolver {
+        let mut symbols = SymbolResolver::new();
+        symbols.add_struct(
+            "net_device",
+            0x200,
+            &[
+                ("mtu", 0x20),
+                ("flags", 0x24),
+                ("promiscuity", 0x28),
+                ("dev_addr", 0x30),
+                ("addr_len", 0x38),
+                ("ip_ptr", 0x40),
+                ("ip6_ptr", 0x48),
+            ],
+        );
+        symbols.add_struct("in_device", 0x40, &[("ifa_list", 8)]);
+        symbols.add_struct(
+            "in_ifaddr",
+            0x40,
+            &[
+                ("ifa_next", 0),
+                ("ifa_local", 0x10),
+                ("ifa_prefixlen", 0x19),
+            ],
+        );
+        symbols.add_struct("inet6_dev", 0x40, &[("addr_list", 0x10)]);
+        if with_ipv6_prefix {
+            symbols.add_struct(
+                "inet6_ifaddr",
+                0x40,
+                &[("addr", 0), ("prefix_len", 0x10), ("if_list", 0x20)],
+            );
+        } else {
+            symbols.add_struct("inet6_ifaddr", 0x40, &[("addr", 0), ("if_list", 0x20)]);
+        }
+        symbols
+    }
+
+    /// An UP device with a MAC, two IPv4 addresses and one IPv6 address
+    fn device() -> Dump {
+        let mut dump = Dump::new(0x4000);
+        dump.bytes(DEV + 0x20, &1500u32.to_le_bytes());
+        dump.bytes(DEV + 0x24, &0x43u32.to_le_bytes());
+        dump.bytes(DEV + 0x28, &1u32.to_le_bytes());
+        dump.ptr(DEV + 0x30, MAC);
+        dump.bytes(DEV + 0x38, &[6]);
+        dump.bytes(MAC, &[0x52, 0x54, 0x00, 0x12, 0x34, 0x56]);
+        dump.ptr(DEV + 0x40, IN_DEV);
+        dump.ptr(DEV + 0x48, IN6_DEV);
+
+        // in_device->ifa_list -> 10.0.0.5/24 -> 192.168.1.1/16
+        dump.ptr(IN_DEV + 8, 0x2100);
+        dump.ptr(0x2100, 0x2200);
+        dump.bytes(0x2110, &[10, 0, 0, 5]);
+        dump.bytes(0x2119, &[24]);
+        dump.bytes(0x2210, &[192, 168, 1, 1]);
+        dump.bytes(0x2219, &[16]);
+
+        // inet6_dev->addr_list <-> fe80::1/64
+        dump.ptr(IN6_DEV + 0x10, IFA6 + 0x20);
+        dump.ptr(IFA6 + 0x20, IN6_DEV + 0x10);
+        let mut fe80 = [0u8; 16];
+        fe80[0] = 0xfe;
+        fe80[1] = 0x80;
+        fe80[15] = 1;
+        dump.bytes(IFA6, &fe80);
+        dump.bytes(IFA6 + 0x10, &64u32.to_le_bytes());
+        dump
+    }
+
+    #[test]
+    fn test_read_walks_device_addresses() {
+        let dump = device();
+        let translator = dump.translator();
+        let symbols = symbols(true);
+        let reader = DeviceReader::new(&dump.0, &translator, &symbols).unwrap();
+
+        let info = reader.read(DIRECT_MAP + DEV, 4026531992, "eth0".to_string());
+        assert_eq!(info.offset, DEV);
+        assert_eq!(info.net_namespace, 4026531992);
+        assert_eq!(info.mac, "52:54:00:12:34:56");
+        assert_eq!(info.mtu, 1500);
+        assert_eq!(info.flags, format_interface_flags(0x43));
+        // IFF_PROMISC is clear, but the promiscuity counter is not
+        assert!(info.promiscuous);
+        assert_eq!(info.ipv4, vec!["10.0.0.5/24", "192.168.1.1/16"]);
+        assert_eq!(info.ipv6, vec!["fe80::1/64"]);
+    }
+
+    #[test]
+    fn test_read_skips_ipv6_without_inet6_ifaddr_layout() {
+        let dump = device();
+        let translator = dump.translator();
+        let symbols = symbols(false);
+        let reader = DeviceReader::new(&dump.0, &translator, &symbols).unwrap();
+
+        let info = reader.read(DIRECT_MAP + DEV, 0, "eth0".to_string());
+        assert_eq!(info.ipv4.len(), 2);
+        assert!(info.ipv6.is_empty());
+    }
+}
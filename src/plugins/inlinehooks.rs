@@ -0,0 +1,398 @@
+//! InlineHooks plugin - detects modified kernel function text (inline hooks)
+use crate::error::AnalysisError;
+use crate::kernel::text::{
+    decode_trampoline, format_bytes, Trampoline, VmlinuxImage, ENDBR64, NOP5,
+};
+use crate::kernel::{InlineHookInfo, KernelParser};
+use crate::plugins::plugin_trait::{AnalysisContext, ForensicPlugin, PluginOutput};
+use crate::translation::Architecture;
+use std::path::PathBuf;
+
+// Macro for conditional warning output
+macro_rules! warn {
+    ($($arg:tt)*) => {
+        if std::env::var("LINMEMPARSER_VERBOSE").is_ok() {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+// Bytes inspected at each function entry when no vmlinux is available
+const PROLOGUE_LENGTH: usize = 32;
+// Bytes shown for the original/current instruction bytes
+const MAX_REPORTED_BYTES: usize = 16;
+// Modified regions reported per function before moving on
+const MAX_REGIONS_PER_FUNCTION: usize = 8;
+// Differences closer together than this are reported as one region
+const REGION_MERGE_GAP: usize = 4;
+// Trampoline sequences are at most 12 bytes (movabs + jmp)
+const MAX_TRAMPOLINE_LENGTH: usize = 12;
+
+// Symbols whose bodies the kernel rewrites as a whole
+const SELF_MODIFYING_PREFIXES: [&str; 2] = ["__SCT__", "bpf_dispatcher_"];
+
+pub struct InlineHooksPlugin {
+    /// vmlinux image matching the dump; without one only prologues are inspected
+    pub vmlinux: Option<PathBuf>,
+}
+
+impl ForensicPlugin for InlineHooksPlugin {
+    fn name(&self) -> &str {
+        "inlinehooks"
+    }
+
+    fn description(&self) -> &str {
+        "Detect inline hooks by checking kernel function text integrity"
+    }
+
+    fn run(&self, context: &AnalysisContext) -> Result<PluginOutput, AnalysisError> {
+        // Prologues, trampolines and relocations are decoded as x86-64 code
+        if !matches!(context.translator.architecture(), Architecture::X86_64) {
+            return Err(AnalysisError::PluginError(
+                "inlinehooks only decodes x86-64 kernel text".to_string(),
+            ));
+        }
+        let resolver = context.symbol_resolver;
+        let stext = resolver
+            .get_symbol_address("_stext")
+            .ok_or_else(|| AnalysisError::SymbolNotFound("_stext".to_string()))?;
+        let etext = resolver
+            .get_symbol_address("_etext")
+            .ok_or_else(|| AnalysisError::SymbolNotFound("_etext".to_string()))?;
+        let checker = TextChecker {
+            context,
+            stext,
+            etext,
+        };
+
+        let hooks = match &self.vmlinux {
+            Some(path) => {
+                let image = VmlinuxImage::open(path, resolver)?;
+                checker.compare_with_image(&image)
+            }
+            None => checker.scan_prologues(),
+        };
+
+        Ok(PluginOutput::InlineHooks(hooks))
+    }
+}
+
+/// Compares in-memory kernel text with vmlinux or with known-good prologue shapes
+struct TextChecker<'a, 'c> {
+    context: &'a AnalysisContext<'c>,
+    stext: u64,
+    etext: u64,
+}
+
+impl TextChecker<'_, '_> {
+    fn read(&self, address: u64, length: usize) -> Option<Vec<u8>> {
+        KernelParser::read_virt_bytes(
+            &self.context.memory_map.mapped[..],
+            self.context.translator,
+            address,
+            length,
+        )
+    }
+
+    fn in_kernel_text(&self, address: u64) -> bool {
+        address >= self.stext && address < self.etext
+    }
+
+    /// Resolve a trampoline destination, following the pointer slot of indirect jumps
+    fn destination(&self, trampoline: &Trampoline) -> Option<u64> {
+        let target = trampoline.target?;
+        if trampoline.indirect {
+            KernelParser::read_virt_pointer(
+                &self.context.memory_map.mapped[..],
+                self.context.translator,
+                target,
+            )
+        } else {
+            Some(target)
+        }
+    }
+
+    /// Describe an address as symbol+offset inside kernel text, or flag it as outside
+    fn describe(&self, symbols: &[(u64, &str)], address: Option<u64>) -> String {
+        let address = match address {
+            Some(a) => a,
+            None => return String::new(),
+        };
+        if !self.in_kernel_text(address) {
+            return format!("{:#x} (outside kernel text)", address);
+        }
+        let index = symbols.partition_point(|&(a, _)| a <= address);
+        match index.checked_sub(1).map(|i| symbols[i]) {
+            Some((start, name)) if address == start => name.to_string(),
+            Some((start, name)) => format!("{}+{:#x}", name, address - start),
+            None => format!("{:#x}", address),
+        }
+    }
+
+    /// Compare every vmlinux function with its in-memory copy
+    fn compare_with_image(&self, image: &VmlinuxImage) -> Vec<InlineHookInfo> {
+        // Symbols are KASLR-relocated; the vmlinux is not
+        let slide = ["_text", "_stext"]
+            .iter()
+            .find_map(|name| {
+                Some(
+                    self.context
+                        .symbol_resolver
+                        .get_symbol_address(name)?
+                        .wrapping_sub(image.symbol_address(name)?),
+                )
+            })
+            .unwrap_or(0);
+
+        let symbols: Vec<(u64, &str)> = image
+            .functions()
+            .map(|f| (f.address.wrapping_add(slide), f.name.as_str()))
+            .collect();
+
+        let mut hooks = Vec::new();
+        let mut compared = 0usize;
+        let mut modified = 0usize;
+        for function in image.functions() {
+            if SELF_MODIFYING_PREFIXES
+                .iter()
+                .any(|p| function.name.starts_with(p))
+            {
+                continue;
+            }
+            let size = function.size as usize;
+            let original = match image.bytes(function.address, size) {
+                Some(b) => b,
+                None => continue,
+            };
+            let runtime = function.address.wrapping_add(slide);
+            let current = match self.read(runtime, size) {
+                Some(b) => b,
+                None => continue,
+            };
+            compared += 1;
+
+            let mut diff: Vec<bool> = (0..size)
+                .map(|i| {
+                    original[i] != current[i] && !image.is_patch_site(function.address + i as u64)
+                })
+                .collect();
+            if slide != 0 {
+                mask_relocations(original, &current, slide, &mut diff);
+            }
+
+            let found = hooks.len();
+
+            // ftrace entry site: nop5 or a call, either of which is legitimate while the
+            // call stays inside kernel text (ftrace_caller / ftrace_regs_caller)
+            let entry = if original.starts_with(&ENDBR64) { 4 } else { 0 };
+            if is_ftrace_site(&original[entry.min(size)..]) && size >= entry + 5 {
+                let site = &current[entry..entry + 5];
+                if site != NOP5 {
+                    let call = decode_trampoline(site, runtime + entry as u64)
+                        .filter(|t| t.kind == "call");
+                    let target = call.as_ref().and_then(|t| t.target);
+                    if call.is_none() || !target.map(|t| self.in_kernel_text(t)).unwrap_or(false) {
+                        hooks.push(InlineHookInfo {
+                            offset: self
+                                .context
+                                .translator
+                                .virtual_to_file_offset(runtime + entry as u64)
+                                .unwrap_or(0),
+                            function: function.name.clone(),
+                            address: runtime,
+                            hook_offset: entry as u64,
+                            kind: if call.is_some() {
+                                "ftrace call".to_string()
+                            } else {
+                                "modified".to_string()
+                            },
+                            target: self.describe(&symbols, target),
+                            original: format_bytes(&original[entry..entry + 5]),
+                            current: format_bytes(site),
+                        });
+                    }
+                }
+                diff[entry..entry + 5].iter_mut().for_each(|d| *d = false);
+            }
+
+            for (start, end) in diff_regions(&diff)
+                .into_iter()
+                .take(MAX_REGIONS_PER_FUNCTION)
+            {
+                // Back up to the instruction that covers the first modified byte
+                let decoded = (start.saturating_sub(MAX_TRAMPOLINE_LENGTH - 1)..=start)
+                    .rev()
+                    .find_map(|at| {
+                        decode_trampoline(&current[at..], runtime + at as u64)
+                            .filter(|t| at + t.length > start)
+                            .map(|t| (at, t))
+                    });
+                let (at, kind, target, length) = match &decoded {
+                    Some((at, t)) => (*at, t.kind, self.destination(t), t.length),
+                    None => (start, "modified", None, 0),
+                };
+                let shown = (end - at)
+                    .max(length)
+                    .min(MAX_REPORTED_BYTES)
+                    .min(size - at);
+
+                hooks.push(InlineHookInfo {
+                    offset: self
+                        .context
+                        .translator
+                        .virtual_to_file_offset(runtime + at as u64)
+                        .unwrap_or(0),
+                    function: function.name.clone(),
+                    address: runtime,
+                    hook_offset: at as u64,
+                    kind: kind.to_string(),
+                    target: self.describe(&symbols, target),
+                    original: format_bytes(&original[at..at + shown]),
+                    current: format_bytes(&current[at..at + shown]),
+                });
+            }
+
+            if hooks.len() > found {
+                modified += 1;
+            }
+        }
+
+        if compared > 0 && modified * 4 > compared {
+            warn!(
+                "[WARNING] {} of {} functions differ; the vmlinux image may not match this dump",
+                modified, compared
+            );
+        }
+
+        hooks
+    }
+
+    /// Without vmlinux, flag function entries that transfer control out of kernel text
+    fn scan_prologues(&self) -> Vec<InlineHookInfo> {
+        let mut symbols: Vec<(u64, &str)> = self
+            .context
+            .symbol_resolver
+            .get_symbols()
+            .iter()
+            .filter(|(_, &address)| self.in_kernel_text(address))
+            .map(|(name, &address)| (address, name.as_str()))
+            .collect();
+        symbols.sort();
+        symbols.dedup_by_key(|(address, _)| *address);
+
+        let mut hooks = Vec::new();
+        for &(address, name) in &symbols {
+            if SELF_MODIFYING_PREFIXES.iter().any(|p| name.starts_with(p)) {
+                continue;
+            }
+            let current = match self.read(address, PROLOGUE_LENGTH) {
+                Some(b) => b,
+                None => continue,
+            };
+            let entry = if current.starts_with(&ENDBR64) { 4 } else { 0 };
+            let trampoline = match decode_trampoline(&current[entry..], address + entry as u64) {
+                Some(t) => t,
+                None => continue,
+            };
+            let target = self.destination(&trampoline);
+
+            // Relative branches within kernel text are ordinary tail calls and ftrace calls
+            let suspicious = match trampoline.kind {
+                "jmp" | "call" => !target.map(|t| self.in_kernel_text(t)).unwrap_or(false),
+                _ => true,
+            };
+            if !suspicious {
+                continue;
+            }
+
+            let shown = trampoline.length.min(MAX_REPORTED_BYTES);
+            hooks.push(InlineHookInfo {
+                offset: self
+                    .context
+                    .translator
+                    .virtual_to_file_offset(address + entry as u64)
+                    .unwrap_or(0),
+                function: name.to_string(),
+                address,
+                hook_offset: entry as u64,
+                kind: trampoline.kind.to_string(),
+                target: self.describe(&symbols, target),
+                original: String::new(),
+                current: format_bytes(&current[entry..entry + shown]),
+            });
+        }
+
+        hooks
+    }
+}
+
+/// Whether the bytes at a function entry are an ftrace site (`call __fentry__` or nop5)
+fn is_ftrace_site(bytes: &[u8]) -> bool {
+    bytes.starts_with(&NOP5) || bytes.first() == Some(&0xe8)
+}
+
+/// Clear differences explained by KASLR relocation of absolute addresses
+///
+/// The boot-time relocator adds the slide to 32-bit sign-extended and 64-bit
+/// absolute references, so a window whose value moved by exactly the slide is
+/// not a modification.
+fn mask_relocations(original: &[u8], current: &[u8], slide: u64, diff: &mut [bool]) {
+    for i in 0..diff.len() {
+        if !diff[i] {
+            continue;
+        }
+        for width in [4usize, 8] {
+            let first = i.saturating_sub(width - 1);
+            for at in first..=i {
+                if at + width > original.len() {
+                    break;
+                }
+                let moved = match width {
+                    4 => {
+                        let o = u32::from_le_bytes(original[at..at + 4].try_into().unwrap());
+                        let c = u32::from_le_bytes(current[at..at + 4].try_into().unwrap());
+                        c.wrapping_sub(o) == slide as u32
+                    }
+                    _ => {
+                        let o = u64::from_le_bytes(original[at..at + 8].try_into().unwrap());
+                        let c = u64::from_le_bytes(current[at..at + 8].try_into().unwrap());
+                        c.wrapping_sub(o) == slide
+                    }
+                };
+                if moved {
+                    diff[at..at + width].iter_mut().for_each(|d| *d = false);
+                }
+            }
+        }
+    }
+}
+
+/// Group modified bytes into [start, end) regions
+fn diff_regions(diff: &[bool]) -> Vec<(usize, usize)> {
+    let mut regions: Vec<(usize, usize)> = Vec::new();
+    for (i, _) in diff.iter().enumerate().filter(|(_, &d)| d) {
+        match regions.last_mut() {
+            Some((_, end)) if i < *end + REGION_MERGE_GAP => *end = i + 1,
+            _ => regions.push((i, i + 1)),
+        }
+    }
+    regions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mask_relocations_and_regions() {
+        let slide = 0x1e00000u64;
+        let original = [0x48, 0xc7, 0xc7, 0x00, 0x10, 0x00, 0x81, 0x90, 0x90, 0x90];
+        let mut current = original;
+        current[3..7].copy_from_slice(&(0x81001000u32.wrapping_add(slide as u32)).to_le_bytes());
+        current[9] = 0xcc;
+
+        let mut diff: Vec<bool> = original.iter().zip(&current).map(|(o, c)| o != c).collect();
+        mask_relocations(&original, &current, slide, &mut diff);
+        assert_eq!(diff_regions(&diff), vec![(9, 10)]);
+    }
+}
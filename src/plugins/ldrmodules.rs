@@ -0,0 +1,322 @@
+//! LdrModules plugin - compares mapped shared objects with the dynamic loader's link_map
+use crate::error::AnalysisError;
+use crate::kernel::loader::{link_map, LinkMapEntry};
+use crate::kernel::mm::{ProcessMemory, VM_EXEC};
+use crate::kernel::process_extractor::ProcessExtractor;
+use crate::kernel::vfs::VfsWalker;
+use crate::kernel::{LibraryInfo, ProcessInfo};
+use crate::plugins::plugin_trait::{AnalysisContext, ForensicPlugin, PluginOutput};
+use std::collections::HashMap;
+
+// Macro for conditional warning output
+macro_rules! warn {
+    ($($arg:tt)*) => {
+        if std::env::var("LINMEMPARSER_VERBOSE").is_ok() {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+// Upper bound on the environment block searched for LD_PRELOAD
+const MAX_ENV_BYTES: u64 = 0x10000;
+
+// Objects the loader lists without a backing file
+const VDSO_NAMES: [&str; 2] = ["linux-vdso.so.1", "linux-gate.so.1"];
+
+pub struct LdrModulesPlugin {
+    pub pid: Option<i32>,
+}
+
+impl ForensicPlugin for LdrModulesPlugin {
+    fn name(&self) -> &str {
+        "ldrmodules"
+    }
+
+    fn description(&self) -> &str {
+        "Compare executable file mappings with the dynamic loader's link_map"
+    }
+
+    fn run(&self, context: &AnalysisContext) -> Result<PluginOutput, AnalysisError> {
+        let mapped = &context.memory_map.mapped[..];
+        let translator = context.translator;
+        let resolver = context.symbol_resolver;
+        let processes = ProcessExtractor::new().walk_process_list(
+            context.memory_map,
+            translator,
+            resolver,
+            context.init_task_offset as u64,
+        )?;
+        let checker = LibraryChecker {
+            vfs: VfsWalker::new(mapped, translator, resolver)?,
+        };
+
+        let mut libraries = Vec::new();
+        for process in &processes {
+            if self.pid.is_some_and(|pid| pid != process.pid) {
+                continue;
+            }
+            // Kernel threads have no address space
+            let memory = match ProcessMemory::for_task(mapped, translator, resolver, process.offset)
+            {
+                Some(m) => m,
+                None => continue,
+            };
+            match checker.process_libraries(&memory, process) {
+                Ok(found) => libraries.extend(found),
+                Err(e) => warn!("[WARNING] PID {}: VMAs unavailable: {}", process.pid, e),
+            }
+        }
+
+        Ok(PluginOutput::Libraries(libraries))
+    }
+}
+
+/// Builds the kernel and loader views of one process and merges them
+struct LibraryChecker<'a> {
+    vfs: VfsWalker<'a>,
+}
+
+/// A file with at least one executable mapping
+struct MappedFile {
+    base: u64, // Lowest start of any mapping of the file
+    path: String,
+    deleted: bool,
+}
+
+impl<'a> LibraryChecker<'a> {
+    fn process_libraries(
+        &self,
+        memory: &ProcessMemory,
+        process: &ProcessInfo,
+    ) -> Result<Vec<LibraryInfo>, AnalysisError> {
+        let exe = memory
+            .mm_field("exe_file")
+            .filter(|&f| f != 0)
+            .and_then(|f| self.vfs.file_path(f));
+        let preload = ld_preload(memory);
+
+        // Kernel view: file-backed mappings, grouped by path
+        let mut paths: HashMap<u64, String> = HashMap::new();
+        let mut mapped: Vec<MappedFile> = Vec::new();
+        let mut executable: Vec<String> = Vec::new();
+        for vma in memory.vmas()? {
+            if vma.file == 0 {
+                continue;
+            }
+            let path = paths
+                .entry(vma.file)
+                .or_insert_with(|| {
+                    self.vfs
+                        .file_path(vma.file)
+                        .unwrap_or_else(|| format!("{:#x}", vma.file))
+                })
+                .clone();
+            if Some(&path) == exe.as_ref() {
+                continue;
+            }
+            if vma.flags & VM_EXEC != 0 && !executable.contains(&path) {
+                executable.push(path.clone());
+            }
+            match mapped.iter_mut().find(|m| m.path == path) {
+                Some(existing) => existing.base = existing.base.min(vma.start),
+                None => mapped.push(MappedFile {
+                    base: vma.start,
+                    deleted: self.vfs.file_deleted(vma.file),
+                    path,
+                }),
+            }
+        }
+        mapped.retain(|m| executable.contains(&m.path));
+        mapped.sort_by_key(|m| m.base);
+
+        // Loader view: link_map, minus the main program and the vDSO
+        let linked: Vec<_> = link_map(memory)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|entry| !entry.name.is_empty() && !VDSO_NAMES.contains(&entry.name.as_str()))
+            .collect();
+
+        Ok(compare_views(&mapped, &linked, &preload, process))
+    }
+}
+
+/// Pair each executable file mapping with its link_map entry
+///
+/// Entries match on load base first, then on path or file name; link_map
+/// entries left over are objects the loader knows about but the kernel does not
+/// map, and unmatched files are mappings the loader never recorded.
+fn compare_views(
+    files: &[MappedFile],
+    linked: &[LinkMapEntry],
+    preload: &[String],
+    process: &ProcessInfo,
+) -> Vec<LibraryInfo> {
+    let mut matched = vec![false; linked.len()];
+    let record =
+        |base: u64, path: &str, is_mapped: bool, is_linked: bool, deleted: bool| LibraryInfo {
+            offset: process.offset,
+            pid: process.pid,
+            comm: process.comm.clone(),
+            base,
+            path: path.to_string(),
+            mapped: is_mapped,
+            linked: is_linked,
+            deleted,
+            memfd: path.starts_with("/memfd:"),
+            preloaded: preload
+                .iter()
+                .any(|p| p == path || basename(p) == basename(path)),
+        };
+
+    let mut libraries = Vec::new();
+    for file in files {
+        // l_addr is the load bias, which equals the first mapping for shared objects
+        let found = linked
+            .iter()
+            .enumerate()
+            .find(|(i, e)| !matched[*i] && e.base == file.base)
+            .or_else(|| {
+                linked.iter().enumerate().find(|(i, e)| {
+                    !matched[*i]
+                        && (e.name == file.path || basename(&e.name) == basename(&file.path))
+                })
+            })
+            .map(|(i, _)| i);
+        if let Some(i) = found {
+            matched[i] = true;
+        }
+        libraries.push(record(
+            file.base,
+            &file.path,
+            true,
+            found.is_some(),
+            file.deleted,
+        ));
+    }
+    for (entry, _) in linked.iter().zip(&matched).filter(|(_, &m)| !m) {
+        libraries.push(record(entry.base, &entry.name, false, true, false));
+    }
+
+    libraries
+}
+
+/// Read the entries of LD_PRELOAD from the process environment
+fn ld_preload(memory: &ProcessMemory) -> Vec<String> {
+    let (start, end) = match (memory.mm_field("env_start"), memory.mm_field("env_end")) {
+        (Some(s), Some(e)) if e > s => (s, e.min(s + MAX_ENV_BYTES)),
+        _ => return Vec::new(),
+    };
+    match memory.read_bytes(start, (end - start) as usize) {
+        Some(env) => parse_ld_preload(&env),
+        None => Vec::new(),
+    }
+}
+
+/// Split the LD_PRELOAD value out of a NUL-separated environment block
+fn parse_ld_preload(env: &[u8]) -> Vec<String> {
+    env.split(|&b| b == 0)
+        .filter_map(|var| var.strip_prefix(b"LD_PRELOAD="))
+        .flat_map(|value| {
+            String::from_utf8_lossy(value)
+                .split([':', ' '])
+                .filter(|p| !p.is_empty())
+                .map(|p| p.to_string())
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+fn basename(path: &str) -> &str {
+    path.rsplit('/').next().unwrap_or(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(base: u64, path: &str, deleted: bool) -> MappedFile {
+        MappedFile {
+            base,
+            path: path.to_string(),
+            deleted,
+        }
+    }
+
+    fn entry(base: u64, name: &str) -> LinkMapEntry {
+        LinkMapEntry {
+            base,
+            name: name.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_compare_views() {
+        let process = ProcessInfo {
+            offset: 0x1234,
+            pid: 4321,
+            comm: "sshd".to_string(),
+            ppid: 1,
+            start_time: 0,
+            uid: 0,
+            gid: 0,
+            state: "S".to_string(),
+            cmdline: String::new(),
+        };
+        let files = [
+            file(
+                0x7f00_0000_0000,
+                "/usr/lib/x86_64-linux-gnu/libc.so.6",
+                false,
+            ),
+            file(0x7f10_0000_0000, "/tmp/evil.so", true),
+            file(0x7f20_0000_0000, "/memfd:payload (deleted)", true),
+            file(0x7f40_0000_0000, "/usr/lib/libfoo.so", false),
+        ];
+        let linked = [
+            // Same base, path spelled through the /lib symlink
+            entry(0x7f00_0000_0000, "/lib/x86_64-linux-gnu/libc.so.6"),
+            // Known to the loader but not mapped by the kernel
+            entry(0x7f30_0000_0000, "/opt/libhidden.so"),
+            // Base disagrees; matched by file name
+            entry(0x1000, "libfoo.so"),
+        ];
+        let preload = ["/tmp/evil.so".to_string()];
+
+        let libraries = compare_views(&files, &linked, &preload, &process);
+        let summary: Vec<String> = libraries
+            .iter()
+            .map(|l| {
+                format!(
+                    "{:#x} {} mapped={} linked={} deleted={} memfd={} preloaded={}",
+                    l.base, l.path, l.mapped, l.linked, l.deleted, l.memfd, l.preloaded
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                "0x7f0000000000 /usr/lib/x86_64-linux-gnu/libc.so.6 mapped=true linked=true deleted=false memfd=false preloaded=false",
+                "0x7f1000000000 /tmp/evil.so mapped=true linked=false deleted=true memfd=false preloaded=true",
+                "0x7f2000000000 /memfd:payload (deleted) mapped=true linked=false deleted=true memfd=true preloaded=false",
+                "0x7f4000000000 /usr/lib/libfoo.so mapped=true linked=true deleted=false memfd=false preloaded=false",
+                "0x7f3000000000 /opt/libhidden.so mapped=false linked=true deleted=false memfd=false preloaded=false",
+            ]
+        );
+        assert!(libraries
+            .iter()
+            .all(|l| l.pid == 4321 && l.offset == 0x1234 && l.comm == "sshd"));
+    }
+
+    #[test]
+    fn test_parse_ld_preload() {
+        let env = b"PATH=/bin\0LD_PRELOAD=/tmp/a.so:/tmp/b.so  libc.so\0HOME=/root\0";
+        assert_eq!(
+            parse_ld_preload(env),
+            vec!["/tmp/a.so", "/tmp/b.so", "libc.so"]
+        );
+        assert!(parse_ld_preload(b"HOME=/root\0XLD_PRELOAD=/tmp/x.so\0").is_empty());
+        assert!(parse_ld_preload(b"LD_PRELOAD=\0").is_empty());
+        assert_eq!(basename("/usr/lib/libc.so.6"), "libc.so.6");
+        assert_eq!(basename("libc.so.6"), "libc.so.6");
+    }
+}
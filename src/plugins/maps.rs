@@ -0,0 +1,149 @@
+//! Maps plugin - lists process mappings with how many of their pages are in the dump or in swap
+use crate::error::AnalysisError;
+use crate::kernel::mm::{ProcessMemory, VmaInfo, VM_EXEC, VM_READ, VM_SHARED, VM_WRITE};
+use crate::kernel::process_extractor::ProcessExtractor;
+use crate::kernel::vfs::VfsWalker;
+use crate::kernel::MapsInfo;
+use crate::plugins::plugin_trait::{AnalysisContext, ForensicPlugin, PluginOutput};
+
+// Macro for conditional warning output
+macro_rules! warn {
+    ($($arg:tt)*) => {
+        if std::env::var("LINMEMPARSER_VERBOSE").is_ok() {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+const PAGE_SIZE: u64 = 0x1000;
+
+pub struct MapsPlugin {
+    pub pid: Option<i32>,
+}
+
+impl ForensicPlugin for MapsPlugin {
+    fn name(&self) -> &str {
+        "maps"
+    }
+
+    fn description(&self) -> &str {
+        "List process memory mappings with resident, swapped and unavailable page counts"
+    }
+
+    fn run(&self, context: &AnalysisContext) -> Result<PluginOutput, AnalysisError> {
+        let mapped = &context.memory_map.mapped[..];
+        let translator = context.translator;
+        let resolver = context.symbol_resolver;
+        let processes = ProcessExtractor::new().walk_process_list(
+            context.memory_map,
+            translator,
+            resolver,
+            context.init_task_offset as u64,
+        )?;
+        let vfs = VfsWalker::new(mapped, translator, resolver)?;
+
+        let mut maps = Vec::new();
+        for process in &processes {
+            if self.pid.is_some_and(|pid| pid != process.pid) {
+                continue;
+            }
+            // Kernel threads have no address space
+            let memory = match ProcessMemory::for_task(mapped, translator, resolver, process.offset)
+            {
+                Some(m) => m,
+                None => continue,
+            };
+            let vmas = match memory.vmas() {
+                Ok(v) => v,
+                Err(e) => {
+                    warn!("[WARNING] PID {}: VMAs unavailable: {}", process.pid, e);
+                    continue;
+                }
+            };
+
+            for vma in vmas {
+                let (resident, swapped, swap_readable) = count_pages(context, &memory, &vma);
+                let pages = (vma.end - vma.start) / PAGE_SIZE;
+                maps.push(MapsInfo {
+                    pid: process.pid,
+                    comm: process.comm.clone(),
+                    start: vma.start,
+                    end: vma.end,
+                    perms: perms(vma.flags),
+                    pgoff: vma.pgoff,
+                    path: if vma.file == 0 {
+                        String::new()
+                    } else {
+                        vfs.file_path(vma.file)
+                            .unwrap_or_else(|| format!("{:#x}", vma.file))
+                    },
+                    resident,
+                    swapped,
+                    swap_readable,
+                    unavailable: pages.saturating_sub(resident + swapped),
+                });
+            }
+        }
+
+        Ok(PluginOutput::Maps(maps))
+    }
+}
+
+/// Count the pages of a VMA that are in the dump, swapped out, and readable from swap
+fn count_pages(
+    context: &AnalysisContext,
+    memory: &ProcessMemory,
+    vma: &VmaInfo,
+) -> (u64, u64, u64) {
+    let mapped = &context.memory_map.mapped[..];
+    let translator = context.translator;
+    let (mut resident, mut swapped, mut swap_readable) = (0, 0, 0);
+
+    translator.walk_page_table(
+        mapped,
+        memory.pgd_phys,
+        vma.start,
+        vma.end,
+        &mut |virtual_addr, physical, size| {
+            // Large pages can extend past the VMA
+            let first = virtual_addr.max(vma.start);
+            let last = (virtual_addr + size).min(vma.end);
+            for page in (first..last).step_by(PAGE_SIZE as usize) {
+                if translator
+                    .physical_to_file_offset(physical + (page - virtual_addr))
+                    .is_some()
+                {
+                    resident += 1;
+                }
+            }
+        },
+    );
+    translator.walk_swap_entries(
+        mapped,
+        memory.pgd_phys,
+        vma.start,
+        vma.end,
+        &mut |_, entry| {
+            swapped += 1;
+            if translator.swap_page_readable(mapped, entry) {
+                swap_readable += 1;
+            }
+        },
+    );
+
+    (resident, swapped, swap_readable)
+}
+
+/// Format vm_flags like /proc/<pid>/maps
+fn perms(flags: u64) -> String {
+    let bit = |mask: u64, c: char| if flags & mask != 0 { c } else { '-' };
+    let shared = if flags & VM_SHARED != 0 { 's' } else { 'p' };
+    [
+        bit(VM_READ, 'r'),
+        bit(VM_WRITE, 'w'),
+        bit(VM_EXEC, 'x'),
+        shared,
+    ]
+    .iter()
+    .collect()
+}
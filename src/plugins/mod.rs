@@ -1,18 +1,64 @@
 //! Plugin manager for the Linux Memory Parser tool
 use std::collections::HashMap;
 
+pub mod arp;
+pub mod bash;
+pub mod bpf;
+pub mod bpfmaps;
+pub mod containers;
+pub mod elfs;
 pub mod files;
+pub mod ifconfig;
+pub mod inlinehooks;
+pub mod ldrmodules;
+pub mod maps;
 pub mod modules;
+pub mod namespaces;
+pub mod netfilter;
 pub mod netstat;
+pub mod notifiers;
+pub mod opscheck;
+pub mod pageinfo;
 pub mod plugin_trait;
 pub mod pslist;
 pub mod pstree;
+pub mod rawsockets;
+pub mod routes;
+pub mod strings;
+pub mod tmpfs;
+pub mod tracehooks;
+pub mod ttycheck;
+pub mod unix;
+pub mod yarascan;
 
+pub use arp::ArpPlugin;
+pub use bash::BashPlugin;
+pub use bpf::BpfPlugin;
+pub use bpfmaps::BpfMapsPlugin;
+pub use containers::ContainersPlugin;
+pub use elfs::ElfsPlugin;
 pub use files::FilesPlugin;
+pub use ifconfig::IfconfigPlugin;
+pub use inlinehooks::InlineHooksPlugin;
+pub use ldrmodules::LdrModulesPlugin;
+pub use maps::MapsPlugin;
 pub use modules::ModulesPlugin;
+pub use namespaces::NamespacesPlugin;
+pub use netfilter::NetfilterPlugin;
 pub use netstat::NetStatPlugin;
+pub use notifiers::NotifiersPlugin;
+pub use opscheck::OpsCheckPlugin;
+pub use pageinfo::PageInfoPlugin;
 pub use pslist::PsListPlugin;
 pub use pstree::PsTreePlugin;
+pub use rawsockets::RawSocketsPlugin;
+pub use routes::RoutesPlugin;
+pub use strings::StringsPlugin;
+pub use tmpfs::TmpfsPlugin;
+pub use tracehooks::TraceHooksPlugin;
+pub use ttycheck::TtyCheckPlugin;
+pub use unix::UnixPlugin;
+pub use yarascan::YaraScanPlugin;
 
 // For now, use a simplified plugin manager that doesn't depend on the complex plugin modules
 #[allow(dead_code)]
@@ -36,6 +82,89 @@ pub fn list_plugins(&self) -> Vec<(&str, &str)> {
             ("netstat", "Extract network connections"),
             ("modules", "List loaded kernel modules"),
             ("files", "List open file handles (not yet implemented)"),
+            (
+                "tmpfs",
+                "Reconstruct tmpfs/ramfs filesystems and memfd files",
+            ),
+            ("bash", "Recover bash command history from process memory"),
+            (
+                "ifconfig",
+                "List network interfaces, addresses and promiscuous mode",
+            ),
+            ("arp", "Extract the ARP and IPv6 neighbour caches"),
+            ("routes", "Extract the IPv4 and IPv6 routing tables"),
+            (
+                "unix",
+                "List Unix domain sockets with owning and peer processes",
+            ),
+            (
+                "rawsockets",
+                "List packet, raw and netlink sockets with attached BPF filters",
+            ),
+            (
+                "bpf",
+                "List loaded eBPF programs with attach points and JIT images",
+            ),
+            (
+                "bpfmaps",
+                "List eBPF maps with key/value sizes and capacity",
+            ),
+            (
+                "inlinehooks",
+                "Detect inline hooks by checking kernel function text integrity",
+            ),
+            (
+                "tracehooks",
+                "List ftrace ops, kprobes and tracepoint probes with resolved callbacks",
+            ),
+            (
+                "netfilter",
+                "List netfilter hooks with priorities and resolved callbacks",
+            ),
+            (
+                "notifiers",
+                "List keyboard, module, netdev, reboot and panic notifier callbacks",
+            ),
+            (
+                "ttycheck",
+                "Check tty line discipline receive_buf and driver write handlers",
+            ),
+            (
+                "opscheck",
+                "Check syscall tables, IDT, /proc and network ops structures for hijacked pointers",
+            ),
+            (
+                "namespaces",
+                "List per-process namespaces, namespace-local PIDs, hostnames and cgroups",
+            ),
+            (
+                "containers",
+                "Group processes into containers by namespace set, hostname and cgroup",
+            ),
+            (
+                "ldrmodules",
+                "Compare executable file mappings with the dynamic loader's link_map",
+            ),
+            (
+                "elfs",
+                "Carve ELF images from process memory with build IDs",
+            ),
+            (
+                "yarascan",
+                "Scan physical, kernel or process memory with YARA-style rules",
+            ),
+            (
+                "strings",
+                "Attribute strings in the dump to physical, kernel and process addresses",
+            ),
+            (
+                "pageinfo",
+                "Decode the struct page (flags, mapping, counts) of a physical address",
+            ),
+            (
+                "maps",
+                "List process memory mappings with resident, swapped and unavailable page counts",
+            ),
         ]
     }
 
@@ -46,6 +175,29 @@ pub fn get_plugin_names(&self) -> Vec<String> {
             "netstat".to_string(),
             "modules".to_string(),
             "files".to_string(),
+            "tmpfs".to_string(),
+            "bash".to_string(),
+            "ifconfig".to_string(),
+            "arp".to_string(),
+            "routes".to_string(),
+            "unix".to_string(),
+            "rawsockets".to_string(),
+            "bpf".to_string(),
+            "bpfmaps".to_string(),
+            "inlinehooks".to_string(),
+            "tracehooks".to_string(),
+            "netfilter".to_string(),
+            "notifiers".to_string(),
+            "ttycheck".to_string(),
+            "opscheck".to_string(),
+            "namespaces".to_string(),
+            "containers".to_string(),
+            "ldrmodules".to_string(),
+            "elfs".to_string(),
+            "yarascan".to_string(),
+            "strings".to_string(),
+            "pageinfo".to_string(),
+            "maps".to_string(),
         ]
     }
 }
@@ -0,0 +1,64 @@
+//! Namespaces plugin - lists the namespaces, local PID, hostname and cgroup of each process
+use crate::error::AnalysisError;
+use crate::kernel::namespaces::NamespaceWalker;
+use crate::kernel::process_extractor::ProcessExtractor;
+use crate::kernel::ProcessNamespaceInfo;
+use crate::plugins::plugin_trait::{AnalysisContext, ForensicPlugin, PluginOutput};
+
+pub struct NamespacesPlugin;
+
+impl ForensicPlugin for NamespacesPlugin {
+    fn name(&self) -> &str {
+        "namespaces"
+    }
+
+    fn description(&self) -> &str {
+        "List per-process namespaces, namespace-local PIDs, hostnames and cgroups"
+    }
+
+    fn run(&self, context: &AnalysisContext) -> Result<PluginOutput, AnalysisError> {
+        Ok(PluginOutput::ProcessNamespaces(process_namespaces(
+            context,
+        )?))
+    }
+}
+
+/// Walk the process list and read each task's namespace membership
+pub fn process_namespaces(
+    context: &AnalysisContext,
+) -> Result<Vec<ProcessNamespaceInfo>, AnalysisError> {
+    let processes = ProcessExtractor::new().walk_process_list(
+        context.memory_map,
+        context.translator,
+        context.symbol_resolver,
+        context.init_task_offset as u64,
+    )?;
+    let walker = NamespaceWalker::new(
+        &context.memory_map.mapped[..],
+        context.translator,
+        context.symbol_resolver,
+    )?;
+
+    Ok(processes
+        .into_iter()
+        .map(|process| {
+            let ns = walker.task_namespaces(process.offset);
+            ProcessNamespaceInfo {
+                offset: process.offset,
+                pid: process.pid,
+                ns_pid: ns.ns_pid,
+                comm: process.comm,
+                user_ns: ns.user,
+                mnt_ns: ns.mnt,
+                uts_ns: ns.uts,
+                ipc_ns: ns.ipc,
+                pid_ns: ns.pid,
+                net_ns: ns.net,
+                cgroup_ns: ns.cgroup,
+                time_ns: ns.time,
+                hostname: ns.hostname,
+                cgroup: ns.cgroup_path,
+            }
+        })
+        .collect())
+}
@@ -0,0 +1,417 @@
+//! Netfilter plugin - lists registered netfilter hooks and their callbacks
+use crate::error::AnalysisError;
+use crate::kernel::net::NetWalker;
+use crate::kernel::pointers::PointerIndex;
+use crate::kernel::{KernelParser, NetfilterHookInfo};
+use crate::plugins::plugin_trait::{AnalysisContext, ForensicPlugin, PluginOutput};
+use crate::symbols::SymbolResolver;
+use crate::translation::MemoryTranslator;
+
+// Macro for conditional warning output
+macro_rules! warn {
+    ($($arg:tt)*) => {
+        if std::env::var("LINMEMPARSER_VERBOSE").is_ok() {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+// Protocol families (NFPROTO_*)
+const NFPROTO_IPV4: u64 = 2;
+const NFPROTO_ARP: u64 = 3;
+const NFPROTO_BRIDGE: u64 = 7;
+const NFPROTO_IPV6: u64 = 10;
+const NFPROTO_NUMPROTO: u64 = 13;
+// NF_MAX_HOOKS (dimension of the older two-level hook arrays)
+const NF_MAX_HOOKS: u64 = 8;
+// NF_INET_NUMHOOKS / NF_ARP_NUMHOOKS
+const NF_INET_NUMHOOKS: u64 = 5;
+const NF_ARP_NUMHOOKS: u64 = 3;
+// Safety limit on hooks registered at one hook point
+const MAX_HOOK_ENTRIES: u64 = 1024;
+// sizeof(struct nf_hook_entry): hook function and priv pointer
+const NF_HOOK_ENTRY_SIZE: u64 = 16;
+
+pub struct NetfilterPlugin;
+
+impl ForensicPlugin for NetfilterPlugin {
+    fn name(&self) -> &str {
+        "netfilter"
+    }
+
+    fn description(&self) -> &str {
+        "List netfilter hooks with priorities and resolved callbacks"
+    }
+
+    fn run(&self, context: &AnalysisContext) -> Result<PluginOutput, AnalysisError> {
+        let mapped = &context.memory_map.mapped[..];
+        let resolver = context.symbol_resolver;
+        let callbacks = PointerIndex::new(mapped, context.translator, resolver)?;
+        let reader = HookReader {
+            mapped,
+            translator: context.translator,
+            symbol_resolver: resolver,
+            callbacks: &callbacks,
+        };
+
+        // Before 4.3 hooks lived in a global nf_hooks[NFPROTO][HOOK] list array
+        if resolver.get_symbol_address("nf_hooks").is_some() && !resolver.has_field("net", "nf") {
+            return Ok(PluginOutput::NetfilterHooks(reader.global_hooks()?));
+        }
+
+        let walker = NetWalker::new(mapped, context.translator, resolver)?;
+        let mut hooks = Vec::new();
+        for net in walker.namespaces()? {
+            let net_namespace = walker.namespace_inum(net);
+            match reader.namespace_hooks(net, net_namespace) {
+                Ok(found) => hooks.extend(found),
+                Err(e) => warn!("[WARNING] Netfilter hooks unavailable: {}", e),
+            }
+        }
+
+        Ok(PluginOutput::NetfilterHooks(hooks))
+    }
+}
+
+/// Reader for per-namespace nf_hook_entries blobs and the legacy nf_hooks lists
+struct HookReader<'a> {
+    mapped: &'a [u8],
+    translator: &'a MemoryTranslator,
+    symbol_resolver: &'a SymbolResolver,
+    callbacks: &'a PointerIndex,
+}
+
+impl<'a> HookReader<'a> {
+    fn field(&self, s: &str, f: &str) -> Result<u64, AnalysisError> {
+        self.symbol_resolver
+            .require_field_offset(s, f)
+            .map(|o| o as u64)
+    }
+
+    fn read_ptr(&self, addr: u64) -> u64 {
+        KernelParser::read_virt_pointer(self.mapped, self.translator, addr).unwrap_or(0)
+    }
+
+    /// Build a record for one registered hook function
+    fn hook(
+        &self,
+        object: u64,
+        net_namespace: u32,
+        pf: u64,
+        hooknum: u64,
+        priority: i32,
+        callback: u64,
+    ) -> NetfilterHookInfo {
+        let resolved = self.callbacks.classify(callback);
+        NetfilterHookInfo {
+            offset: self.translator.virtual_to_file_offset(object).unwrap_or(0),
+            net_namespace,
+            family: family_name(pf),
+            hook: hook_name(pf, hooknum),
+            priority,
+            callback,
+            symbol: resolved.symbol,
+            owner: resolved.class.to_string(),
+        }
+    }
+
+    /// Read the hooks of one network namespace (4.13+)
+    fn namespace_hooks(
+        &self,
+        net: u64,
+        net_namespace: u32,
+    ) -> Result<Vec<NetfilterHookInfo>, AnalysisError> {
+        let resolver = self.symbol_resolver;
+        let nf = net + self.field("net", "nf")?;
+        let mut hooks = Vec::new();
+
+        // 4.16+: one nf_hook_entries pointer array per family
+        let families = [
+            ("hooks_ipv4", NFPROTO_IPV4, NF_INET_NUMHOOKS),
+            ("hooks_ipv6", NFPROTO_IPV6, NF_INET_NUMHOOKS),
+            ("hooks_arp", NFPROTO_ARP, NF_ARP_NUMHOOKS),
+            ("hooks_bridge", NFPROTO_BRIDGE, NF_INET_NUMHOOKS),
+        ];
+        if resolver.has_field("netns_nf", "hooks_ipv4") {
+            for (field, pf, count) in families {
+                let array = match resolver.get_struct_field_offset("netns_nf", field, None) {
+                    Some(o) => nf + o,
+                    None => continue,
+                };
+                for hooknum in 0..count {
+                    let entries = self.read_ptr(array + hooknum * self.translator.pointer_size());
+                    hooks.extend(self.entries(entries, net_namespace, pf, hooknum)?);
+                }
+            }
+            return Ok(hooks);
+        }
+
+        // 4.13 - 4.15: hooks[NFPROTO_NUMPROTO][NF_MAX_HOOKS]
+        let array = nf + self.field("netns_nf", "hooks")?;
+        for pf in 0..NFPROTO_NUMPROTO {
+            for hooknum in 0..NF_MAX_HOOKS {
+                let entries = self.read_ptr(
+                    array + (pf * NF_MAX_HOOKS + hooknum) * self.translator.pointer_size(),
+                );
+                hooks.extend(self.entries(entries, net_namespace, pf, hooknum)?);
+            }
+        }
+        Ok(hooks)
+    }
+
+    /// Decode a struct nf_hook_entries blob
+    ///
+    /// The hooks[] array is followed by an orig_ops[] array of nf_hook_ops
+    /// pointers, which carries each hook's priority.
+    fn entries(
+        &self,
+        entries: u64,
+        net_namespace: u32,
+        pf: u64,
+        hooknum: u64,
+    ) -> Result<Vec<NetfilterHookInfo>, AnalysisError> {
+        let mut hooks = Vec::new();
+        if entries == 0 {
+            return Ok(hooks);
+        }
+
+        let count = KernelParser::read_virt_u16(
+            self.mapped,
+            self.translator,
+            entries + self.field("nf_hook_entries", "num_hook_entries")?,
+        )
+        .unwrap_or(0) as u64;
+        let array = entries + self.field("nf_hook_entries", "hooks")?;
+        let hook = self.field("nf_hook_entry", "hook")?;
+        let stride = self
+            .symbol_resolver
+            .get_struct_size("nf_hook_entry")
+            .map(|s| s as u64)
+            .unwrap_or(NF_HOOK_ENTRY_SIZE);
+        let priority = self.field("nf_hook_ops", "priority")?;
+        let orig_ops = array + count * stride;
+
+        for i in 0..count.min(MAX_HOOK_ENTRIES) {
+            let callback = self.read_ptr(array + i * stride + hook);
+            if callback == 0 {
+                continue;
+            }
+            let ops = self.read_ptr(orig_ops + i * self.translator.pointer_size());
+            let prio = if ops != 0 {
+                KernelParser::read_virt_i32(self.mapped, self.translator, ops + priority)
+                    .unwrap_or(0)
+            } else {
+                0
+            };
+            hooks.push(self.hook(entries, net_namespace, pf, hooknum, prio, callback));
+        }
+
+        Ok(hooks)
+    }
+
+    /// Walk the global nf_hooks[NFPROTO_NUMPROTO][NF_MAX_HOOKS] list heads (before 4.3)
+    fn global_hooks(&self) -> Result<Vec<NetfilterHookInfo>, AnalysisError> {
+        let base = self
+            .symbol_resolver
+            .get_symbol_address("nf_hooks")
+            .ok_or_else(|| AnalysisError::SymbolNotFound("nf_hooks".to_string()))?;
+        let list = self.field("nf_hook_ops", "list")?;
+        let hook = self.field("nf_hook_ops", "hook")?;
+        let priority = self.field("nf_hook_ops", "priority")?;
+
+        let mut hooks = Vec::new();
+        for pf in 0..NFPROTO_NUMPROTO {
+            for hooknum in 0..NF_MAX_HOOKS {
+                // struct list_head is two pointers
+                let head = base + (pf * NF_MAX_HOOKS + hooknum) * 16;
+                for node in KernelParser::walk_list(
+                    self.mapped,
+                    self.translator,
+                    head,
+                    MAX_HOOK_ENTRIES as usize,
+                ) {
+                    let ops = node - list;
+                    let callback = self.read_ptr(ops + hook);
+                    if callback == 0 {
+                        continue;
+                    }
+                    let prio =
+                        KernelParser::read_virt_i32(self.mapped, self.translator, ops + priority)
+                            .unwrap_or(0);
+                    hooks.push(self.hook(ops, 0, pf, hooknum, prio, callback));
+                }
+            }
+        }
+
+        Ok(hooks)
+    }
+}
+
+/// Name a netfilter protocol family
+fn family_name(pf: u64) -> String {
+    match pf {
+        1 => "inet".to_string(),
+        NFPROTO_IPV4 => "ipv4".to_string(),
+        NFPROTO_ARP => "arp".to_string(),
+        5 => "netdev".to_string(),
+        NFPROTO_BRIDGE => "bridge".to_string(),
+        NFPROTO_IPV6 => "ipv6".to_string(),
+        12 => "decnet".to_string(),
+        other => format!("pf_{}", other),
+    }
+}
+
+/// Name a hook point within its family
+fn hook_name(pf: u64, hooknum: u64) -> String {
+    const INET: [&str; 5] = [
+        "PRE_ROUTING",
+        "LOCAL_IN",
+        "FORWARD",
+        "LOCAL_OUT",
+        "POST_ROUTING",
+    ];
+    const ARP: [&str; 3] = ["IN", "OUT", "FORWARD"];
+    let names: &[&str] = if pf == NFPROTO_ARP { &ARP } else { &INET };
+    names
+        .get(hooknum as usize)
+        .map(|n| n.to_string())
+        .unwrap_or_else(|| format!("hook_{}", hooknum))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kernel::modules::LoadedModule;
+    use crate::testutil::{Dump, DIRECT_MAP};
+
+    const KERNEL_HOOK: u64 = 0xffff_ffff_8100_1010;
+    const MODULE_HOOK: u64 = 0xffff_ffff_c000_1000;
+
+    /// Write an nf_hook_ops at ops with the given priority
+    fn hook_ops(dump: &mut Dump, ops: u64, priority: i32) {
+        dump.bytes(ops + 0x10, &priority.to_le_bytes());
+    }
+
+    fn symbols() -> SymbolResolver {
+        let mut symbols = SymbolResolver::new();
+        symbols.add_struct("net", 0x200, &[("nf", 0x100)]);
+        symbols.add_struct("netns_nf", 0x80, &[("hooks_ipv4", 0), ("hooks_arp", 0x28)]);
+        symbols.add_struct(
+            "nf_hook_entries",
+            0x10,
+            &[("num_hook_entries", 0), ("hooks", 8)],
+        );
+        symbols.add_struct("nf_hook_entry", 0x10, &[("hook", 0)]);
+        symbols.add_struct(
+            "nf_hook_ops",
+            0x30,
+            &[("list", 0), ("hook", 8), ("priority", 0x10)],
+        );
+        symbols
+    }
+
+    fn callbacks() -> PointerIndex {
+        PointerIndex::from_parts(
+            0xffff_ffff_8100_0000,
+            0xffff_ffff_8200_0000,
+            vec![(0xffff_ffff_8100_1000, "nf_conntrack_in".to_string())],
+            vec![LoadedModule {
+                name: "rootkit".to_string(),
+                ranges: vec![(0xffff_ffff_c000_0000, 0xffff_ffff_c000_4000)],
+                symbols: vec![(MODULE_HOOK, "evil_hook".to_string())],
+            }],
+        )
+    }
+
+    fn summary(hooks: &[NetfilterHookInfo]) -> Vec<String> {
+        hooks
+            .iter()
+            .map(|h| {
+                format!(
+                    "{} {} {} {} {}",
+                    h.family, h.hook, h.priority, h.symbol, h.owner
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_namespace_hook_entries() {
+        let mut dump = Dump::new(0x1000);
+        // hooks_ipv4[PRE_ROUTING] and hooks_arp[OUT]
+        dump.ptr(0x100, 0x400);
+        dump.ptr(0x130, 0x600);
+        // Three entries, the middle one unregistered, then orig_ops[3]
+        dump.bytes(0x400, &3u16.to_le_bytes());
+        dump.word(0x408, KERNEL_HOOK);
+        dump.word(0x428, MODULE_HOOK);
+        dump.ptr(0x438, 0x800);
+        hook_ops(&mut dump, 0x800, -200);
+        dump.bytes(0x600, &1u16.to_le_bytes());
+        dump.word(0x608, 0xdead_0000);
+        dump.ptr(0x618, 0x900);
+        hook_ops(&mut dump, 0x900, 100);
+
+        let translator = dump.translator();
+        let symbols = symbols();
+        let callbacks = callbacks();
+        let reader = HookReader {
+            mapped: &dump.0,
+            translator: &translator,
+            symbol_resolver: &symbols,
+            callbacks: &callbacks,
+        };
+
+        let hooks = reader.namespace_hooks(DIRECT_MAP, 42).unwrap();
+        assert_eq!(
+            summary(&hooks),
+            vec![
+                "ipv4 PRE_ROUTING -200 nf_conntrack_in+0x10 kernel",
+                "ipv4 PRE_ROUTING 0 evil_hook rootkit",
+                "arp OUT 100 0xdead0000 unknown",
+            ]
+        );
+        assert_eq!((hooks[0].offset, hooks[0].net_namespace), (0x400, 42));
+        assert_eq!(hooks[1].callback, MODULE_HOOK);
+    }
+
+    #[test]
+    fn test_global_hook_lists() {
+        let mut dump = Dump::new(0x2000);
+        // nf_hooks[NFPROTO_IPV6][LOCAL_IN] -> one nf_hook_ops at 0x1800
+        let head = (NFPROTO_IPV6 * NF_MAX_HOOKS + 1) * 16;
+        dump.ptr(head, 0x1800);
+        dump.ptr(0x1800, head);
+        dump.word(0x1808, MODULE_HOOK + 0x20);
+        hook_ops(&mut dump, 0x1800, -150);
+
+        let translator = dump.translator();
+        let mut symbols = symbols();
+        symbols.add_symbol("nf_hooks".to_string(), DIRECT_MAP);
+        let callbacks = callbacks();
+        let reader = HookReader {
+            mapped: &dump.0,
+            translator: &translator,
+            symbol_resolver: &symbols,
+            callbacks: &callbacks,
+        };
+
+        let hooks = reader.global_hooks().unwrap();
+        assert_eq!(
+            summary(&hooks),
+            vec!["ipv6 LOCAL_IN -150 evil_hook+0x20 rootkit"]
+        );
+        assert_eq!(hooks[0].offset, 0x1800);
+    }
+
+    #[test]
+    fn test_family_and_hook_names() {
+        assert_eq!(family_name(NFPROTO_IPV4), "ipv4");
+        assert_eq!(family_name(NFPROTO_BRIDGE), "bridge");
+        assert_eq!(family_name(9), "pf_9");
+        assert_eq!(hook_name(NFPROTO_IPV4, 4), "POST_ROUTING");
+        assert_eq!(hook_name(NFPROTO_ARP, 2), "FORWARD");
+        assert_eq!(hook_name(NFPROTO_ARP, 3), "hook_3");
+        assert_eq!(hook_name(NFPROTO_IPV6, 7), "hook_7");
+    }
+}
@@ -1,7 +1,35 @@
 //! NetStat plugin - extracts network connections
 use crate::error::AnalysisError;
-use crate::kernel::ConnectionInfo;
+use crate::kernel::net::{format_ipv4, format_ipv6, format_tcp_state, SockReader, SocketIndex};
+use crate::kernel::process_extractor::ProcessExtractor;
+use crate::kernel::{ConnectionInfo, KernelParser, SocketOwner};
 use crate::plugins::plugin_trait::{AnalysisContext, ForensicPlugin, PluginOutput};
+use crate::symbols::SymbolResolver;
+use crate::translation::MemoryTranslator;
+use std::collections::HashSet;
+
+// Macro for conditional warning output
+macro_rules! warn {
+    ($($arg:tt)*) => {
+        if std::env::var("LINMEMPARSER_VERBOSE").is_ok() {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+// Safety limits for hash table walks
+const MAX_BUCKETS: u64 = 1 << 24;
+const MAX_CHAIN_LENGTH: usize = 65536;
+// INET_LHTABLE_SIZE for kernels with the legacy listening_hash
+const INET_LHTABLE_SIZE: u64 = 32;
+
+// Address families
+const AF_INET: u16 = 2;
+const AF_INET6: u16 = 10;
+
+// Mini-socks that share only struct sock_common with a full sock
+const TCP_TIME_WAIT: u8 = 6;
+const TCP_NEW_SYN_RECV: u8 = 12;
 
 pub struct NetStatPlugin;
 
@@ -14,17 +42,418 @@ fn description(&self) -> &str {
         "Extract network connections"
     }
 
-    fn run(&self, _context: &AnalysisContext) -> Result<PluginOutput, AnalysisError> {
-        // This is a stub implementation - in a real implementation, we would:
-        // 1. Find init_net symbol
-        // 2. Parse TCP hash table (struct inet_hashinfo)
-        // 3. Parse UDP hash table (struct udp_table)
-        // 4. Extract socket information (struct sock)
+    fn run(&self, context: &AnalysisContext) -> Result<PluginOutput, AnalysisError> {
+        let mapped = &context.memory_map.mapped[..];
+        let translator = context.translator;
+        let resolver = context.symbol_resolver;
+
+        let processes = ProcessExtractor::new().walk_process_list(
+            context.memory_map,
+            translator,
+            resolver,
+            context.init_task_offset as u64,
+        )?;
+        // Without fd-table offsets, connections are still listed with PID 0
+        let index =
+            SocketIndex::build(mapped, translator, resolver, &processes).unwrap_or_else(|e| {
+                warn!("[WARNING] Socket owners unavailable: {}", e);
+                SocketIndex::default()
+            });
+        let reader = InetReader::new(mapped, translator, resolver)?;
+
+        let mut socks = Vec::new();
+        match reader.tcp_socks() {
+            Ok(found) => socks.extend(found.into_iter().map(|sk| ("tcp", sk))),
+            Err(e) => warn!("[WARNING] TCP sockets unavailable: {}", e),
+        }
+        match reader.udp_socks() {
+            Ok(found) => socks.extend(found.into_iter().map(|sk| ("udp", sk))),
+            Err(e) => warn!("[WARNING] UDP sockets unavailable: {}", e),
+        }
 
-        // For now, return an empty list of connections
-        let connections = Vec::<ConnectionInfo>::new();
+        let mut connections = Vec::new();
+        for (protocol, sk) in socks {
+            if let Some(conn) = reader.connection(protocol, sk) {
+                let owners = if conn.inode != 0 {
+                    index.owners(conn.inode)
+                } else {
+                    Vec::new()
+                };
+                connections.extend(owned_rows(conn, owners));
+            }
+        }
 
-        // In the future, we'll implement the full functionality
         Ok(PluginOutput::Connections(connections))
     }
 }
+
+/// Expand a connection into one row per owning file descriptor
+///
+/// Unowned sockets (mini-socks, kernel sockets, closed files) keep a single row
+/// with PID 0.
+fn owned_rows(conn: ConnectionInfo, owners: Vec<SocketOwner>) -> Vec<ConnectionInfo> {
+    if owners.is_empty() {
+        return vec![conn];
+    }
+    owners
+        .into_iter()
+        .map(|owner| ConnectionInfo {
+            pid: owner.pid,
+            fd: Some(owner.fd),
+            comm: owner.comm,
+            ..conn.clone()
+        })
+        .collect()
+}
+
+/// Keep only the rows owned by one process (the `--pid` filter)
+pub fn filter_by_pid(connections: &mut Vec<ConnectionInfo>, pid: i32) {
+    connections.retain(|c| c.pid == pid);
+}
+
+/// Reader for the TCP/UDP hash tables and inet socket addresses
+struct InetReader<'a> {
+    mapped: &'a [u8],
+    translator: &'a MemoryTranslator,
+    symbol_resolver: &'a SymbolResolver,
+    socks: SockReader<'a>,
+    // sock_common
+    node: u64,
+    daddr: u64,
+    rcv_saddr: u64,
+    dport: u64,
+    num: u64,
+    v6_daddr: Option<u64>,
+    v6_rcv_saddr: Option<u64>,
+}
+
+impl<'a> InetReader<'a> {
+    fn new(
+        mapped: &'a [u8],
+        translator: &'a MemoryTranslator,
+        symbol_resolver: &'a SymbolResolver,
+    ) -> Result<Self, AnalysisError> {
+        let common = symbol_resolver
+            .get_struct_field_offset("sock", "__sk_common", None)
+            .unwrap_or(0);
+        let field = |f: &str| {
+            symbol_resolver
+                .require_field_offset("sock_common", f)
+                .map(|o| common + o as u64)
+        };
+        // IPv6 addresses are only present with CONFIG_IPV6
+        let optional = |f: &str| {
+            if symbol_resolver.has_field("sock_common", f) {
+                field(f).ok()
+            } else {
+                None
+            }
+        };
+
+        Ok(InetReader {
+            mapped,
+            translator,
+            symbol_resolver,
+            socks: SockReader::new(mapped, translator, symbol_resolver)?,
+            node: field("skc_node")?,
+            daddr: field("skc_daddr")?,
+            rcv_saddr: field("skc_rcv_saddr")?,
+            dport: field("skc_dport")?,
+            num: field("skc_num")?,
+            v6_daddr: optional("skc_v6_daddr"),
+            v6_rcv_saddr: optional("skc_v6_rcv_saddr"),
+        })
+    }
+
+    fn field(&self, s: &str, f: &str) -> Result<u64, AnalysisError> {
+        self.symbol_resolver
+            .require_field_offset(s, f)
+            .map(|o| o as u64)
+    }
+
+    fn read_ptr(&self, addr: u64) -> u64 {
+        KernelParser::read_virt_pointer(self.mapped, self.translator, addr).unwrap_or(0)
+    }
+
+    fn read_u32(&self, addr: u64) -> u32 {
+        KernelParser::read_virt_u32(self.mapped, self.translator, addr).unwrap_or(0)
+    }
+
+    /// Walk `buckets` chain heads spaced `stride` bytes apart
+    ///
+    /// Chains may be plain hlists or hlist_nulls; both end at a NULL or odd pointer.
+    fn walk_buckets(
+        &self,
+        base: u64,
+        buckets: u64,
+        stride: u64,
+        node_offset: u64,
+        seen: &mut HashSet<u64>,
+    ) -> Vec<u64> {
+        let mut socks = Vec::new();
+        for bucket in 0..buckets.min(MAX_BUCKETS) {
+            let first = self.read_ptr(base + bucket * stride);
+            for node in KernelParser::walk_hlist_nulls(
+                self.mapped,
+                self.translator,
+                first,
+                MAX_CHAIN_LENGTH,
+            ) {
+                let sk = node.wrapping_sub(node_offset);
+                if seen.insert(sk) {
+                    socks.push(sk);
+                }
+            }
+        }
+        socks
+    }
+
+    /// Collect listening and established TCP socks from tcp_hashinfo
+    fn tcp_socks(&self) -> Result<Vec<u64>, AnalysisError> {
+        let resolver = self.symbol_resolver;
+        let hashinfo = resolver
+            .get_symbol_address("tcp_hashinfo")
+            .ok_or_else(|| AnalysisError::SymbolNotFound("tcp_hashinfo".to_string()))?;
+        let mut seen = HashSet::new();
+        let mut socks = Vec::new();
+
+        // Listening sockets: lhash2 (4.16+) and/or the legacy listening_hash
+        if resolver.has_field("inet_hashinfo", "lhash2") {
+            let lhash2 = self.read_ptr(hashinfo + self.field("inet_hashinfo", "lhash2")?);
+            let mask = self.read_u32(hashinfo + self.field("inet_hashinfo", "lhash2_mask")?);
+            let stride = resolver
+                .get_struct_size("inet_listen_hashbucket")
+                .unwrap_or(16) as u64;
+            // Before 5.19 lhash2 chained icsk_listen_portaddr_node through an hlist
+            let (head, node_offset) = if resolver.has_field("inet_listen_hashbucket", "head")
+                && resolver.has_field("inet_connection_sock", "icsk_listen_portaddr_node")
+            {
+                (
+                    self.field("inet_listen_hashbucket", "head")?,
+                    self.field("inet_connection_sock", "icsk_listen_portaddr_node")?,
+                )
+            } else {
+                (
+                    self.field("inet_listen_hashbucket", "nulls_head")?,
+                    self.node,
+                )
+            };
+            if lhash2 != 0 {
+                socks.extend(self.walk_buckets(
+                    lhash2 + head,
+                    mask as u64 + 1,
+                    stride,
+                    node_offset,
+                    &mut seen,
+                ));
+            }
+        }
+        if resolver.has_field("inet_hashinfo", "listening_hash") {
+            let listening = hashinfo + self.field("inet_hashinfo", "listening_hash")?;
+            let stride = resolver
+                .get_struct_size("inet_listen_hashbucket")
+                .unwrap_or(16) as u64;
+            let head = resolver
+                .get_struct_field_offset("inet_listen_hashbucket", "head", None)
+                .or_else(|| {
+                    resolver.get_struct_field_offset("inet_listen_hashbucket", "nulls_head", None)
+                })
+                .unwrap_or(8);
+            socks.extend(self.walk_buckets(
+                listening + head,
+                INET_LHTABLE_SIZE,
+                stride,
+                self.node,
+                &mut seen,
+            ));
+        }
+
+        // Established (and TIME_WAIT / SYN_RECV mini-socks): ehash of hlist_nulls_heads
+        let ehash = self.read_ptr(hashinfo + self.field("inet_hashinfo", "ehash")?);
+        let ehash_mask = self.read_u32(hashinfo + self.field("inet_hashinfo", "ehash_mask")?);
+        if ehash != 0 {
+            let stride = resolver.get_struct_size("inet_ehash_bucket").unwrap_or(8) as u64;
+            socks.extend(self.walk_buckets(
+                ehash,
+                ehash_mask as u64 + 1,
+                stride,
+                self.node,
+                &mut seen,
+            ));
+        }
+
+        Ok(socks)
+    }
+
+    /// Collect UDP (v4 and v6) socks from udp_table
+    fn udp_socks(&self) -> Result<Vec<u64>, AnalysisError> {
+        let resolver = self.symbol_resolver;
+        let table = resolver
+            .get_symbol_address("udp_table")
+            .ok_or_else(|| AnalysisError::SymbolNotFound("udp_table".to_string()))?;
+        let hash = self.read_ptr(table + self.field("udp_table", "hash")?);
+        let mask = self.read_u32(table + self.field("udp_table", "mask")?);
+        if hash == 0 {
+            return Ok(Vec::new());
+        }
+
+        let stride = resolver.get_struct_size("udp_hslot").unwrap_or(16) as u64;
+        let head = resolver
+            .get_struct_field_offset("udp_hslot", "head", None)
+            .unwrap_or(0);
+        let mut seen = HashSet::new();
+        Ok(self.walk_buckets(hash + head, mask as u64 + 1, stride, self.node, &mut seen))
+    }
+
+    /// Build a connection record for an inet sock
+    fn connection(&self, protocol: &str, sk: u64) -> Option<ConnectionInfo> {
+        let family = self.socks.family(sk);
+        let state = self.socks.state(sk);
+        let read = |offset: u64, length: usize| {
+            KernelParser::read_virt_bytes(self.mapped, self.translator, sk + offset, length)
+        };
+
+        let (protocol, local_addr, remote_addr) = match family {
+            AF_INET => (
+                protocol.to_string(),
+                format_ipv4(&read(self.rcv_saddr, 4)?),
+                format_ipv4(&read(self.daddr, 4)?),
+            ),
+            AF_INET6 => (
+                format!("{}6", protocol),
+                format_ipv6(&read(self.v6_rcv_saddr?, 16)?),
+                format_ipv6(&read(self.v6_daddr?, 16)?),
+            ),
+            _ => return None,
+        };
+
+        let local_port = KernelParser::read_virt_u16(self.mapped, self.translator, sk + self.num)?;
+        let dport = read(self.dport, 2)?;
+
+        // Mini-socks have no struct socket and so no inode
+        let inode = if state == TCP_TIME_WAIT || state == TCP_NEW_SYN_RECV {
+            0
+        } else {
+            self.socks.inode_number(sk)
+        };
+
+        Some(ConnectionInfo {
+            offset: self.translator.virtual_to_file_offset(sk).unwrap_or(0),
+            protocol,
+            local_addr,
+            local_port,
+            remote_addr,
+            // skc_dport is kept in network byte order, skc_num in host order
+            remote_port: u16::from_be_bytes([dport[0], dport[1]]),
+            state: format_tcp_state(state),
+            pid: 0,
+            fd: None,
+            comm: String::new(),
+            inode,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testutil::{socket_symbols, Dump, DIRECT_MAP};
+
+    fn connection(kind: &str, pid: i32) -> ConnectionInfo {
+        ConnectionInfo {
+            offset: 0,
+            protocol: kind.to_string(),
+            local_addr: "0.0.0.0".to_string(),
+            local_port: 22,
+            remote_addr: "0.0.0.0".to_string(),
+            remote_port: 0,
+            state: "LISTEN".to_string(),
+            pid,
+            fd: None,
+            comm: String::new(),
+            inode: 0,
+        }
+    }
+
+    #[test]
+    fn test_connection_decoding() {
+        let mut dump = Dump::new(0x2000);
+        // An IPv4 TIME_WAIT mini-sock at 0x100: 192.168.1.5:51234 -> 10.0.0.1:8080
+        dump.bytes(0x100, &[10, 0, 0, 1]);
+        dump.bytes(0x104, &[192, 168, 1, 5]);
+        dump.bytes(0x10c, &8080u16.to_be_bytes());
+        dump.bytes(0x10e, &51234u16.to_le_bytes());
+        dump.bytes(0x110, &AF_INET.to_le_bytes());
+        dump.bytes(0x112, &[TCP_TIME_WAIT]);
+        // A dual-stack listener at 0x800 on ::ffff:10.1.2.3:443 whose socket is at 0x1000
+        dump.bytes(0x848 + 10, &[0xff, 0xff, 10, 1, 2, 3]);
+        dump.bytes(0x80e, &443u16.to_le_bytes());
+        dump.bytes(0x810, &AF_INET6.to_le_bytes());
+        dump.bytes(0x812, &[10]);
+        dump.ptr(0x900, 0x1000);
+        dump.word(0x1080, 12345);
+        // A sock of another family is skipped
+        dump.bytes(0x1810, &1u16.to_le_bytes());
+
+        let translator = dump.translator();
+        let symbols = socket_symbols();
+        let reader = InetReader::new(&dump.0, &translator, &symbols).unwrap();
+
+        let conn = reader.connection("tcp", DIRECT_MAP + 0x100).unwrap();
+        assert_eq!(
+            (
+                conn.protocol.as_str(),
+                conn.local_addr.as_str(),
+                conn.local_port
+            ),
+            ("tcp", "192.168.1.5", 51234)
+        );
+        assert_eq!(
+            (conn.remote_addr.as_str(), conn.remote_port),
+            ("10.0.0.1", 8080)
+        );
+        assert_eq!(conn.state, "TIME_WAIT");
+        // Mini-socks have no struct socket, so no inode either
+        assert_eq!(conn.inode, 0);
+        assert_eq!(conn.offset, 0x100);
+
+        let conn = reader.connection("tcp", DIRECT_MAP + 0x800).unwrap();
+        assert_eq!(
+            (
+                conn.protocol.as_str(),
+                conn.local_addr.as_str(),
+                conn.local_port
+            ),
+            ("tcp6", "::ffff:10.1.2.3", 443)
+        );
+        assert_eq!((conn.remote_addr.as_str(), conn.remote_port), ("::", 0));
+        assert_eq!((conn.state.as_str(), conn.inode), ("LISTEN", 12345));
+
+        assert!(reader.connection("udp", DIRECT_MAP + 0x1800).is_none());
+    }
+
+    #[test]
+    fn test_owned_rows_and_pid_filter() {
+        let owner = |pid: i32, fd: u32| SocketOwner {
+            pid,
+            fd,
+            comm: format!("proc{}", pid),
+        };
+        let mut rows = owned_rows(connection("tcp", 0), vec![owner(100, 3), owner(200, 7)]);
+        assert_eq!(rows.len(), 2);
+        assert_eq!(
+            (rows[1].pid, rows[1].fd, rows[1].comm.as_str()),
+            (200, Some(7), "proc200")
+        );
+        // A socket nobody holds open keeps one row without an owner
+        rows.extend(owned_rows(connection("udp", 0), Vec::new()));
+        assert_eq!((rows[2].pid, rows[2].fd), (0, None));
+
+        let mut filtered = rows.clone();
+        filter_by_pid(&mut filtered, 200);
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].fd, Some(7));
+        filter_by_pid(&mut rows, 300);
+        assert!(rows.is_empty());
+    }
+}
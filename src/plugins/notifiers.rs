@@ -0,0 +1,230 @@
+//! Notifiers plugin - lists callbacks registered on kernel notifier chains
+use crate::error::AnalysisError;
+use crate::kernel::pointers::PointerIndex;
+use crate::kernel::{KernelParser, NotifierInfo};
+use crate::plugins::plugin_trait::{AnalysisContext, ForensicPlugin, PluginOutput};
+use crate::symbols::SymbolResolver;
+use crate::translation::MemoryTranslator;
+use std::collections::HashSet;
+
+// Macro for conditional warning output
+macro_rules! warn {
+    ($($arg:tt)*) => {
+        if std::env::var("LINMEMPARSER_VERBOSE").is_ok() {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+// Safety limit on blocks per chain
+const MAX_CHAIN_LENGTH: usize = 4096;
+
+// Chains worth auditing and the notifier head type each is declared with
+const CHAINS: [(&str, &str); 9] = [
+    ("keyboard_notifier_list", "atomic_notifier_head"),
+    ("vt_notifier_list", "atomic_notifier_head"),
+    ("module_notify_list", "blocking_notifier_head"),
+    ("netdev_chain", "raw_notifier_head"),
+    ("inetaddr_chain", "blocking_notifier_head"),
+    ("inet6addr_chain", "atomic_notifier_head"),
+    ("reboot_notifier_list", "blocking_notifier_head"),
+    ("panic_notifier_list", "atomic_notifier_head"),
+    ("die_chain", "atomic_notifier_head"),
+];
+
+pub struct NotifiersPlugin;
+
+impl ForensicPlugin for NotifiersPlugin {
+    fn name(&self) -> &str {
+        "notifiers"
+    }
+
+    fn description(&self) -> &str {
+        "List keyboard, module, netdev, reboot and panic notifier callbacks"
+    }
+
+    fn run(&self, context: &AnalysisContext) -> Result<PluginOutput, AnalysisError> {
+        let mapped = &context.memory_map.mapped[..];
+        let translator = context.translator;
+        let resolver = context.symbol_resolver;
+        let callbacks = PointerIndex::new(mapped, translator, resolver)?;
+        let reader = ChainReader::new(mapped, translator, resolver, &callbacks)?;
+
+        let mut notifiers = Vec::new();
+        for (chain, head_type) in CHAINS {
+            let address = match resolver.get_symbol_address(chain) {
+                Some(a) => a,
+                None => {
+                    warn!("[WARNING] Notifier chain {} not found", chain);
+                    continue;
+                }
+            };
+            let head = match head_offset(resolver, head_type) {
+                Some(o) => o,
+                None => {
+                    warn!(
+                        "[WARNING] No {}::head offset, skipping {}",
+                        head_type, chain
+                    );
+                    continue;
+                }
+            };
+            notifiers.extend(reader.blocks(chain, address + head));
+        }
+
+        Ok(PluginOutput::Notifiers(notifiers))
+    }
+}
+
+/// Offset of the first-block pointer within a notifier head
+///
+/// raw_notifier_head is nothing but the head pointer, so older layouts without
+/// the field still resolve.
+fn head_offset(resolver: &SymbolResolver, head_type: &str) -> Option<u64> {
+    match resolver.get_struct_field_offset(head_type, "head", None) {
+        Some(o) => Some(o),
+        None if head_type == "raw_notifier_head" => Some(0),
+        None => None,
+    }
+}
+
+/// Reader for notifier_block chains
+struct ChainReader<'a> {
+    mapped: &'a [u8],
+    translator: &'a MemoryTranslator,
+    callbacks: &'a PointerIndex,
+    notifier_call: u64,
+    next: u64,
+    priority: u64,
+}
+
+impl<'a> ChainReader<'a> {
+    fn new(
+        mapped: &'a [u8],
+        translator: &'a MemoryTranslator,
+        resolver: &SymbolResolver,
+        callbacks: &'a PointerIndex,
+    ) -> Result<Self, AnalysisError> {
+        let field = |f: &str| {
+            resolver
+                .require_field_offset("notifier_block", f)
+                .map(|o| o as u64)
+        };
+        Ok(ChainReader {
+            mapped,
+            translator,
+            callbacks,
+            notifier_call: field("notifier_call")?,
+            next: field("next")?,
+            priority: field("priority")?,
+        })
+    }
+
+    /// Follow a chain from its head pointer
+    ///
+    /// The chain is a singly linked list of notifier_blocks, sorted by priority.
+    fn blocks(&self, chain: &str, head: u64) -> Vec<NotifierInfo> {
+        let read_ptr = |addr: u64| {
+            KernelParser::read_virt_pointer(self.mapped, self.translator, addr).unwrap_or(0)
+        };
+        let mut notifiers = Vec::new();
+        let mut visited = HashSet::new();
+        let mut block = read_ptr(head);
+        while block != 0 && notifiers.len() < MAX_CHAIN_LENGTH && visited.insert(block) {
+            let callback = read_ptr(block + self.notifier_call);
+            let resolved = self.callbacks.classify(callback);
+            notifiers.push(NotifierInfo {
+                offset: self.translator.virtual_to_file_offset(block).unwrap_or(0),
+                chain: chain.to_string(),
+                priority: KernelParser::read_virt_i32(
+                    self.mapped,
+                    self.translator,
+                    block + self.priority,
+                )
+                .unwrap_or(0),
+                callback,
+                symbol: resolved.symbol,
+                owner: resolved.class.to_string(),
+            });
+            block = read_ptr(block + self.next);
+        }
+        notifiers
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kernel::modules::LoadedModule;
+    use crate::testutil::{Dump, DIRECT_MAP};
+
+    /// Write a notifier_block { notifier_call, next, priority } at a physical address
+    fn block(dump: &mut Dump, at: u64, callback: u64, next: u64, priority: i32) {
+        dump.word(at, callback);
+        dump.ptr(at + 8, next);
+        dump.bytes(at + 16, &priority.to_le_bytes());
+    }
+
+    #[test]
+    fn test_chain_callbacks() {
+        let mut dump = Dump::new(0x1000);
+        // keyboard_notifier_list.head -> kernel, module and stray callbacks
+        dump.ptr(0x10, 0x100);
+        block(&mut dump, 0x100, 0xffff_ffff_8100_2000, 0x200, 10);
+        block(&mut dump, 0x200, 0xffff_ffff_c000_0040, 0x300, 0);
+        // The last block links back to the second; the walk stops there
+        block(&mut dump, 0x300, 0xffff_8880_1234_0000, 0x200, -5);
+
+        let translator = dump.translator();
+        let mut symbols = SymbolResolver::new();
+        symbols.add_struct(
+            "notifier_block",
+            0x18,
+            &[("notifier_call", 0), ("next", 8), ("priority", 16)],
+        );
+        symbols.add_struct("atomic_notifier_head", 0x18, &[("lock", 0), ("head", 0x10)]);
+        let callbacks = PointerIndex::from_parts(
+            0xffff_ffff_8100_0000,
+            0xffff_ffff_8200_0000,
+            vec![(0xffff_ffff_8100_2000, "kbd_keycode".to_string())],
+            vec![LoadedModule {
+                name: "keylogger".to_string(),
+                ranges: vec![(0xffff_ffff_c000_0000, 0xffff_ffff_c000_1000)],
+                symbols: vec![(0xffff_ffff_c000_0000, "kl_notify".to_string())],
+            }],
+        );
+        let reader = ChainReader::new(&dump.0, &translator, &symbols, &callbacks).unwrap();
+
+        let head = head_offset(&symbols, "atomic_notifier_head").unwrap();
+        let notifiers = reader.blocks("keyboard_notifier_list", DIRECT_MAP + head);
+        let summary: Vec<String> = notifiers
+            .iter()
+            .map(|n| format!("{} {} {} {}", n.offset, n.priority, n.symbol, n.owner))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                "256 10 kbd_keycode kernel",
+                "512 0 kl_notify+0x40 keylogger",
+                "768 -5 0xffff888012340000 unknown",
+            ]
+        );
+        assert!(notifiers
+            .iter()
+            .all(|n| n.chain == "keyboard_notifier_list"));
+        assert!(reader.blocks("die_chain", DIRECT_MAP + 0x800).is_empty());
+    }
+
+    #[test]
+    fn test_head_offset() {
+        let mut symbols = SymbolResolver::new();
+        symbols.add_struct(
+            "blocking_notifier_head",
+            0x40,
+            &[("rwsem", 0), ("head", 0x38)],
+        );
+        assert_eq!(head_offset(&symbols, "blocking_notifier_head"), Some(0x38));
+        assert_eq!(head_offset(&symbols, "raw_notifier_head"), Some(0));
+        assert_eq!(head_offset(&symbols, "atomic_notifier_head"), None);
+    }
+}
@@ -0,0 +1,324 @@
+//! OpsCheck plugin - validates function pointers in dispatch tables and ops structures
+use crate::error::AnalysisError;
+use crate::kernel::pointers::{PointerClass, PointerIndex};
+use crate::kernel::target::Target;
+use crate::kernel::{KernelParser, OpsPointerInfo};
+use crate::plugins::plugin_trait::{AnalysisContext, ForensicPlugin, PluginOutput};
+use crate::symbols::SymbolResolver;
+use crate::translation::MemoryTranslator;
+
+// Macro for conditional warning output
+macro_rules! warn {
+    ($($arg:tt)*) => {
+        if std::env::var("LINMEMPARSER_VERBOSE").is_ok() {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+// Safety limit on syscall table entries (NR_syscalls is well below this)
+const MAX_SYSCALLS: u64 = 1024;
+// x86_64 IDT: 256 gates of 16 bytes
+const IDT_ENTRIES: u64 = 256;
+const IDT_GATE_SIZE: u64 = 16;
+
+// System call dispatch tables
+const SYSCALL_TABLES: [&str; 3] = [
+    "sys_call_table",
+    "ia32_sys_call_table",
+    "x32_sys_call_table",
+];
+
+// Statically allocated ops structures and their types
+const OPS_SYMBOLS: [(&str, &str); 16] = [
+    ("proc_root_operations", "file_operations"),
+    ("proc_root_inode_operations", "inode_operations"),
+    ("tcp4_seq_ops", "seq_operations"),
+    ("tcp6_seq_ops", "seq_operations"),
+    ("udp_seq_ops", "seq_operations"),
+    ("udp6_seq_ops", "seq_operations"),
+    ("raw_seq_ops", "seq_operations"),
+    ("unix_seq_ops", "seq_operations"),
+    ("inet_stream_ops", "proto_ops"),
+    ("inet_dgram_ops", "proto_ops"),
+    ("inet6_stream_ops", "proto_ops"),
+    ("inet6_dgram_ops", "proto_ops"),
+    ("unix_stream_ops", "proto_ops"),
+    ("packet_ops", "proto_ops"),
+    ("tcp_prot", "proto"),
+    ("udp_prot", "proto"),
+];
+
+// Function pointer members worth checking in each ops type
+fn ops_members(struct_type: &str) -> &'static [&'static str] {
+    match struct_type {
+        "file_operations" => &[
+            "llseek",
+            "read",
+            "write",
+            "read_iter",
+            "write_iter",
+            "iterate",
+            "iterate_shared",
+            "readdir",
+            "poll",
+            "unlocked_ioctl",
+            "mmap",
+            "open",
+            "release",
+        ],
+        "inode_operations" => &[
+            "lookup",
+            "getattr",
+            "permission",
+            "readlink",
+            "get_link",
+            "setattr",
+        ],
+        "seq_operations" => &["start", "stop", "next", "show"],
+        "proto_ops" => &[
+            "release", "bind", "connect", "accept", "getname", "poll", "ioctl", "listen",
+            "shutdown", "sendmsg", "recvmsg", "mmap",
+        ],
+        "proto" => &[
+            "close",
+            "connect",
+            "disconnect",
+            "accept",
+            "ioctl",
+            "init",
+            "destroy",
+            "shutdown",
+            "setsockopt",
+            "getsockopt",
+            "sendmsg",
+            "recvmsg",
+            "bind",
+            "backlog_rcv",
+            "hash",
+            "unhash",
+            "get_port",
+        ],
+        _ => &[],
+    }
+}
+
+pub struct OpsCheckPlugin {
+    pub all_entries: bool, // Also report pointers into core kernel text
+}
+
+impl ForensicPlugin for OpsCheckPlugin {
+    fn name(&self) -> &str {
+        "opscheck"
+    }
+
+    fn description(&self) -> &str {
+        "Check syscall tables, IDT, /proc and network ops structures for hijacked pointers"
+    }
+
+    fn run(&self, context: &AnalysisContext) -> Result<PluginOutput, AnalysisError> {
+        let mapped = &context.memory_map.mapped[..];
+        let resolver = context.symbol_resolver;
+        let index = PointerIndex::new(mapped, context.translator, resolver)?;
+        let mut checker = OpsChecker {
+            mapped,
+            translator: context.translator,
+            symbol_resolver: resolver,
+            index: &index,
+            all_entries: self.all_entries,
+            pointers: Vec::new(),
+        };
+
+        for table in SYSCALL_TABLES {
+            checker.syscall_table(table);
+        }
+        checker.idt();
+        for (symbol, struct_type) in OPS_SYMBOLS {
+            match resolver.get_symbol_address(symbol) {
+                Some(address) => checker.ops(symbol, address, struct_type),
+                None => warn!("[WARNING] {} not found, skipping", symbol),
+            }
+        }
+        checker.proc_root();
+        checker.seq_afinfo();
+
+        Ok(PluginOutput::OpsPointers(checker.pointers))
+    }
+}
+
+/// Reads pointer slots and records those that fail classification
+struct OpsChecker<'a> {
+    mapped: &'a [u8],
+    translator: &'a MemoryTranslator,
+    symbol_resolver: &'a SymbolResolver,
+    index: &'a PointerIndex,
+    all_entries: bool,
+    pointers: Vec<OpsPointerInfo>,
+}
+
+impl<'a> OpsChecker<'a> {
+    fn read_ptr(&self, addr: u64) -> u64 {
+        KernelParser::read_virt_pointer(self.mapped, self.translator, addr).unwrap_or(0)
+    }
+
+    /// Classify one pointer and keep it if it leaves kernel text (or always with --all-entries)
+    fn check(&mut self, slot: u64, table: &str, member: String, pointer: u64) {
+        if pointer == 0 {
+            return;
+        }
+        let resolved = self.index.classify(pointer);
+        if resolved.class == PointerClass::Kernel && !self.all_entries {
+            return;
+        }
+        self.pointers.push(OpsPointerInfo {
+            offset: self.translator.virtual_to_file_offset(slot).unwrap_or(0),
+            table: table.to_string(),
+            member,
+            pointer,
+            symbol: resolved.symbol,
+            owner: resolved.class.to_string(),
+        });
+    }
+
+    /// Check a system call table, bounded by the next symbol in the image
+    fn syscall_table(&mut self, name: &str) {
+        let base = match self.symbol_resolver.get_symbol_address(name) {
+            Some(a) => a,
+            None => return,
+        };
+        let count = match self.symbol_resolver.next_symbol_address(base) {
+            Some(next) => ((next - base) / 8).min(MAX_SYSCALLS),
+            None => {
+                warn!("[WARNING] Cannot bound {}, skipping", name);
+                return;
+            }
+        };
+        for nr in 0..count {
+            let slot = base + nr * self.translator.pointer_size();
+            let pointer = self.read_ptr(slot);
+            self.check(slot, name, nr.to_string(), pointer);
+        }
+    }
+
+    /// Check the handler of every present x86_64 interrupt gate
+    fn idt(&mut self) {
+        let base = match self.symbol_resolver.get_symbol_address("idt_table") {
+            Some(a) => a,
+            None => {
+                warn!("[WARNING] idt_table not found, skipping");
+                return;
+            }
+        };
+        let table = match KernelParser::read_virt_bytes(
+            self.mapped,
+            self.translator,
+            base,
+            (IDT_ENTRIES * IDT_GATE_SIZE) as usize,
+        ) {
+            Some(b) => b,
+            None => return,
+        };
+        for (vector, gate) in table.chunks_exact(IDT_GATE_SIZE as usize).enumerate() {
+            if let Some(handler) = decode_idt_gate(gate, self.translator.target()) {
+                let slot = base + vector as u64 * IDT_GATE_SIZE;
+                self.check(slot, "idt_table", vector.to_string(), handler);
+            }
+        }
+    }
+
+    /// Check the function pointer members of an ops structure
+    fn ops(&mut self, table: &str, address: u64, struct_type: &str) {
+        for member in ops_members(struct_type) {
+            if let Some(offset) =
+                self.symbol_resolver
+                    .get_struct_field_offset(struct_type, member, None)
+            {
+                let pointer = self.read_ptr(address + offset);
+                self.check(address + offset, table, member.to_string(), pointer);
+            }
+        }
+    }
+
+    /// Follow proc_root's ops pointers, which a rootkit can swap for its own copy
+    fn proc_root(&mut self) {
+        let root = match self.symbol_resolver.get_symbol_address("proc_root") {
+            Some(a) => a,
+            None => return,
+        };
+        // proc_dir_ops (5.6+) and proc_fops (earlier) are both file_operations
+        let fields = [
+            ("proc_iops", "inode_operations"),
+            ("proc_dir_ops", "file_operations"),
+            ("proc_fops", "file_operations"),
+        ];
+        for (field, struct_type) in fields {
+            let offset =
+                match self
+                    .symbol_resolver
+                    .get_struct_field_offset("proc_dir_entry", field, None)
+                {
+                    Some(o) => o,
+                    None => continue,
+                };
+            let ops = self.read_ptr(root + offset);
+            if ops != 0 {
+                self.ops(&format!("proc_root->{}", field), ops, struct_type);
+            }
+        }
+    }
+
+    /// Check the seq_operations embedded in tcp{4,6}_seq_afinfo (before 4.18)
+    fn seq_afinfo(&mut self) {
+        let seq_ops =
+            match self
+                .symbol_resolver
+                .get_struct_field_offset("tcp_seq_afinfo", "seq_ops", None)
+            {
+                Some(o) => o,
+                None => return,
+            };
+        for name in ["tcp4_seq_afinfo", "tcp6_seq_afinfo"] {
+            if let Some(afinfo) = self.symbol_resolver.get_symbol_address(name) {
+                self.ops(
+                    &format!("{}.seq_ops", name),
+                    afinfo + seq_ops,
+                    "seq_operations",
+                );
+            }
+        }
+    }
+}
+
+/// Decode the handler address of a present x86_64 gate descriptor
+///
+/// Layout: offset_low u16, segment u16, bits u16 (present is bit 15),
+/// offset_middle u16, offset_high u32, reserved u32.
+fn decode_idt_gate(gate: &[u8], target: Target) -> Option<u64> {
+    let u16_at = |i: usize| target.u16([gate[i], gate[i + 1]]) as u64;
+    if u16_at(4) & 0x8000 == 0 {
+        return None;
+    }
+    let high = target.u32(gate[8..12].try_into().ok()?) as u64;
+    Some(u16_at(0) | u16_at(6) << 16 | high << 32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_idt_gate() {
+        // asm_exc_page_fault at 0xffffffff81a00b70, interrupt gate, present
+        let gate = [
+            0x70, 0x0b, 0x10, 0x00, 0x00, 0x8e, 0xa0, 0x81, 0xff, 0xff, 0xff, 0xff, 0, 0, 0, 0,
+        ];
+        assert_eq!(
+            decode_idt_gate(&gate, Target::default()),
+            Some(0xffffffff81a00b70)
+        );
+
+        let mut absent = gate;
+        absent[5] = 0x0e;
+        assert_eq!(decode_idt_gate(&absent, Target::default()), None);
+    }
+}
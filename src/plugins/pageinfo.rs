@@ -0,0 +1,120 @@
+//! PageInfo plugin - decodes the struct page describing physical page frames
+use crate::error::AnalysisError;
+use crate::kernel::page::{MappingKind, PageMap};
+use crate::kernel::vfs::VfsWalker;
+use crate::kernel::{KernelParser, PageInfo};
+use crate::plugins::plugin_trait::{AnalysisContext, ForensicPlugin, PluginOutput};
+
+// Macro for conditional warning output
+macro_rules! warn {
+    ($($arg:tt)*) => {
+        if std::env::var("LINMEMPARSER_VERBOSE").is_ok() {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+const PAGE_SIZE: u64 = 0x1000;
+
+pub struct PageInfoPlugin {
+    /// Physical address of the first page
+    pub address: u64,
+    /// Number of consecutive pages to decode
+    pub count: usize,
+}
+
+impl ForensicPlugin for PageInfoPlugin {
+    fn name(&self) -> &str {
+        "pageinfo"
+    }
+
+    fn description(&self) -> &str {
+        "Decode the struct page (flags, mapping, counts) of a physical address"
+    }
+
+    fn run(&self, context: &AnalysisContext) -> Result<PluginOutput, AnalysisError> {
+        let mapped = &context.memory_map.mapped[..];
+        let translator = context.translator;
+        let resolver = context.symbol_resolver;
+        let pages = PageMap::new(mapped, translator, resolver);
+
+        let descriptors: Vec<_> = (0..self.count.max(1) as u64)
+            .map(|i| (self.address & !(PAGE_SIZE - 1)) + i * PAGE_SIZE)
+            .filter_map(|physical| {
+                let descriptor = pages.describe(physical);
+                if descriptor.is_none() {
+                    warn!("[WARNING] struct page for {:#x} not in the dump", physical);
+                }
+                descriptor
+            })
+            .collect();
+        if descriptors.is_empty() {
+            return Ok(PluginOutput::PageInfo(Vec::new()));
+        }
+
+        let vfs = match VfsWalker::new(mapped, translator, resolver) {
+            Ok(v) => Some(v),
+            Err(e) => {
+                warn!("[WARNING] File paths unavailable: {}", e);
+                None
+            }
+        };
+        let host_offset = resolver
+            .has_field("address_space", "host")
+            .then(|| resolver.require_field_offset("address_space", "host").ok())
+            .flatten();
+        // Walking every page table is only worth it when some page is mapped
+        let rmap = if descriptors.iter().any(|d| d.mapcount > 0) {
+            Some(context.reverse_map()?)
+        } else {
+            None
+        };
+
+        let results = descriptors
+            .into_iter()
+            .map(|d| {
+                let physical = d.pfn * PAGE_SIZE;
+                // Page cache pages: address_space->host is the file's inode
+                let host = match (d.mapping_kind, host_offset) {
+                    (MappingKind::File, Some(off)) => {
+                        KernelParser::read_virt_pointer(mapped, translator, d.mapping + off as u64)
+                    }
+                    _ => None,
+                };
+                let inode = host
+                    .zip(vfs.as_ref())
+                    .and_then(|(host, vfs)| vfs.read_inode(host));
+                let path = host
+                    .zip(vfs.as_ref())
+                    .and_then(|(host, vfs)| vfs.inode_alias(host).map(|d| vfs.dentry_path(d)))
+                    .unwrap_or_default();
+
+                PageInfo {
+                    physical,
+                    pfn: d.pfn,
+                    page: d.page,
+                    flags: d.flags,
+                    flag_names: d.flag_names,
+                    head: d.head,
+                    mapping: d.mapping,
+                    mapping_type: d.mapping_kind.name().to_string(),
+                    inode: inode.map(|i| i.ino),
+                    path,
+                    index: d.index,
+                    refcount: d.refcount,
+                    mapcount: d.mapcount,
+                    mapped_by: rmap
+                        .map(|r| {
+                            r.process_owners(physical)
+                                .iter()
+                                .map(|o| format!("{}/{}@{:#x}", o.pid, o.comm, o.address))
+                                .collect()
+                        })
+                        .unwrap_or_default(),
+                }
+            })
+            .collect();
+
+        Ok(PluginOutput::PageInfo(results))
+    }
+}
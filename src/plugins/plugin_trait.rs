@@ -1,9 +1,18 @@
 //! Plugin system trait for the Linux Memory Parser tool
 use crate::error::AnalysisError;
-use crate::kernel::{ConnectionInfo, ModuleInfo, ProcessInfo};
+use crate::kernel::process_extractor::ProcessExtractor;
+use crate::kernel::rmap::ReverseMap;
+use crate::kernel::{
+    BashHistoryInfo, BpfMapInfo, BpfProgInfo, ConnectionInfo, ContainerInfo, ElfInfo,
+    InlineHookInfo, InterfaceInfo, LibraryInfo, MapsInfo, ModuleInfo, NeighbourInfo,
+    NetfilterHookInfo, NotifierInfo, OpsPointerInfo, PageInfo, ProcessInfo, ProcessNamespaceInfo,
+    RawSocketInfo, RouteInfo, ScanMatchInfo, StringInfo, TmpfsFileInfo, TraceHookInfo, TtyHookInfo,
+    UnixSocketInfo,
+};
 use crate::memory::MemoryMap;
 use crate::symbols::SymbolResolver;
 use crate::translation::MemoryTranslator;
+use std::cell::OnceCell;
 
 /// Analysis context that provides access to all necessary components
 pub struct AnalysisContext<'a> {
@@ -11,6 +20,31 @@ pub struct AnalysisContext<'a> {
     pub translator: &'a MemoryTranslator,
     pub symbol_resolver: &'a SymbolResolver,
     pub init_task_offset: usize, // File offset of init_task (with KASLR applied)
+    pub reverse_map: OnceCell<ReverseMap<'a>>, // Built on first use by reverse_map()
+}
+
+impl<'a> AnalysisContext<'a> {
+    /// Get the physical-to-virtual reverse map, walking every page table on first use
+    ///
+    /// The index is cached so plugins sharing a run (e.g. `all`) build it once.
+    pub fn reverse_map(&self) -> Result<&ReverseMap<'a>, AnalysisError> {
+        if let Some(rmap) = self.reverse_map.get() {
+            return Ok(rmap);
+        }
+        let processes = ProcessExtractor::new().walk_process_list(
+            self.memory_map,
+            self.translator,
+            self.symbol_resolver,
+            self.init_task_offset as u64,
+        )?;
+        let rmap = ReverseMap::build(
+            &self.memory_map.mapped[..],
+            self.translator,
+            self.symbol_resolver,
+            &processes,
+        );
+        Ok(self.reverse_map.get_or_init(|| rmap))
+    }
 }
 
 /// Output from plugins - different types of data
@@ -18,6 +52,29 @@ pub enum PluginOutput {
     Processes(Vec<ProcessInfo>),
     Connections(Vec<ConnectionInfo>),
     Modules(Vec<ModuleInfo>),
+    TmpfsFiles(Vec<TmpfsFileInfo>),
+    BashHistory(Vec<BashHistoryInfo>),
+    Interfaces(Vec<InterfaceInfo>),
+    Neighbours(Vec<NeighbourInfo>),
+    Routes(Vec<RouteInfo>),
+    UnixSockets(Vec<UnixSocketInfo>),
+    RawSockets(Vec<RawSocketInfo>),
+    BpfPrograms(Vec<BpfProgInfo>),
+    BpfMaps(Vec<BpfMapInfo>),
+    InlineHooks(Vec<InlineHookInfo>),
+    TraceHooks(Vec<TraceHookInfo>),
+    NetfilterHooks(Vec<NetfilterHookInfo>),
+    Notifiers(Vec<NotifierInfo>),
+    TtyHooks(Vec<TtyHookInfo>),
+    OpsPointers(Vec<OpsPointerInfo>),
+    ProcessNamespaces(Vec<ProcessNamespaceInfo>),
+    Containers(Vec<ContainerInfo>),
+    Libraries(Vec<LibraryInfo>),
+    Elfs(Vec<ElfInfo>),
+    ScanMatches(Vec<ScanMatchInfo>),
+    Strings(Vec<StringInfo>),
+    PageInfo(Vec<PageInfo>),
+    Maps(Vec<MapsInfo>),
     Tree(String), // For process tree output
     #[allow(dead_code)]
     Custom(String), // For any custom output format
@@ -0,0 +1,569 @@
+//! Rawsockets plugin - finds packet, raw and netlink sockets and their BPF filters
+use crate::error::AnalysisError;
+use crate::kernel::net::{format_socket_type, NetWalker, SockReader, SocketIndex};
+use crate::kernel::process_extractor::ProcessExtractor;
+use crate::kernel::{KernelParser, RawSocketInfo};
+use crate::plugins::plugin_trait::{AnalysisContext, ForensicPlugin, PluginOutput};
+use crate::symbols::SymbolResolver;
+use crate::translation::MemoryTranslator;
+use std::collections::{HashMap, HashSet};
+
+// Macro for conditional warning output
+macro_rules! warn {
+    ($($arg:tt)*) => {
+        if std::env::var("LINMEMPARSER_VERBOSE").is_ok() {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+// Safety limits for list and table walks
+const MAX_CHAIN_LENGTH: usize = 65536;
+const MAX_RHT_BUCKETS: u32 = 1 << 24;
+// RAW_HTABLE_SIZE (MAX_INET_PROTOS before 6.0)
+const RAW_HTABLE_SIZE: u64 = 256;
+// MAX_LINKS in net/netlink/af_netlink.h
+const NETLINK_MAX_LINKS: u64 = 32;
+// BPF_MAXINSNS for classic filters
+const BPF_MAXINSNS: u32 = 4096;
+// sizeof(struct sock_filter)
+const SOCK_FILTER_SIZE: usize = 8;
+
+// Address families
+const AF_INET: u16 = 2;
+const AF_INET6: u16 = 10;
+const AF_NETLINK: u16 = 16;
+const AF_PACKET: u16 = 17;
+
+pub struct RawSocketsPlugin;
+
+impl ForensicPlugin for RawSocketsPlugin {
+    fn name(&self) -> &str {
+        "rawsockets"
+    }
+
+    fn description(&self) -> &str {
+        "List packet, raw and netlink sockets with attached BPF filters"
+    }
+
+    fn run(&self, context: &AnalysisContext) -> Result<PluginOutput, AnalysisError> {
+        let mapped = &context.memory_map.mapped[..];
+        let translator = context.translator;
+        let resolver = context.symbol_resolver;
+
+        let processes = ProcessExtractor::new().walk_process_list(
+            context.memory_map,
+            translator,
+            resolver,
+            context.init_task_offset as u64,
+        )?;
+        let index = SocketIndex::build(mapped, translator, resolver, &processes)?;
+        let net_walker = NetWalker::new(mapped, translator, resolver)?;
+        let scanner = Scanner {
+            mapped,
+            translator,
+            symbol_resolver: resolver,
+            socks: SockReader::new(mapped, translator, resolver)?,
+            interfaces: net_walker.interface_names()?,
+        };
+
+        let mut found: Vec<(u64, Option<u32>)> = Vec::new();
+        match scanner.packet_socks(&net_walker) {
+            Ok(socks) => found.extend(socks.into_iter().map(|sk| (sk, None))),
+            Err(e) => warn!("[WARNING] Packet sockets unavailable: {}", e),
+        }
+        match scanner.raw_socks() {
+            Ok(socks) => found.extend(socks.into_iter().map(|sk| (sk, None))),
+            Err(e) => warn!("[WARNING] Raw sockets unavailable: {}", e),
+        }
+        match scanner.netlink_socks() {
+            Ok(socks) => found.extend(socks.into_iter().map(|(sk, proto)| (sk, Some(proto)))),
+            Err(e) => warn!("[WARNING] Netlink sockets unavailable: {}", e),
+        }
+
+        let mut seen = HashSet::new();
+        let mut sockets = Vec::new();
+        for (sk, netlink_protocol) in found {
+            if !seen.insert(sk) {
+                continue;
+            }
+            if let Some(mut info) = scanner.describe(sk, netlink_protocol) {
+                info.net_namespace = scanner
+                    .socks
+                    .net(sk)
+                    .map(|net| net_walker.namespace_inum(net))
+                    .unwrap_or(0);
+                if info.inode != 0 {
+                    info.owners = index.owners(info.inode);
+                }
+                sockets.push(info);
+            }
+        }
+
+        Ok(PluginOutput::RawSockets(sockets))
+    }
+}
+
+struct Scanner<'a> {
+    mapped: &'a [u8],
+    translator: &'a MemoryTranslator,
+    symbol_resolver: &'a SymbolResolver,
+    socks: SockReader<'a>,
+    interfaces: HashMap<(u64, i32), String>,
+}
+
+impl<'a> Scanner<'a> {
+    fn field(&self, s: &str, f: &str) -> Result<u64, AnalysisError> {
+        self.symbol_resolver
+            .require_field_offset(s, f)
+            .map(|o| o as u64)
+    }
+
+    fn read_ptr(&self, addr: u64) -> u64 {
+        KernelParser::read_virt_pointer(self.mapped, self.translator, addr).unwrap_or(0)
+    }
+
+    fn read_u32(&self, addr: u64) -> u32 {
+        KernelParser::read_virt_u32(self.mapped, self.translator, addr).unwrap_or(0)
+    }
+
+    /// Offset of sock_common.skc_node within struct sock
+    fn sk_node(&self) -> Result<u64, AnalysisError> {
+        let common = self
+            .symbol_resolver
+            .get_struct_field_offset("sock", "__sk_common", None)
+            .unwrap_or(0);
+        Ok(common + self.field("sock_common", "skc_node")?)
+    }
+
+    /// AF_PACKET sockets are linked on net->packet.sklist in every namespace
+    fn packet_socks(&self, net_walker: &NetWalker) -> Result<Vec<u64>, AnalysisError> {
+        let sklist = self.field("net", "packet")? + self.field("netns_packet", "sklist")?;
+        let node = self.sk_node()?;
+        let mut socks = Vec::new();
+
+        for net in net_walker.namespaces()? {
+            let first = self.read_ptr(net + sklist);
+            for entry in
+                KernelParser::walk_hlist(self.mapped, self.translator, first, MAX_CHAIN_LENGTH)
+            {
+                socks.push(entry - node);
+            }
+        }
+
+        Ok(socks)
+    }
+
+    /// Raw IPv4/IPv6 sockets are hashed by protocol in raw_v4/raw_v6_hashinfo
+    fn raw_socks(&self) -> Result<Vec<u64>, AnalysisError> {
+        let ht = self.field("raw_hashinfo", "ht")?;
+        let node = self.sk_node()?;
+        let mut socks = Vec::new();
+
+        for symbol in ["raw_v4_hashinfo", "raw_v6_hashinfo"] {
+            let hashinfo = match self.symbol_resolver.get_symbol_address(symbol) {
+                Some(addr) => addr,
+                None => continue, // IPv6 may be a module or compiled out
+            };
+            for bucket in 0..RAW_HTABLE_SIZE {
+                // hlist_head before 6.0, hlist_nulls_head afterwards
+                let first = self.read_ptr(hashinfo + ht + bucket * self.translator.pointer_size());
+                for entry in KernelParser::walk_hlist_nulls(
+                    self.mapped,
+                    self.translator,
+                    first,
+                    MAX_CHAIN_LENGTH,
+                ) {
+                    socks.push(entry - node);
+                }
+            }
+        }
+
+        Ok(socks)
+    }
+
+    /// Netlink sockets live in one rhashtable per protocol, hung off nl_table
+    fn netlink_socks(&self) -> Result<Vec<(u64, u32)>, AnalysisError> {
+        let nl_table = self
+            .symbol_resolver
+            .get_symbol_address("nl_table")
+            .ok_or_else(|| AnalysisError::SymbolNotFound("nl_table".to_string()))?;
+        let tables = self.read_ptr(nl_table);
+        if tables == 0 {
+            return Ok(Vec::new());
+        }
+
+        let table_size = self
+            .symbol_resolver
+            .get_struct_size("netlink_table")
+            .ok_or_else(|| AnalysisError::SymbolNotFound("sizeof(netlink_table)".to_string()))?
+            as u64;
+        let hash = self.field("netlink_table", "hash")?;
+        let tbl = self.field("rhashtable", "tbl")?;
+        let size = self.field("bucket_table", "size")?;
+        let buckets = self.field("bucket_table", "buckets")?;
+        let node = self.field("netlink_sock", "node")?;
+
+        let mut socks = Vec::new();
+        for protocol in 0..NETLINK_MAX_LINKS {
+            let bucket_table = self.read_ptr(tables + protocol * table_size + hash + tbl);
+            if bucket_table == 0 {
+                continue;
+            }
+            let bucket_count = self.read_u32(bucket_table + size).min(MAX_RHT_BUCKETS);
+            for bucket in 0..bucket_count as u64 {
+                // Bit 0 of a bucket pointer is the bucket lock (5.7+)
+                let first = self
+                    .read_ptr(bucket_table + buckets + bucket * self.translator.pointer_size())
+                    & !1;
+                for entry in KernelParser::walk_hlist_nulls(
+                    self.mapped,
+                    self.translator,
+                    first,
+                    MAX_CHAIN_LENGTH,
+                ) {
+                    socks.push((entry - node, protocol as u32));
+                }
+            }
+        }
+
+        Ok(socks)
+    }
+
+    /// Build the record for a sock, rejecting anything of an unexpected family
+    fn describe(&self, sk: u64, netlink_protocol: Option<u32>) -> Option<RawSocketInfo> {
+        let resolver = self.symbol_resolver;
+        let family_id = self.socks.family(sk);
+        let net = self.socks.net(sk).unwrap_or(0);
+        let interface_name = |ifindex: i32| {
+            if ifindex == 0 {
+                "*".to_string()
+            } else {
+                self.interfaces
+                    .get(&(net, ifindex))
+                    .cloned()
+                    .unwrap_or_else(|| format!("if{}", ifindex))
+            }
+        };
+        let bound_dev_if = || {
+            let common = resolver
+                .get_struct_field_offset("sock", "__sk_common", None)
+                .unwrap_or(0);
+            resolver
+                .get_struct_field_offset("sock_common", "skc_bound_dev_if", None)
+                .map(|o| self.read_u32(sk + common + o) as i32)
+                .unwrap_or(0)
+        };
+
+        let (family, protocol, interface, portid) = match (family_id, netlink_protocol) {
+            (AF_NETLINK, Some(protocol)) => {
+                let portid = resolver
+                    .get_struct_field_offset("netlink_sock", "portid", None)
+                    .map(|o| self.read_u32(sk + o));
+                (
+                    "netlink",
+                    format_netlink_protocol(protocol),
+                    "*".to_string(),
+                    portid,
+                )
+            }
+            (AF_PACKET, None) => {
+                let num = resolver
+                    .get_struct_field_offset("packet_sock", "num", None)
+                    .and_then(|o| {
+                        KernelParser::read_virt_bytes(self.mapped, self.translator, sk + o, 2)
+                    })
+                    .map(|b| u16::from_be_bytes([b[0], b[1]]))
+                    .unwrap_or(0);
+                let ifindex = resolver
+                    .get_struct_field_offset("packet_sock", "ifindex", None)
+                    .map(|o| self.read_u32(sk + o) as i32)
+                    .unwrap_or(0);
+                (
+                    "packet",
+                    format_ethertype(num),
+                    interface_name(ifindex),
+                    None,
+                )
+            }
+            (AF_INET, None) | (AF_INET6, None) => {
+                // inet_num holds the IP protocol number for raw sockets
+                let common = resolver
+                    .get_struct_field_offset("sock", "__sk_common", None)
+                    .unwrap_or(0);
+                let protocol = resolver
+                    .get_struct_field_offset("sock_common", "skc_num", None)
+                    .and_then(|o| {
+                        KernelParser::read_virt_u16(self.mapped, self.translator, sk + common + o)
+                    })
+                    .unwrap_or(0);
+                (
+                    if family_id == AF_INET { "raw" } else { "raw6" },
+                    format_ip_protocol(protocol),
+                    interface_name(bound_dev_if()),
+                    None,
+                )
+            }
+            _ => return None,
+        };
+
+        let (filter_insns, filter) = self.read_filter(sk);
+        Some(RawSocketInfo {
+            offset: self.translator.virtual_to_file_offset(sk).unwrap_or(0),
+            net_namespace: 0,
+            family: family.to_string(),
+            protocol,
+            socket_type: format_socket_type(self.socks.socket_type(sk)),
+            interface,
+            portid,
+            inode: self.socks.inode_number(sk),
+            owners: Vec::new(),
+            filter_insns,
+            filter,
+        })
+    }
+
+    /// Read the socket filter: sk->sk_filter->prog, with the original classic
+    /// program preserved in prog->orig_prog
+    fn read_filter(&self, sk: u64) -> (u32, String) {
+        let resolver = self.symbol_resolver;
+        let offset = |s: &str, f: &str| resolver.get_struct_field_offset(s, f, None);
+
+        let sk_filter = match offset("sock", "sk_filter") {
+            Some(o) => self.read_ptr(sk + o),
+            None => return (0, String::new()),
+        };
+        if sk_filter == 0 {
+            return (0, String::new());
+        }
+        let prog = match offset("sk_filter", "prog") {
+            Some(o) => self.read_ptr(sk_filter + o),
+            None => return (0, String::new()),
+        };
+        if prog == 0 {
+            return (0, String::new());
+        }
+        let insns = offset("bpf_prog", "len")
+            .map(|o| self.read_u32(prog + o))
+            .unwrap_or(0);
+
+        // SO_ATTACH_BPF (eBPF) programs have no classic original to show
+        let orig = offset("bpf_prog", "orig_prog")
+            .map(|o| self.read_ptr(prog + o))
+            .unwrap_or(0);
+        if orig == 0 {
+            return (insns, String::new());
+        }
+        let len = offset("sock_fprog_kern", "len")
+            .and_then(|o| KernelParser::read_virt_u16(self.mapped, self.translator, orig + o))
+            .unwrap_or(0) as u32;
+        let filter = offset("sock_fprog_kern", "filter")
+            .map(|o| self.read_ptr(orig + o))
+            .unwrap_or(0);
+        if len == 0 || len > BPF_MAXINSNS || filter == 0 {
+            return (insns, String::new());
+        }
+
+        let bytes = KernelParser::read_virt_bytes(
+            self.mapped,
+            self.translator,
+            filter,
+            len as usize * SOCK_FILTER_SIZE,
+        )
+        .map(|b| b.iter().map(|x| format!("{:02x}", x)).collect::<String>())
+        .unwrap_or_default();
+        (len, bytes)
+    }
+}
+
+/// Name a packet socket protocol (ETH_P_* from include/uapi/linux/if_ether.h)
+fn format_ethertype(protocol: u16) -> String {
+    let name = match protocol {
+        0x0000 => "NONE",
+        0x0003 => "ALL",
+        0x0800 => "IP",
+        0x0806 => "ARP",
+        0x8100 => "8021Q",
+        0x86dd => "IPV6",
+        0x888e => "PAE",
+        _ => return format!("0x{:04x}", protocol),
+    };
+    format!("0x{:04x} ({})", protocol, name)
+}
+
+/// Name an IP protocol number
+fn format_ip_protocol(protocol: u16) -> String {
+    let name = match protocol {
+        1 => "ICMP",
+        2 => "IGMP",
+        6 => "TCP",
+        17 => "UDP",
+        47 => "GRE",
+        58 => "ICMPV6",
+        132 => "SCTP",
+        255 => "RAW",
+        _ => return protocol.to_string(),
+    };
+    format!("{} ({})", protocol, name)
+}
+
+/// Name a netlink protocol (NETLINK_* from include/uapi/linux/netlink.h)
+fn format_netlink_protocol(protocol: u32) -> String {
+    let name = match protocol {
+        0 => "ROUTE",
+        2 => "USERSOCK",
+        3 => "FIREWALL",
+        4 => "SOCK_DIAG",
+        5 => "NFLOG",
+        6 => "XFRM",
+        7 => "SELINUX",
+        8 => "ISCSI",
+        9 => "AUDIT",
+        10 => "FIB_LOOKUP",
+        11 => "CONNECTOR",
+        12 => "NETFILTER",
+        13 => "IP6_FW",
+        14 => "DNRTMSG",
+        15 => "KOBJECT_UEVENT",
+        16 => "GENERIC",
+        18 => "SCSITRANSPORT",
+        19 => "ECRYPTFS",
+        20 => "RDMA",
+        21 => "CRYPTO",
+        22 => "SMC",
+        _ => return protocol.to_string(),
+    };
+    name.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testutil::{socket_symbols, Dump, DIRECT_MAP};
+
+    const NET: u64 = 0x2000;
+
+    /// Attach sk_filter -> prog (len insns) -> orig_prog at the given addresses
+    fn filter(dump: &mut Dump, sk: u64, at: u64, insns: u32, orig: Option<(u16, u64)>) {
+        dump.ptr(sk + 0x110, at);
+        dump.ptr(at + 8, at + 0x100);
+        dump.bytes(at + 0x104, &insns.to_le_bytes());
+        if let Some((len, filter)) = orig {
+            dump.ptr(at + 0x110, at + 0x200);
+            dump.bytes(at + 0x200, &len.to_le_bytes());
+            dump.ptr(at + 0x208, filter);
+        }
+    }
+
+    fn symbols() -> SymbolResolver {
+        let mut symbols = socket_symbols();
+        symbols.add_struct("packet_sock", 0x300, &[("num", 0x200), ("ifindex", 0x204)]);
+        symbols.add_struct("netlink_sock", 0x300, &[("portid", 0x200)]);
+        symbols.add_struct("sk_filter", 0x10, &[("prog", 8)]);
+        symbols.add_struct("bpf_prog", 0x40, &[("len", 4), ("orig_prog", 0x10)]);
+        symbols.add_struct("sock_fprog_kern", 0x10, &[("len", 0), ("filter", 8)]);
+        symbols
+    }
+
+    /// A packet sock with a classic filter, a raw sock with an eBPF program and
+    /// a netlink sock whose classic program is longer than BPF_MAXINSNS
+    fn synthetic_dump() -> Dump {
+        let mut dump = Dump::new(0x4000);
+
+        // Packet sock at 0: ETH_P_ALL (network order) on ifindex 2, inode 4242
+        dump.bytes(0x10, &AF_PACKET.to_le_bytes());
+        dump.ptr(0x30, NET);
+        dump.bytes(0x200, &0x0003u16.to_be_bytes());
+        dump.bytes(0x204, &2u32.to_le_bytes());
+        dump.ptr(0x100, 0x400);
+        dump.bytes(0x404, &3u16.to_le_bytes());
+        dump.word(0x480, 4242);
+        filter(&mut dump, 0, 0x500, 2, Some((2, 0x800)));
+        // ldh [12]; ret #0xffff
+        dump.bytes(
+            0x800,
+            &[
+                0x28, 0, 0, 0, 0x0c, 0, 0, 0, 0x06, 0, 0, 0, 0xff, 0xff, 0, 0,
+            ],
+        );
+
+        // Raw IPv4 ICMP sock at 0x1000 bound to ifindex 5, no struct socket
+        dump.bytes(0x1010, &AF_INET.to_le_bytes());
+        dump.ptr(0x1030, NET);
+        dump.bytes(0x100e, &1u16.to_le_bytes());
+        dump.bytes(0x1028, &5u32.to_le_bytes());
+        dump.bytes(0x1108, &3u16.to_le_bytes());
+        filter(&mut dump, 0x1000, 0x1400, 7, None);
+
+        // Netlink sock at 0x2800 with portid 1234
+        dump.bytes(0x2810, &AF_NETLINK.to_le_bytes());
+        dump.bytes(0x2a00, &1234u32.to_le_bytes());
+        filter(&mut dump, 0x2800, 0x3000, 5000, Some((5000, 0x800)));
+        dump
+    }
+
+    #[test]
+    fn test_describe_and_read_filter() {
+        let dump = synthetic_dump();
+        let translator = dump.translator();
+        let symbols = symbols();
+        let scanner = Scanner {
+            mapped: &dump.0,
+            translator: &translator,
+            symbol_resolver: &symbols,
+            socks: SockReader::new(&dump.0, &translator, &symbols).unwrap(),
+            interfaces: HashMap::from([((DIRECT_MAP + NET, 2), "eth0".to_string())]),
+        };
+
+        let packet = scanner.describe(DIRECT_MAP, None).unwrap();
+        assert_eq!(
+            (packet.family.as_str(), packet.protocol.as_str()),
+            ("packet", "0x0003 (ALL)")
+        );
+        assert_eq!((packet.interface.as_str(), packet.inode), ("eth0", 4242));
+        assert_eq!(packet.socket_type, "RAW");
+        assert_eq!(packet.filter_insns, 2);
+        assert_eq!(packet.filter, "280000000c00000006000000ffff0000");
+
+        let raw = scanner.describe(DIRECT_MAP + 0x1000, None).unwrap();
+        assert_eq!(
+            (
+                raw.family.as_str(),
+                raw.protocol.as_str(),
+                raw.interface.as_str()
+            ),
+            ("raw", "1 (ICMP)", "if5")
+        );
+        assert_eq!((raw.socket_type.as_str(), raw.inode), ("RAW", 0));
+        assert_eq!((raw.filter_insns, raw.filter.as_str()), (7, ""));
+
+        let netlink = scanner.describe(DIRECT_MAP + 0x2800, Some(9)).unwrap();
+        assert_eq!(
+            (netlink.family.as_str(), netlink.protocol.as_str()),
+            ("netlink", "AUDIT")
+        );
+        assert_eq!(
+            (netlink.interface.as_str(), netlink.portid),
+            ("*", Some(1234))
+        );
+        assert_eq!((netlink.filter_insns, netlink.filter.as_str()), (5000, ""));
+
+        // A netlink sock reached without a protocol, and a raw sock claimed by
+        // the netlink table, are both rejected
+        assert!(scanner.describe(DIRECT_MAP + 0x2800, None).is_none());
+        assert!(scanner.describe(DIRECT_MAP + 0x1000, Some(0)).is_none());
+    }
+
+    #[test]
+    fn test_protocol_names() {
+        assert_eq!(format_ethertype(0x0800), "0x0800 (IP)");
+        assert_eq!(format_ethertype(0x86dd), "0x86dd (IPV6)");
+        assert_eq!(format_ethertype(0x1234), "0x1234");
+        assert_eq!(format_ip_protocol(6), "6 (TCP)");
+        assert_eq!(format_ip_protocol(255), "255 (RAW)");
+        assert_eq!(format_ip_protocol(200), "200");
+        assert_eq!(format_netlink_protocol(0), "ROUTE");
+        assert_eq!(format_netlink_protocol(15), "KOBJECT_UEVENT");
+        assert_eq!(format_netlink_protocol(31), "31");
+    }
+}
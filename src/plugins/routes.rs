@@ -0,0 +1,743 @@
+//! Routes plugin - extracts the IPv4 FIB tries and IPv6 routing trees
+use crate::error::AnalysisError;
+use crate::kernel::net::{format_ipv4, format_ipv6, NetWalker};
+use crate::kernel::{KernelParser, RouteInfo};
+use crate::plugins::plugin_trait::{AnalysisContext, ForensicPlugin, PluginOutput};
+use crate::symbols::SymbolResolver;
+use crate::translation::MemoryTranslator;
+use std::collections::HashSet;
+use std::net::Ipv4Addr;
+
+// Macro for conditional warning output
+macro_rules! warn {
+    ($($arg:tt)*) => {
+        if std::env::var("LINMEMPARSER_VERBOSE").is_ok() {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+// Safety limits for table and tree walks
+const MAX_TABLES: usize = 1024;
+const MAX_ROUTES: usize = 1000000;
+const MAX_TRIE_BITS: u8 = 20;
+const MAX_NEXTHOPS: u32 = 32;
+
+// FIB_TABLE_HASHSZ / FIB6_TABLE_HASHSZ with and without multiple-table support
+const TABLE_HASHSZ_MULTIPLE: u64 = 256;
+const FIB_TABLE_HASHSZ_SINGLE: u64 = 2;
+const FIB6_TABLE_HASHSZ_SINGLE: u64 = 1;
+
+// struct key_vector layout (net/ipv4/fib_trie.c)
+const KV_BITS: u64 = 5;
+const KV_UNION: u64 = 8;
+const KEYLENGTH: u8 = 32;
+
+// fib6_node->fn_flags (include/net/ip6_fib.h)
+const RTN_RTINFO: u16 = 0x4;
+
+// Nexthop gateway address families
+const AF_INET: u8 = 2;
+const AF_INET6: u8 = 10;
+
+pub struct RoutesPlugin;
+
+impl ForensicPlugin for RoutesPlugin {
+    fn name(&self) -> &str {
+        "routes"
+    }
+
+    fn description(&self) -> &str {
+        "Extract the IPv4 and IPv6 routing tables"
+    }
+
+    fn run(&self, context: &AnalysisContext) -> Result<PluginOutput, AnalysisError> {
+        let mapped = &context.memory_map.mapped[..];
+        let walker = NetWalker::new(mapped, context.translator, context.symbol_resolver)?;
+        let reader = RouteReader {
+            mapped,
+            translator: context.translator,
+            symbol_resolver: context.symbol_resolver,
+            walker: &walker,
+        };
+        let mut routes = Vec::new();
+
+        for net in walker.namespaces()? {
+            let net_namespace = walker.namespace_inum(net);
+            if let Err(e) = reader.ipv4_routes(net, net_namespace, &mut routes) {
+                warn!("[WARNING] IPv4 routes unavailable: {}", e);
+            }
+            if let Err(e) = reader.ipv6_routes(net, net_namespace, &mut routes) {
+                warn!("[WARNING] IPv6 routes unavailable: {}", e);
+            }
+        }
+
+        Ok(PluginOutput::Routes(routes))
+    }
+}
+
+/// Gateway and device of a single nexthop
+struct Nexthop {
+    gateway: String,
+    interface: String,
+}
+
+struct RouteReader<'a> {
+    mapped: &'a [u8],
+    translator: &'a MemoryTranslator,
+    symbol_resolver: &'a SymbolResolver,
+    walker: &'a NetWalker<'a>,
+}
+
+impl<'a> RouteReader<'a> {
+    fn field(&self, s: &str, f: &str) -> Result<u64, AnalysisError> {
+        self.symbol_resolver
+            .require_field_offset(s, f)
+            .map(|o| o as u64)
+    }
+
+    fn read_ptr(&self, addr: u64) -> u64 {
+        KernelParser::read_virt_pointer(self.mapped, self.translator, addr).unwrap_or(0)
+    }
+
+    fn read_u32(&self, addr: u64) -> u32 {
+        KernelParser::read_virt_u32(self.mapped, self.translator, addr).unwrap_or(0)
+    }
+
+    fn read_u16(&self, addr: u64) -> u16 {
+        KernelParser::read_virt_u16(self.mapped, self.translator, addr).unwrap_or(0)
+    }
+
+    fn read_u8(&self, addr: u64) -> u8 {
+        KernelParser::read_virt_bytes(self.mapped, self.translator, addr, 1)
+            .map(|b| b[0])
+            .unwrap_or(0)
+    }
+
+    fn device_name(&self, dev: u64) -> String {
+        if dev == 0 {
+            return "-".to_string();
+        }
+        self.walker
+            .device_name(dev)
+            .unwrap_or_else(|| "?".to_string())
+    }
+
+    /// Collect the tables linked on a fib_table_hash array of hlist_heads
+    fn hashed_tables(&self, hash: u64, buckets: u64, hlist_offset: u64) -> Vec<u64> {
+        let mut tables = Vec::new();
+        if hash == 0 {
+            return tables;
+        }
+        for bucket in 0..buckets {
+            let first = self.read_ptr(hash + bucket * self.translator.pointer_size());
+            for node in KernelParser::walk_hlist(self.mapped, self.translator, first, MAX_TABLES) {
+                let table = node.wrapping_sub(hlist_offset);
+                if !tables.contains(&table) {
+                    tables.push(table);
+                }
+            }
+        }
+        tables
+    }
+
+    /// Walk every IPv4 FIB table of a namespace
+    fn ipv4_routes(
+        &self,
+        net: u64,
+        net_namespace: u32,
+        routes: &mut Vec<RouteInfo>,
+    ) -> Result<(), AnalysisError> {
+        let ipv4 = net + self.field("net", "ipv4")?;
+        let hash = self.read_ptr(ipv4 + self.field("netns_ipv4", "fib_table_hash")?);
+        let buckets = if self.symbol_resolver.has_field("netns_ipv4", "rules_ops") {
+            TABLE_HASHSZ_MULTIPLE
+        } else {
+            FIB_TABLE_HASHSZ_SINGLE
+        };
+        let tb_hlist = self.field("fib_table", "tb_hlist")?;
+        let tb_id = self.field("fib_table", "tb_id")?;
+        let tb_data = self.field("fib_table", "tb_data")?;
+
+        for table in self.hashed_tables(hash, buckets, tb_hlist) {
+            let table_name = format_table_id(self.read_u32(table + tb_id));
+            // tb_data points at the struct trie; its first member is the root key_vector
+            let trie = self.read_ptr(table + tb_data);
+            if trie == 0 {
+                continue;
+            }
+
+            let mut leaves = Vec::new();
+            let mut visited = HashSet::new();
+            let root = self.read_ptr(trie + KV_UNION);
+            self.collect_trie_leaves(root, &mut visited, &mut leaves);
+
+            for leaf in leaves {
+                self.read_leaf(leaf, net_namespace, &table_name, routes)?;
+                if routes.len() >= MAX_ROUTES {
+                    return Ok(());
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Depth-first walk of the LC-trie collecting leaf key_vectors
+    fn collect_trie_leaves(&self, node: u64, visited: &mut HashSet<u64>, leaves: &mut Vec<u64>) {
+        if node == 0 || !visited.insert(node) || leaves.len() >= MAX_ROUTES {
+            return;
+        }
+
+        let bits = self.read_u8(node + KV_BITS);
+        if bits == 0 {
+            leaves.push(node);
+            return;
+        }
+        if bits > MAX_TRIE_BITS {
+            return;
+        }
+
+        for i in 0..(1u64 << bits) {
+            let child = self.read_ptr(node + KV_UNION + i * self.translator.pointer_size());
+            self.collect_trie_leaves(child, visited, leaves);
+        }
+    }
+
+    /// Emit one route per fib_alias (and nexthop) hanging off a trie leaf
+    fn read_leaf(
+        &self,
+        leaf: u64,
+        net_namespace: u32,
+        table_name: &str,
+        routes: &mut Vec<RouteInfo>,
+    ) -> Result<(), AnalysisError> {
+        let fa_list = self.field("fib_alias", "fa_list")?;
+        let fa_info = self.field("fib_alias", "fa_info")?;
+        let fa_type = self.field("fib_alias", "fa_type")?;
+        let fa_slen = self.field("fib_alias", "fa_slen")?;
+        let fib_priority = self.field("fib_info", "fib_priority")?;
+
+        // The key is the prefix in host byte order
+        let key = self.read_u32(leaf);
+        let first = self.read_ptr(leaf + KV_UNION);
+
+        for node in KernelParser::walk_hlist(self.mapped, self.translator, first, MAX_ROUTES) {
+            let alias = node.wrapping_sub(fa_list);
+            let prefix_len = KEYLENGTH.saturating_sub(self.read_u8(alias + fa_slen));
+            let fi = self.read_ptr(alias + fa_info);
+            let metric = if fi != 0 {
+                self.read_u32(fi + fib_priority)
+            } else {
+                0
+            };
+
+            for nexthop in self.ipv4_nexthops(fi) {
+                routes.push(RouteInfo {
+                    offset: self.translator.virtual_to_file_offset(alias).unwrap_or(0),
+                    net_namespace,
+                    family: "ipv4".to_string(),
+                    table: table_name.to_string(),
+                    destination: format!("{}/{}", Ipv4Addr::from(key), prefix_len),
+                    gateway: nexthop.gateway,
+                    interface: nexthop.interface,
+                    metric,
+                    route_type: format_route_type(self.read_u8(alias + fa_type)),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Read the fib_nh array of a fib_info
+    fn ipv4_nexthops(&self, fi: u64) -> Vec<Nexthop> {
+        let unresolved = || {
+            vec![Nexthop {
+                gateway: "-".to_string(),
+                interface: "?".to_string(),
+            }]
+        };
+        if fi == 0 {
+            return unresolved();
+        }
+
+        let resolver = self.symbol_resolver;
+        let (fib_nh, nh_size) = match (
+            resolver.get_struct_field_offset("fib_info", "fib_nh", None),
+            resolver.get_struct_size("fib_nh"),
+        ) {
+            (Some(o), Some(s)) => (o, s as u64),
+            _ => return unresolved(),
+        };
+        let nhs = resolver
+            .get_struct_field_offset("fib_info", "fib_nhs", None)
+            .map(|o| self.read_u32(fi + o) & 0xffff)
+            .unwrap_or(1)
+            .clamp(1, MAX_NEXTHOPS);
+
+        (0..nhs as u64)
+            .map(|i| {
+                let nh = fi + fib_nh + i * nh_size;
+                // 5.3+ moved the device and gateway into struct fib_nh_common
+                match resolver.get_struct_field_offset("fib_nh", "nh_common", None) {
+                    Some(common) => self.read_nh_common(nh + common),
+                    None => {
+                        let dev = resolver
+                            .get_struct_field_offset("fib_nh", "nh_dev", None)
+                            .map(|o| self.read_ptr(nh + o))
+                            .unwrap_or(0);
+                        let gateway = resolver
+                            .get_struct_field_offset("fib_nh", "nh_gw", None)
+                            .and_then(|o| {
+                                KernelParser::read_virt_bytes(
+                                    self.mapped,
+                                    self.translator,
+                                    nh + o,
+                                    4,
+                                )
+                            })
+                            .filter(|gw| gw.iter().any(|&b| b != 0))
+                            .map(|gw| format_ipv4(&gw))
+                            .unwrap_or_else(|| "-".to_string());
+                        Nexthop {
+                            gateway,
+                            interface: self.device_name(dev),
+                        }
+                    }
+                }
+            })
+            .collect()
+    }
+
+    /// Read the device and gateway of a struct fib_nh_common
+    fn read_nh_common(&self, nhc: u64) -> Nexthop {
+        let resolver = self.symbol_resolver;
+        let dev = resolver
+            .get_struct_field_offset("fib_nh_common", "nhc_dev", None)
+            .map(|o| self.read_ptr(nhc + o))
+            .unwrap_or(0);
+        let family = resolver
+            .get_struct_field_offset("fib_nh_common", "nhc_gw_family", None)
+            .map(|o| self.read_u8(nhc + o))
+            .unwrap_or(0);
+        let gateway = resolver
+            .get_struct_field_offset("fib_nh_common", "nhc_gw", None)
+            .and_then(|o| {
+                let length = if family == AF_INET6 { 16 } else { 4 };
+                KernelParser::read_virt_bytes(self.mapped, self.translator, nhc + o, length)
+            })
+            .map(|gw| match family {
+                AF_INET => format_ipv4(&gw),
+                AF_INET6 => format_ipv6(&gw),
+                _ => "-".to_string(),
+            })
+            .unwrap_or_else(|| "-".to_string());
+
+        Nexthop {
+            gateway,
+            interface: self.device_name(dev),
+        }
+    }
+
+    /// Walk every IPv6 routing table of a namespace
+    fn ipv6_routes(
+        &self,
+        net: u64,
+        net_namespace: u32,
+        routes: &mut Vec<RouteInfo>,
+    ) -> Result<(), AnalysisError> {
+        let ipv6 = net + self.field("net", "ipv6")?;
+        let tb6_hlist = self.field("fib6_table", "tb6_hlist")?;
+        let tb6_id = self.field("fib6_table", "tb6_id")?;
+        let tb6_root = self.field("fib6_table", "tb6_root")?;
+
+        let buckets = if self
+            .symbol_resolver
+            .has_field("netns_ipv6", "fib6_rules_ops")
+        {
+            TABLE_HASHSZ_MULTIPLE
+        } else {
+            FIB6_TABLE_HASHSZ_SINGLE
+        };
+        let hash = self.read_ptr(ipv6 + self.field("netns_ipv6", "fib_table_hash")?);
+        let mut tables = self.hashed_tables(hash, buckets, tb6_hlist);
+        // The main table is always allocated even when the hash is not populated
+        let main = self.read_ptr(ipv6 + self.field("netns_ipv6", "fib6_main_tbl")?);
+        if main != 0 && !tables.contains(&main) {
+            tables.push(main);
+        }
+
+        for table in tables {
+            let table_name = format_table_id(self.read_u32(table + tb6_id));
+            let mut nodes = Vec::new();
+            let mut visited = HashSet::new();
+            self.collect_fib6_nodes(table + tb6_root, &mut visited, &mut nodes)?;
+
+            for node in nodes {
+                self.read_fib6_node(node, net_namespace, &table_name, routes)?;
+                if routes.len() >= MAX_ROUTES {
+                    return Ok(());
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Collect every fib6_node of the radix tree that carries route information
+    fn collect_fib6_nodes(
+        &self,
+        node: u64,
+        visited: &mut HashSet<u64>,
+        nodes: &mut Vec<u64>,
+    ) -> Result<(), AnalysisError> {
+        if node == 0 || !visited.insert(node) || nodes.len() >= MAX_ROUTES {
+            return Ok(());
+        }
+
+        let fn_flags = self.read_u16(node + self.field("fib6_node", "fn_flags")?);
+        if fn_flags & RTN_RTINFO != 0 {
+            nodes.push(node);
+        }
+
+        let left = self.read_ptr(node + self.field("fib6_node", "left")?);
+        let right = self.read_ptr(node + self.field("fib6_node", "right")?);
+        self.collect_fib6_nodes(left, visited, nodes)?;
+        self.collect_fib6_nodes(right, visited, nodes)?;
+
+        // Source-specific routing hangs a second tree off the node
+        if let Some(subtree) =
+            self.symbol_resolver
+                .get_struct_field_offset("fib6_node", "subtree", None)
+        {
+            let sub = self.read_ptr(node + subtree);
+            self.collect_fib6_nodes(sub, visited, nodes)?;
+        }
+
+        Ok(())
+    }
+
+    /// Emit one route per fib6_info chained from a fib6_node leaf
+    fn read_fib6_node(
+        &self,
+        node: u64,
+        net_namespace: u32,
+        table_name: &str,
+        routes: &mut Vec<RouteInfo>,
+    ) -> Result<(), AnalysisError> {
+        let resolver = self.symbol_resolver;
+        let leaf_offset = self.field("fib6_node", "leaf")?;
+        let next_offset = self.field("fib6_info", "fib6_next")?;
+        let dst_offset = self.field("fib6_info", "fib6_dst")?;
+        let metric_offset = self.field("fib6_info", "fib6_metric")?;
+        let type_offset = self.field("fib6_info", "fib6_type")?;
+        let nh_offset = self.field("fib6_info", "fib6_nh")?;
+        let plen_offset = resolver
+            .get_struct_field_offset("rt6key", "plen", None)
+            .unwrap_or(16);
+
+        let mut visited = HashSet::new();
+        let mut rt = self.read_ptr(node + leaf_offset);
+        while rt != 0 && visited.insert(rt) && routes.len() < MAX_ROUTES {
+            let destination =
+                KernelParser::read_virt_bytes(self.mapped, self.translator, rt + dst_offset, 16)
+                    .map(|addr| {
+                        let plen = self.read_u32(rt + dst_offset + plen_offset);
+                        format!("{}/{}", format_ipv6(&addr), plen)
+                    })
+                    .unwrap_or_else(|| "?".to_string());
+
+            let nexthop = self.ipv6_nexthop(rt + nh_offset);
+            routes.push(RouteInfo {
+                offset: self.translator.virtual_to_file_offset(rt).unwrap_or(0),
+                net_namespace,
+                family: "ipv6".to_string(),
+                table: table_name.to_string(),
+                destination,
+                gateway: nexthop.gateway,
+                interface: nexthop.interface,
+                metric: self.read_u32(rt + metric_offset),
+                route_type: format_route_type(self.read_u8(rt + type_offset)),
+            });
+
+            rt = self.read_ptr(rt + next_offset);
+        }
+
+        Ok(())
+    }
+
+    /// Read the first struct fib6_nh of a fib6_info
+    fn ipv6_nexthop(&self, nh: u64) -> Nexthop {
+        let resolver = self.symbol_resolver;
+        if let Some(common) = resolver.get_struct_field_offset("fib6_nh", "nh_common", None) {
+            return self.read_nh_common(nh + common);
+        }
+
+        // 4.19 - 5.3 kept the device and gateway directly in fib6_nh
+        let dev = resolver
+            .get_struct_field_offset("fib6_nh", "nh_dev", None)
+            .map(|o| self.read_ptr(nh + o))
+            .unwrap_or(0);
+        let gateway = resolver
+            .get_struct_field_offset("fib6_nh", "nh_gw", None)
+            .and_then(|o| KernelParser::read_virt_bytes(self.mapped, self.translator, nh + o, 16))
+            .filter(|gw| gw.iter().any(|&b| b != 0))
+            .map(|gw| format_ipv6(&gw))
+            .unwrap_or_else(|| "-".to_string());
+
+        Nexthop {
+            gateway,
+            interface: self.device_name(dev),
+        }
+    }
+}
+
+/// Name the reserved routing table ids (include/uapi/linux/rtnetlink.h)
+fn format_table_id(id: u32) -> String {
+    match id {
+        253 => "default".to_string(),
+        254 => "main".to_string(),
+        255 => "local".to_string(),
+        _ => id.to_string(),
+    }
+}
+
+/// Name a route type (RTN_* from include/uapi/linux/rtnetlink.h)
+fn format_route_type(route_type: u8) -> String {
+    match route_type {
+        1 => "unicast",
+        2 => "local",
+        3 => "broadcast",
+        4 => "anycast",
+        5 => "multicast",
+        6 => "blackhole",
+        7 => "unreachable",
+        8 => "prohibit",
+        9 => "throw",
+        10 => "nat",
+        11 => "xresolve",
+        _ => "unknown",
+    }
+    .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testutil::{Dump, DIRECT_MAP};
+
+    /// A struct name, its size and its (field, offset) pairs
+    type Layout = (&'static str, usize, &'static [(&'static str, usize)]);
+
+    fn symbols() -> SymbolResolver {
+        let mut symbols = SymbolResolver::new();
+        let structs: [Layout; 14] = [
+            (
+                "net",
+                0x300,
+                &[
+                    ("list", 0),
+                    ("dev_base_head", 0x10),
+                    ("ipv4", 0x100),
+                    ("ipv6", 0x200),
+                ],
+            ),
+            ("net_device", 0x100, &[("dev_list", 0), ("name", 0x10)]),
+            ("netns_ipv4", 0x80, &[("fib_table_hash", 8)]),
+            (
+                "fib_table",
+                0x40,
+                &[("tb_hlist", 0), ("tb_id", 0x10), ("tb_data", 0x30)],
+            ),
+            (
+                "fib_alias",
+                0x30,
+                &[
+                    ("fa_list", 0),
+                    ("fa_info", 0x10),
+                    ("fa_type", 0x19),
+                    ("fa_slen", 0x1a),
+                ],
+            ),
+            (
+                "fib_info",
+                0x100,
+                &[("fib_priority", 0x20), ("fib_nhs", 0x24), ("fib_nh", 0x80)],
+            ),
+            ("fib_nh", 0x70, &[("nh_common", 0)]),
+            (
+                "fib_nh_common",
+                0x40,
+                &[("nhc_dev", 0), ("nhc_gw_family", 0xb), ("nhc_gw", 0x10)],
+            ),
+            (
+                "netns_ipv6",
+                0x80,
+                &[("fib_table_hash", 8), ("fib6_main_tbl", 0x10)],
+            ),
+            (
+                "fib6_table",
+                0x60,
+                &[("tb6_hlist", 0), ("tb6_id", 0x10), ("tb6_root", 0x20)],
+            ),
+            (
+                "fib6_node",
+                0x30,
+                &[
+                    ("left", 8),
+                    ("right", 0x10),
+                    ("leaf", 0x20),
+                    ("fn_flags", 0x2a),
+                ],
+            ),
+            (
+                "fib6_info",
+                0x100,
+                &[
+                    ("fib6_next", 8),
+                    ("fib6_dst", 0x20),
+                    ("fib6_metric", 0x40),
+                    ("fib6_type", 0x48),
+                    ("fib6_nh", 0x60),
+                ],
+            ),
+            ("fib6_nh", 0x40, &[("nh_common", 0)]),
+            ("rt6key", 0x14, &[("addr", 0), ("plen", 0x10)]),
+        ];
+        for (name, size, fields) in structs {
+            symbols.add_struct(name, size, fields);
+        }
+        symbols
+    }
+
+    /// A namespace at 0 with one IPv4 table (an LC-trie with two leaves) and the
+    /// IPv6 main table (a radix tree with two routes on one node)
+    fn synthetic_dump() -> Dump {
+        let mut dump = Dump::new(0x2000);
+        dump.bytes(0x1010, b"eth0\0");
+        dump.bytes(0x1110, b"eth1\0");
+
+        // netns_ipv4.fib_table_hash: the table hangs off the second bucket
+        dump.ptr(0x108, 0x400);
+        dump.ptr(0x408, 0x500);
+        dump.bytes(0x510, &254u32.to_le_bytes());
+        dump.ptr(0x530, 0x600);
+        // trie root -> tnode with 2 bits: leaf A, leaf B, itself (a cycle) and NULL
+        dump.ptr(0x608, 0x700);
+        dump.bytes(0x705, &[2]);
+        dump.ptr(0x708, 0x800);
+        dump.ptr(0x710, 0x900);
+        dump.ptr(0x718, 0x700);
+
+        // Leaf A: 10.0.0.0/8 via a fib_info with two nexthops
+        dump.bytes(0x800, &0x0a00_0000u32.to_le_bytes());
+        dump.ptr(0x808, 0xa00);
+        dump.ptr(0xa10, 0xc00);
+        dump.bytes(0xa19, &[1, 24]);
+        dump.bytes(0xc20, &100u32.to_le_bytes());
+        dump.bytes(0xc24, &2u32.to_le_bytes());
+        dump.ptr(0xc80, 0x1000);
+        dump.bytes(0xc8b, &[AF_INET]);
+        dump.bytes(0xc90, &[10, 0, 0, 254]);
+        dump.ptr(0xcf0, 0x1100);
+
+        // Leaf B: 192.168.1.0 with a /24 local alias and a /32 broadcast alias
+        dump.bytes(0x900, &0xc0a8_0100u32.to_le_bytes());
+        dump.ptr(0x908, 0xb00);
+        dump.ptr(0xb00, 0xb40);
+        dump.bytes(0xb19, &[2, 8]);
+        dump.bytes(0xb59, &[3, 0]);
+
+        // netns_ipv6: empty table hash, main table at 0x1200 with its root node
+        dump.ptr(0x210, 0x1200);
+        dump.bytes(0x1210, &254u32.to_le_bytes());
+        dump.ptr(0x1228, 0x1300);
+        // Node L carries routes; its right child R does not and points back to L
+        dump.bytes(0x132a, &RTN_RTINFO.to_le_bytes());
+        dump.ptr(0x1320, 0x1400);
+        dump.ptr(0x1310, 0x1380);
+        dump.ptr(0x1388, 0x1300);
+        // 2001:db8::/32 metric 1024 via fe80::1 on eth0, then an unreachable ::/0
+        dump.ptr(0x1408, 0x1500);
+        dump.bytes(0x1420, &[0x20, 0x01, 0x0d, 0xb8]);
+        dump.bytes(0x1430, &32u32.to_le_bytes());
+        dump.bytes(0x1440, &1024u32.to_le_bytes());
+        dump.bytes(0x1448, &[1]);
+        dump.ptr(0x1460, 0x1000);
+        dump.bytes(0x146b, &[AF_INET6]);
+        dump.bytes(0x1470, &[0xfe, 0x80]);
+        dump.bytes(0x147f, &[1]);
+        dump.bytes(0x1540, &1u32.to_le_bytes());
+        dump.bytes(0x1548, &[7]);
+        dump
+    }
+
+    fn summary(routes: &[RouteInfo]) -> Vec<String> {
+        routes
+            .iter()
+            .map(|r| {
+                format!(
+                    "{} {} {} via {} dev {} metric {} {}",
+                    r.family,
+                    r.table,
+                    r.destination,
+                    r.gateway,
+                    r.interface,
+                    r.metric,
+                    r.route_type
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_fib_trie_and_fib6_walk() {
+        let dump = synthetic_dump();
+        let translator = dump.translator();
+        let symbols = symbols();
+        let walker = NetWalker::new(&dump.0, &translator, &symbols).unwrap();
+        let reader = RouteReader {
+            mapped: &dump.0,
+            translator: &translator,
+            symbol_resolver: &symbols,
+            walker: &walker,
+        };
+
+        let mut routes = Vec::new();
+        reader.ipv4_routes(DIRECT_MAP, 7, &mut routes).unwrap();
+        assert_eq!(
+            summary(&routes),
+            vec![
+                "ipv4 main 10.0.0.0/8 via 10.0.0.254 dev eth0 metric 100 unicast",
+                "ipv4 main 10.0.0.0/8 via - dev eth1 metric 100 unicast",
+                "ipv4 main 192.168.1.0/24 via - dev ? metric 0 local",
+                "ipv4 main 192.168.1.0/32 via - dev ? metric 0 broadcast",
+            ]
+        );
+        assert_eq!((routes[0].offset, routes[0].net_namespace), (0xa00, 7));
+
+        let mut routes = Vec::new();
+        reader.ipv6_routes(DIRECT_MAP, 7, &mut routes).unwrap();
+        assert_eq!(
+            summary(&routes),
+            vec![
+                "ipv6 main 2001:db8::/32 via fe80::1 dev eth0 metric 1024 unicast",
+                "ipv6 main ::/0 via - dev - metric 1 unreachable",
+            ]
+        );
+        assert_eq!(routes[1].offset, 0x1500);
+    }
+
+    #[test]
+    fn test_format_table_and_route_type() {
+        assert_eq!(format_table_id(254), "main");
+        assert_eq!(format_table_id(255), "local");
+        assert_eq!(format_table_id(253), "default");
+        assert_eq!(format_table_id(100), "100");
+        assert_eq!(format_route_type(1), "unicast");
+        assert_eq!(format_route_type(6), "blackhole");
+        assert_eq!(format_route_type(9), "throw");
+        assert_eq!(format_route_type(0), "unknown");
+    }
+}
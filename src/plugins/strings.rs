@@ -0,0 +1,149 @@
+//! Strings plugin - maps strings in the dump to their physical, kernel and process addresses
+use crate::error::AnalysisError;
+use crate::kernel::{StringInfo, StringOwner};
+use crate::plugins::plugin_trait::{AnalysisContext, ForensicPlugin, PluginOutput};
+use crate::translation::MemoryTranslator;
+use std::path::PathBuf;
+
+pub struct StringsPlugin {
+    /// `strings -td` or `offset:string` output to attribute; strings are extracted
+    /// from the dump when not given
+    pub strings_file: Option<PathBuf>,
+    /// Shortest printable run extracted from the dump
+    pub min_length: usize,
+}
+
+impl ForensicPlugin for StringsPlugin {
+    fn name(&self) -> &str {
+        "strings"
+    }
+
+    fn description(&self) -> &str {
+        "Attribute strings in the dump to physical, kernel and process addresses"
+    }
+
+    fn run(&self, context: &AnalysisContext) -> Result<PluginOutput, AnalysisError> {
+        let mapped = &context.memory_map.mapped[..];
+        let translator = context.translator;
+
+        let found = match &self.strings_file {
+            Some(path) => std::fs::read_to_string(path)?
+                .lines()
+                .filter_map(parse_strings_line)
+                .collect(),
+            None => extract_strings(mapped, translator, self.min_length.max(1)),
+        };
+
+        let rmap = context.reverse_map()?;
+
+        let strings = found
+            .into_iter()
+            .map(|(offset, string)| match rmap.lookup_file_offset(offset) {
+                Some(owners) => StringInfo {
+                    offset,
+                    physical: Some(owners.physical),
+                    string,
+                    kernel: owners.kernel,
+                    owners: owners
+                        .processes
+                        .into_iter()
+                        .map(|o| StringOwner {
+                            pid: o.pid,
+                            comm: o.comm,
+                            address: o.address,
+                            vma_start: o.vma_start,
+                            vma_end: o.vma_end,
+                            path: o.path,
+                        })
+                        .collect(),
+                },
+                None => StringInfo {
+                    offset,
+                    physical: None,
+                    string,
+                    kernel: Vec::new(),
+                    owners: Vec::new(),
+                },
+            })
+            .collect();
+
+        Ok(PluginOutput::Strings(strings))
+    }
+}
+
+/// Parse `<offset> <string>` (`strings -td`) or `<offset>:<string>`
+///
+/// The offset may be decimal or `0x`-prefixed hex.
+fn parse_strings_line(line: &str) -> Option<(u64, String)> {
+    let line = line.trim_start();
+    let (number, rest) = line.split_at(
+        line.find(|c: char| c == ':' || c.is_whitespace())
+            .unwrap_or(line.len()),
+    );
+    let offset = match number.strip_prefix("0x") {
+        Some(hex) => u64::from_str_radix(hex, 16).ok()?,
+        None => number.parse().ok()?,
+    };
+    // Drop the single separator; the string itself may start with spaces
+    let string = rest.get(1..)?;
+    if string.is_empty() {
+        return None;
+    }
+    Some((offset, string.to_string()))
+}
+
+/// Extract printable ASCII runs from each LiME region, by file offset
+fn extract_strings(
+    mapped: &[u8],
+    translator: &MemoryTranslator,
+    min_length: usize,
+) -> Vec<(u64, String)> {
+    let mut strings = Vec::new();
+    for region in translator.get_regions() {
+        let start = region.file_offset as usize;
+        let end =
+            (start as u64 + (region.end - region.start) + 1).min(mapped.len() as u64) as usize;
+        if start >= end {
+            continue;
+        }
+        let data = &mapped[start..end];
+
+        let mut run_start = None;
+        for (i, &byte) in data.iter().chain(std::iter::once(&0)).enumerate() {
+            let printable = byte == b'\t' || (0x20..0x7f).contains(&byte);
+            match (printable, run_start) {
+                (true, None) => run_start = Some(i),
+                (false, Some(s)) => {
+                    if i - s >= min_length {
+                        strings.push((
+                            (start + s) as u64,
+                            String::from_utf8_lossy(&data[s..i]).into_owned(),
+                        ));
+                    }
+                    run_start = None;
+                }
+                _ => {}
+            }
+        }
+    }
+    strings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_strings_line() {
+        assert_eq!(
+            parse_strings_line("   4096 /bin/bash -c id"),
+            Some((4096, "/bin/bash -c id".to_string()))
+        );
+        assert_eq!(
+            parse_strings_line("0x1000:  padded"),
+            Some((0x1000, "  padded".to_string()))
+        );
+        assert_eq!(parse_strings_line("not an offset"), None);
+        assert_eq!(parse_strings_line("12"), None);
+    }
+}
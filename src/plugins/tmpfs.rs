@@ -0,0 +1,307 @@
+//! Tmpfs plugin - reconstructs tmpfs/ramfs filesystems and memfd files
+use crate::error::AnalysisError;
+use crate::kernel::vfs::{file_type_name, format_mode, InodeDetails, VfsWalker};
+use crate::kernel::TmpfsFileInfo;
+use crate::plugins::plugin_trait::{AnalysisContext, ForensicPlugin, PluginOutput};
+use std::collections::HashSet;
+use std::io::{Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+// Macro for conditional warning output
+macro_rules! warn {
+    ($($arg:tt)*) => {
+        if std::env::var("LINMEMPARSER_VERBOSE").is_ok() {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+// Filesystem types whose contents live entirely in the page cache
+const MEMORY_FS_TYPES: [&str; 2] = ["tmpfs", "ramfs"];
+const MAX_TREE_ENTRIES: usize = 1000000;
+const PAGE_SIZE: u64 = 0x1000;
+// tmpfs contents live in RAM and swap: a larger i_size is treated as corrupt
+const MAX_PLAUSIBLE_SIZE: u64 = 1 << 40;
+
+pub struct TmpfsPlugin {
+    /// Directory to export recovered file contents into
+    pub export_dir: Option<PathBuf>,
+}
+
+impl ForensicPlugin for TmpfsPlugin {
+    fn name(&self) -> &str {
+        "tmpfs"
+    }
+
+    fn description(&self) -> &str {
+        "Reconstruct tmpfs/ramfs filesystems and memfd files"
+    }
+
+    fn run(&self, context: &AnalysisContext) -> Result<PluginOutput, AnalysisError> {
+        let mapped = &context.memory_map.mapped[..];
+        let walker = VfsWalker::new(mapped, context.translator, context.symbol_resolver)?;
+        let mut files = Vec::new();
+
+        if let Some(dir) = &self.export_dir {
+            std::fs::create_dir_all(dir)?;
+        }
+
+        for (sb_index, sb) in walker.super_blocks()?.into_iter().enumerate() {
+            let fs_type = match walker.fs_type_name(sb) {
+                Some(name) if MEMORY_FS_TYPES.contains(&name.as_str()) => name,
+                _ => continue,
+            };
+            let mount = walker.mount_path(sb).unwrap_or_else(|| "?".to_string());
+            let export_root = self
+                .export_dir
+                .as_ref()
+                .map(|dir| dir.join(format!("{:03}_{}", sb_index, sanitize_component(&mount))));
+
+            let mut seen = HashSet::new();
+
+            // Pass 1: walk the dentry tree from the superblock root
+            if let Some(root) = walker.root_dentry(sb) {
+                let mut stack = vec![(root, "/".to_string())];
+                while let Some((dentry, path)) = stack.pop() {
+                    if seen.len() >= MAX_TREE_ENTRIES {
+                        warn!("[WARNING] tmpfs tree walk limit reached on {}", mount);
+                        break;
+                    }
+                    let inode = match walker.dentry_inode(dentry) {
+                        Some(i) => i,
+                        None => continue, // Negative dentry
+                    };
+                    if !seen.insert(inode) {
+                        continue;
+                    }
+                    let details = match walker.read_inode(inode) {
+                        Some(d) => d,
+                        None => continue,
+                    };
+
+                    if details.mode & 0o170000 == 0o040000 {
+                        for child in walker.dentry_children(dentry) {
+                            if let Some(name) = walker.dentry_name(child) {
+                                let child_path = if path == "/" {
+                                    format!("/{}", name)
+                                } else {
+                                    format!("{}/{}", path, name)
+                                };
+                                stack.push((child, child_path));
+                            }
+                        }
+                    }
+
+                    let pages = cached_pages(&walker, &details);
+                    let exported = export_root.as_ref().and_then(|dir| {
+                        self.export_file(&walker, &details, &pages, &dir.join("tree"), &path)
+                    });
+                    files.push(build_record(
+                        context, inode, &fs_type, &mount, path, "tree", &details, &pages, exported,
+                    ));
+                }
+            }
+
+            // Pass 2: inodes on the superblock that are not reachable from the root
+            // (memfd_create files, SysV shared memory and unlinked-but-open files)
+            for inode in walker.sb_inodes(sb) {
+                if seen.contains(&inode) {
+                    continue;
+                }
+                seen.insert(inode);
+                let details = match walker.read_inode(inode) {
+                    Some(d) => d,
+                    None => continue,
+                };
+                let name = walker
+                    .inode_alias(inode)
+                    .and_then(|dentry| walker.dentry_name(dentry))
+                    .unwrap_or_default();
+                let origin = if name.starts_with("memfd:") {
+                    "memfd"
+                } else if details.nlink == 0 {
+                    "unlinked"
+                } else {
+                    "anonymous"
+                };
+                let path = if name.is_empty() {
+                    format!("<inode {}>", details.ino)
+                } else {
+                    name.clone()
+                };
+
+                let pages = cached_pages(&walker, &details);
+                let exported = export_root.as_ref().and_then(|dir| {
+                    let file_name = format!("{}_{}", details.ino, sanitize_component(&name));
+                    self.export_file(&walker, &details, &pages, &dir.join(origin), &file_name)
+                });
+                files.push(build_record(
+                    context, inode, &fs_type, &mount, path, origin, &details, &pages, exported,
+                ));
+            }
+        }
+
+        Ok(PluginOutput::TmpfsFiles(files))
+    }
+}
+
+impl TmpfsPlugin {
+    /// Write the cached contents of a regular file below the export directory
+    fn export_file(
+        &self,
+        walker: &VfsWalker,
+        details: &InodeDetails,
+        cached: &[(u64, u64)],
+        base: &Path,
+        path: &str,
+    ) -> Option<String> {
+        if details.mode & 0o170000 != 0o100000 || details.mapping == 0 {
+            return None;
+        }
+
+        let mut target = base.to_path_buf();
+        for component in path.split('/').filter(|c| !c.is_empty()) {
+            target.push(sanitize_component(component));
+        }
+
+        let result = (|| -> Result<(), AnalysisError> {
+            if let Some(parent) = target.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            let mut file = std::fs::File::create(&target)?;
+            let pages: Vec<(u64, u64)> = cached
+                .iter()
+                .filter_map(|&(index, page)| Some((index.checked_mul(PAGE_SIZE)?, page)))
+                .filter(|&(position, _)| position < details.size)
+                .collect();
+            for &(position, page) in &pages {
+                if let Some(data) = walker.read_page(page) {
+                    let length = (details.size - position).min(PAGE_SIZE) as usize;
+                    file.seek(SeekFrom::Start(position))?;
+                    file.write_all(&data[..length])?;
+                }
+            }
+            // Pages that were never cached (or swapped out) are left as holes
+            file.set_len(export_length(details.size, pages.iter().map(|&(p, _)| p)))?;
+            Ok(())
+        })();
+
+        match result {
+            Ok(()) => Some(target.to_string_lossy().to_string()),
+            Err(e) => {
+                warn!("[WARNING] Failed to export {}: {}", target.display(), e);
+                None
+            }
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn build_record(
+    context: &AnalysisContext,
+    inode: u64,
+    fs_type: &str,
+    mount: &str,
+    path: String,
+    origin: &str,
+    details: &InodeDetails,
+    pages: &[(u64, u64)],
+    exported: Option<String>,
+) -> TmpfsFileInfo {
+    TmpfsFileInfo {
+        offset: context
+            .translator
+            .virtual_to_file_offset(inode)
+            .unwrap_or(0),
+        fs_type: fs_type.to_string(),
+        mount: mount.to_string(),
+        path,
+        origin: origin.to_string(),
+        inode: details.ino,
+        file_type: file_type_name(details.mode).to_string(),
+        mode: format_mode(details.mode),
+        uid: details.uid,
+        gid: details.gid,
+        size: details.size,
+        cached_pages: pages.len() as u64,
+        atime: details.atime,
+        mtime: details.mtime,
+        ctime: details.ctime,
+        exported,
+    }
+}
+
+/// Walk the page cache of a file once, for both the export and the record
+fn cached_pages(walker: &VfsWalker, details: &InodeDetails) -> Vec<(u64, u64)> {
+    if details.mapping != 0 {
+        walker.cached_pages(details.mapping)
+    } else {
+        Vec::new()
+    }
+}
+
+/// Length of an exported file: i_size, or the end of the last cached page when
+/// i_size is implausible (a corrupt i_size must not create a huge sparse file)
+fn export_length(size: u64, positions: impl Iterator<Item = u64>) -> u64 {
+    if size <= MAX_PLAUSIBLE_SIZE {
+        return size;
+    }
+    positions
+        .max()
+        .map_or(0, |last| last.saturating_add(PAGE_SIZE))
+}
+
+/// Make a path component safe to create on the analysis host
+fn sanitize_component(name: &str) -> String {
+    let cleaned: String = name
+        .chars()
+        .map(|c| {
+            if c == '/' || c == '\\' || c.is_control() {
+                '_'
+            } else {
+                c
+            }
+        })
+        .collect();
+    match cleaned.as_str() {
+        "" => "_".to_string(),
+        "." | ".." => format!("_{}", cleaned),
+        _ => cleaned,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_component() {
+        assert_eq!(sanitize_component("notes.txt"), "notes.txt");
+        assert_eq!(sanitize_component("a/b\\c"), "a_b_c");
+        assert_eq!(sanitize_component("line\nbreak"), "line_break");
+        assert_eq!(sanitize_component(".."), "_..");
+        assert_eq!(sanitize_component("."), "_.");
+        assert_eq!(sanitize_component(""), "_");
+        assert_eq!(
+            sanitize_component("memfd:payload (deleted)"),
+            "memfd:payload (deleted)"
+        );
+    }
+
+    #[test]
+    fn test_export_length_caps_only_implausible_sizes() {
+        assert_eq!(
+            export_length(0x2800, [0, 0x1000, 0x2000].into_iter()),
+            0x2800
+        );
+        // Trailing swapped-out or sparse ranges keep the recorded i_size
+        assert_eq!(export_length(0x10000, [0x1000].into_iter()), 0x10000);
+        assert_eq!(export_length(0x10000, std::iter::empty()), 0x10000);
+        // A corrupt size is cut at the end of the last cached page
+        assert_eq!(
+            export_length(u64::MAX, [0x3000, 0x1000].into_iter()),
+            0x4000
+        );
+        assert_eq!(export_length(u64::MAX, std::iter::empty()), 0);
+    }
+}
@@ -0,0 +1,385 @@
+//! TraceHooks plugin - lists ftrace ops, kprobes and tracepoint probes
+use crate::error::AnalysisError;
+use crate::kernel::pointers::PointerIndex;
+use crate::kernel::{KernelParser, TraceHookInfo};
+use crate::plugins::plugin_trait::{AnalysisContext, ForensicPlugin, PluginOutput};
+use crate::symbols::SymbolResolver;
+use crate::translation::MemoryTranslator;
+
+// Macro for conditional warning output
+macro_rules! warn {
+    ($($arg:tt)*) => {
+        if std::env::var("LINMEMPARSER_VERBOSE").is_ok() {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+// Safety limits
+const MAX_FTRACE_OPS: usize = 4096;
+const MAX_HASH_ENTRIES: usize = 1 << 20;
+const MAX_PROBES_PER_TRACEPOINT: u64 = 256;
+// Filtered functions listed per ftrace_ops before summarising
+const MAX_LISTED_FUNCTIONS: usize = 8;
+// KPROBE_TABLE_SIZE (1 << KPROBE_HASH_BITS)
+const KPROBE_TABLE_SIZE: u64 = 64;
+const MAX_KPROBES_PER_BUCKET: usize = 4096;
+
+// KPROBE_FLAG_* values
+const KPROBE_FLAG_GONE: u32 = 1;
+const KPROBE_FLAG_DISABLED: u32 = 2;
+const KPROBE_FLAG_OPTIMIZED: u32 = 4;
+const KPROBE_FLAG_FTRACE: u32 = 8;
+
+pub struct TraceHooksPlugin;
+
+impl ForensicPlugin for TraceHooksPlugin {
+    fn name(&self) -> &str {
+        "tracehooks"
+    }
+
+    fn description(&self) -> &str {
+        "List ftrace ops, kprobes and tracepoint probes with resolved callbacks"
+    }
+
+    fn run(&self, context: &AnalysisContext) -> Result<PluginOutput, AnalysisError> {
+        let mapped = &context.memory_map.mapped[..];
+        let index = PointerIndex::new(mapped, context.translator, context.symbol_resolver)?;
+        let reader = HookReader {
+            mapped,
+            translator: context.translator,
+            symbol_resolver: context.symbol_resolver,
+            callbacks: &index,
+        };
+
+        let mut hooks = Vec::new();
+        match reader.ftrace_ops() {
+            Ok(found) => hooks.extend(found),
+            Err(e) => warn!("[WARNING] ftrace ops unavailable: {}", e),
+        }
+        match reader.kprobes() {
+            Ok(found) => hooks.extend(found),
+            Err(e) => warn!("[WARNING] kprobes unavailable: {}", e),
+        }
+        match reader.tracepoint_probes() {
+            Ok(found) => hooks.extend(found),
+            Err(e) => warn!("[WARNING] Tracepoint probes unavailable: {}", e),
+        }
+
+        Ok(PluginOutput::TraceHooks(hooks))
+    }
+}
+
+/// Reader for the ftrace, kprobe and tracepoint callback registries
+struct HookReader<'a> {
+    mapped: &'a [u8],
+    translator: &'a MemoryTranslator,
+    symbol_resolver: &'a SymbolResolver,
+    callbacks: &'a PointerIndex,
+}
+
+impl<'a> HookReader<'a> {
+    fn field(&self, s: &str, f: &str) -> Result<u64, AnalysisError> {
+        self.symbol_resolver
+            .require_field_offset(s, f)
+            .map(|o| o as u64)
+    }
+
+    fn symbol(&self, name: &str) -> Result<u64, AnalysisError> {
+        self.symbol_resolver
+            .get_symbol_address(name)
+            .ok_or_else(|| AnalysisError::SymbolNotFound(name.to_string()))
+    }
+
+    fn read_ptr(&self, addr: u64) -> u64 {
+        KernelParser::read_virt_pointer(self.mapped, self.translator, addr).unwrap_or(0)
+    }
+
+    fn read_u32(&self, addr: u64) -> u32 {
+        KernelParser::read_virt_u32(self.mapped, self.translator, addr).unwrap_or(0)
+    }
+
+    fn file_offset(&self, addr: u64) -> u64 {
+        self.translator.virtual_to_file_offset(addr).unwrap_or(0)
+    }
+
+    /// Build a record for one callback
+    fn hook(
+        &self,
+        object: u64,
+        hook_type: &str,
+        target: String,
+        handler: &str,
+        callback: u64,
+        details: String,
+    ) -> TraceHookInfo {
+        let resolved = self.callbacks.classify(callback);
+        TraceHookInfo {
+            offset: self.file_offset(object),
+            hook_type: hook_type.to_string(),
+            target,
+            handler: handler.to_string(),
+            callback,
+            symbol: resolved.symbol,
+            owner: resolved.class.to_string(),
+            details,
+        }
+    }
+
+    /// Walk ftrace_ops_list up to the ftrace_list_end sentinel
+    fn ftrace_ops(&self) -> Result<Vec<TraceHookInfo>, AnalysisError> {
+        let list = self.symbol("ftrace_ops_list")?;
+        let end = self.symbol("ftrace_list_end")?;
+        let func = self.field("ftrace_ops", "func")?;
+        let next = self.field("ftrace_ops", "next")?;
+        let flags = self.field("ftrace_ops", "flags")?;
+
+        let mut hooks = Vec::new();
+        let mut ops = self.read_ptr(list);
+        while ops != 0 && ops != end && hooks.len() < MAX_FTRACE_OPS {
+            let callback = self.read_ptr(ops + func);
+            let target = match self.filtered_functions(ops) {
+                Some(functions) if !functions.is_empty() => summarize(functions),
+                _ => "*".to_string(), // No filter: every traceable function
+            };
+            hooks.push(self.hook(
+                ops,
+                "ftrace",
+                target,
+                "func",
+                callback,
+                format!("flags={:#x}", self.read_ptr(ops + flags)),
+            ));
+            ops = self.read_ptr(ops + next);
+        }
+
+        Ok(hooks)
+    }
+
+    /// Resolve the functions in an ftrace_ops filter hash
+    fn filtered_functions(&self, ops: u64) -> Option<Vec<String>> {
+        let resolver = self.symbol_resolver;
+        let func_hash = resolver.get_struct_field_offset("ftrace_ops", "func_hash", None)?;
+        let filter_hash =
+            resolver.get_struct_field_offset("ftrace_ops_hash", "filter_hash", None)?;
+        let size_bits = resolver.get_struct_field_offset("ftrace_hash", "size_bits", None)?;
+        let buckets = resolver.get_struct_field_offset("ftrace_hash", "buckets", None)?;
+        let ip = resolver.get_struct_field_offset("ftrace_func_entry", "ip", None)?;
+        let hlist = resolver
+            .get_struct_field_offset("ftrace_func_entry", "hlist", None)
+            .unwrap_or(0);
+
+        let ops_hash = self.read_ptr(ops + func_hash);
+        if ops_hash == 0 {
+            return None;
+        }
+        let hash = self.read_ptr(ops_hash + filter_hash);
+        if hash == 0 {
+            return None;
+        }
+        let bits = self.read_ptr(hash + size_bits);
+        let table = self.read_ptr(hash + buckets);
+        if bits > 20 || table == 0 {
+            return None;
+        }
+
+        let mut functions = Vec::new();
+        for bucket in 0..(1u64 << bits) {
+            if functions.len() >= MAX_HASH_ENTRIES {
+                break;
+            }
+            let first = self.read_ptr(table + bucket * self.translator.pointer_size());
+            for node in
+                KernelParser::walk_hlist(self.mapped, self.translator, first, MAX_HASH_ENTRIES)
+            {
+                let address = self.read_ptr(node - hlist + ip);
+                functions.push(self.callbacks.classify(address).symbol);
+            }
+        }
+        Some(functions)
+    }
+
+    /// Walk kprobe_table, expanding aggregate probes and kretprobes
+    fn kprobes(&self) -> Result<Vec<TraceHookInfo>, AnalysisError> {
+        let table = self.symbol("kprobe_table")?;
+        let hlist = self.field("kprobe", "hlist")?;
+        let list = self.field("kprobe", "list")?;
+        let pre_handler = self.field("kprobe", "pre_handler")?;
+        let aggr_pre_handler = self.symbol_resolver.get_symbol_address("aggr_pre_handler");
+
+        let mut hooks = Vec::new();
+        for bucket in 0..KPROBE_TABLE_SIZE {
+            let first = self.read_ptr(table + bucket * self.translator.pointer_size());
+            for node in KernelParser::walk_hlist(
+                self.mapped,
+                self.translator,
+                first,
+                MAX_KPROBES_PER_BUCKET,
+            ) {
+                let probe = node - hlist;
+                let handler = self.read_ptr(probe + pre_handler);
+                if aggr_pre_handler.is_some() && Some(handler) == aggr_pre_handler {
+                    // Several probes on one address hang off the aggregate's list
+                    for child in KernelParser::walk_list(
+                        self.mapped,
+                        self.translator,
+                        probe + list,
+                        MAX_KPROBES_PER_BUCKET,
+                    ) {
+                        hooks.extend(self.kprobe_handlers(child - list)?);
+                    }
+                } else {
+                    hooks.extend(self.kprobe_handlers(probe)?);
+                }
+            }
+        }
+
+        Ok(hooks)
+    }
+
+    /// Report the handlers of a single kprobe (or the kretprobe wrapping it)
+    fn kprobe_handlers(&self, probe: u64) -> Result<Vec<TraceHookInfo>, AnalysisError> {
+        let resolver = self.symbol_resolver;
+        let addr = self.read_ptr(probe + self.field("kprobe", "addr")?);
+        let pre = self.read_ptr(probe + self.field("kprobe", "pre_handler")?);
+        let post = self.read_ptr(probe + self.field("kprobe", "post_handler")?);
+        let flags = resolver
+            .get_struct_field_offset("kprobe", "flags", None)
+            .map(|o| self.read_u32(probe + o))
+            .unwrap_or(0);
+
+        let target = self.callbacks.classify(addr).symbol;
+        let details = format_kprobe_flags(flags);
+        let mut hooks = Vec::new();
+
+        let is_kretprobe = resolver.get_symbol_address("pre_handler_kretprobe") == Some(pre);
+        if is_kretprobe {
+            let kp = resolver
+                .get_struct_field_offset("kretprobe", "kp", None)
+                .unwrap_or(0);
+            let kretprobe = probe - kp;
+            for (field, handler) in [("entry_handler", "entry"), ("handler", "return")] {
+                if let Some(offset) = resolver.get_struct_field_offset("kretprobe", field, None) {
+                    let callback = self.read_ptr(kretprobe + offset);
+                    if callback != 0 {
+                        hooks.push(self.hook(
+                            kretprobe,
+                            "kretprobe",
+                            target.clone(),
+                            handler,
+                            callback,
+                            details.clone(),
+                        ));
+                    }
+                }
+            }
+            return Ok(hooks);
+        }
+
+        for (callback, handler) in [(pre, "pre"), (post, "post")] {
+            if callback != 0 {
+                hooks.push(self.hook(
+                    probe,
+                    "kprobe",
+                    target.clone(),
+                    handler,
+                    callback,
+                    details.clone(),
+                ));
+            }
+        }
+        Ok(hooks)
+    }
+
+    /// Read the probe arrays of every `__tracepoint_*` symbol
+    fn tracepoint_probes(&self) -> Result<Vec<TraceHookInfo>, AnalysisError> {
+        let name = self.field("tracepoint", "name")?;
+        let funcs = self.field("tracepoint", "funcs")?;
+        let func = self.field("tracepoint_func", "func")?;
+        let prio = self
+            .symbol_resolver
+            .get_struct_field_offset("tracepoint_func", "prio", None);
+        let stride = self
+            .symbol_resolver
+            .get_struct_size("tracepoint_func")
+            .unwrap_or(24) as u64;
+
+        let mut tracepoints: Vec<(u64, &str)> = self
+            .symbol_resolver
+            .get_symbols()
+            .iter()
+            .filter(|(symbol, _)| {
+                symbol.starts_with("__tracepoint_") && !symbol.starts_with("__tracepoint_ptr_")
+            })
+            .map(|(symbol, &address)| (address, symbol.as_str()))
+            .collect();
+        tracepoints.sort();
+
+        let mut hooks = Vec::new();
+        for (tracepoint, symbol) in tracepoints {
+            let probes = self.read_ptr(tracepoint + funcs);
+            if probes == 0 {
+                continue;
+            }
+            let tp_name = KernelParser::read_virt_cstring(
+                self.mapped,
+                self.translator,
+                self.read_ptr(tracepoint + name),
+                256,
+            )
+            .unwrap_or_else(|| symbol.trim_start_matches("__tracepoint_").to_string());
+
+            for i in 0..MAX_PROBES_PER_TRACEPOINT {
+                let entry = probes + i * stride;
+                let callback = self.read_ptr(entry + func);
+                if callback == 0 {
+                    break;
+                }
+                let details = prio
+                    .and_then(|o| {
+                        KernelParser::read_virt_i32(self.mapped, self.translator, entry + o)
+                    })
+                    .map(|p| format!("prio={}", p))
+                    .unwrap_or_default();
+                hooks.push(self.hook(
+                    tracepoint,
+                    "tracepoint",
+                    tp_name.clone(),
+                    "probe",
+                    callback,
+                    details,
+                ));
+            }
+        }
+
+        Ok(hooks)
+    }
+}
+
+/// Join function names, summarising long filter lists
+fn summarize(functions: Vec<String>) -> String {
+    if functions.len() <= MAX_LISTED_FUNCTIONS {
+        return functions.join(", ");
+    }
+    format!(
+        "{} (+{} more)",
+        functions[..MAX_LISTED_FUNCTIONS].join(", "),
+        functions.len() - MAX_LISTED_FUNCTIONS
+    )
+}
+
+/// Describe kprobe state flags
+fn format_kprobe_flags(flags: u32) -> String {
+    let names = [
+        (KPROBE_FLAG_GONE, "gone"),
+        (KPROBE_FLAG_DISABLED, "disabled"),
+        (KPROBE_FLAG_OPTIMIZED, "optimized"),
+        (KPROBE_FLAG_FTRACE, "ftrace"),
+    ];
+    names
+        .iter()
+        .filter(|(bit, _)| flags & bit != 0)
+        .map(|(_, name)| *name)
+        .collect::<Vec<_>>()
+        .join(",")
+}
@@ -0,0 +1,288 @@
+//! TtyCheck plugin - checks tty line discipline and driver handlers for hooks
+use crate::error::AnalysisError;
+use crate::kernel::pointers::PointerIndex;
+use crate::kernel::{KernelParser, TtyHookInfo};
+use crate::plugins::plugin_trait::{AnalysisContext, ForensicPlugin, PluginOutput};
+use crate::symbols::SymbolResolver;
+use crate::translation::MemoryTranslator;
+
+// Safety limits
+const MAX_DRIVERS: usize = 1024;
+const MAX_TTYS_PER_DRIVER: u32 = 4096;
+// sizeof(tty_struct.name)
+const TTY_NAME_LEN: usize = 64;
+
+// Line discipline handlers that see every byte typed on the terminal
+const LDISC_HANDLERS: [&str; 2] = ["receive_buf", "receive_buf2"];
+
+pub struct TtyCheckPlugin;
+
+impl ForensicPlugin for TtyCheckPlugin {
+    fn name(&self) -> &str {
+        "ttycheck"
+    }
+
+    fn description(&self) -> &str {
+        "Check tty line discipline receive_buf and driver write handlers"
+    }
+
+    fn run(&self, context: &AnalysisContext) -> Result<PluginOutput, AnalysisError> {
+        let mapped = &context.memory_map.mapped[..];
+        let translator = context.translator;
+        let resolver = context.symbol_resolver;
+        let callbacks = PointerIndex::new(mapped, translator, resolver)?;
+        let reader = TtyReader::new(mapped, translator, resolver, &callbacks)?;
+
+        let head = resolver
+            .get_symbol_address("tty_drivers")
+            .ok_or_else(|| AnalysisError::SymbolNotFound("tty_drivers".to_string()))?;
+        let mut hooks = Vec::new();
+        for node in KernelParser::walk_list(mapped, translator, head, MAX_DRIVERS) {
+            hooks.extend(reader.driver_hooks(node - reader.driver_list));
+        }
+
+        Ok(PluginOutput::TtyHooks(hooks))
+    }
+}
+
+/// Reader for tty drivers, their ttys and the handlers each tty dispatches to
+struct TtyReader<'a> {
+    mapped: &'a [u8],
+    translator: &'a MemoryTranslator,
+    callbacks: &'a PointerIndex,
+    driver_list: u64,
+    driver_name: u64,
+    driver_ttys: u64,
+    driver_num: u64,
+    tty_name: u64,
+    tty_ldisc: u64,
+    tty_ops: u64,
+    ldisc_ops: u64,
+    ldisc_ops_name: u64,
+    // Optional handler offsets: receive_buf2 is 3.12+ and either may be absent
+    ldisc_handlers: Vec<(&'static str, u64)>,
+    write: Option<u64>,
+}
+
+impl<'a> TtyReader<'a> {
+    fn new(
+        mapped: &'a [u8],
+        translator: &'a MemoryTranslator,
+        resolver: &SymbolResolver,
+        callbacks: &'a PointerIndex,
+    ) -> Result<Self, AnalysisError> {
+        let field = |s: &str, f: &str| resolver.require_field_offset(s, f).map(|o| o as u64);
+        Ok(TtyReader {
+            mapped,
+            translator,
+            callbacks,
+            driver_list: field("tty_driver", "tty_drivers")?,
+            driver_name: field("tty_driver", "driver_name")?,
+            driver_ttys: field("tty_driver", "ttys")?,
+            driver_num: field("tty_driver", "num")?,
+            tty_name: field("tty_struct", "name")?,
+            tty_ldisc: field("tty_struct", "ldisc")?,
+            tty_ops: field("tty_struct", "ops")?,
+            ldisc_ops: field("tty_ldisc", "ops")?,
+            ldisc_ops_name: field("tty_ldisc_ops", "name")?,
+            ldisc_handlers: LDISC_HANDLERS
+                .iter()
+                .filter_map(|&handler| {
+                    resolver
+                        .get_struct_field_offset("tty_ldisc_ops", handler, None)
+                        .map(|o| (handler, o))
+                })
+                .collect(),
+            write: resolver.get_struct_field_offset("tty_operations", "write", None),
+        })
+    }
+
+    fn read_ptr(&self, addr: u64) -> u64 {
+        KernelParser::read_virt_pointer(self.mapped, self.translator, addr).unwrap_or(0)
+    }
+
+    fn read_name(&self, addr: u64, len: usize) -> String {
+        KernelParser::read_virt_cstring(self.mapped, self.translator, addr, len).unwrap_or_default()
+    }
+
+    /// Check every allocated tty of one tty_driver
+    fn driver_hooks(&self, driver: u64) -> Vec<TtyHookInfo> {
+        let mut hooks = Vec::new();
+        let driver_label = self.read_name(self.read_ptr(driver + self.driver_name), 64);
+        // Devpts-backed drivers (unix98 ptys) allocate no ttys array
+        let ttys = self.read_ptr(driver + self.driver_ttys);
+        if ttys == 0 {
+            return hooks;
+        }
+        let num =
+            KernelParser::read_virt_u32(self.mapped, self.translator, driver + self.driver_num)
+                .unwrap_or(0)
+                .min(MAX_TTYS_PER_DRIVER);
+
+        for index in 0..num as u64 {
+            let tty = self.read_ptr(ttys + index * self.translator.pointer_size());
+            if tty == 0 {
+                continue;
+            }
+            let name = self.read_name(tty + self.tty_name, TTY_NAME_LEN);
+            let mut push = |ldisc: &str, handler: &str, callback: u64| {
+                let resolved = self.callbacks.classify(callback);
+                hooks.push(TtyHookInfo {
+                    offset: self.translator.virtual_to_file_offset(tty).unwrap_or(0),
+                    tty: name.clone(),
+                    driver: driver_label.clone(),
+                    ldisc: ldisc.to_string(),
+                    handler: handler.to_string(),
+                    callback,
+                    symbol: resolved.symbol,
+                    owner: resolved.class.to_string(),
+                });
+            };
+
+            // tty->ldisc->ops->receive_buf{,2}
+            let ldisc = self.read_ptr(tty + self.tty_ldisc);
+            let ops = if ldisc != 0 {
+                self.read_ptr(ldisc + self.ldisc_ops)
+            } else {
+                0
+            };
+            let ldisc_label = if ops != 0 {
+                self.read_name(self.read_ptr(ops + self.ldisc_ops_name), 64)
+            } else {
+                String::new()
+            };
+            if ops != 0 {
+                for &(handler, offset) in &self.ldisc_handlers {
+                    let callback = self.read_ptr(ops + offset);
+                    if callback != 0 {
+                        push(&ldisc_label, handler, callback);
+                    }
+                }
+            }
+
+            // tty->ops->write
+            if let Some(offset) = self.write {
+                let driver_ops = self.read_ptr(tty + self.tty_ops);
+                let callback = if driver_ops != 0 {
+                    self.read_ptr(driver_ops + offset)
+                } else {
+                    0
+                };
+                if callback != 0 {
+                    push(&ldisc_label, "write", callback);
+                }
+            }
+        }
+
+        hooks
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kernel::modules::LoadedModule;
+    use crate::testutil::{Dump, DIRECT_MAP};
+
+    const N_TTY_RECEIVE_BUF: u64 = 0xffff_ffff_8150_0000;
+    const UART_WRITE: u64 = 0xffff_ffff_8160_0000;
+    const SNIFFER: u64 = 0xffff_ffff_c000_0200;
+
+    fn symbols() -> SymbolResolver {
+        let mut symbols = SymbolResolver::new();
+        symbols.add_struct(
+            "tty_driver",
+            0x100,
+            &[
+                ("tty_drivers", 0),
+                ("driver_name", 0x10),
+                ("num", 0x18),
+                ("ttys", 0x20),
+            ],
+        );
+        symbols.add_struct(
+            "tty_struct",
+            0x200,
+            &[("name", 0x10), ("ldisc", 0x50), ("ops", 0x58)],
+        );
+        symbols.add_struct("tty_ldisc", 0x10, &[("ops", 0)]);
+        symbols.add_struct(
+            "tty_ldisc_ops",
+            0x80,
+            &[("name", 0), ("receive_buf", 0x20), ("receive_buf2", 0x28)],
+        );
+        symbols.add_struct("tty_operations", 0x100, &[("write", 0x10)]);
+        symbols
+    }
+
+    #[test]
+    fn test_driver_hooks() {
+        let mut dump = Dump::new(0x2000);
+        // "serial" driver with ttys[3] = { ttyS0, NULL, ttyS2 }
+        dump.ptr(0x10, 0x100);
+        dump.bytes(0x100, b"serial\0");
+        dump.bytes(0x18, &3u32.to_le_bytes());
+        dump.ptr(0x20, 0x200);
+        dump.ptr(0x200, 0x400);
+        dump.ptr(0x210, 0x600);
+
+        // ttyS0: stock n_tty receive_buf and a uart write handler
+        dump.bytes(0x410, b"ttyS0\0");
+        dump.ptr(0x450, 0x800);
+        dump.ptr(0x800, 0x900);
+        dump.ptr(0x900, 0x980);
+        dump.bytes(0x980, b"n_tty\0");
+        dump.word(0x920, N_TTY_RECEIVE_BUF);
+        dump.ptr(0x458, 0xa00);
+        dump.word(0xa10, UART_WRITE);
+
+        // ttyS2: a line discipline whose receive_buf2 lives in a module, no ops
+        dump.bytes(0x610, b"ttyS2\0");
+        dump.ptr(0x650, 0xb00);
+        dump.ptr(0xb00, 0xc00);
+        dump.ptr(0xc00, 0xc80);
+        dump.bytes(0xc80, b"n_evil\0");
+        dump.word(0xc28, SNIFFER);
+
+        let translator = dump.translator();
+        let symbols = symbols();
+        let callbacks = PointerIndex::from_parts(
+            0xffff_ffff_8100_0000,
+            0xffff_ffff_8200_0000,
+            vec![
+                (N_TTY_RECEIVE_BUF, "n_tty_receive_buf".to_string()),
+                (UART_WRITE, "uart_write".to_string()),
+            ],
+            vec![LoadedModule {
+                name: "sniffer".to_string(),
+                ranges: vec![(0xffff_ffff_c000_0000, 0xffff_ffff_c000_1000)],
+                symbols: vec![(SNIFFER, "sniff_receive_buf".to_string())],
+            }],
+        );
+        let reader = TtyReader::new(&dump.0, &translator, &symbols, &callbacks).unwrap();
+
+        let hooks = reader.driver_hooks(DIRECT_MAP);
+        let summary: Vec<String> = hooks
+            .iter()
+            .map(|h| {
+                format!(
+                    "{} {} {} {} {} {}",
+                    h.driver, h.tty, h.ldisc, h.handler, h.symbol, h.owner
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                "serial ttyS0 n_tty receive_buf n_tty_receive_buf kernel",
+                "serial ttyS0 n_tty write uart_write kernel",
+                "serial ttyS2 n_evil receive_buf2 sniff_receive_buf sniffer",
+            ]
+        );
+        assert_eq!((hooks[0].offset, hooks[2].offset), (0x400, 0x600));
+        assert_eq!(hooks[2].callback, SNIFFER);
+
+        // A driver without a ttys array (devpts) has nothing to check
+        assert!(reader.driver_hooks(DIRECT_MAP + 0x1000).is_empty());
+    }
+}
@@ -0,0 +1,291 @@
+//! Unix plugin - enumerates Unix domain sockets with their owners and peers
+use crate::error::AnalysisError;
+use crate::kernel::net::{format_socket_type, NetWalker, SockReader, SocketIndex};
+use crate::kernel::process_extractor::ProcessExtractor;
+use crate::kernel::{KernelParser, SocketOwner, UnixSocketInfo};
+use crate::plugins::plugin_trait::{AnalysisContext, ForensicPlugin, PluginOutput};
+use crate::symbols::SymbolResolver;
+use crate::translation::MemoryTranslator;
+use std::collections::HashSet;
+
+// Macro for conditional debug output
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        if std::env::var("LINMEMPARSER_DEBUG").is_ok() {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+// Macro for conditional warning output
+macro_rules! warn {
+    ($($arg:tt)*) => {
+        if std::env::var("LINMEMPARSER_VERBOSE").is_ok() {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+// 2 * UNIX_HASH_SIZE buckets (bound and unbound halves)
+const UNIX_TABLE_BUCKETS: u64 = 512;
+const MAX_CHAIN_LENGTH: usize = 65536;
+// sizeof(sun_path)
+const UNIX_PATH_MAX: usize = 108;
+// sun_path follows the 2-byte sun_family
+const SUN_PATH_OFFSET: u64 = 2;
+
+// sk_state values used by AF_UNIX
+const TCP_ESTABLISHED: u8 = 1;
+const TCP_CLOSE: u8 = 7;
+const TCP_LISTEN: u8 = 10;
+
+pub struct UnixPlugin;
+
+impl ForensicPlugin for UnixPlugin {
+    fn name(&self) -> &str {
+        "unix"
+    }
+
+    fn description(&self) -> &str {
+        "List Unix domain sockets with owning and peer processes"
+    }
+
+    fn run(&self, context: &AnalysisContext) -> Result<PluginOutput, AnalysisError> {
+        let mapped = &context.memory_map.mapped[..];
+        let translator = context.translator;
+        let resolver = context.symbol_resolver;
+        let read_ptr =
+            |addr: u64| KernelParser::read_virt_pointer(mapped, translator, addr).unwrap_or(0);
+        let field = |s: &str, f: &str| resolver.require_field_offset(s, f).map(|o| o as u64);
+
+        let net_walker = NetWalker::new(mapped, translator, resolver)?;
+        let socks = SockReader::new(mapped, translator, resolver)?;
+
+        let processes = ProcessExtractor::new().walk_process_list(
+            context.memory_map,
+            translator,
+            resolver,
+            context.init_task_offset as u64,
+        )?;
+        let index = SocketIndex::build(mapped, translator, resolver, &processes)?;
+
+        let common = resolver
+            .get_struct_field_offset("sock", "__sk_common", None)
+            .unwrap_or(0);
+        let node_offset = common + field("sock_common", "skc_node")?;
+        let layout = UnixLayout::new(resolver)?;
+
+        // Before 5.17 a single global table holds every namespace's sockets;
+        // newer kernels hang a table off each struct net
+        let mut tables = Vec::new();
+        if let Some(table) = resolver.get_symbol_address("unix_socket_table") {
+            tables.push(table);
+        } else {
+            let unx = field("net", "unx")? + field("netns_unix", "table")?;
+            let buckets = field("unix_table", "buckets")?;
+            for net in net_walker.namespaces()? {
+                let table = read_ptr(net + unx + buckets);
+                if table != 0 {
+                    tables.push(table);
+                }
+            }
+        }
+        debug!("[DEBUG] Walking {} unix socket table(s)", tables.len());
+
+        let mut seen = HashSet::new();
+        let mut sockets = Vec::new();
+
+        for table in tables {
+            for bucket in 0..UNIX_TABLE_BUCKETS {
+                let first = read_ptr(table + bucket * translator.pointer_size());
+                for node in KernelParser::walk_hlist(mapped, translator, first, MAX_CHAIN_LENGTH) {
+                    let sk = node - node_offset;
+                    if !seen.insert(sk) {
+                        continue;
+                    }
+
+                    let path = layout.path(mapped, translator, sk);
+                    let peer = layout.peer(mapped, translator, sk);
+                    let peer_inode = if peer != 0 {
+                        socks.inode_number(peer)
+                    } else {
+                        0
+                    };
+                    let peer_pids = owner_pids(&index.owners(peer_inode));
+
+                    let inode = socks.inode_number(sk);
+                    sockets.push(UnixSocketInfo {
+                        offset: translator.virtual_to_file_offset(sk).unwrap_or(0),
+                        net_namespace: socks
+                            .net(sk)
+                            .map(|net| net_walker.namespace_inum(net))
+                            .unwrap_or(0),
+                        inode,
+                        path,
+                        socket_type: format_socket_type(socks.socket_type(sk)),
+                        state: format_unix_state(socks.state(sk)),
+                        owners: if inode != 0 {
+                            index.owners(inode)
+                        } else {
+                            Vec::new()
+                        },
+                        peer_inode,
+                        peer_pids,
+                    });
+                }
+            }
+        }
+
+        if sockets.is_empty() {
+            warn!("[WARNING] No unix sockets found; table offsets may be wrong");
+        }
+
+        sockets.sort_by_key(|s| s.inode);
+        Ok(PluginOutput::UnixSockets(sockets))
+    }
+}
+
+/// unix_sock and unix_address offsets
+struct UnixLayout {
+    addr: u64,
+    peer: u64,
+    len: u64,
+    name: u64,
+}
+
+impl UnixLayout {
+    fn new(resolver: &SymbolResolver) -> Result<Self, AnalysisError> {
+        let field = |s: &str, f: &str| resolver.require_field_offset(s, f).map(|o| o as u64);
+        Ok(UnixLayout {
+            addr: field("unix_sock", "addr")?,
+            peer: field("unix_sock", "peer")?,
+            len: field("unix_address", "len")?,
+            name: field("unix_address", "name")?,
+        })
+    }
+
+    /// Return the connected peer sock (0 when there is none)
+    fn peer(&self, mapped: &[u8], translator: &MemoryTranslator, sk: u64) -> u64 {
+        KernelParser::read_virt_pointer(mapped, translator, sk + self.peer).unwrap_or(0)
+    }
+
+    /// Read sun_path; abstract names start with a NUL byte and are shown as "@name"
+    fn path(&self, mapped: &[u8], translator: &MemoryTranslator, sk: u64) -> String {
+        let addr = match KernelParser::read_virt_pointer(mapped, translator, sk + self.addr) {
+            Some(addr) if addr != 0 => addr,
+            _ => return String::new(),
+        };
+        // len counts sun_family plus the path bytes
+        let len = KernelParser::read_virt_u32(mapped, translator, addr + self.len).unwrap_or(0);
+        let length = (len as usize)
+            .saturating_sub(SUN_PATH_OFFSET as usize)
+            .min(UNIX_PATH_MAX);
+        if length == 0 {
+            return String::new();
+        }
+        let sun_path = addr + self.name + SUN_PATH_OFFSET;
+        let bytes = match KernelParser::read_virt_bytes(mapped, translator, sun_path, length) {
+            Some(b) => b,
+            None => return "?".to_string(),
+        };
+
+        if bytes[0] == 0 {
+            format!("@{}", String::from_utf8_lossy(&bytes[1..]))
+        } else {
+            let end = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+            String::from_utf8_lossy(&bytes[..end]).to_string()
+        }
+    }
+}
+
+/// Distinct pids among a socket's owners
+fn owner_pids(owners: &[SocketOwner]) -> Vec<i32> {
+    let mut pids: Vec<i32> = owners.iter().map(|o| o.pid).collect();
+    pids.sort_unstable();
+    pids.dedup();
+    pids
+}
+
+/// Name a Unix socket state the way netstat does
+fn format_unix_state(state: u8) -> String {
+    match state {
+        TCP_ESTABLISHED => "CONNECTED",
+        TCP_CLOSE => "UNCONNECTED",
+        TCP_LISTEN => "LISTENING",
+        _ => "UNKNOWN",
+    }
+    .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testutil::{socket_symbols, Dump, DIRECT_MAP};
+
+    /// Bind a sock to a unix_address holding len and sun_path
+    fn bind(dump: &mut Dump, sk: u64, at: u64, len: u32, sun_path: &[u8]) {
+        dump.ptr(sk + 0x300, at);
+        dump.bytes(at + 4, &len.to_le_bytes());
+        dump.bytes(at + 8 + SUN_PATH_OFFSET, sun_path);
+    }
+
+    fn symbols() -> SymbolResolver {
+        let mut symbols = socket_symbols();
+        symbols.add_struct("unix_sock", 0x400, &[("addr", 0x300), ("peer", 0x308)]);
+        symbols.add_struct("unix_address", 0x80, &[("len", 4), ("name", 8)]);
+        symbols
+    }
+
+    #[test]
+    fn test_paths_and_peers() {
+        let mut dump = Dump::new(0x4000);
+        // A: bound to a filesystem path and connected to B
+        bind(&mut dump, 0, 0x800, 2 + 14, b"/run/app.sock\0");
+        dump.ptr(0x308, 0x1000);
+        // B: abstract name, owned through struct socket with inode 777
+        bind(&mut dump, 0x1000, 0x1800, 2 + 9, b"\0dbus-abc");
+        dump.ptr(0x1100, 0x1400);
+        dump.word(0x1480, 777);
+        // C (0x2000) is unbound, D has an empty name and E a length past sun_path
+        bind(&mut dump, 0x2800, 0x2a00, 2, b"");
+        bind(&mut dump, 0x3000, 0x3400, 500, &[b'x'; 200]);
+
+        let translator = dump.translator();
+        let symbols = symbols();
+        let layout = UnixLayout::new(&symbols).unwrap();
+        let socks = SockReader::new(&dump.0, &translator, &symbols).unwrap();
+        let path = |sk: u64| layout.path(&dump.0, &translator, DIRECT_MAP + sk);
+
+        assert_eq!(path(0), "/run/app.sock");
+        assert_eq!(path(0x1000), "@dbus-abc");
+        assert_eq!(path(0x2000), "");
+        assert_eq!(path(0x2800), "");
+        assert_eq!(path(0x3000), "x".repeat(UNIX_PATH_MAX));
+
+        let peer = layout.peer(&dump.0, &translator, DIRECT_MAP);
+        assert_eq!(peer, DIRECT_MAP + 0x1000);
+        assert_eq!(socks.inode_number(peer), 777);
+        assert_eq!(layout.peer(&dump.0, &translator, DIRECT_MAP + 0x1000), 0);
+        assert_eq!(socks.inode_number(DIRECT_MAP), 0);
+    }
+
+    #[test]
+    fn test_owner_pids_and_names() {
+        let owner = |pid: i32, fd: u32| SocketOwner {
+            pid,
+            fd,
+            comm: "app".to_string(),
+        };
+        let owners = [owner(300, 4), owner(12, 3), owner(300, 9)];
+        assert_eq!(owner_pids(&owners), vec![12, 300]);
+        assert!(owner_pids(&[]).is_empty());
+
+        assert_eq!(format_unix_state(TCP_ESTABLISHED), "CONNECTED");
+        assert_eq!(format_unix_state(TCP_CLOSE), "UNCONNECTED");
+        assert_eq!(format_unix_state(TCP_LISTEN), "LISTENING");
+        assert_eq!(format_unix_state(2), "UNKNOWN");
+        assert_eq!(format_socket_type(1), "STREAM");
+        assert_eq!(format_socket_type(2), "DGRAM");
+        assert_eq!(format_socket_type(5), "SEQPACKET");
+    }
+}
@@ -0,0 +1,287 @@
+//! YaraScan plugin - matches signature rules over physical, kernel or process memory
+use crate::error::AnalysisError;
+use crate::kernel::mm::ProcessMemory;
+use crate::kernel::modules::ModuleWalker;
+use crate::kernel::process_extractor::ProcessExtractor;
+use crate::kernel::vfs::VfsWalker;
+use crate::kernel::ScanMatchInfo;
+use crate::plugins::plugin_trait::{AnalysisContext, ForensicPlugin, PluginOutput};
+use crate::scan::{RuleSet, ScanHit, ScanScope, UnitScanner};
+use std::path::PathBuf;
+
+// Macro for conditional warning output
+macro_rules! warn {
+    ($($arg:tt)*) => {
+        if std::env::var("LINMEMPARSER_VERBOSE").is_ok() {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+const PAGE_SIZE: u64 = 0x1000;
+
+pub struct YaraScanPlugin {
+    /// Rule file in the supported YARA subset
+    pub rules_path: PathBuf,
+    pub scope: ScanScope,
+    /// Only scan this process (process scope)
+    pub pid: Option<i32>,
+}
+
+impl ForensicPlugin for YaraScanPlugin {
+    fn name(&self) -> &str {
+        "yarascan"
+    }
+
+    fn description(&self) -> &str {
+        "Scan physical, kernel or process memory with YARA-style rules"
+    }
+
+    fn run(&self, context: &AnalysisContext) -> Result<PluginOutput, AnalysisError> {
+        let rules = RuleSet::from_file(&self.rules_path)?;
+        let matches = match self.scope {
+            ScanScope::Physical => scan_physical(context, &rules)?,
+            ScanScope::Kernel => scan_kernel(context, &rules)?,
+            ScanScope::Process => self.scan_processes(context, &rules)?,
+        };
+        Ok(PluginOutput::ScanMatches(matches))
+    }
+}
+
+impl YaraScanPlugin {
+    /// Scan each VMA of the selected processes as its own unit
+    fn scan_processes(
+        &self,
+        context: &AnalysisContext,
+        rules: &RuleSet,
+    ) -> Result<Vec<ScanMatchInfo>, AnalysisError> {
+        let mapped = &context.memory_map.mapped[..];
+        let translator = context.translator;
+        let resolver = context.symbol_resolver;
+        let processes = ProcessExtractor::new().walk_process_list(
+            context.memory_map,
+            translator,
+            resolver,
+            context.init_task_offset as u64,
+        )?;
+        let vfs = VfsWalker::new(mapped, translator, resolver)?;
+
+        let mut matches = Vec::new();
+        for process in &processes {
+            if self.pid.is_some_and(|pid| pid != process.pid) {
+                continue;
+            }
+            // Kernel threads have no address space
+            let memory = match ProcessMemory::for_task(mapped, translator, resolver, process.offset)
+            {
+                Some(m) => m,
+                None => continue,
+            };
+            let vmas = match memory.vmas() {
+                Ok(v) => v,
+                Err(e) => {
+                    warn!("[WARNING] PID {}: VMAs unavailable: {}", process.pid, e);
+                    continue;
+                }
+            };
+
+            for vma in vmas {
+                let mut scanner = UnitScanner::new(rules);
+                for page in (vma.start..vma.end).step_by(PAGE_SIZE as usize) {
+                    if let Some(bytes) = memory.read_page(page) {
+                        scanner.feed(page, &bytes);
+                    }
+                }
+                let hits = scanner.finish();
+                if hits.is_empty() {
+                    continue;
+                }
+
+                let path = if vma.file == 0 {
+                    "[anonymous]".to_string()
+                } else {
+                    vfs.file_path(vma.file)
+                        .unwrap_or_else(|| format!("{:#x}", vma.file))
+                };
+                let vma_context = format!("{:#x}-{:#x} {}", vma.start, vma.end, path);
+                matches.extend(hits.into_iter().map(|hit| {
+                    match_info(
+                        hit,
+                        ScanScope::Process,
+                        Some(process.pid),
+                        &process.comm,
+                        vma_context.clone(),
+                    )
+                }));
+            }
+        }
+
+        Ok(matches)
+    }
+}
+
+/// Scan each LiME region as its own unit, attributing hits through the reverse map
+fn scan_physical(
+    context: &AnalysisContext,
+    rules: &RuleSet,
+) -> Result<Vec<ScanMatchInfo>, AnalysisError> {
+    let mapped = &context.memory_map.mapped[..];
+    let mut hits = Vec::new();
+    for region in context.translator.get_regions() {
+        let start = region.file_offset as usize;
+        let end =
+            (start as u64 + (region.end - region.start) + 1).min(mapped.len() as u64) as usize;
+        if start >= end {
+            continue;
+        }
+        let mut scanner = UnitScanner::new(rules);
+        scanner.feed(region.start, &mapped[start..end]);
+        hits.extend(scanner.finish());
+    }
+    if hits.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let rmap = context.reverse_map()?;
+    Ok(hits
+        .into_iter()
+        .map(|hit| {
+            let owners = rmap.lookup(hit.address);
+            // Every page has a direct-map alias, so only the others say anything
+            let direct_map = context.translator.physical_to_direct_map(hit.address);
+            let kernel: Vec<String> = owners
+                .kernel
+                .iter()
+                .filter(|&&a| Some(a) != direct_map)
+                .map(|a| format!("kernel@{:#x}", a))
+                .collect();
+            // Processes first, then kernel aliases, then just the region
+            let (pid, comm, owner_context) = match owners.processes.first() {
+                Some(first) => (
+                    Some(first.pid),
+                    first.comm.clone(),
+                    owners
+                        .processes
+                        .iter()
+                        .map(|o| format!("{}/{}@{:#x} {}", o.pid, o.comm, o.address, o.path))
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                ),
+                None if !kernel.is_empty() => (None, String::new(), kernel.join(", ")),
+                None => (
+                    None,
+                    String::new(),
+                    owners
+                        .region
+                        .map(|r| format!("region {:#x}-{:#x}", r.start, r.end))
+                        .unwrap_or_default(),
+                ),
+            };
+            match_info(hit, ScanScope::Physical, pid, &comm, owner_context)
+        })
+        .collect())
+}
+
+/// Scan the kernel image and each loaded module as separate units
+fn scan_kernel(
+    context: &AnalysisContext,
+    rules: &RuleSet,
+) -> Result<Vec<ScanMatchInfo>, AnalysisError> {
+    let mapped = &context.memory_map.mapped[..];
+    let translator = context.translator;
+    let resolver = context.symbol_resolver;
+    // vmalloc and module pages translate through the kernel page table
+    let read_page = |page: u64| {
+        let offset = translator.virtual_to_file_offset(page)? as usize;
+        mapped.get(offset..offset + PAGE_SIZE as usize)
+    };
+
+    let symbol = |name: &str| {
+        resolver
+            .get_symbol_address(name)
+            .ok_or_else(|| AnalysisError::SymbolNotFound(name.to_string()))
+    };
+    let text = symbol("_text")?;
+    let end = symbol("_end")?;
+
+    // Nearest kernel symbol at or below a match
+    let mut symbols: Vec<(u64, &str)> = resolver
+        .get_symbols()
+        .iter()
+        .filter(|(_, &address)| address >= text && address < end)
+        .map(|(name, &address)| (address, name.as_str()))
+        .collect();
+    symbols.sort();
+
+    let mut matches = Vec::new();
+    let mut scanner = UnitScanner::new(rules);
+    for page in (text & !(PAGE_SIZE - 1)..end).step_by(PAGE_SIZE as usize) {
+        if let Some(bytes) = read_page(page) {
+            scanner.feed(page, bytes);
+        }
+    }
+    for hit in scanner.finish() {
+        let index = symbols.partition_point(|(a, _)| *a <= hit.address);
+        let symbol_context = match index.checked_sub(1).map(|i| symbols[i]) {
+            Some((start, name)) => format_symbol(name, hit.address - start),
+            None => "kernel".to_string(),
+        };
+        matches.push(match_info(hit, ScanScope::Kernel, None, "", symbol_context));
+    }
+
+    let modules =
+        match ModuleWalker::new(mapped, translator, resolver).and_then(|walker| walker.modules()) {
+            Ok(modules) => modules,
+            Err(e) => {
+                warn!("[WARNING] Module list unavailable: {}", e);
+                Vec::new()
+            }
+        };
+    for module in &modules {
+        let mut scanner = UnitScanner::new(rules);
+        for &(start, end) in &module.ranges {
+            for page in (start & !(PAGE_SIZE - 1)..end).step_by(PAGE_SIZE as usize) {
+                if let Some(bytes) = read_page(page) {
+                    scanner.feed(page, bytes);
+                }
+            }
+        }
+        for hit in scanner.finish() {
+            let symbol_context = match module.symbol_for(hit.address) {
+                Some((name, offset)) => format!("{}: {}", module.name, format_symbol(name, offset)),
+                None => module.name.clone(),
+            };
+            matches.push(match_info(hit, ScanScope::Kernel, None, "", symbol_context));
+        }
+    }
+
+    Ok(matches)
+}
+
+fn match_info(
+    hit: ScanHit,
+    scope: ScanScope,
+    pid: Option<i32>,
+    comm: &str,
+    context: String,
+) -> ScanMatchInfo {
+    ScanMatchInfo {
+        rule: hit.rule,
+        string: hit.string,
+        space: scope.to_string(),
+        address: hit.address,
+        pid,
+        comm: comm.to_string(),
+        context,
+        data: hit.data.iter().map(|b| format!("{:02x}", b)).collect(),
+    }
+}
+
+/// Format a symbol with an optional offset
+fn format_symbol(name: &str, offset: u64) -> String {
+    if offset == 0 {
+        name.to_string()
+    } else {
+        format!("{}+{:#x}", name, offset)
+    }
+}
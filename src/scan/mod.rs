@@ -0,0 +1,1196 @@
+//! Signature scanning: a pure-Rust subset of YARA rules matched over memory
+//!
+//! Supported syntax:
+//! - `rule name [: tags] { meta: ... strings: ... condition: ... }` with `//` and `/* */` comments
+//! - Text strings `"..."` with `\" \\ \n \r \t \0 \xHH` escapes and the `ascii`, `wide`
+//!   and `nocase` modifiers
+//! - Hex strings `{ 4D 5A ?? 9? [2-4] ( 0F | 1F ) }`
+//! - Regular expressions `/.../` with the `i` and `s` flags or the `nocase` modifier
+//! - Conditions built from `$name`, `any|all|N of them`, `any|all|N of ($a, $b*)`, `and`,
+//!   `or`, `not`, `true`, `false` and parentheses
+//! - Comparisons of match counts `#a > 2` and match addresses `@a == 0x1000`, `@a[2] < 64`
+//!   against integers (`==`, `!=`, `<`, `<=`, `>`, `>=`)
+//!
+//! Conditions are evaluated once per scan unit (a LiME region, the kernel image, a
+//! module or a VMA), and only rules with at least one matching string are reported.
+use crate::error::AnalysisError;
+use memchr::memmem;
+use regex::bytes::{Regex, RegexBuilder};
+use std::fmt;
+
+// Bytes scanned per pass, and bytes carried into the next pass so matches
+// spanning a chunk boundary are still found
+const CHUNK_SIZE: usize = 1 << 20;
+const CHUNK_OVERLAP: usize = 0x1000;
+// Safety limits
+const MAX_MATCHES_PER_STRING: usize = 256;
+const MAX_MATCH_PREVIEW: usize = 64;
+
+/// Address space a scan runs over
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScanScope {
+    Physical, // Every LiME region, by physical address
+    Kernel,   // The kernel image and loaded modules, by virtual address
+    Process,  // Process VMAs, by user virtual address
+}
+
+impl fmt::Display for ScanScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScanScope::Physical => write!(f, "physical"),
+            ScanScope::Kernel => write!(f, "kernel"),
+            ScanScope::Process => write!(f, "process"),
+        }
+    }
+}
+
+/// Compiled form of one rule string
+enum Matcher {
+    Literal(Box<memmem::Finder<'static>>),
+    Regex(Regex),
+}
+
+impl Matcher {
+    /// Collect the (start, end) of each match in a buffer
+    fn find_all(&self, haystack: &[u8]) -> Vec<(usize, usize)> {
+        match self {
+            Matcher::Literal(finder) => {
+                let length = finder.needle().len();
+                finder
+                    .find_iter(haystack)
+                    .take(MAX_MATCHES_PER_STRING)
+                    .map(|start| (start, start + length))
+                    .collect()
+            }
+            Matcher::Regex(regex) => regex
+                .find_iter(haystack)
+                .take(MAX_MATCHES_PER_STRING)
+                .map(|m| (m.start(), m.end()))
+                .collect(),
+        }
+    }
+}
+
+struct RuleString {
+    name: String, // Identifier including the `$`
+    matcher: Matcher,
+}
+
+#[derive(Debug)]
+enum Quantifier {
+    Any,
+    All,
+    Count(usize),
+}
+
+/// Value a comparison reads from a string's matches
+#[derive(Debug)]
+enum Operand {
+    Count(usize),         // #a
+    Offset(usize, usize), // @a[n] (1-based)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Comparison {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl Comparison {
+    fn holds(self, left: u64, right: u64) -> bool {
+        match self {
+            Comparison::Eq => left == right,
+            Comparison::Ne => left != right,
+            Comparison::Lt => left < right,
+            Comparison::Le => left <= right,
+            Comparison::Gt => left > right,
+            Comparison::Ge => left >= right,
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Condition {
+    Bool(bool),
+    String(usize),
+    Of(Quantifier, Vec<usize>),
+    Compare(Operand, Comparison, u64),
+    Not(Box<Condition>),
+    And(Box<Condition>, Box<Condition>),
+    Or(Box<Condition>, Box<Condition>),
+}
+
+impl Condition {
+    /// Evaluate against the matches of each string
+    fn eval(&self, matches: &[Vec<StringMatch>]) -> bool {
+        match self {
+            Condition::Bool(value) => *value,
+            Condition::String(i) => !matches[*i].is_empty(),
+            Condition::Of(quantifier, strings) => {
+                let matched = strings.iter().filter(|&&i| !matches[i].is_empty()).count();
+                match quantifier {
+                    Quantifier::Any => matched > 0,
+                    Quantifier::All => matched == strings.len(),
+                    Quantifier::Count(n) => matched >= *n,
+                }
+            }
+            Condition::Compare(operand, comparison, value) => {
+                let left = match operand {
+                    Operand::Count(i) => Some(matches[*i].len() as u64),
+                    Operand::Offset(i, n) => matches[*i].get(n - 1).map(|(address, _)| *address),
+                };
+                // An offset of a match that does not exist makes the comparison false
+                left.is_some_and(|left| comparison.holds(left, *value))
+            }
+            Condition::Not(inner) => !inner.eval(matches),
+            Condition::And(a, b) => a.eval(matches) && b.eval(matches),
+            Condition::Or(a, b) => a.eval(matches) || b.eval(matches),
+        }
+    }
+}
+
+struct Rule {
+    name: String,
+    strings: Vec<RuleString>,
+    condition: Condition,
+}
+
+/// A compiled set of rules
+pub struct RuleSet {
+    rules: Vec<Rule>,
+}
+
+impl RuleSet {
+    /// Parse and compile rule source text
+    pub fn parse(source: &str) -> Result<Self, AnalysisError> {
+        let mut parser = RuleParser {
+            chars: source.chars().collect(),
+            pos: 0,
+        };
+        let mut rules = Vec::new();
+        loop {
+            parser.skip_whitespace();
+            if parser.at_end() {
+                break;
+            }
+            rules.push(parser.parse_rule()?);
+        }
+        if rules.is_empty() {
+            return Err(AnalysisError::ParseError(
+                "no rules found in rule source".to_string(),
+            ));
+        }
+        Ok(RuleSet { rules })
+    }
+
+    /// Read and compile a rule file
+    pub fn from_file(path: &std::path::Path) -> Result<Self, AnalysisError> {
+        Self::parse(&std::fs::read_to_string(path)?)
+    }
+}
+
+/// A string match from a rule whose condition held
+#[derive(Debug, Clone)]
+pub struct ScanHit {
+    pub rule: String,
+    pub string: String,
+    pub address: u64,
+    pub data: Vec<u8>, // Matched bytes, truncated to a short preview
+}
+
+/// Address and preview bytes of one string match
+type StringMatch = (u64, Vec<u8>);
+
+/// Scans one unit of memory fed as a series of (address, bytes) pieces
+///
+/// Adjacent pieces are joined so matches spanning page or chunk boundaries are
+/// found; a gap in the addresses starts a new run.
+pub struct UnitScanner<'r> {
+    rules: &'r RuleSet,
+    matches: Vec<Vec<Vec<StringMatch>>>, // [rule][string] -> matches
+    pending: Vec<u8>,
+    pending_address: u64,
+    scanned: usize, // Leading bytes of `pending` already scanned
+}
+
+impl<'r> UnitScanner<'r> {
+    pub fn new(rules: &'r RuleSet) -> Self {
+        UnitScanner {
+            rules,
+            matches: rules
+                .rules
+                .iter()
+                .map(|r| vec![Vec::new(); r.strings.len()])
+                .collect(),
+            pending: Vec::with_capacity(CHUNK_SIZE),
+            pending_address: 0,
+            scanned: 0,
+        }
+    }
+
+    /// Add bytes that start at an address
+    pub fn feed(&mut self, address: u64, mut data: &[u8]) {
+        if self.pending_address + self.pending.len() as u64 != address {
+            self.flush();
+            self.pending.clear();
+            self.scanned = 0;
+        }
+        if self.pending.is_empty() {
+            self.pending_address = address;
+        }
+
+        while !data.is_empty() {
+            let take = (CHUNK_SIZE - self.pending.len()).min(data.len());
+            self.pending.extend_from_slice(&data[..take]);
+            data = &data[take..];
+            if self.pending.len() >= CHUNK_SIZE {
+                self.scan_pending();
+                let drop = self.pending.len() - CHUNK_OVERLAP;
+                self.pending.drain(..drop);
+                self.pending_address += drop as u64;
+                self.scanned = self.pending.len();
+            }
+        }
+    }
+
+    /// Evaluate every rule over what was fed and return the matches of the rules that hit
+    pub fn finish(mut self) -> Vec<ScanHit> {
+        self.flush();
+
+        let mut hits = Vec::new();
+        for (rule, matches) in self.rules.rules.iter().zip(self.matches) {
+            if matches.iter().all(Vec::is_empty) || !rule.condition.eval(&matches) {
+                continue;
+            }
+            for (string, found) in rule.strings.iter().zip(matches) {
+                hits.extend(found.into_iter().map(|(address, data)| ScanHit {
+                    rule: rule.name.clone(),
+                    string: string.name.clone(),
+                    address,
+                    data,
+                }));
+            }
+        }
+        hits.sort_by_key(|h| h.address);
+        hits
+    }
+
+    fn flush(&mut self) {
+        if self.pending.len() > self.scanned {
+            self.scan_pending();
+        }
+    }
+
+    fn scan_pending(&mut self) {
+        for (rule, matches) in self.rules.rules.iter().zip(self.matches.iter_mut()) {
+            for (string, found) in rule.strings.iter().zip(matches.iter_mut()) {
+                for (start, end) in string.matcher.find_all(&self.pending) {
+                    if found.len() >= MAX_MATCHES_PER_STRING {
+                        break;
+                    }
+                    // Matches inside the carried overlap were reported by the previous pass
+                    let address = self.pending_address + start as u64;
+                    if found.last().is_some_and(|(a, _)| *a >= address) {
+                        continue;
+                    }
+                    let end = end.min(start + MAX_MATCH_PREVIEW);
+                    found.push((address, self.pending[start..end].to_vec()));
+                }
+            }
+        }
+    }
+}
+
+/// Recursive-descent parser over the rule source
+struct RuleParser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl RuleParser {
+    fn error(&self, message: &str) -> AnalysisError {
+        let line = self.chars[..self.pos.min(self.chars.len())]
+            .iter()
+            .filter(|&&c| c == '\n')
+            .count()
+            + 1;
+        AnalysisError::ParseError(format!("rule line {}: {}", line, message))
+    }
+
+    fn at_end(&self) -> bool {
+        self.pos >= self.chars.len()
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.pos + offset).copied()
+    }
+
+    /// Skip whitespace and comments
+    fn skip_whitespace(&mut self) {
+        loop {
+            match (self.peek(), self.peek_at(1)) {
+                (Some(c), _) if c.is_whitespace() => self.pos += 1,
+                (Some('/'), Some('/')) => {
+                    while self.peek().is_some_and(|c| c != '\n') {
+                        self.pos += 1;
+                    }
+                }
+                (Some('/'), Some('*')) => {
+                    self.pos += 2;
+                    while !self.at_end()
+                        && !(self.peek() == Some('*') && self.peek_at(1) == Some('/'))
+                    {
+                        self.pos += 1;
+                    }
+                    self.pos = (self.pos + 2).min(self.chars.len());
+                }
+                _ => break,
+            }
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), AnalysisError> {
+        self.skip_whitespace();
+        if self.peek() == Some(expected) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected '{}'", expected)))
+        }
+    }
+
+    /// Read an identifier ([A-Za-z0-9_]+), or an empty string if none follows
+    fn identifier(&mut self) -> String {
+        self.skip_whitespace();
+        let start = self.pos;
+        while self
+            .peek()
+            .is_some_and(|c| c.is_ascii_alphanumeric() || c == '_')
+        {
+            self.pos += 1;
+        }
+        self.chars[start..self.pos].iter().collect()
+    }
+
+    /// Check for `<identifier>:` without consuming it
+    fn at_section(&mut self) -> bool {
+        self.skip_whitespace();
+        let start = self.pos;
+        let name = self.identifier();
+        self.skip_whitespace();
+        let found = !name.is_empty() && self.peek() == Some(':');
+        self.pos = start;
+        found
+    }
+
+    fn parse_rule(&mut self) -> Result<Rule, AnalysisError> {
+        let mut keyword = self.identifier();
+        while keyword == "private" || keyword == "global" {
+            keyword = self.identifier();
+        }
+        if keyword != "rule" {
+            return Err(self.error("expected 'rule'"));
+        }
+        let name = self.identifier();
+        if name.is_empty() {
+            return Err(self.error("expected rule name"));
+        }
+        self.skip_whitespace();
+        if self.peek() == Some(':') {
+            // Tags
+            self.pos += 1;
+            while !self.identifier().is_empty() {}
+        }
+        self.expect('{')?;
+
+        let mut strings = Vec::new();
+        let mut condition = None;
+        loop {
+            self.skip_whitespace();
+            if self.peek() == Some('}') {
+                self.pos += 1;
+                break;
+            }
+            let section = self.identifier();
+            self.expect(':')?;
+            match section.as_str() {
+                "meta" => self.parse_meta()?,
+                "strings" => strings = self.parse_strings()?,
+                "condition" => condition = Some(self.parse_or(&strings)?),
+                _ => return Err(self.error(&format!("unknown section '{}'", section))),
+            }
+        }
+
+        Ok(Rule {
+            condition: condition
+                .ok_or_else(|| self.error(&format!("rule '{}' has no condition", name)))?,
+            name,
+            strings,
+        })
+    }
+
+    /// Skip `key = value` meta entries
+    fn parse_meta(&mut self) -> Result<(), AnalysisError> {
+        while !self.at_section() {
+            self.skip_whitespace();
+            if self.peek() == Some('}') {
+                break;
+            }
+            if self.identifier().is_empty() {
+                return Err(self.error("expected meta key"));
+            }
+            self.expect('=')?;
+            self.skip_whitespace();
+            match self.peek() {
+                Some('"') => {
+                    self.text_literal()?;
+                }
+                Some('-') => {
+                    self.pos += 1;
+                    self.identifier();
+                }
+                _ => {
+                    if self.identifier().is_empty() {
+                        return Err(self.error("expected meta value"));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn parse_strings(&mut self) -> Result<Vec<RuleString>, AnalysisError> {
+        let mut strings = Vec::new();
+        loop {
+            self.skip_whitespace();
+            if self.peek() != Some('$') {
+                break;
+            }
+            self.pos += 1;
+            let name = format!("${}", self.identifier());
+            if name != "$" && strings.iter().any(|s: &RuleString| s.name == name) {
+                return Err(self.error(&format!("duplicate string '{}'", name)));
+            }
+            self.expect('=')?;
+            self.skip_whitespace();
+            let matcher = match self.peek() {
+                Some('"') => {
+                    let text = self.text_literal()?;
+                    let modifiers = self.modifiers(&["ascii", "wide", "nocase"])?;
+                    compile_text(&text, &modifiers)
+                        .ok_or_else(|| self.error(&format!("empty string '{}'", name)))?
+                }
+                Some('{') => {
+                    let pattern = self.hex_pattern()?;
+                    self.modifiers(&[])?;
+                    pattern
+                }
+                Some('/') => {
+                    let (pattern, mut nocase, dotall) = self.regex_literal()?;
+                    nocase |= self.modifiers(&["nocase"])?.iter().any(|m| m == "nocase");
+                    Matcher::Regex(
+                        RegexBuilder::new(&pattern)
+                            .unicode(false)
+                            .case_insensitive(nocase)
+                            .dot_matches_new_line(dotall)
+                            .build()?,
+                    )
+                }
+                _ => return Err(self.error(&format!("expected value for '{}'", name))),
+            };
+            strings.push(RuleString { name, matcher });
+        }
+        Ok(strings)
+    }
+
+    /// Read the modifiers following a string, rejecting any not in `allowed`
+    fn modifiers(&mut self, allowed: &[&str]) -> Result<Vec<String>, AnalysisError> {
+        let mut modifiers = Vec::new();
+        loop {
+            if self.at_section() {
+                break;
+            }
+            let start = self.pos;
+            let modifier = self.identifier();
+            if modifier.is_empty() {
+                self.pos = start;
+                break;
+            }
+            if !allowed.contains(&modifier.as_str()) {
+                return Err(self.error(&format!("unsupported string modifier '{}'", modifier)));
+            }
+            modifiers.push(modifier);
+        }
+        Ok(modifiers)
+    }
+
+    /// Read a double-quoted string with escapes
+    fn text_literal(&mut self) -> Result<Vec<u8>, AnalysisError> {
+        self.expect('"')?;
+        let mut bytes = Vec::new();
+        loop {
+            let c = self
+                .peek()
+                .ok_or_else(|| self.error("unterminated string"))?;
+            self.pos += 1;
+            match c {
+                '"' => break,
+                '\\' => {
+                    let escape = self.peek().ok_or_else(|| self.error("bad escape"))?;
+                    self.pos += 1;
+                    match escape {
+                        'n' => bytes.push(b'\n'),
+                        'r' => bytes.push(b'\r'),
+                        't' => bytes.push(b'\t'),
+                        '0' => bytes.push(0),
+                        'x' => {
+                            let hex: String = self
+                                .chars
+                                .get(self.pos..self.pos + 2)
+                                .map_or(String::new(), |h| h.iter().collect());
+                            let byte = u8::from_str_radix(&hex, 16)
+                                .map_err(|_| self.error("bad \\x escape"))?;
+                            bytes.push(byte);
+                            self.pos += 2;
+                        }
+                        other => {
+                            let mut buf = [0u8; 4];
+                            bytes.extend_from_slice(other.encode_utf8(&mut buf).as_bytes());
+                        }
+                    }
+                }
+                other => {
+                    let mut buf = [0u8; 4];
+                    bytes.extend_from_slice(other.encode_utf8(&mut buf).as_bytes());
+                }
+            }
+        }
+        Ok(bytes)
+    }
+
+    /// Read `{ ... }` into a literal finder, or a regex when it has wildcards,
+    /// jumps or alternatives
+    fn hex_pattern(&mut self) -> Result<Matcher, AnalysisError> {
+        self.expect('{')?;
+        let mut regex = String::from("(?s)");
+        let mut literal = Some(Vec::new());
+        loop {
+            self.skip_whitespace();
+            let c = self
+                .peek()
+                .ok_or_else(|| self.error("unterminated hex string"))?;
+            match c {
+                '}' => {
+                    self.pos += 1;
+                    break;
+                }
+                '[' => {
+                    self.pos += 1;
+                    let start = self.pos;
+                    while self.peek().is_some_and(|c| c != ']') {
+                        self.pos += 1;
+                    }
+                    let jump: String = self.chars[start..self.pos]
+                        .iter()
+                        .filter(|c| !c.is_whitespace())
+                        .collect();
+                    self.expect(']')?;
+                    regex.push_str(&jump_regex(&jump).ok_or_else(|| self.error("bad jump"))?);
+                    literal = None;
+                }
+                '(' | '|' | ')' => {
+                    self.pos += 1;
+                    regex.push_str(match c {
+                        '(' => "(?:",
+                        '|' => "|",
+                        _ => ")",
+                    });
+                    literal = None;
+                }
+                _ => {
+                    let high = c;
+                    let low = self
+                        .peek_at(1)
+                        .ok_or_else(|| self.error("unterminated hex string"))?;
+                    self.pos += 2;
+                    let nibble = |n: char| n.to_digit(16).map(|d| d as u8);
+                    match (high, nibble(high), low, nibble(low)) {
+                        (_, Some(h), _, Some(l)) => {
+                            let byte = h << 4 | l;
+                            regex.push_str(&format!("\\x{:02x}", byte));
+                            if let Some(bytes) = literal.as_mut() {
+                                bytes.push(byte);
+                            }
+                        }
+                        ('?', _, '?', _) => {
+                            regex.push('.');
+                            literal = None;
+                        }
+                        (_, Some(h), '?', _) => {
+                            regex.push_str(&format!("[\\x{:02x}-\\x{:02x}]", h << 4, h << 4 | 0xf));
+                            literal = None;
+                        }
+                        ('?', _, _, Some(l)) => {
+                            regex.push('[');
+                            for h in 0..16u8 {
+                                regex.push_str(&format!("\\x{:02x}", h << 4 | l));
+                            }
+                            regex.push(']');
+                            literal = None;
+                        }
+                        _ => return Err(self.error("bad hex byte")),
+                    }
+                }
+            }
+        }
+
+        match literal {
+            Some(bytes) if !bytes.is_empty() => Ok(Matcher::Literal(Box::new(
+                memmem::Finder::new(&bytes).into_owned(),
+            ))),
+            Some(_) => Err(self.error("empty hex string")),
+            None => Ok(Matcher::Regex(
+                RegexBuilder::new(&regex).unicode(false).build()?,
+            )),
+        }
+    }
+
+    /// Read `/pattern/flags`, returning (pattern, case-insensitive, dot-all)
+    fn regex_literal(&mut self) -> Result<(String, bool, bool), AnalysisError> {
+        self.expect('/')?;
+        let mut pattern = String::new();
+        loop {
+            let c = self
+                .peek()
+                .ok_or_else(|| self.error("unterminated regular expression"))?;
+            self.pos += 1;
+            match c {
+                '/' => break,
+                '\\' if self.peek() == Some('/') => {
+                    pattern.push('/');
+                    self.pos += 1;
+                }
+                '\\' => {
+                    pattern.push('\\');
+                    if let Some(next) = self.peek() {
+                        pattern.push(next);
+                        self.pos += 1;
+                    }
+                }
+                other => pattern.push(other),
+            }
+        }
+        if pattern.is_empty() {
+            return Err(self.error("empty regular expression"));
+        }
+        let (mut nocase, mut dotall) = (false, false);
+        while let Some(flag) = self.peek() {
+            match flag {
+                'i' => nocase = true,
+                's' => dotall = true,
+                _ => break,
+            }
+            self.pos += 1;
+        }
+        Ok((pattern, nocase, dotall))
+    }
+
+    fn parse_or(&mut self, strings: &[RuleString]) -> Result<Condition, AnalysisError> {
+        let mut left = self.parse_and(strings)?;
+        while self.keyword("or") {
+            left = Condition::Or(Box::new(left), Box::new(self.parse_and(strings)?));
+        }
+        Ok(left)
+    }
+
+    fn parse_and(&mut self, strings: &[RuleString]) -> Result<Condition, AnalysisError> {
+        let mut left = self.parse_unary(strings)?;
+        while self.keyword("and") {
+            left = Condition::And(Box::new(left), Box::new(self.parse_unary(strings)?));
+        }
+        Ok(left)
+    }
+
+    fn parse_unary(&mut self, strings: &[RuleString]) -> Result<Condition, AnalysisError> {
+        if self.keyword("not") {
+            return Ok(Condition::Not(Box::new(self.parse_unary(strings)?)));
+        }
+        self.skip_whitespace();
+        match self.peek() {
+            Some('(') => {
+                self.pos += 1;
+                let inner = self.parse_or(strings)?;
+                self.expect(')')?;
+                Ok(inner)
+            }
+            Some('$') => {
+                self.pos += 1;
+                self.string_index(strings, '$').map(Condition::String)
+            }
+            Some('#') => {
+                self.pos += 1;
+                let operand = Operand::Count(self.string_index(strings, '#')?);
+                self.comparison(operand)
+            }
+            Some('@') => {
+                self.pos += 1;
+                let string = self.string_index(strings, '@')?;
+                let mut n = 1;
+                if self.peek() == Some('[') {
+                    self.pos += 1;
+                    n = self
+                        .integer()?
+                        .try_into()
+                        .ok()
+                        .filter(|&n| n > 0)
+                        .ok_or_else(|| self.error("match index must be at least 1"))?;
+                    self.expect(']')?;
+                }
+                self.comparison(Operand::Offset(string, n))
+            }
+            _ => {
+                let word = self.identifier();
+                let quantifier = match word.as_str() {
+                    "true" => return Ok(Condition::Bool(true)),
+                    "false" => return Ok(Condition::Bool(false)),
+                    "any" => Quantifier::Any,
+                    "all" => Quantifier::All,
+                    _ => Quantifier::Count(
+                        word.parse()
+                            .map_err(|_| self.error(&format!("unexpected '{}'", word)))?,
+                    ),
+                };
+                if !self.keyword("of") {
+                    return Err(self.error("expected 'of'"));
+                }
+                Ok(Condition::Of(quantifier, self.string_set(strings)?))
+            }
+        }
+    }
+
+    /// Resolve the name after a `$`, `#` or `@` sigil to a string index
+    fn string_index(
+        &mut self,
+        strings: &[RuleString],
+        sigil: char,
+    ) -> Result<usize, AnalysisError> {
+        let name = format!("${}", self.identifier());
+        strings
+            .iter()
+            .position(|s| s.name == name && name != "$")
+            .ok_or_else(|| self.error(&format!("undefined string '{}{}'", sigil, &name[1..])))
+    }
+
+    /// Read a comparison operator and an integer after a `#a` or `@a` operand
+    fn comparison(&mut self, operand: Operand) -> Result<Condition, AnalysisError> {
+        self.skip_whitespace();
+        let operator = match (self.peek(), self.peek_at(1)) {
+            (Some('='), Some('=')) => (Comparison::Eq, 2),
+            (Some('!'), Some('=')) => (Comparison::Ne, 2),
+            (Some('<'), Some('=')) => (Comparison::Le, 2),
+            (Some('>'), Some('=')) => (Comparison::Ge, 2),
+            (Some('<'), _) => (Comparison::Lt, 1),
+            (Some('>'), _) => (Comparison::Gt, 1),
+            _ => return Err(self.error("expected comparison operator")),
+        };
+        self.pos += operator.1;
+        Ok(Condition::Compare(operand, operator.0, self.integer()?))
+    }
+
+    /// Read a decimal or `0x` hexadecimal integer
+    fn integer(&mut self) -> Result<u64, AnalysisError> {
+        let word = self.identifier();
+        let value = match word.strip_prefix("0x") {
+            Some(hex) => u64::from_str_radix(hex, 16),
+            None => word.parse(),
+        };
+        value.map_err(|_| self.error(&format!("expected integer, found '{}'", word)))
+    }
+
+    /// Read `them` or `($a, $b*)` into string indices
+    fn string_set(&mut self, strings: &[RuleString]) -> Result<Vec<usize>, AnalysisError> {
+        if self.keyword("them") {
+            return Ok((0..strings.len()).collect());
+        }
+        self.expect('(')?;
+        let mut set = Vec::new();
+        loop {
+            self.expect('$')?;
+            let name = format!("${}", self.identifier());
+            let wildcard = self.peek() == Some('*');
+            if wildcard {
+                self.pos += 1;
+            }
+            let before = set.len();
+            set.extend(
+                strings
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, s)| {
+                        if wildcard {
+                            s.name.starts_with(&name)
+                        } else {
+                            s.name == name
+                        }
+                    })
+                    .map(|(i, _)| i),
+            );
+            if set.len() == before {
+                return Err(self.error(&format!("undefined string '{}'", name)));
+            }
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => self.pos += 1,
+                _ => break,
+            }
+        }
+        self.expect(')')?;
+        set.sort_unstable();
+        set.dedup();
+        Ok(set)
+    }
+
+    /// Consume a keyword if it comes next
+    fn keyword(&mut self, word: &str) -> bool {
+        let start = self.pos;
+        if self.identifier() == word {
+            true
+        } else {
+            self.pos = start;
+            false
+        }
+    }
+}
+
+/// Compile a text string with its ascii/wide/nocase modifiers
+fn compile_text(text: &[u8], modifiers: &[String]) -> Option<Matcher> {
+    if text.is_empty() {
+        return None;
+    }
+    let has = |m: &str| modifiers.iter().any(|x| x == m);
+    let wide = has("wide");
+    let ascii = has("ascii") || !wide;
+
+    let mut variants = Vec::new();
+    if ascii {
+        variants.push(text.to_vec());
+    }
+    if wide {
+        variants.push(text.iter().flat_map(|&b| [b, 0]).collect());
+    }
+
+    if !has("nocase") && variants.len() == 1 {
+        return Some(Matcher::Literal(Box::new(
+            memmem::Finder::new(&variants[0]).into_owned(),
+        )));
+    }
+    let pattern = variants
+        .iter()
+        .map(|bytes| {
+            bytes
+                .iter()
+                .map(|&b| {
+                    if has("nocase") && b.is_ascii_alphabetic() {
+                        format!(
+                            "[{}{}]",
+                            b.to_ascii_lowercase() as char,
+                            b.to_ascii_uppercase() as char
+                        )
+                    } else {
+                        format!("\\x{:02x}", b)
+                    }
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("|");
+    RegexBuilder::new(&pattern)
+        .unicode(false)
+        .build()
+        .ok()
+        .map(Matcher::Regex)
+}
+
+/// Translate a hex-string jump (`4`, `2-4`, `2-`, `-`) into a regex repetition
+fn jump_regex(jump: &str) -> Option<String> {
+    let bound = |s: &str| -> Option<Option<u32>> {
+        if s.is_empty() {
+            Some(None)
+        } else {
+            s.parse().ok().map(Some)
+        }
+    };
+    match jump.split_once('-') {
+        None => Some(format!(".{{{}}}", jump.parse::<u32>().ok()?)),
+        Some((low, high)) => match (bound(low)?, bound(high)?) {
+            (low, Some(high)) if low.unwrap_or(0) <= high => {
+                Some(format!(".{{{},{}}}", low.unwrap_or(0), high))
+            }
+            (low, None) => Some(format!(".{{{},}}", low.unwrap_or(0))),
+            _ => None,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Scan one buffer at address 0 and return the (string, address) of each hit
+    fn scan(source: &str, data: &[u8]) -> Vec<(String, u64)> {
+        let rules = RuleSet::parse(source).unwrap();
+        let mut scanner = UnitScanner::new(&rules);
+        scanner.feed(0, data);
+        scanner
+            .finish()
+            .into_iter()
+            .map(|h| (h.string, h.address))
+            .collect()
+    }
+
+    /// Check whether a rule with one string `$a` hits a buffer
+    fn hits(string: &str, data: &[u8]) -> bool {
+        !scan(
+            &format!("rule t {{ strings: $a = {} condition: $a }}", string),
+            data,
+        )
+        .is_empty()
+    }
+
+    #[test]
+    fn test_hex_strings() {
+        assert!(hits("{ DE AD be ef }", b"xx\xde\xad\xbe\xefxx"));
+        assert!(!hits("{ DE AD be ef }", b"\xde\xad\xbe\xee"));
+        // Full and nibble wildcards
+        assert!(hits("{ 4D 5A ?? 9? }", b"MZ\x00\x9f"));
+        assert!(!hits("{ 4D 5A ?? 9? }", b"MZ\x00\xa0"));
+        assert!(hits("{ 4D ?A }", b"M\x7a"));
+        assert!(!hits("{ 4D ?A }", b"M\x7b"));
+        // Fixed, ranged and open jumps
+        assert!(hits("{ 01 [2] 02 }", b"\x01ab\x02"));
+        assert!(!hits("{ 01 [2] 02 }", b"\x01abc\x02"));
+        assert!(hits("{ 01 [1-3] 02 }", b"\x01abc\x02"));
+        assert!(!hits("{ 01 [1-3] 02 }", b"\x01abcd\x02"));
+        assert!(hits("{ 01 [2-] 02 }", b"\x01abcdefgh\x02"));
+        assert!(hits("{ 01 [-] 02 }", b"\x01\x02"));
+        // Alternatives
+        assert!(hits("{ 0F ( 05 | 34 ) }", b"\x0f\x34"));
+        assert!(!hits("{ 0F ( 05 | 34 ) }", b"\x0f\x0b"));
+
+        for bad in [
+            "{ 4 }",
+            "{ 4G }",
+            "{ }",
+            "{ 01 [4-2] 02 }",
+            "{ 01 [x] 02 }",
+            "{ 01",
+        ] {
+            let source = format!("rule t {{ strings: $a = {} condition: $a }}", bad);
+            assert!(RuleSet::parse(&source).is_err(), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn test_text_modifiers() {
+        assert!(hits(r#""Evil""#, b"an Evil string"));
+        assert!(!hits(r#""Evil""#, b"an evil string"));
+        assert!(hits(r#""Evil" nocase"#, b"an eVIL string"));
+        assert!(hits(r#""Evil" wide"#, b"E\0v\0i\0l\0"));
+        assert!(!hits(r#""Evil" wide"#, b"Evil"));
+        assert!(hits(r#""Evil" wide ascii"#, b"Evil"));
+        assert!(hits(r#""Evil" wide nocase"#, b"e\0V\0I\0l\0"));
+        assert!(hits(r#""tab\there\x41\0""#, b"tab\there\x41\x00"));
+        assert!(hits(r#""say \"hi\"""#, b"say \"hi\""));
+    }
+
+    #[test]
+    fn test_regex_strings() {
+        assert!(hits(r"/ab+c/", b"xabbbcx"));
+        assert!(!hits(r"/ab+c/", b"xABBCx"));
+        assert!(hits(r"/ab+c/i", b"xABBCx"));
+        assert!(hits(r"/ab+c/ nocase", b"xABBCx"));
+        // `.` only crosses a newline with the s flag
+        assert!(!hits(r"/a.b/", b"a\nb"));
+        assert!(hits(r"/a.b/s", b"a\nb"));
+        assert!(hits(r"/\/bin\/sh/", b"exec /bin/sh -i"));
+        assert!(hits(r"/\x7fELF/", b"\x7fELF"));
+    }
+
+    #[test]
+    fn test_of_quantifiers() {
+        let source = |condition: &str| {
+            format!(
+                r#"rule t {{ strings: $a1 = "one" $a2 = "two" $b = "three" condition: {} }}"#,
+                condition
+            )
+        };
+        let holds = |condition: &str, data: &[u8]| !scan(&source(condition), data).is_empty();
+
+        assert!(holds("any of them", b"two"));
+        assert!(!holds("all of them", b"one two"));
+        assert!(holds("all of them", b"one two three"));
+        assert!(holds("2 of them", b"one three"));
+        assert!(!holds("2 of them", b"three"));
+        assert!(holds("all of ($a*)", b"one two"));
+        assert!(!holds("all of ($a*)", b"one three"));
+        assert!(holds("any of ($a1, $b)", b"three"));
+        assert!(holds("$b and not $a1", b"three two"));
+        assert!(!holds("$b and not ($a1 or $a2)", b"three two"));
+        assert!(holds("false or 1 of ($a2)", b"two"));
+        assert!(RuleSet::parse(&source("any of ($c*)")).is_err());
+        assert!(RuleSet::parse(&source("2 $a1")).is_err());
+    }
+
+    #[test]
+    fn test_count_and_offset_conditions() {
+        let source = |condition: &str| {
+            format!(
+                r#"rule t {{ strings: $a = "ab" $b = "zz" condition: {} }}"#,
+                condition
+            )
+        };
+        let data = b"ab..ab..ab..zz";
+        let holds = |condition: &str| !scan(&source(condition), data).is_empty();
+
+        assert!(holds("#a == 3"));
+        assert!(holds("#a > 2 and #b < 2"));
+        assert!(!holds("#a >= 4"));
+        assert!(holds("#a != 0 and #b <= 1"));
+        assert!(holds("@a == 0"));
+        assert!(holds("@a[2] == 4 and @a[3] == 0x8"));
+        // An offset past the last match is never equal to anything
+        assert!(!holds("@a[4] == 0"));
+        assert!(holds("not (@a[4] == 0) and $b"));
+
+        for bad in [
+            "#a",
+            "#a = 1",
+            "#c > 0",
+            "@b > @a",
+            "@a[0] == 0",
+            "@a[1 == 0",
+            "#a > x",
+        ] {
+            assert!(RuleSet::parse(&source(bad)).is_err(), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn test_malformed_rules() {
+        let errors = [
+            "",
+            "// only a comment",
+            "rule { condition: true }",
+            "rule t { strings: $a = \"x\" }",
+            "rule t { strings: $a = \"x\" $a = \"y\" condition: $a }",
+            "rule t { strings: $a = \"x condition: $a }",
+            "rule t { strings: $a = \"\" condition: $a }",
+            "rule t { strings: $a = // condition: $a }",
+            "rule t { strings: $a = /(/ condition: $a }",
+            "rule t { imports: condition: true }",
+            "rule t { condition: $a }",
+            "rule t { condition: true",
+            "import \"pe\" rule t { condition: true }",
+        ];
+        for source in errors {
+            assert!(RuleSet::parse(source).is_err(), "{:?}", source);
+        }
+        match RuleSet::parse("rule t {\n condition:\n $missing\n}") {
+            Err(AnalysisError::ParseError(message)) => {
+                assert!(message.starts_with("rule line 3:"), "{}", message)
+            }
+            _ => panic!("expected a parse error"),
+        }
+
+        // Comments, tags, meta and rule prefixes are accepted
+        let rules = RuleSet::parse(
+            "/* header */ private rule a : t1 t2 { meta: n = -1 s = \"x\" condition: true }
+             global rule b { condition: false } // trailing",
+        )
+        .unwrap();
+        assert_eq!(rules.rules.len(), 2);
+    }
+
+    #[test]
+    fn test_scanner_joins_pieces_and_chunks() {
+        let rules =
+            RuleSet::parse(r"rule t { strings: $a = /needle[0-9]+/ condition: $a }").unwrap();
+
+        // A match crossing the second chunk boundary, fed a page at a time
+        let base = 0x7f00_0000_0000u64;
+        let mut memory = vec![0u8; 2 * CHUNK_SIZE + 0x1000];
+        let at = 2 * CHUNK_SIZE - 4;
+        memory[at..at + 9].copy_from_slice(b"needle123");
+        let mut scanner = UnitScanner::new(&rules);
+        for (i, page) in memory.chunks(0x1000).enumerate() {
+            scanner.feed(base + i as u64 * 0x1000, page);
+        }
+        let hits = scanner.finish();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].address, base + at as u64);
+        assert_eq!(hits[0].data, b"needle123");
+
+        // A gap in the addresses starts a new run: halves on either side do not join
+        let mut scanner = UnitScanner::new(&rules);
+        scanner.feed(0x1000, b"xxneed");
+        scanner.feed(0x3000, b"le1xx");
+        assert!(scanner.finish().is_empty());
+    }
+
+    #[test]
+    fn test_rule_matching_across_chunks() {
+        let rules = RuleSet::parse(
+            r#"
+            rule Implant : linux {
+                meta:
+                    author = "ir"
+                    version = 2
+                strings:
+                    $magic = { 7F 45 4C 46 ?? 01 [1-2] 4? }
+                    $name = "evil" wide nocase
+                    $url = /https?:\/\/[a-z]+\.example/
+                condition:
+                    $magic and 1 of ($name, $url*)
+            }
+            rule Unmatched { strings: $a = "absent" condition: any of them }
+            "#,
+        )
+        .unwrap();
+        assert_eq!(rules.rules.len(), 2);
+
+        let mut memory = vec![0u8; CHUNK_SIZE + 0x100];
+        memory[0x10..0x18].copy_from_slice(b"\x7fELF\x02\x01\x01\x42");
+        // Straddles the first chunk boundary
+        let wide = b"E\0v\0I\0l\0";
+        memory[CHUNK_SIZE - 3..CHUNK_SIZE + 5].copy_from_slice(wide);
+
+        let mut scanner = UnitScanner::new(&rules);
+        // Fed in two adjacent pieces, as pages would be
+        scanner.feed(0x1000, &memory[..0x800]);
+        scanner.feed(0x1800, &memory[0x800..]);
+        let hits = scanner.finish();
+
+        let found: Vec<(&str, u64)> = hits
+            .iter()
+            .map(|h| (h.string.as_str(), h.address))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("$magic", 0x1010),
+                ("$name", 0x1000 + CHUNK_SIZE as u64 - 3)
+            ]
+        );
+
+        assert!(RuleSet::parse("rule Bad { strings: $a = \"x\" fullword condition: $a }").is_err());
+        assert!(RuleSet::parse("rule Bad { strings: $a = \"x\" condition: $b }").is_err());
+    }
+}
@@ -35,8 +35,12 @@ pub struct SymbolResolver {
     symbols: HashMap<String, u64>,
     // Store structure field offsets from dwarf2json: "struct_name::field_name" -> offset
     struct_offsets: HashMap<String, usize>,
+    // Store structure sizes from dwarf2json: "struct_name" -> size in bytes
+    struct_sizes: HashMap<String, usize>,
     // Store the dwarf2json file path to reload offsets when needed
     dwarf2json_path: Option<String>,
+    // Byte order and pointer size from the dwarf2json "pointer" base type
+    target: Option<crate::kernel::target::Target>,
 }
 
 impl SymbolResolver {
@@ -45,7 +49,9 @@ pub fn new() -> Self {
         SymbolResolver {
             symbols: HashMap::new(),
             struct_offsets: HashMap::new(),
+            struct_sizes: HashMap::new(),
             dwarf2json_path: None,
+            target: None,
         }
     }
 
@@ -81,11 +87,249 @@ pub fn get_symbols(&self) -> &HashMap<String, u64> {
         &self.symbols
     }
 
+    /// Get the address of the first symbol placed after an address
+    ///
+    /// Symbol files carry no sizes, so the gap to the next symbol bounds tables
+    /// such as sys_call_table.
+    pub fn next_symbol_address(&self, address: u64) -> Option<u64> {
+        self.symbols
+            .values()
+            .copied()
+            .filter(|&a| a > address)
+            .min()
+    }
+
+    /// Get the data model (byte order, pointer size) recorded by dwarf2json
+    pub fn target(&self) -> Option<crate::kernel::target::Target> {
+        self.target
+    }
+
     /// Get the number of symbols
     pub fn symbol_count(&self) -> usize {
         self.symbols.len()
     }
 
+    /// Shift every symbol address by the virtual KASLR slide
+    ///
+    /// Symbol files describe the unrelocated kernel, while pointers read from memory
+    /// hold runtime addresses. Once the slide is known, relocating the symbols lets
+    /// plugins compare symbol addresses with pointers directly.
+    pub fn apply_kaslr_slide(&mut self, slide: i64) {
+        if slide == 0 {
+            return;
+        }
+        for address in self.symbols.values_mut() {
+            *address = address.wrapping_add(slide as u64);
+        }
+    }
+
+    /// Detect the virtual KASLR slide from the init_task tasks list
+    ///
+    /// The last task on the circular tasks list points back at the runtime address of
+    /// init_task.tasks. Comparing that with the static init_task symbol gives the slide.
+    /// Must be called while the translator still maps static symbol addresses.
+    pub fn detect_virtual_kaslr_slide(
+        &self,
+        mapped: &[u8],
+        translator: &crate::translation::MemoryTranslator,
+        init_task_offset: usize,
+    ) -> Option<i64> {
+        use crate::kernel::KernelParser;
+
+        let static_init_task = self.get_symbol_address("init_task")?;
+        let tasks_offset = self
+            .get_struct_field_offset_fallback("task_struct", "tasks")
+            .unwrap_or(0xa00) as usize;
+
+        // tasks.prev of init_task is the last task's tasks list_head
+        let prev = KernelParser::read_pointer(
+            mapped,
+            init_task_offset + tasks_offset + translator.pointer_size() as usize,
+            translator.target(),
+        )?;
+        let runtime_tasks = if prev >= 0xffffffff80000000 {
+            // Only init_task on the list, prev already points at init_task.tasks
+            prev
+        } else {
+            let prev_offset = translator.virtual_to_file_offset(prev)?;
+            KernelParser::read_pointer(mapped, prev_offset as usize, translator.target())?
+        };
+
+        if runtime_tasks < 0xffffffff80000000 {
+            debug!(
+                "[DEBUG] Runtime init_task.tasks 0x{:x} is outside the kernel image",
+                runtime_tasks
+            );
+            return None;
+        }
+
+        let runtime_init_task = runtime_tasks.wrapping_sub(tasks_offset as u64);
+        let slide = runtime_init_task.wrapping_sub(static_init_task) as i64;
+
+        // KASLR slides are aligned to at least 1MB and bounded by the 1GB kernel image area
+        if slide % 0x100000 != 0 || slide.unsigned_abs() >= 0x40000000 {
+            debug!("[DEBUG] Implausible virtual KASLR slide 0x{:x}", slide);
+            return None;
+        }
+
+        debug!(
+            "[DEBUG] Runtime init_task at 0x{:x}, static 0x{:x}, slide 0x{:x}",
+            runtime_init_task, static_init_task, slide
+        );
+        Some(slide)
+    }
+
+    /// Check for symbols that only exist in AArch64 kernels
+    pub fn is_aarch64_kernel(&self) -> bool {
+        self.get_symbol_address("kimage_voffset").is_some()
+            || self.get_symbol_address("memstart_addr").is_some()
+    }
+
+    /// Detect the AArch64 memory layout and the virtual KASLR slide
+    ///
+    /// The physical address of the kernel image comes from the `linux_banner` string,
+    /// validated by finding init_task (PID 0, "swapper") through the resulting
+    /// kimage_voffset. The runtime kimage_voffset, memstart_addr (PHYS_OFFSET) and
+    /// vabits_actual are then read from the image, PAGE_OFFSET is checked against
+    /// init_task's tasks list, and the granule is the one under which swapper_pg_dir
+    /// maps the banner.
+    pub fn detect_aarch64_layout(
+        &self,
+        mapped: &[u8],
+        translator: &crate::translation::MemoryTranslator,
+    ) -> Option<(crate::translation::aarch64::Aarch64Layout, i64)> {
+        use crate::kernel::KernelParser;
+        use crate::translation::aarch64::{Aarch64Layout, GRANULE_SHIFTS, VA_BITS};
+        use crate::translation::{Architecture, MemoryTranslator};
+
+        let text = self
+            .get_symbol_address("_text")
+            .or_else(|| self.get_symbol_address("_stext"))?;
+        let (image_va_bits, flipped) = Aarch64Layout::image_va_bits(text)?;
+        let banner = self.get_symbol_address("linux_banner")?;
+        let init_task = self.get_symbol_address("init_task")?;
+        let pid_offset = self
+            .get_struct_field_offset_fallback("task_struct", "pid")
+            .unwrap_or(0xad0) as usize;
+        let comm_offset = self
+            .get_struct_field_offset_fallback("task_struct", "comm")
+            .unwrap_or(0xcf0) as usize;
+        let tasks_offset = self
+            .get_struct_field_offset_fallback("task_struct", "tasks")
+            .unwrap_or(0xa00);
+
+        // Reads through the static image mapping for a candidate kimage_voffset
+        let file_offset = |voffset: u64, addr: u64| {
+            translator
+                .physical_to_file_offset(addr.wrapping_sub(voffset))
+                .map(|o| o as usize)
+        };
+        let is_init_task = |voffset: u64| {
+            file_offset(voffset, init_task).is_some_and(|task| {
+                KernelParser::read_i32(mapped, task + pid_offset, translator.target()) == Some(0)
+                    && KernelParser::read_string(mapped, task + comm_offset, 16)
+                        .is_some_and(|comm| comm.starts_with("swapper"))
+            })
+        };
+
+        // The image is aligned to at least 64KB, so its offset is too
+        let (voffset, banner_phys) = memmem::find_iter(mapped, b"Linux version ")
+            .filter_map(|pos| translator.file_offset_to_physical(pos as u64))
+            .map(|phys| (banner.wrapping_sub(phys), phys))
+            .find(|&(voffset, _)| voffset % 0x10000 == 0 && is_init_task(voffset))?;
+        let read_u64 = |addr: u64| {
+            file_offset(voffset, addr)
+                .and_then(|o| KernelParser::read_u64(mapped, o, translator.target()))
+        };
+        debug!(
+            "[DEBUG] linux_banner at physical 0x{:x}, static kimage_voffset 0x{:x}",
+            banner_phys, voffset
+        );
+
+        let runtime_voffset = self
+            .get_symbol_address("kimage_voffset")
+            .and_then(read_u64)
+            .filter(|v| v.wrapping_sub(voffset) % 0x10000 == 0)
+            .unwrap_or(voffset);
+        let slide = runtime_voffset.wrapping_sub(voffset) as i64;
+        let va_bits = self
+            .get_symbol_address("vabits_actual")
+            .and_then(read_u64)
+            .filter(|bits| VA_BITS.contains(bits))
+            .unwrap_or(image_va_bits);
+
+        let mut layout = Aarch64Layout::new(va_bits, 12, flipped);
+        layout.kimage_voffset = runtime_voffset;
+        layout.kimage_start = text.wrapping_add(slide as u64);
+        layout.kimage_end = self
+            .get_symbol_address("_end")
+            .unwrap_or(text + 0x4000_0000)
+            .wrapping_add(slide as u64);
+        layout.phys_offset = self
+            .get_symbol_address("memstart_addr")
+            .and_then(read_u64)
+            .unwrap_or_else(|| {
+                translator
+                    .get_regions()
+                    .iter()
+                    .map(|r| r.start)
+                    .min()
+                    .unwrap_or(0)
+            });
+
+        // A 52-bit kernel on 48-bit hardware keeps the 52-bit PAGE_OFFSET; check the
+        // candidates by following init_task.tasks.next back to init_task
+        let runtime_tasks = init_task.wrapping_add(slide as u64) + tasks_offset;
+        let page_offsets = if flipped {
+            vec![!0u64 << va_bits, !0u64 << 52, !0u64 << image_va_bits]
+        } else {
+            vec![!0u64 << (image_va_bits - 1)]
+        };
+        let next = read_u64(init_task + tasks_offset);
+        for page_offset in page_offsets {
+            let candidate = Aarch64Layout {
+                page_offset,
+                ..layout
+            };
+            let prev = next
+                .and_then(|n| candidate.virtual_to_physical(n + translator.pointer_size()))
+                .and_then(|p| translator.physical_to_file_offset(p))
+                .and_then(|o| KernelParser::read_pointer(mapped, o as usize, translator.target()));
+            if prev == Some(runtime_tasks) {
+                layout.page_offset = page_offset;
+                break;
+            }
+        }
+
+        // Find the granule under which swapper_pg_dir maps linux_banner
+        if let Some(swapper) = self.get_symbol_address("swapper_pg_dir") {
+            let mut probe = MemoryTranslator::new(translator.get_regions().to_vec());
+            let runtime_banner = banner.wrapping_add(slide as u64);
+            for page_shift in GRANULE_SHIFTS {
+                probe.set_architecture(Architecture::Aarch64(Aarch64Layout {
+                    page_shift,
+                    ..layout
+                }));
+                let walked =
+                    probe.page_table_walk(mapped, swapper.wrapping_sub(voffset), runtime_banner);
+                if walked == Some(banner_phys) {
+                    layout.page_shift = page_shift;
+                    break;
+                }
+            }
+        }
+
+        debug!(
+            "[DEBUG] AArch64 layout: VA_BITS={}, {}K pages, kimage_voffset=0x{:x}, PHYS_OFFSET=0x{:x}, PAGE_OFFSET=0x{:x}",
+            layout.va_bits,
+            layout.page_size() / 1024,
+            layout.kimage_voffset,
+            layout.phys_offset,
+            layout.page_offset
+        );
+        Some((layout, slide))
+    }
+
     /// Calculate phys_base using multiple heuristics
     ///
     /// This tries different approaches to determine the correct phys_base value:
@@ -139,7 +383,11 @@ pub fn calculate_phys_base_candidates(&self) -> Vec<u64> {
     /// Returns (kaslr_offset, actual_init_task_file_offset)
     /// Heuristic search for init_task by finding "swapper" string in memory
     /// This is a fallback when KASLR detection fails
-    fn find_init_task_by_swapper_string(&self, memory: &[u8]) -> Option<usize> {
+    fn find_init_task_by_swapper_string(
+        &self,
+        memory: &[u8],
+        target: crate::kernel::target::Target,
+    ) -> Option<usize> {
         debug!(
             "[DEBUG] Attempting heuristic search for init_task by scanning for 'swapper' string..."
         );
@@ -173,18 +421,21 @@ fn find_init_task_by_swapper_string(&self, memory: &[u8]) -> Option<usize> {
                 continue;
             }
 
-            if let Some(pid) =
-                crate::kernel::KernelParser::read_i32(memory, potential_task_struct + pid_offset)
-            {
+            if let Some(pid) = crate::kernel::KernelParser::read_i32(
+                memory,
+                potential_task_struct + pid_offset,
+                target,
+            ) {
                 if pid == 0 {
                     debug!("[DEBUG] Found 'swapper' at offset 0x{:x}, potential task_struct at 0x{:x}, PID={}",
                              match_pos, potential_task_struct, pid);
 
                     // Validate: check tasks.next pointer
                     if potential_task_struct + tasks_offset + 8 <= memory.len() {
-                        if let Some(tasks_next) = crate::kernel::KernelParser::read_u64(
+                        if let Some(tasks_next) = crate::kernel::KernelParser::read_pointer(
                             memory,
                             potential_task_struct + tasks_offset,
+                            target,
                         ) {
                             const MIN_KERNEL_ADDR: u64 = 0xffff800000000000;
                             const MAX_KERNEL_ADDR: u64 = 0xfffffffffff00000;
@@ -264,6 +515,7 @@ pub fn detect_kaslr_offset(
                     if let Some(pid) = crate::kernel::KernelParser::read_i32(
                         memory,
                         file_offset_usize + pid_offset,
+                        translator.target(),
                     ) {
                         if pid == 0 {
                             // Verify this is a real task_struct, not a zero-filled page
@@ -275,6 +527,7 @@ pub fn detect_kaslr_offset(
                                     if let Some(val) = crate::kernel::KernelParser::read_u64(
                                         memory,
                                         file_offset_usize + sample_off,
+                                        translator.target(),
                                     ) {
                                         if val != 0 {
                                             non_zero_count += 1;
@@ -288,10 +541,13 @@ pub fn detect_kaslr_offset(
                                 // CRITICAL: Also verify that tasks.next pointer is non-zero
                                 // The tasks field is a list_head, so tasks.next is at tasks_offset
                                 if file_offset_usize + tasks_offset + 8 <= memory.len() {
-                                    if let Some(tasks_next) = crate::kernel::KernelParser::read_u64(
-                                        memory,
-                                        file_offset_usize + tasks_offset,
-                                    ) {
+                                    if let Some(tasks_next) =
+                                        crate::kernel::KernelParser::read_pointer(
+                                            memory,
+                                            file_offset_usize + tasks_offset,
+                                            translator.target(),
+                                        )
+                                    {
                                         if tasks_next == 0 {
                                             if debug_failures_logged < 10 {
                                                 debug!("[DEBUG] Found PID 0 at 0x{:x} but tasks.next is NULL - not a valid circular list - skipping",
@@ -305,10 +561,11 @@ pub fn detect_kaslr_offset(
                                         // On x86-64, kernel addresses are in high canonical address space
                                         // They should start with 0xffff8... or higher
                                         // BUT also reject sentinel values like 0xffffffffffffffff (-1)
-                                        const MIN_KERNEL_ADDR: u64 = 0xffff800000000000;
+                                        // x86-64 kernel addresses start at 0xffff8..., AArch64 ones lower
+                                        let min_kernel_addr = translator.kernel_space_start();
                                         const MAX_KERNEL_ADDR: u64 = 0xfffffffffff00000; // Leave room for kernel end
 
-                                        if tasks_next < MIN_KERNEL_ADDR
+                                        if tasks_next < min_kernel_addr
                                             || tasks_next >= MAX_KERNEL_ADDR
                                         {
                                             if debug_failures_logged < 10 {
@@ -385,7 +642,9 @@ pub fn detect_kaslr_offset(
         warn!("[WARNING] Falling back to heuristic search for 'swapper' string...");
 
         // Fallback: search for "swapper" string in memory
-        if let Some(init_task_offset) = self.find_init_task_by_swapper_string(memory) {
+        if let Some(init_task_offset) =
+            self.find_init_task_by_swapper_string(memory, translator.target())
+        {
             debug!("[DEBUG] ✓ Heuristic search succeeded!");
             // Return dummy KASLR offset of 0 since we found it directly by file offset
             return Some((0, init_task_offset));
@@ -420,13 +679,8 @@ pub fn read_phys_base(
         // Translate to file offset
         let file_offset = translator.virtual_to_file_offset(phys_base_vaddr)?;
 
-        // Read the 64-bit value at that location
-        if (file_offset as usize) + 8 <= mapped.len() {
-            let bytes = &mapped[file_offset as usize..file_offset as usize + 8];
-            Some(u64::from_le_bytes(bytes.try_into().ok()?))
-        } else {
-            None
-        }
+        // phys_base is an unsigned long
+        crate::kernel::KernelParser::read_pointer(mapped, file_offset as usize, translator.target())
     }
 
     /// Extract kernel version from System.map file by looking for linux_banner symbol
@@ -523,6 +777,10 @@ pub fn load_dwarf2json(&mut self, file_path: &str) -> Result<(), AnalysisError>
                 AnalysisError::SymbolError(format!("Failed to parse dwarf2json: {}", e))
             })?;
 
+        self.target = dwarf.get_pointer_type().and_then(|(size, endian)| {
+            crate::kernel::target::Target::from_pointer_type(size, endian)
+        });
+
         // Load symbols (convert from HashMap to iterator)
         let symbols = dwarf.get_symbols();
         for (name, addr) in symbols {
@@ -532,8 +790,163 @@ pub fn load_dwarf2json(&mut self, file_path: &str) -> Result<(), AnalysisError>
         // Load structure offsets from dwarf2json
         // Store them as "struct_name::field_name" -> offset for easy lookup
         // We'll iterate through known structs we care about
-        let structs_to_load = vec!["task_struct", "cred"];
+        let structs_to_load = vec![
+            "task_struct",
+            "cred",
+            // Process address space (VMAs and page tables)
+            "mm_struct",
+            "vm_area_struct",
+            "maple_tree",
+            "maple_range_64",
+            "maple_arange_64",
+            // VFS structures (tmpfs/memfd reconstruction)
+            "super_block",
+            "file_system_type",
+            "mount",
+            "dentry",
+            "qstr",
+            "inode",
+            "address_space",
+            "xarray",
+            "xa_node",
+            "timespec64",
+            "page",
+            // Swap areas (swapped-out page lookup)
+            "swap_info_struct",
+            "block_device",
+            "gendisk",
+            // zswap entries and pools (compressed swap lookup)
+            "zswap_entry",
+            "zswap_tree",
+            "zswap_pool",
+            // Network namespaces, devices and addresses
+            "net",
+            "ns_common",
+            "net_device",
+            "in_device",
+            "in_ifaddr",
+            "inet6_dev",
+            "inet6_ifaddr",
+            // Neighbour caches and routing tables
+            "neigh_table",
+            "neigh_hash_table",
+            "neighbour",
+            "netns_ipv4",
+            "netns_ipv6",
+            "fib_table",
+            "fib_alias",
+            "fib_info",
+            "fib_nh",
+            "fib_nh_common",
+            "fib6_table",
+            "fib6_node",
+            "fib6_info",
+            "fib6_nh",
+            "rt6key",
+            // Sockets and the fd tables that own them
+            "sock",
+            "sock_common",
+            "socket",
+            "socket_alloc",
+            "unix_sock",
+            "unix_address",
+            "netns_unix",
+            "unix_table",
+            "files_struct",
+            "fdtable",
+            "file",
+            // TCP/UDP socket hash tables
+            "inet_hashinfo",
+            "inet_ehash_bucket",
+            "inet_listen_hashbucket",
+            "inet_connection_sock",
+            "udp_table",
+            "udp_hslot",
+            // Packet, raw and netlink sockets and socket filters
+            "netns_packet",
+            "packet_sock",
+            "raw_hashinfo",
+            "netlink_table",
+            "netlink_sock",
+            "rhashtable",
+            "bucket_table",
+            "sk_filter",
+            "bpf_prog",
+            "sock_fprog_kern",
+            // eBPF programs, maps and links
+            "bpf_prog_aux",
+            "bpf_map",
+            "bpf_link",
+            "bpf_raw_tp_link",
+            "bpf_raw_event_map",
+            "tracepoint",
+            "bpf_xdp_link",
+            "user_struct",
+            "idr",
+            // Kernel text patch tables (vmlinux comparison)
+            "alt_instr",
+            "jump_entry",
+            // Loaded modules and their symbol tables
+            "module",
+            "module_layout",
+            "module_memory",
+            "mod_kallsyms",
+            // ftrace ops, kprobes and tracepoint probes
+            "ftrace_ops",
+            "ftrace_ops_hash",
+            "ftrace_hash",
+            "ftrace_func_entry",
+            "kprobe",
+            "kretprobe",
+            "tracepoint_func",
+            // Netfilter hooks
+            "netns_nf",
+            "nf_hook_entries",
+            "nf_hook_entry",
+            "nf_hook_ops",
+            // Notifier chains
+            "notifier_block",
+            "atomic_notifier_head",
+            "blocking_notifier_head",
+            "raw_notifier_head",
+            // TTY drivers and line disciplines
+            "tty_driver",
+            "tty_struct",
+            "tty_ldisc",
+            "tty_ldisc_ops",
+            "tty_operations",
+            // Ops structures checked for hijacked function pointers
+            "file_operations",
+            "inode_operations",
+            "proc_dir_entry",
+            "seq_operations",
+            "tcp_seq_afinfo",
+            "proto_ops",
+            "proto",
+            // Namespaces, PID namespaces and cgroups
+            "nsproxy",
+            "uts_namespace",
+            "new_utsname",
+            "ipc_namespace",
+            "mnt_namespace",
+            "pid_namespace",
+            "user_namespace",
+            "cgroup_namespace",
+            "time_namespace",
+            "pid",
+            "upid",
+            "pid_link",
+            "css_set",
+            "cgroup_subsys_state",
+            "cgroup",
+            "kernfs_node",
+            // Mapped file paths (struct file -> f_path)
+            "path",
+        ];
         for struct_name in structs_to_load {
+            if let Some(size) = dwarf.get_struct_size(struct_name) {
+                self.struct_sizes.insert(struct_name.to_string(), size);
+            }
             if let Some(fields) = dwarf.get_struct_offsets(struct_name) {
                 debug!(
                     "[DEBUG] Loaded {} fields for struct '{}':",
@@ -665,6 +1078,45 @@ pub fn get_struct_field_offset(
         }
     }
 
+    /// Get the offset of a field that has no hardcoded fallback
+    ///
+    /// Used by plugins that walk structures only described by dwarf2json symbols.
+    pub fn require_field_offset(
+        &self,
+        struct_name: &str,
+        field_name: &str,
+    ) -> Result<usize, AnalysisError> {
+        self.get_struct_field_offset(struct_name, field_name, None)
+            .map(|offset| offset as usize)
+            .ok_or_else(|| {
+                AnalysisError::SymbolNotFound(format!(
+                    "{}::{} (a dwarf2json symbol file is required)",
+                    struct_name, field_name
+                ))
+            })
+    }
+
+    /// Check whether a structure field is described by the loaded symbols
+    pub fn has_field(&self, struct_name: &str, field_name: &str) -> bool {
+        self.struct_offsets
+            .contains_key(&format!("{}::{}", struct_name, field_name))
+    }
+
+    /// Get the size of a structure from dwarf2json
+    pub fn get_struct_size(&self, struct_name: &str) -> Option<usize> {
+        self.struct_sizes.get(struct_name).copied()
+    }
+
+    /// Describe a structure layout directly (synthetic dumps in unit tests)
+    #[cfg(test)]
+    pub fn add_struct(&mut self, struct_name: &str, size: usize, fields: &[(&str, usize)]) {
+        self.struct_sizes.insert(struct_name.to_string(), size);
+        for (field, offset) in fields {
+            self.struct_offsets
+                .insert(format!("{}::{}", struct_name, field), *offset);
+        }
+    }
+
     /// Convenience method with no kernel version (uses only fallbacks)
     pub fn get_struct_field_offset_fallback(
         &self,
@@ -681,6 +1133,7 @@ fn validate_task_struct_with_offsets(
         offset: usize,
         pid_offset: usize,
         comm_offset: usize,
+        target: crate::kernel::target::Target,
     ) -> bool {
         let state_offset = self
             .get_struct_field_offset_fallback("task_struct", "state")
@@ -693,7 +1146,7 @@ fn validate_task_struct_with_offsets(
         }
 
         // Read PID - should be a reasonable value (0 to 2^22, Linux max PID)
-        let pid = match read_i32_helper(mapped, offset + pid_offset) {
+        let pid = match read_i32_helper(mapped, offset + pid_offset, target) {
             Some(p) if p >= 0 && p <= 4194304 => p,
             _ => return false,
         };
@@ -735,7 +1188,7 @@ fn validate_task_struct_with_offsets(
         }
 
         // Read state - should be a small value (0-5 typically)
-        if let Some(state) = read_i32_helper(mapped, offset + state_offset) {
+        if let Some(state) = read_i32_helper(mapped, offset + state_offset, target) {
             if state < -1 || state > 10 {
                 return false;
             }
@@ -750,7 +1203,12 @@ fn validate_task_struct_with_offsets(
     /// Checks PID range, comm field for valid process names, and state value
     /// Uses default offsets from symbol resolver
     #[allow(dead_code)]
-    fn validate_task_struct(&self, mapped: &[u8], offset: usize) -> bool {
+    fn validate_task_struct(
+        &self,
+        mapped: &[u8],
+        offset: usize,
+        target: crate::kernel::target::Target,
+    ) -> bool {
         // Get offsets for validation
         let pid_offset = self
             .get_struct_field_offset_fallback("task_struct", "pid")
@@ -760,7 +1218,7 @@ fn validate_task_struct(&self, mapped: &[u8], offset: usize) -> bool {
             .unwrap_or(0x4a8) as usize;
 
         // Use the new validation function with specific offsets
-        self.validate_task_struct_with_offsets(mapped, offset, pid_offset, comm_offset)
+        self.validate_task_struct_with_offsets(mapped, offset, pid_offset, comm_offset, target)
     }
 
     /// Detect kernel version from the linux_banner string
@@ -768,26 +1226,23 @@ pub fn detect_kernel_version(
         &self,
         mapped: &[u8],
     ) -> Option<crate::core::offsets::KernelVersion> {
-        // Search for "Linux version " string in memory
-        let linux_version_pattern = b"Linux version ";
-        let finder = memchr::memmem::Finder::new(linux_version_pattern);
-
-        if let Some(match_pos) = finder.find(mapped) {
-            // Extract from match_pos to newline or reasonable end
-            let slice = &mapped[match_pos..];
-            let end_pos = slice
-                .iter()
-                .position(|&c| c == b'\n' || c == b'\r')
-                .unwrap_or(slice.len());
-            let banner_str = String::from_utf8_lossy(&slice[..end_pos]);
-
-            // Parse kernel version from banner like "Linux version 5.15.0-91-generic"
-            if let Some(version_part) = banner_str.split("Linux version ").nth(1) {
-                return parse_kernel_version(version_part);
-            }
-        }
+        // Parse kernel version from banner like "Linux version 5.15.0-91-generic"
+        let banner_str = Self::find_banner(mapped)?;
+        let version_part = banner_str.split("Linux version ").nth(1)?;
+        parse_kernel_version(version_part)
+    }
 
-        None
+    /// Find the first "Linux version ..." banner line in memory
+    pub fn find_banner(mapped: &[u8]) -> Option<String> {
+        let match_pos = memchr::memmem::find(mapped, b"Linux version ")?;
+        // Extract from match_pos to newline or reasonable end
+        let slice = &mapped[match_pos..];
+        let end_pos = slice
+            .iter()
+            .take(1024)
+            .position(|&c| c == b'\n' || c == b'\r' || c == 0)
+            .unwrap_or(slice.len().min(1024));
+        Some(String::from_utf8_lossy(&slice[..end_pos]).to_string())
     }
 
     /// Find the init_task address in memory
@@ -800,6 +1255,7 @@ pub fn find_init_task(
         translator: Option<&crate::translation::MemoryTranslator>,
     ) -> Option<u64> {
         println!("Searching for init_task in memory...");
+        let target = translator.map_or_else(Default::default, |t| t.target());
 
         // Strategy 1: Look for init_task symbol if we have it
         if let Some(addr) = self.get_symbol_address("init_task") {
@@ -889,9 +1345,12 @@ pub fn find_init_task(
                     potential_task_offset,
                     *pid_offset,
                     *comm_offset,
+                    target,
                 ) {
                     // Check if PID is 0 or 1
-                    if let Some(pid) = read_i32_helper(mapped, potential_task_offset + pid_offset) {
+                    if let Some(pid) =
+                        read_i32_helper(mapped, potential_task_offset + pid_offset, target)
+                    {
                         if pid == 0 || pid == 1 {
                             println!(
                                 "Found potential init_task at offset 0x{:x} (PID: {})",
@@ -940,9 +1399,12 @@ pub fn find_init_task(
                     potential_task_offset,
                     *pid_offset,
                     *comm_offset,
+                    target,
                 ) {
                     // Check if PID is 1
-                    if let Some(pid) = read_i32_helper(mapped, potential_task_offset + pid_offset) {
+                    if let Some(pid) =
+                        read_i32_helper(mapped, potential_task_offset + pid_offset, target)
+                    {
                         if pid == 1 {
                             println!(
                                 "Found potential init_task at offset 0x{:x} (PID: {})",
@@ -987,14 +1449,17 @@ pub fn derive_page_offset_from_init_task(
         use crate::kernel;
 
         // Read tasks.next pointer from init_task
-        let tasks_next_vaddr =
-            match kernel::KernelParser::read_u64(memory, init_task_file_offset + tasks_offset) {
-                Some(addr) => addr,
-                None => {
-                    debug!("[DEBUG] Could not read tasks.next from init_task");
-                    return None;
-                }
-            };
+        let tasks_next_vaddr = match kernel::KernelParser::read_pointer(
+            memory,
+            init_task_file_offset + tasks_offset,
+            translator.target(),
+        ) {
+            Some(addr) => addr,
+            None => {
+                debug!("[DEBUG] Could not read tasks.next from init_task");
+                return None;
+            }
+        };
 
         debug!(
             "[DEBUG] Deriving PAGE_OFFSET from tasks.next=0x{:x}",
@@ -1066,9 +1531,11 @@ pub fn derive_page_offset_from_init_task(
 
                 // Validate this looks like a task_struct
                 // Use hardcoded offsets for now (should potentially use offsets from self)
-                if let Some(pid) =
-                    kernel::KernelParser::read_i32(memory, (task_base + 0xad0).try_into().unwrap())
-                {
+                if let Some(pid) = kernel::KernelParser::read_i32(
+                    memory,
+                    (task_base + 0xad0).try_into().unwrap(),
+                    translator.target(),
+                ) {
                     if pid > 0 && pid < 1000000 {
                         if let Some(comm) = kernel::KernelParser::read_string(
                             memory,
@@ -1134,11 +1601,15 @@ fn parse_kernel_version(version_str: &str) -> Option<crate::core::offsets::Kerne
 }
 
 // Helper functions for reading data (duplicated from KernelParser to avoid circular dependency)
-fn read_i32_helper(mapped: &[u8], offset: usize) -> Option<i32> {
+fn read_i32_helper(
+    mapped: &[u8],
+    offset: usize,
+    target: crate::kernel::target::Target,
+) -> Option<i32> {
     if offset + 4 <= mapped.len() {
         let mut buf = [0u8; 4];
         buf.copy_from_slice(&mapped[offset..offset + 4]);
-        Some(i32::from_ne_bytes(buf))
+        Some(target.u32(buf) as i32)
     } else {
         None
     }
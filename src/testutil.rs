@@ -0,0 +1,76 @@
+//! Synthetic dump helpers shared by unit tests
+use crate::memory::MemoryRegion;
+use crate::symbols::SymbolResolver;
+use crate::translation::MemoryTranslator;
+
+/// x86-64 direct map base: kernel virtual address of physical address 0
+pub const DIRECT_MAP: u64 = 0xffff_8880_0000_0000;
+
+/// Physical memory addressed through the direct map
+pub struct Dump(pub Vec<u8>);
+
+impl Dump {
+    /// A zero-filled dump of `size` bytes
+    pub fn new(size: usize) -> Self {
+        Dump(vec![0u8; size])
+    }
+
+    pub fn bytes(&mut self, at: u64, bytes: &[u8]) {
+        self.0[at as usize..at as usize + bytes.len()].copy_from_slice(bytes);
+    }
+
+    /// Store a little-endian 64-bit word
+    pub fn word(&mut self, at: u64, value: u64) {
+        self.bytes(at, &value.to_le_bytes());
+    }
+
+    /// Store a pointer to another physical address of the dump
+    pub fn ptr(&mut self, at: u64, to: u64) {
+        self.word(at, DIRECT_MAP + to);
+    }
+
+    /// A translator mapping the whole dump as one region starting at physical 0
+    pub fn translator(&self) -> MemoryTranslator {
+        MemoryTranslator::new(vec![MemoryRegion {
+            start: 0,
+            end: self.0.len() as u64 - 1,
+            file_offset: 0,
+        }])
+    }
+}
+
+/// sock_common, sock, socket and inode layouts shared by the socket plugins
+pub fn socket_symbols() -> SymbolResolver {
+    let mut symbols = SymbolResolver::new();
+    symbols.add_struct(
+        "sock_common",
+        0x88,
+        &[
+            ("skc_daddr", 0),
+            ("skc_rcv_saddr", 4),
+            ("skc_dport", 12),
+            ("skc_num", 14),
+            ("skc_family", 16),
+            ("skc_state", 18),
+            ("skc_node", 24),
+            ("skc_bound_dev_if", 0x28),
+            ("skc_net", 0x30),
+            ("skc_v6_daddr", 0x38),
+            ("skc_v6_rcv_saddr", 0x48),
+        ],
+    );
+    symbols.add_struct(
+        "sock",
+        0x200,
+        &[
+            ("__sk_common", 0),
+            ("sk_socket", 0x100),
+            ("sk_type", 0x108),
+            ("sk_filter", 0x110),
+        ],
+    );
+    symbols.add_struct("socket", 0x30, &[("type", 4)]);
+    symbols.add_struct("socket_alloc", 0x80, &[("vfs_inode", 0x40)]);
+    symbols.add_struct("inode", 0x100, &[("i_ino", 0x40)]);
+    symbols
+}
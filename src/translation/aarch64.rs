@@ -0,0 +1,321 @@
+//! AArch64 memory model: kernel image / linear map translation and page table formats
+use super::{Descriptor, SwapEntry};
+
+// Translation granules: 4K, 16K or 64K pages
+pub const GRANULE_SHIFTS: [u64; 3] = [12, 14, 16];
+// VA_BITS choices for 4K (39, 48), 16K (47, 48) and 64K (42, 48) granules, plus 52-bit
+pub const VA_BITS: [u64; 6] = [36, 39, 42, 47, 48, 52];
+
+// Descriptor bits (arch/arm64/include/asm/pgtable-hwdef.h)
+const PTE_VALID: u64 = 1 << 0;
+const PTE_TABLE: u64 = 1 << 1; // Table (levels above the last) or page (last level)
+const PTE_PROT_NONE: u64 = 1 << 58; // Software bit: PROT_NONE, invalid to the MMU but resident
+const PTE_ADDR_LOW: u64 = 0x0000_ffff_ffff_ffff; // Output address bits [47:0]
+
+// log2 of the largest struct page the vmemmap area is sized for
+const STRUCT_PAGE_MAX_SHIFT: u64 = 6;
+
+// Swap entries in an invalid PTE (arch/arm64/include/asm/pgtable.h)
+const SWP_TYPE_SHIFT: u64 = 3;
+const SWP_TYPE_BITS: u64 = 5;
+const SWP_OFFSET_SHIFT: u64 = SWP_TYPE_SHIFT + SWP_TYPE_BITS;
+const SWP_OFFSET_BITS: u64 = 50;
+
+/// Memory layout of an AArch64 kernel
+///
+/// The kernel image is mapped at `PA + kimage_voffset` and the linear map at
+/// `PAGE_OFFSET + (PA - PHYS_OFFSET)`. Since 5.4 the linear map occupies the lower
+/// half of the kernel address space and the image the upper half; before that the
+/// halves were the other way round.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Aarch64Layout {
+    pub va_bits: u64,        // vabits_actual (VA_BITS before 5.4)
+    pub page_shift: u64,     // Translation granule: 12, 14 or 16
+    pub kimage_voffset: u64, // Kernel image virtual - physical offset
+    pub phys_offset: u64,    // PHYS_OFFSET (memstart_addr): physical base of the linear map
+    pub page_offset: u64,    // PAGE_OFFSET: virtual base of the linear map
+    pub kimage_start: u64,   // _text
+    pub kimage_end: u64,     // _end
+}
+
+impl Aarch64Layout {
+    /// Build a layout with the linear map placement for the kernel generation
+    ///
+    /// `flipped` selects the 5.4+ layout with the linear map at the bottom of the
+    /// kernel address space.
+    pub fn new(va_bits: u64, page_shift: u64, flipped: bool) -> Self {
+        let page_offset = if flipped {
+            !0u64 << va_bits
+        } else {
+            !0u64 << (va_bits - 1)
+        };
+        Aarch64Layout {
+            va_bits,
+            page_shift,
+            kimage_voffset: 0,
+            phys_offset: 0,
+            page_offset,
+            kimage_start: 0,
+            kimage_end: 0,
+        }
+    }
+
+    /// Find the VA_BITS of the kernel image mapping and whether the layout is 5.4+
+    ///
+    /// The image sits just above the linear map (5.4+) or just above the start of
+    /// the kernel address space (older kernels), so the run of leading one bits in
+    /// `_text` gives VA_BITS for exactly one of the two layouts.
+    pub fn image_va_bits(text: u64) -> Option<(u64, bool)> {
+        let ones = text.leading_ones() as u64;
+        if ones == 0 || ones >= 32 {
+            return None;
+        }
+        let flipped = 65 - ones;
+        let legacy = 64 - ones;
+        if VA_BITS.contains(&flipped) {
+            Some((flipped, true))
+        } else if VA_BITS.contains(&legacy) {
+            Some((legacy, false))
+        } else {
+            None
+        }
+    }
+
+    /// Bits of virtual address resolved per table level
+    pub fn bits_per_level(&self) -> u64 {
+        self.page_shift - 3
+    }
+
+    /// Number of translation table levels for the VA size and granule
+    pub fn levels(&self) -> u64 {
+        (self.va_bits - self.page_shift).div_ceil(self.bits_per_level())
+    }
+
+    pub fn page_size(&self) -> u64 {
+        1 << self.page_shift
+    }
+
+    /// Bits of a kernel address: a 52-bit PAGE_OFFSET can sit below vabits_actual
+    fn address_bits(&self) -> u64 {
+        self.va_bits
+            .max(64 - self.page_offset.leading_ones() as u64)
+    }
+
+    /// Size of the linear map region: the lower half of the kernel address space
+    /// since 5.4, everything from PAGE_OFFSET up before
+    fn linear_size(&self) -> u64 {
+        if self.flipped() {
+            self.page_offset.wrapping_neg() >> 1
+        } else {
+            self.page_offset.wrapping_neg()
+        }
+    }
+
+    /// Whether this is the 5.4+ layout, with the linear map at the bottom of the
+    /// kernel address space
+    fn flipped(&self) -> bool {
+        self.page_offset == self.kernel_space_start()
+    }
+
+    /// Range of kernel addresses outside the linear map: modules, BPF, vmalloc,
+    /// vmemmap, the fixmap and the kernel image
+    pub fn non_linear_range(&self) -> (u64, u64) {
+        if self.flipped() {
+            (self.page_offset + self.linear_size(), u64::MAX)
+        } else {
+            (self.kernel_space_start(), self.page_offset)
+        }
+    }
+
+    /// Possible VMEMMAP_START values, newest kernels first
+    ///
+    /// The vmemmap array spans one struct page (at most 64 bytes) per page of the
+    /// linear map. 6.4+ ends it 1GB below the top of the address space, 5.11-6.3
+    /// start it at -(2 * VMEMMAP_SIZE), 5.4-5.10 place it 2MB below the top, and
+    /// older kernels just below PAGE_OFFSET.
+    pub fn vmemmap_starts(&self) -> Vec<u64> {
+        let size = self.vmemmap_size();
+        if !self.flipped() {
+            return vec![self.page_offset - size];
+        }
+        vec![
+            (1u64 << 30).wrapping_neg() - size,
+            (2 * size).wrapping_neg(),
+            (size + (1 << 21)).wrapping_neg(),
+        ]
+    }
+
+    /// Size of the vmemmap area
+    pub fn vmemmap_size(&self) -> u64 {
+        self.linear_size() >> (self.page_shift - STRUCT_PAGE_MAX_SHIFT)
+    }
+
+    /// Kernel image virtual address of a physical address
+    pub fn physical_to_image(&self, physical_addr: u64) -> u64 {
+        physical_addr.wrapping_add(self.kimage_voffset)
+    }
+
+    /// Remove pointer authentication codes and memory tags from a pointer
+    ///
+    /// Bits above VA_BITS are not part of the address: bit 55 selects the kernel
+    /// (all ones) or user (all zeros) half.
+    pub fn strip_pointer(&self, pointer: u64) -> u64 {
+        let mask = (1u64 << self.address_bits()) - 1;
+        if pointer & (1 << 55) != 0 {
+            pointer | !mask
+        } else {
+            pointer & mask
+        }
+    }
+
+    /// Translate a kernel image or linear map address to a physical address
+    pub fn virtual_to_physical(&self, virtual_addr: u64) -> Option<u64> {
+        let virtual_addr = self.strip_pointer(virtual_addr);
+        if virtual_addr >= self.page_offset && virtual_addr - self.page_offset < self.linear_size()
+        {
+            return (virtual_addr - self.page_offset).checked_add(self.phys_offset);
+        }
+        if virtual_addr >= self.kimage_start && virtual_addr < self.kimage_end {
+            return Some(virtual_addr.wrapping_sub(self.kimage_voffset));
+        }
+        None
+    }
+
+    /// Linear map address of a physical address
+    pub fn physical_to_linear(&self, physical_addr: u64) -> Option<u64> {
+        let offset = physical_addr.checked_sub(self.phys_offset)?;
+        (offset < self.linear_size()).then(|| self.page_offset + offset)
+    }
+
+    /// Lowest kernel virtual address (start of the TTBR1 range)
+    pub fn kernel_space_start(&self) -> u64 {
+        !0u64 << self.address_bits()
+    }
+
+    /// Output address of a descriptor
+    ///
+    /// With 52-bit physical addresses, 64K granules keep PA[51:48] in bits [15:12]
+    /// and 4K/16K granules (LPA2) keep PA[51:50] in bits [9:8].
+    fn output_address(&self, entry: u64) -> u64 {
+        let page_mask = !(self.page_size() - 1);
+        if self.page_shift == 16 {
+            (entry & PTE_ADDR_LOW & page_mask) | ((entry >> 12 & 0xf) << 48)
+        } else if self.va_bits == 52 {
+            (entry & 0x0003_ffff_ffff_ffff & page_mask) | ((entry >> 8 & 0x3) << 50)
+        } else {
+            entry & PTE_ADDR_LOW & page_mask
+        }
+    }
+
+    /// Decode a descriptor at a table level (0 is the last level)
+    pub fn decode(&self, entry: u64, level: u64) -> Descriptor {
+        if entry & PTE_VALID != 0 {
+            let address = self.output_address(entry);
+            return match (level, entry & PTE_TABLE != 0) {
+                (0, true) => Descriptor::Leaf(address),
+                (0, false) => Descriptor::Empty, // Reserved encoding at the last level
+                (_, true) => Descriptor::Table(address),
+                (_, false) => Descriptor::Leaf(address), // Block mapping
+            };
+        }
+        if level != 0 || entry == 0 {
+            return Descriptor::Empty;
+        }
+        if entry & PTE_PROT_NONE != 0 {
+            return Descriptor::Leaf(self.output_address(entry));
+        }
+        Descriptor::Swap(SwapEntry {
+            swap_type: (entry >> SWP_TYPE_SHIFT & ((1 << SWP_TYPE_BITS) - 1)) as u8,
+            offset: entry >> SWP_OFFSET_SHIFT & ((1 << SWP_OFFSET_BITS) - 1),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_layouts_and_descriptors() {
+        // 5.4+ 48-bit and 39-bit images, and a 4.x 48-bit image
+        assert_eq!(
+            Aarch64Layout::image_va_bits(0xffff_8000_0800_0000),
+            Some((48, true))
+        );
+        assert_eq!(
+            Aarch64Layout::image_va_bits(0xffff_ffc0_0800_0000),
+            Some((39, true))
+        );
+        assert_eq!(
+            Aarch64Layout::image_va_bits(0xffff_0000_0808_0000),
+            Some((48, false))
+        );
+        assert_eq!(Aarch64Layout::image_va_bits(0xffff_ffff_8100_0000), None);
+
+        let mut layout = Aarch64Layout::new(48, 12, true);
+        layout.phys_offset = 0x4000_0000;
+        assert_eq!(layout.levels(), 4);
+        assert_eq!(layout.page_offset, 0xffff_0000_0000_0000);
+        assert_eq!(
+            layout.virtual_to_physical(0xffff_0000_0012_3456),
+            Some(0x4012_3456)
+        );
+        // A PAC signature in the top bits is ignored
+        assert_eq!(
+            layout.virtual_to_physical(0x1a8b_0000_0012_3456),
+            Some(0x4012_3456)
+        );
+        assert_eq!(layout.strip_pointer(0x0f00_0000_dead_b000), 0xdead_b000);
+        assert_eq!(Aarch64Layout::new(48, 16, true).levels(), 3);
+        assert_eq!(Aarch64Layout::new(47, 14, true).levels(), 3);
+        assert_eq!(Aarch64Layout::new(52, 12, true).levels(), 5);
+
+        // Page, block and table descriptors, and a swap entry (type 2, offset 0x1234)
+        assert_eq!(layout.non_linear_range(), (0xffff_8000_0000_0000, u64::MAX));
+        assert_eq!(layout.vmemmap_size(), 1 << 41);
+        assert_eq!(layout.vmemmap_starts()[1], 0xffff_fc00_0000_0000);
+
+        assert_eq!(
+            layout.decode(0x4567_8000 | 0x3, 0),
+            Descriptor::Leaf(0x4567_8000)
+        );
+        assert_eq!(
+            layout.decode(0x4020_0000 | 0x1, 1),
+            Descriptor::Leaf(0x4020_0000)
+        );
+        assert_eq!(
+            layout.decode(0x4567_8000 | 0x3, 2),
+            Descriptor::Table(0x4567_8000)
+        );
+        let swap = (0x1234 << SWP_OFFSET_SHIFT) | (2 << SWP_TYPE_SHIFT);
+        assert_eq!(
+            layout.decode(swap, 0),
+            Descriptor::Swap(SwapEntry {
+                swap_type: 2,
+                offset: 0x1234
+            })
+        );
+    }
+
+    #[test]
+    fn test_legacy_layout() {
+        // 4.x 48-bit: the linear map is the whole upper half of the kernel space
+        let mut layout = Aarch64Layout::new(48, 12, false);
+        layout.phys_offset = 0x8000_0000;
+        assert_eq!(layout.page_offset, 0xffff_8000_0000_0000);
+        assert_eq!(layout.kernel_space_start(), 0xffff_0000_0000_0000);
+        assert_eq!(
+            layout.virtual_to_physical(0xffff_c000_0000_1000),
+            Some(0x4000_8000_1000)
+        );
+        assert_eq!(
+            layout.physical_to_linear(0x4000_8000_1000),
+            Some(0xffff_c000_0000_1000)
+        );
+        assert_eq!(
+            layout.non_linear_range(),
+            (0xffff_0000_0000_0000, 0xffff_8000_0000_0000)
+        );
+        assert_eq!(layout.vmemmap_starts(), vec![0xffff_7e00_0000_0000]);
+    }
+}
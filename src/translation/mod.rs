@@ -1,5 +1,12 @@
 //! Memory translation module for converting between virtual, physical, and file offsets
-use crate::memory::MemoryRegion;
+use crate::kernel::swap::CompressedSwap;
+use crate::kernel::target::Target;
+use crate::kernel::KernelParser;
+use crate::memory::{MemoryMap, MemoryRegion};
+use std::borrow::Cow;
+
+pub mod aarch64;
+use aarch64::Aarch64Layout;
 
 // Macro for conditional debug output
 macro_rules! debug {
@@ -14,9 +21,146 @@ macro_rules! debug {
 #[allow(dead_code)]
 const KERNEL_TEXT_BASE: u64 = 0xffffffff81000000; // _text (actual kernel text start)
 const KERNEL_MAP_BASE: u64 = 0xffffffff80000000; // __START_KERNEL_map (mapping base)
+const MODULES_VADDR: u64 = 0xffffffffc0000000; // End of the kernel image mapping (1GB)
+const MODULES_END: u64 = 0xffffffffff000000;
+// Default VMALLOC_START / vmemmap_base and area sizes for 4-level and 5-level
+// paging, used when vmalloc_base / vmemmap_base (KASLR) are unavailable
+const VMALLOC_START_4LEVEL: u64 = 0xffff_c900_0000_0000;
+const VMALLOC_SIZE_4LEVEL: u64 = 32 << 40;
+const VMALLOC_START_5LEVEL: u64 = 0xffa0_0000_0000_0000;
+const VMALLOC_SIZE_5LEVEL: u64 = 12800 << 40;
+const VMEMMAP_START_4LEVEL: u64 = 0xffff_ea00_0000_0000;
+const VMEMMAP_SIZE_4LEVEL: u64 = 1 << 40;
+const VMEMMAP_START_5LEVEL: u64 = 0xffd4_0000_0000_0000;
+const VMEMMAP_SIZE_5LEVEL: u64 = 1 << 49;
+// Highest canonical user-space address (5-level paging)
+const USER_SPACE_END: u64 = 0x0100_0000_0000_0000;
+
+// Top-level kernel page table: x86-64 (4.13+, then older kernels) and AArch64
+const X86_64_PGD_SYMBOLS: [&str; 2] = ["init_top_pgt", "init_level4_pgt"];
+const AARCH64_PGD_SYMBOLS: [&str; 1] = ["swapper_pg_dir"];
 const PAGE_OFFSET_4LEVEL: u64 = 0xffff880000000000; // 4-level paging
 const PAGE_OFFSET_5LEVEL: u64 = 0xffff888000000000; // 5-level paging
 
+// x86-64 page table entry bits
+const PTE_PRESENT: u64 = 1 << 0;
+const PTE_PAGE_SIZE: u64 = 1 << 7; // Large page (2MB/1GB) when set in a PMD/PUD
+const PTE_ADDR_MASK: u64 = 0x000f_ffff_ffff_f000;
+const PTE_PROTNONE: u64 = 1 << 8; // PROT_NONE / NUMA hinting entry, page still resident
+
+// x86-64 swap entry layout in a non-present PTE (arch/x86/include/asm/pgtable_64.h):
+// type in the top 5 bits, inverted offset from bit 9
+const SWP_TYPE_BITS: u64 = 5;
+const SWP_OFFSET_SHIFT: u64 = 14;
+const SWAP_PAGE_SIZE: u64 = 0x1000;
+
+/// Swap entry decoded from a non-present page table entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SwapEntry {
+    pub swap_type: u8, // Index into the kernel's swap_info array
+    pub offset: u64,   // Page index within the swap area
+}
+
+impl SwapEntry {
+    /// Decode a last-level entry that is neither present nor empty
+    ///
+    /// Migration, device-private and hwpoison entries share the encoding and use
+    /// the highest swap types.
+    pub fn from_pte(entry: u64) -> Option<Self> {
+        if entry == 0 || entry & (PTE_PRESENT | PTE_PROTNONE) != 0 {
+            return None;
+        }
+        Some(SwapEntry {
+            swap_type: (entry >> (64 - SWP_TYPE_BITS)) as u8,
+            offset: (!entry << SWP_TYPE_BITS) >> SWP_OFFSET_SHIFT,
+        })
+    }
+}
+
+/// Where the page behind a process virtual address is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PageLocation {
+    Memory(u64),        // File offset of the page in the dump
+    Swapped(SwapEntry), // Swapped out; readable when its swap area image is loaded
+    Unavailable,        // Not mapped, or the page or its page table was not captured
+}
+
+/// CPU architecture of the dump, selecting how kernel addresses and page tables translate
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Architecture {
+    X86_64,
+    Aarch64(Aarch64Layout),
+}
+
+/// Kernel address space regions of the architecture without a fixed offset to
+/// physical memory, and where the kernel page table and user space end
+#[derive(Debug, Clone)]
+pub struct KernelRegions {
+    pub vmalloc: (u64, u64), // Default vmalloc area; on AArch64 all non-linear kernel space
+    pub modules: (u64, u64), // Empty on AArch64, where modules are inside vmalloc
+    pub vmemmap_starts: Vec<u64>, // Possible VMEMMAP_START values, newest kernels first
+    pub vmemmap_size: u64,
+    pub vmemmap_first_pfn: u64, // PFN described by the first struct page (PHYS_OFFSET on AArch64)
+    pub user_space_end: u64,
+    pub pgd_symbols: &'static [&'static str],
+}
+
+/// A page table entry decoded for the architecture
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Descriptor {
+    Table(u64),      // Physical address of the next-level table
+    Leaf(u64),       // Physical address of a page or large page / block
+    Swap(SwapEntry), // Swapped-out last-level entry
+    Empty,           // Not mapped
+}
+
+/// Geometry of a page table: page size, index bits per level, levels and VA bits
+#[derive(Debug, Clone, Copy)]
+struct TableFormat {
+    page_shift: u64,
+    level_bits: u64,
+    levels: u64,
+    address_bits: u64,
+}
+
+impl TableFormat {
+    fn shift(&self, level: u64) -> u64 {
+        self.page_shift + self.level_bits * level
+    }
+
+    fn index(&self, virtual_addr: u64, level: u64) -> u64 {
+        let address = virtual_addr & ((1u64 << self.address_bits) - 1);
+        (address >> self.shift(level)) & ((1u64 << self.level_bits) - 1)
+    }
+}
+
+/// Decode an x86-64 page table entry; 1GB and 2MB pages are PUD (level 2) and PMD (level 1) leaves
+fn decode_x86_64(entry: u64, level: u64) -> Descriptor {
+    match entry_frame(entry) {
+        Some(frame) if level == 0 || ((level == 1 || level == 2) && entry & PTE_PAGE_SIZE != 0) => {
+            Descriptor::Leaf(frame)
+        }
+        Some(frame) => Descriptor::Table(frame),
+        None if level == 0 => {
+            SwapEntry::from_pte(entry).map_or(Descriptor::Empty, Descriptor::Swap)
+        }
+        None => Descriptor::Empty,
+    }
+}
+
+/// Physical frame of a leaf or table entry that maps memory
+///
+/// Since 4.19 (L1TF mitigation) a PROT_NONE entry stores its frame inverted.
+fn entry_frame(entry: u64) -> Option<u64> {
+    if entry & PTE_PRESENT != 0 {
+        Some(entry & PTE_ADDR_MASK)
+    } else if entry & PTE_PROTNONE != 0 {
+        Some(!entry & PTE_ADDR_MASK)
+    } else {
+        None
+    }
+}
+
 /// Memory translation layer for converting between address spaces
 pub struct MemoryTranslator {
     regions: Vec<MemoryRegion>,
@@ -27,6 +171,21 @@ pub struct MemoryTranslator {
     page_offset_4level: u64,
     /// PAGE_OFFSET for 5-level paging (can be adjusted for KASLR)
     page_offset_5level: u64,
+    /// Number of page table levels used by process page tables (4 or 5)
+    paging_levels: u8,
+    /// Swap area images by swap type, for reading swapped-out pages
+    swap_images: Vec<(u8, MemoryMap)>,
+    /// zram / zswap pages still held compressed in the dump
+    compressed_swap: Option<CompressedSwap>,
+    /// Architecture of the dump (x86-64 unless AArch64 is detected)
+    architecture: Architecture,
+    /// Kernel ranges (vmalloc, vmemmap, modules) translated through the kernel page table
+    kernel_ranges: Vec<(u64, u64)>,
+    /// Leaf mappings of the kernel page table in those ranges: (virtual, physical, size),
+    /// sorted by virtual address
+    kernel_mappings: Vec<(u64, u64, u64)>,
+    /// Byte order and pointer size of the dumped kernel
+    target: Target,
 }
 
 impl MemoryTranslator {
@@ -38,6 +197,143 @@ pub fn new(regions: Vec<MemoryRegion>) -> Self {
             phys_base: 0x1000000,                   // Default 16MB
             page_offset_4level: PAGE_OFFSET_4LEVEL, // Standard 4-level paging
             page_offset_5level: PAGE_OFFSET_5LEVEL, // Standard 5-level paging
+            paging_levels: 4,
+            swap_images: Vec::new(),
+            compressed_swap: None,
+            architecture: Architecture::X86_64,
+            kernel_ranges: Vec::new(),
+            kernel_mappings: Vec::new(),
+            target: Target::default(),
+        }
+    }
+
+    /// Set the architecture (and for AArch64, the detected memory layout)
+    pub fn set_architecture(&mut self, architecture: Architecture) {
+        self.architecture = architecture;
+    }
+
+    /// Architecture of the dump
+    pub fn architecture(&self) -> &Architecture {
+        &self.architecture
+    }
+
+    /// Set the byte order and pointer size kernel data is read with
+    pub fn set_target(&mut self, target: Target) {
+        self.target = target;
+    }
+
+    /// Byte order and pointer size of the dumped kernel
+    pub fn target(&self) -> Target {
+        self.target
+    }
+
+    /// Size of a kernel pointer (and unsigned long) in bytes
+    pub fn pointer_size(&self) -> u64 {
+        self.target.pointer_size as u64
+    }
+
+    /// Remove pointer authentication codes and memory tags (AArch64) from a pointer
+    pub fn strip_pointer(&self, pointer: u64) -> u64 {
+        match &self.architecture {
+            Architecture::X86_64 => pointer,
+            Architecture::Aarch64(layout) => layout.strip_pointer(pointer),
+        }
+    }
+
+    /// Lowest kernel virtual address; pointers below it belong to user space
+    pub fn kernel_space_start(&self) -> u64 {
+        match &self.architecture {
+            Architecture::X86_64 => 0xffff800000000000,
+            Architecture::Aarch64(layout) => layout.kernel_space_start(),
+        }
+    }
+
+    /// Size of a kernel page (and of a swap slot)
+    pub fn page_size(&self) -> u64 {
+        match &self.architecture {
+            Architecture::X86_64 => SWAP_PAGE_SIZE,
+            Architecture::Aarch64(layout) => layout.page_size(),
+        }
+    }
+
+    /// Kernel address space regions for the architecture and paging mode
+    pub fn kernel_regions(&self) -> KernelRegions {
+        match &self.architecture {
+            Architecture::X86_64 => {
+                let (vmalloc, vmalloc_size, vmemmap, vmemmap_size) = if self.paging_levels == 5 {
+                    (
+                        VMALLOC_START_5LEVEL,
+                        VMALLOC_SIZE_5LEVEL,
+                        VMEMMAP_START_5LEVEL,
+                        VMEMMAP_SIZE_5LEVEL,
+                    )
+                } else {
+                    (
+                        VMALLOC_START_4LEVEL,
+                        VMALLOC_SIZE_4LEVEL,
+                        VMEMMAP_START_4LEVEL,
+                        VMEMMAP_SIZE_4LEVEL,
+                    )
+                };
+                KernelRegions {
+                    vmalloc: (vmalloc, vmalloc + vmalloc_size),
+                    modules: (MODULES_VADDR, MODULES_END),
+                    vmemmap_starts: vec![vmemmap],
+                    vmemmap_size,
+                    vmemmap_first_pfn: 0,
+                    user_space_end: USER_SPACE_END,
+                    pgd_symbols: &X86_64_PGD_SYMBOLS,
+                }
+            }
+            Architecture::Aarch64(layout) => KernelRegions {
+                vmalloc: layout.non_linear_range(),
+                modules: (0, 0),
+                vmemmap_starts: layout.vmemmap_starts(),
+                vmemmap_size: layout.vmemmap_size(),
+                vmemmap_first_pfn: layout.phys_offset >> layout.page_shift,
+                user_space_end: 1 << layout.va_bits,
+                pgd_symbols: &AARCH64_PGD_SYMBOLS,
+            },
+        }
+    }
+
+    /// Kernel image virtual address of a physical address (it may lie outside the image)
+    pub fn physical_to_kernel_image(&self, physical_addr: u64) -> u64 {
+        match &self.architecture {
+            Architecture::X86_64 => physical_addr
+                .wrapping_sub(self.phys_base)
+                .wrapping_add(KERNEL_MAP_BASE),
+            Architecture::Aarch64(layout) => layout.physical_to_image(physical_addr),
+        }
+    }
+
+    /// Sizes a leaf mapping can have: a page and the two block / large page sizes
+    pub fn leaf_page_sizes(&self) -> [u64; 3] {
+        let format = self.table_format();
+        [format.shift(0), format.shift(1), format.shift(2)].map(|shift| 1 << shift)
+    }
+
+    fn table_format(&self) -> TableFormat {
+        match &self.architecture {
+            Architecture::X86_64 => TableFormat {
+                page_shift: 12,
+                level_bits: 9,
+                levels: self.paging_levels as u64,
+                address_bits: 12 + 9 * self.paging_levels as u64,
+            },
+            Architecture::Aarch64(layout) => TableFormat {
+                page_shift: layout.page_shift,
+                level_bits: layout.bits_per_level(),
+                levels: layout.levels(),
+                address_bits: layout.va_bits,
+            },
+        }
+    }
+
+    fn decode(&self, entry: u64, level: u64) -> Descriptor {
+        match &self.architecture {
+            Architecture::X86_64 => decode_x86_64(entry, level),
+            Architecture::Aarch64(layout) => layout.decode(entry, level),
         }
     }
 
@@ -73,21 +369,45 @@ pub fn get_page_offset_5level(&self) -> u64 {
         self.page_offset_5level
     }
 
+    /// Set the number of page table levels (5 when LA57 is enabled)
+    pub fn set_paging_levels(&mut self, levels: u8) {
+        self.paging_levels = levels;
+    }
+
     /// Convert a kernel virtual address to physical address
     ///
     /// For x86-64 Linux kernel:
     /// - Kernel text (.text, .data, etc.): virtual >= 0xffffffff81000000 -> physical = (virtual - 0xffffffff80000000) + phys_base
     /// - Direct mapping: virtual PAGE_OFFSET + offset -> physical 0x0 + offset
-    fn virtual_to_physical(&self, virtual_addr: u64) -> Option<u64> {
+    /// - vmalloc, vmemmap and module addresses: the kernel page table, once loaded
+    pub fn virtual_to_physical(&self, virtual_addr: u64) -> Option<u64> {
+        // The AArch64 linear map and image have fixed bounds; everything else in
+        // kernel space goes through the page table
+        if let Architecture::Aarch64(layout) = &self.architecture {
+            return layout
+                .virtual_to_physical(virtual_addr)
+                .or_else(|| self.kernel_page_table_lookup(layout.strip_pointer(virtual_addr)));
+        }
+        // Checked first: with KASLR these ranges can start below the fixed bounds
+        // of the direct map checked further down
+        if self
+            .kernel_ranges
+            .iter()
+            .any(|&(start, end)| virtual_addr >= start && virtual_addr < end)
+        {
+            return self.kernel_page_table_lookup(virtual_addr);
+        }
         debug!("[DEBUG] Translating virtual address: 0x{:x}", virtual_addr);
         debug!("[DEBUG] Using phys_base: 0x{:x}", self.phys_base);
 
-        // Check if it's in kernel text/data region (0xffffffff80000000 - 0xffffffffff000000)
-        if virtual_addr >= KERNEL_MAP_BASE && virtual_addr < 0xffffffffff000000 {
+        // Check if it's in kernel text/data region (0xffffffff80000000 - 0xffffffffc0000000).
+        // Modules above it are vmalloc'd and only translate through the page table.
+        if virtual_addr >= KERNEL_MAP_BASE && virtual_addr < MODULES_VADDR {
             // Kernel text mapping:
             // Virtual addresses in this range map to: phys_base + (virtual - __START_KERNEL_map)
+            // phys_base may have been adjusted for a virtual KASLR slide, so wrap
             let offset = virtual_addr - KERNEL_MAP_BASE;
-            let physical = self.phys_base + offset;
+            let physical = self.phys_base.wrapping_add(offset);
 
             debug!(
                 "[DEBUG] Kernel text mapping: offset=0x{:x}, physical=0x{:x}",
@@ -123,6 +443,53 @@ fn virtual_to_physical(&self, virtual_addr: u64) -> Option<u64> {
         None
     }
 
+    /// Walk the kernel page table over `ranges` and translate addresses in them
+    /// through its leaf mappings from now on
+    ///
+    /// vmalloc, vmemmap and module memory has no fixed virtual-to-physical offset.
+    /// Walking once up front keeps `virtual_to_physical` free of dump reads.
+    pub fn load_kernel_page_table(&mut self, mapped: &[u8], pgd_phys: u64, ranges: &[(u64, u64)]) {
+        let mut mappings = Vec::new();
+        for &(start, end) in ranges {
+            self.walk_page_table(
+                mapped,
+                pgd_phys,
+                start,
+                end,
+                &mut |virtual_addr, physical, size| mappings.push((virtual_addr, physical, size)),
+            );
+        }
+        mappings.sort_unstable();
+        mappings.dedup();
+        debug!(
+            "[DEBUG] Kernel page table: {} leaf mappings in {} ranges",
+            mappings.len(),
+            ranges.len()
+        );
+        self.kernel_ranges = ranges.to_vec();
+        self.kernel_mappings = mappings;
+    }
+
+    /// Number of leaf mappings loaded from the kernel page table
+    pub fn kernel_mapping_count(&self) -> usize {
+        self.kernel_mappings.len()
+    }
+
+    fn kernel_page_table_lookup(&self, virtual_addr: u64) -> Option<u64> {
+        let index = self
+            .kernel_mappings
+            .partition_point(|&(start, _, _)| start <= virtual_addr);
+        let &(start, physical, size) = self.kernel_mappings.get(index.checked_sub(1)?)?;
+        let offset = virtual_addr - start;
+        (offset < size).then_some(physical + offset)
+    }
+
+    /// Get the number of page table levels
+    #[allow(dead_code)] // Reserved for diagnostic/debug features
+    pub fn get_paging_levels(&self) -> u8 {
+        self.paging_levels
+    }
+
     /// Translate a virtual kernel address to a file offset
     ///
     /// This function:
@@ -134,6 +501,11 @@ pub fn virtual_to_file_offset(&self, virtual_addr: u64) -> Option<u64> {
         let physical_addr = self.virtual_to_physical(virtual_addr)?;
 
         // Now find which region contains this physical address
+        self.physical_to_file_offset(physical_addr)
+    }
+
+    /// Translate a physical address to a file offset using the LIME regions
+    pub fn physical_to_file_offset(&self, physical_addr: u64) -> Option<u64> {
         for region in &self.regions {
             if physical_addr >= region.start && physical_addr <= region.end {
                 let offset_in_region = physical_addr - region.start;
@@ -144,6 +516,247 @@ pub fn virtual_to_file_offset(&self, virtual_addr: u64) -> Option<u64> {
         None
     }
 
+    /// Translate a file offset back to a physical address using the LIME regions
+    pub fn file_offset_to_physical(&self, file_offset: u64) -> Option<u64> {
+        self.regions
+            .iter()
+            .find(|r| {
+                file_offset >= r.file_offset && file_offset - r.file_offset <= r.end - r.start
+            })
+            .map(|r| r.start + (file_offset - r.file_offset))
+    }
+
+    /// Find the LIME region holding a physical address
+    pub fn physical_region(&self, physical_addr: u64) -> Option<&MemoryRegion> {
+        self.regions.iter().find(|r| r.contains(physical_addr))
+    }
+
+    /// Find the direct-map virtual address of a physical address
+    ///
+    /// Only offsets that translate back to the same physical address are returned,
+    /// since the 4-level and 5-level PAGE_OFFSET ranges overlap.
+    pub fn physical_to_direct_map(&self, physical_addr: u64) -> Option<u64> {
+        if let Architecture::Aarch64(layout) = &self.architecture {
+            return layout.physical_to_linear(physical_addr);
+        }
+        [self.page_offset_5level, self.page_offset_4level]
+            .into_iter()
+            .map(|offset| offset.wrapping_add(physical_addr))
+            .find(|&virtual_addr| self.virtual_to_physical(virtual_addr) == Some(physical_addr))
+    }
+
+    /// Translate a virtual address by walking a page table
+    ///
+    /// `pgd_phys` is the physical address of the top-level table (mm->pgd translated
+    /// through the direct map). Handles large pages (x86-64 1GB/2MB pages, AArch64
+    /// blocks).
+    pub fn page_table_walk(&self, mapped: &[u8], pgd_phys: u64, virtual_addr: u64) -> Option<u64> {
+        self.walk_entry(mapped, pgd_phys, virtual_addr).ok()
+    }
+
+    /// Walk a page table to the physical address, or to the entry where the walk stopped
+    ///
+    /// The error holds the swap entry when the last-level entry is swapped out, or
+    /// None when the address is not mapped or a table was not captured.
+    fn walk_entry(
+        &self,
+        mapped: &[u8],
+        pgd_phys: u64,
+        virtual_addr: u64,
+    ) -> Result<u64, Option<SwapEntry>> {
+        let format = self.table_format();
+        let virtual_addr = self.strip_pointer(virtual_addr);
+        let mut table = pgd_phys & PTE_ADDR_MASK;
+
+        for level in (0..format.levels).rev() {
+            let index = format.index(virtual_addr, level);
+            let entry_offset = self
+                .physical_to_file_offset(table + index * 8)
+                .ok_or(None)? as usize;
+            // Entries are in the kernel's byte order
+            let entry = KernelParser::read_u64(mapped, entry_offset, self.target).ok_or(None)?;
+
+            match self.decode(entry, level) {
+                Descriptor::Leaf(frame) => {
+                    let page_mask = (1u64 << format.shift(level)) - 1;
+                    return Ok((frame & !page_mask) | (virtual_addr & page_mask));
+                }
+                Descriptor::Table(next) => table = next,
+                Descriptor::Swap(swap) => return Err(Some(swap)),
+                Descriptor::Empty => return Err(None),
+            }
+        }
+
+        Err(None)
+    }
+
+    /// Enumerate the present leaf mappings of a page table within `[start, end)`
+    ///
+    /// Calls `visit(virtual, physical, size)` once for each 4KB page and each
+    /// 2MB/1GB large page that overlaps the range. Tables outside the range are
+    /// never read.
+    pub fn walk_page_table(
+        &self,
+        mapped: &[u8],
+        pgd_phys: u64,
+        start: u64,
+        end: u64,
+        visit: &mut dyn FnMut(u64, u64, u64),
+    ) {
+        let top = self.table_format().levels - 1;
+        let mut ignore = |_, _| {};
+        let table = pgd_phys & PTE_ADDR_MASK;
+        self.walk_table(mapped, table, top, 0, start, end, visit, &mut ignore);
+    }
+
+    /// Enumerate the swap entries of a page table within `[start, end)`
+    ///
+    /// Calls `visit(virtual, entry)` once for each swapped-out 4KB page.
+    pub fn walk_swap_entries(
+        &self,
+        mapped: &[u8],
+        pgd_phys: u64,
+        start: u64,
+        end: u64,
+        visit: &mut dyn FnMut(u64, SwapEntry),
+    ) {
+        let top = self.table_format().levels - 1;
+        let mut ignore = |_, _, _| {};
+        let table = pgd_phys & PTE_ADDR_MASK;
+        self.walk_table(mapped, table, top, 0, start, end, &mut ignore, visit);
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn walk_table(
+        &self,
+        mapped: &[u8],
+        table: u64,
+        level: u64,
+        base: u64,
+        start: u64,
+        end: u64,
+        visit: &mut dyn FnMut(u64, u64, u64),
+        swapped: &mut dyn FnMut(u64, SwapEntry),
+    ) {
+        if start >= end {
+            return;
+        }
+        let format = self.table_format();
+        let shift = format.shift(level);
+        let size = 1u64 << shift;
+        // Compare on the canonical-stripped address so kernel ranges work too
+        let (first, last) = (format.index(start, level), format.index(end - 1, level));
+        let top_bits = start & !((1u64 << format.address_bits) - 1);
+
+        for index in first..=last.max(first) {
+            let virtual_addr = top_bits | base | (index << shift);
+            let entry_offset = match self.physical_to_file_offset(table + index * 8) {
+                Some(o) => o as usize,
+                None => continue,
+            };
+            let entry = match KernelParser::read_u64(mapped, entry_offset, self.target) {
+                Some(e) => e,
+                None => continue,
+            };
+
+            match self.decode(entry, level) {
+                Descriptor::Leaf(frame) => visit(virtual_addr, frame & !(size - 1), size),
+                Descriptor::Table(next) => {
+                    let child_start = start.max(virtual_addr);
+                    let child_end = virtual_addr.checked_add(size).map_or(end, |e| end.min(e));
+                    self.walk_table(
+                        mapped,
+                        next,
+                        level - 1,
+                        base | (index << shift),
+                        child_start,
+                        child_end,
+                        visit,
+                        swapped,
+                    );
+                }
+                Descriptor::Swap(swap) => swapped(virtual_addr, swap),
+                Descriptor::Empty => {}
+            }
+        }
+    }
+
+    /// Translate a process virtual address to a file offset using its page table
+    pub fn user_virtual_to_file_offset(
+        &self,
+        mapped: &[u8],
+        pgd_phys: u64,
+        virtual_addr: u64,
+    ) -> Option<u64> {
+        let physical = self.page_table_walk(mapped, pgd_phys, virtual_addr)?;
+        self.physical_to_file_offset(physical)
+    }
+
+    /// Locate the page behind a process virtual address: in the dump, in swap, or neither
+    pub fn locate_user_page(
+        &self,
+        mapped: &[u8],
+        pgd_phys: u64,
+        virtual_addr: u64,
+    ) -> PageLocation {
+        match self.walk_entry(mapped, pgd_phys, virtual_addr) {
+            Ok(physical) => self
+                .physical_to_file_offset(physical)
+                .map_or(PageLocation::Unavailable, PageLocation::Memory),
+            Err(swap) => swap.map_or(PageLocation::Unavailable, PageLocation::Swapped),
+        }
+    }
+
+    /// Attach the image of a swap area (partition or swap file) for a swap type
+    pub fn add_swap_image(&mut self, swap_type: u8, image: MemoryMap) {
+        self.swap_images.retain(|(t, _)| *t != swap_type);
+        self.swap_images.push((swap_type, image));
+    }
+
+    /// Check whether the image of a swap area is loaded
+    pub fn has_swap_image(&self, swap_type: u8) -> bool {
+        self.swap_images.iter().any(|(t, _)| *t == swap_type)
+    }
+
+    /// Attach the zram / zswap reader for swapped-out pages kept in memory
+    pub fn set_compressed_swap(&mut self, compressed_swap: CompressedSwap) {
+        self.compressed_swap = Some(compressed_swap);
+    }
+
+    /// Check whether a swapped-out page can be read from memory or a swap area image
+    pub fn swap_page_readable(&self, mapped: &[u8], entry: SwapEntry) -> bool {
+        self.has_swap_image(entry.swap_type)
+            || self
+                .compressed_swap
+                .as_ref()
+                .is_some_and(|c| c.contains(mapped, self, entry))
+    }
+
+    /// Read a swapped-out page, decompressing it from zswap / zram when it is still
+    /// in memory and otherwise reading it from its swap area image
+    ///
+    /// Swap offsets are page indexes into the area; page 0 is the swap header. Slots are
+    /// one kernel page, which is larger than 4KB with 16K/64K AArch64 granules.
+    pub fn read_swap_page(&self, mapped: &[u8], entry: SwapEntry) -> Option<Cow<'_, [u8]>> {
+        if let Some(page) = self
+            .compressed_swap
+            .as_ref()
+            .and_then(|c| c.read_page(mapped, self, entry))
+        {
+            return Some(Cow::Owned(page));
+        }
+        let (_, image) = self
+            .swap_images
+            .iter()
+            .find(|(t, _)| *t == entry.swap_type)?;
+        let slot = self.page_size();
+        let start = entry.offset.checked_mul(slot)? as usize;
+        image
+            .mapped
+            .get(start..start + slot as usize)
+            .map(Cow::Borrowed)
+    }
+
     /// Find which region contains a virtual address
     #[allow(dead_code)]
     pub fn find_region(&self, virtual_addr: u64) -> Option<&MemoryRegion> {
@@ -155,7 +768,6 @@ pub fn find_region(&self, virtual_addr: u64) -> Option<&MemoryRegion> {
     }
 
     /// Get all memory regions
-    #[allow(dead_code)]
     pub fn get_regions(&self) -> &[MemoryRegion] {
         &self.regions
     }
@@ -166,3 +778,223 @@ pub fn region_count(&self) -> usize {
         self.regions.len()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_walk_page_table_reports_leaf_mappings() {
+        let mut mapped = vec![0u8; 0x10000];
+        let mut set = |table: u64, index: u64, entry: u64| {
+            let at = (table + index * 8) as usize;
+            mapped[at..at + 8].copy_from_slice(&entry.to_le_bytes());
+        };
+        // PGD 0x1000 -> PUD 0x2000 -> PMD 0x3000 -> PT 0x4000
+        set(0x1000, 0, 0x2000 | PTE_PRESENT);
+        set(0x2000, 0, 0x3000 | PTE_PRESENT);
+        set(0x3000, 2, 0x4000 | PTE_PRESENT);
+        set(0x4000, 5, 0x9000 | PTE_PRESENT);
+        set(0x4000, 6, 0xa000); // Not present
+        set(0x3000, 3, 0x4000_0000 | PTE_PRESENT | PTE_PAGE_SIZE);
+
+        let translator = MemoryTranslator::new(vec![MemoryRegion {
+            start: 0,
+            end: 0xffff,
+            file_offset: 0,
+        }]);
+        let mut found = Vec::new();
+        translator.walk_page_table(&mapped, 0x1000, 0x400000, 0x800000, &mut |v, p, size| {
+            found.push((v, p, size))
+        });
+
+        assert_eq!(
+            found,
+            vec![
+                (0x405000, 0x9000, 0x1000),
+                (0x600000, 0x4000_0000, 0x20_0000)
+            ]
+        );
+        assert_eq!(
+            translator.page_table_walk(&mapped, 0x1000, 0x405123),
+            Some(0x9123)
+        );
+    }
+
+    #[test]
+    fn test_kernel_page_table_resolves_vmemmap() {
+        let mut mapped = vec![0u8; 0x10000];
+        let mut set = |table: u64, index: u64, entry: u64| {
+            let at = (table + index * 8) as usize;
+            mapped[at..at + 8].copy_from_slice(&entry.to_le_bytes());
+        };
+        // The struct page of PFN 0x100 at the default 4-level vmemmap_base, mapped by
+        // a 4KB page, and a 2MB vmemmap page further up
+        let vmemmap_base = 0xffff_ea00_0000_0000u64;
+        let page = vmemmap_base + 0x100 * 64;
+        let index = |level: u64| (page >> (12 + 9 * level)) & 0x1ff;
+        set(0x1000, index(3), 0x2000 | PTE_PRESENT);
+        set(0x2000, index(2), 0x3000 | PTE_PRESENT);
+        set(0x3000, index(1), 0x4000 | PTE_PRESENT);
+        set(0x4000, index(0), 0x7000 | PTE_PRESENT);
+        set(
+            0x3000,
+            index(1) + 1,
+            0x20_0000 | PTE_PRESENT | PTE_PAGE_SIZE,
+        );
+
+        let mut translator = MemoryTranslator::new(vec![MemoryRegion {
+            start: 0,
+            end: 0xffff,
+            file_offset: 0,
+        }]);
+        assert_eq!(translator.virtual_to_physical(page), None);
+        translator.load_kernel_page_table(
+            &mapped,
+            0x1000,
+            &[(vmemmap_base, vmemmap_base + (1 << 40))],
+        );
+        assert_eq!(translator.virtual_to_physical(page + 0x10), Some(0x7010));
+        assert_eq!(
+            translator.virtual_to_physical(vmemmap_base + 0x20_1234),
+            Some(0x20_1234)
+        );
+        assert_eq!(
+            translator.virtual_to_physical(vmemmap_base + 0x40_0000),
+            None
+        );
+        assert_eq!(translator.virtual_to_file_offset(page), Some(0x7000));
+        // Fixed mappings are unaffected
+        assert_eq!(
+            translator.virtual_to_physical(0xffff_8880_0000_1000),
+            Some(0x1000)
+        );
+    }
+
+    #[test]
+    fn test_module_addresses_skip_the_image_mapping() {
+        let mut mapped = vec![0u8; 0x10000];
+        let mut set = |table: u64, index: u64, entry: u64| {
+            let at = (table + index * 8) as usize;
+            mapped[at..at + 8].copy_from_slice(&entry.to_le_bytes());
+        };
+        let module = 0xffff_ffff_c012_3000u64;
+        let index = |level: u64| (module >> (12 + 9 * level)) & 0x1ff;
+        set(0x1000, index(3), 0x2000 | PTE_PRESENT);
+        set(0x2000, index(2), 0x3000 | PTE_PRESENT);
+        set(0x3000, index(1), 0x4000 | PTE_PRESENT);
+        set(0x4000, index(0), 0x8000 | PTE_PRESENT);
+
+        let mut translator = MemoryTranslator::new(vec![MemoryRegion {
+            start: 0,
+            end: 0xffff,
+            file_offset: 0,
+        }]);
+        assert_eq!(
+            translator.virtual_to_physical(0xffff_ffff_8100_0000),
+            Some(0x200_0000)
+        );
+        // Not phys_base + (address - __START_KERNEL_map)
+        assert_eq!(translator.virtual_to_physical(module), None);
+        translator.load_kernel_page_table(
+            &mapped,
+            0x1000,
+            &[(MODULES_VADDR, 0xffff_ffff_ff00_0000)],
+        );
+        assert_eq!(translator.virtual_to_physical(module + 0x42), Some(0x8042));
+    }
+
+    #[test]
+    fn test_swap_entry_decoding() {
+        // __swp_entry(type 1, offset 0x1234): type in the top bits, offset inverted
+        let entry = (1u64 << 59) | (!0x1234u64 << SWP_OFFSET_SHIFT >> SWP_TYPE_BITS);
+        assert_eq!(
+            SwapEntry::from_pte(entry),
+            Some(SwapEntry {
+                swap_type: 1,
+                offset: 0x1234
+            })
+        );
+        assert_eq!(SwapEntry::from_pte(0), None);
+        assert_eq!(SwapEntry::from_pte(0x9000 | PTE_PRESENT), None);
+        // PROT_NONE entries are resident, with the frame inverted
+        let protnone = (!0x9000u64 & PTE_ADDR_MASK) | PTE_PROTNONE;
+        assert_eq!(SwapEntry::from_pte(protnone), None);
+        assert_eq!(entry_frame(protnone), Some(0x9000));
+    }
+
+    #[test]
+    fn test_read_swap_page_from_a_swap_image() {
+        use std::io::Write;
+
+        // __swp_entry(type 1, offset)
+        let swap_pte = |offset: u64| (1u64 << 59) | (!offset << SWP_OFFSET_SHIFT >> SWP_TYPE_BITS);
+        let mut mapped = vec![0u8; 0x10000];
+        let mut set = |table: u64, index: u64, entry: u64| {
+            let at = (table + index * 8) as usize;
+            mapped[at..at + 8].copy_from_slice(&entry.to_le_bytes());
+        };
+        // PGD 0x1000 -> PUD 0x2000 -> PMD 0x3000 -> PT 0x4000, with
+        // 0x405000 in slot 2 and 0x406000 in slot 5 of swap type 1
+        set(0x1000, 0, 0x2000 | PTE_PRESENT);
+        set(0x2000, 0, 0x3000 | PTE_PRESENT);
+        set(0x3000, 2, 0x4000 | PTE_PRESENT);
+        set(0x4000, 5, swap_pte(2));
+        set(0x4000, 6, swap_pte(5));
+
+        // A short image: the header page and three slots
+        let mut image = vec![0u8; 0x4000];
+        image[0xff6..0x1000].copy_from_slice(b"SWAPSPACE2");
+        image[0x2000..0x3000].fill(0xab);
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(&image).unwrap();
+
+        let mut translator = MemoryTranslator::new(vec![MemoryRegion {
+            start: 0,
+            end: 0xffff,
+            file_offset: 0,
+        }]);
+        translator.add_swap_image(1, MemoryMap::new(file.path().to_str().unwrap()).unwrap());
+
+        let entry = match translator.locate_user_page(&mapped, 0x1000, 0x405123) {
+            PageLocation::Swapped(entry) => entry,
+            other => panic!("expected a swapped page, got {:?}", other),
+        };
+        assert_eq!(
+            entry,
+            SwapEntry {
+                swap_type: 1,
+                offset: 2
+            }
+        );
+        assert!(translator.swap_page_readable(&mapped, entry));
+        let page = translator.read_swap_page(&mapped, entry).unwrap();
+        assert_eq!(page.len(), 0x1000);
+        assert!(page.iter().all(|&b| b == 0xab));
+
+        // Offsets index pages from the start of the area, header included
+        let header = SwapEntry {
+            swap_type: 1,
+            offset: 0,
+        };
+        assert!(translator
+            .read_swap_page(&mapped, header)
+            .unwrap()
+            .ends_with(b"SWAPSPACE2"));
+
+        // Slot 5 is past the end of the image; type 0 has no image at all
+        let PageLocation::Swapped(past_end) =
+            translator.locate_user_page(&mapped, 0x1000, 0x406000)
+        else {
+            panic!("expected a swapped page");
+        };
+        assert_eq!(past_end.offset, 5);
+        assert!(translator.read_swap_page(&mapped, past_end).is_none());
+        let other_type = SwapEntry {
+            swap_type: 0,
+            offset: 2,
+        };
+        assert!(!translator.swap_page_readable(&mapped, other_type));
+        assert!(translator.read_swap_page(&mapped, other_type).is_none());
+    }
+}